@@ -11,8 +11,10 @@ mod linux;
 mod macos;
 mod path_utils;
 mod platform;
+mod sbom;
 mod settings;
 mod updater_bundle;
+mod updater_delta;
 mod windows;
 
 use tauri_utils::display_path;
@@ -21,7 +23,9 @@ pub use self::{
   category::AppCategory,
   settings::{
     AppImageSettings, BundleBinary, BundleSettings, CustomSignCommandSettings, DebianSettings,
-    DmgSettings, MacOsSettings, PackageSettings, PackageType, Position, RpmSettings, Settings,
+    DmgSettings, FlatpakOutput, FlatpakSettings, MacOsSettings, MetainfoRelease,
+    MetainfoScreenshot, MetainfoSettings, PackageSettings, PackageType, Position, RpmSettings,
+    SbomEcosystem, SbomFormat, SbomHash, SbomHashAlgorithm, SbomPackage, SbomSettings, Settings,
     SettingsBuilder, Size, UpdaterSettings,
   },
 };
@@ -96,6 +100,9 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<Bundle>> {
   }
 
   let mut bundles = Vec::<Bundle>::new();
+  // the system packages the Linux packages depend on, for their bill of materials
+  #[allow(unused_mut)]
+  let mut system_dependencies = Vec::<(PackageType, Vec<String>)>::new();
   for package_type in &package_types {
     // bundle was already built! e.g. DMG already built .app
     if bundles.iter().any(|b| b.package_type == *package_type) {
@@ -125,11 +132,21 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<Bundle>> {
       PackageType::Nsis => windows::nsis::bundle_project(settings, false)?,
 
       #[cfg(target_os = "linux")]
-      PackageType::Deb => linux::debian::bundle_project(settings)?,
+      PackageType::Deb => {
+        let (paths, dependencies) = linux::debian::bundle_project(settings)?;
+        system_dependencies.push((PackageType::Deb, dependencies));
+        paths
+      }
       #[cfg(target_os = "linux")]
-      PackageType::Rpm => linux::rpm::bundle_project(settings)?,
+      PackageType::Rpm => {
+        let (paths, dependencies) = linux::rpm::bundle_project(settings)?;
+        system_dependencies.push((PackageType::Rpm, dependencies));
+        paths
+      }
       #[cfg(target_os = "linux")]
       PackageType::AppImage => linux::appimage::bundle_project(settings)?,
+      #[cfg(target_os = "linux")]
+      PackageType::Flatpak => linux::flatpak::bundle_project(settings)?,
       _ => {
         log::warn!("ignoring {}", package_type.short_name());
         continue;
@@ -175,6 +192,41 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<Bundle>> {
     if updater.v1_compatible {
       log::warn!("Legacy v1 compatible updater is deprecated and will be removed in v3, change bundle > createUpdaterArtifacts to true when your users are updated to the version with v2 updater plugin");
     }
+
+    if !settings.delta_from().is_empty() {
+      let artifacts = bundles
+        .iter()
+        .filter(|bundle| {
+          if updater.v1_compatible {
+            bundle.package_type == PackageType::Updater
+          } else {
+            matches!(
+              bundle.package_type,
+              PackageType::Updater
+                | PackageType::AppImage
+                | PackageType::Nsis
+                | PackageType::WindowsMsi
+            )
+          }
+        })
+        .flat_map(|bundle| bundle.bundle_paths.clone())
+        .collect::<Vec<_>>();
+      let delta_paths = updater_delta::bundle_project(settings, &artifacts)?;
+      if !delta_paths.is_empty() {
+        match bundles
+          .iter_mut()
+          .find(|bundle| bundle.package_type == PackageType::Updater)
+        {
+          Some(bundle) => bundle.bundle_paths.extend(delta_paths),
+          None => bundles.push(Bundle {
+            package_type: PackageType::Updater,
+            bundle_paths: delta_paths,
+          }),
+        }
+      }
+    }
+  } else if !settings.delta_from().is_empty() {
+    log::warn!("Delta updater artifacts require the updater artifacts, enable bundle > createUpdaterArtifacts to create them");
   }
 
   #[cfg(target_os = "macos")]
@@ -208,6 +260,21 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<Bundle>> {
     return Err(anyhow::anyhow!("No bundles were built").into());
   }
 
+  let mut sbom_paths = Vec::new();
+  if let Some(sbom) = settings.sbom() {
+    for bundle in bundles
+      .iter()
+      .filter(|b| b.package_type != PackageType::Updater)
+    {
+      let dependencies = system_dependencies
+        .iter()
+        .find(|(package_type, _)| *package_type == bundle.package_type)
+        .map(|(_, dependencies)| dependencies.as_slice())
+        .unwrap_or_default();
+      sbom_paths.extend(sbom::bundle_project(settings, sbom, bundle, dependencies)?);
+    }
+  }
+
   let bundles_wo_updater = bundles
     .iter()
     .filter(|b| b.package_type != PackageType::Updater)
@@ -231,6 +298,10 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<Bundle>> {
       writeln!(printable_paths, "        {path_display}{note}").unwrap();
     }
   }
+  for path in &sbom_paths {
+    let path_display = display_path(path);
+    writeln!(printable_paths, "        {path_display} (sbom)").unwrap();
+  }
 
   log::info!(action = "Finished"; "{finished_bundles} {pluralised} at:\n{printable_paths}");
 
@@ -36,6 +36,76 @@ pub fn create_file(path: &Path) -> crate::Result<BufWriter<File>> {
   Ok(BufWriter::new(file))
 }
 
+/// Returns the `SOURCE_DATE_EPOCH` environment variable, the timestamp reproducible builds use
+/// instead of the current time. See <https://reproducible-builds.org/specs/source-date-epoch/>.
+pub fn source_date_epoch() -> Option<u64> {
+  std::env::var("SOURCE_DATE_EPOCH")
+    .ok()
+    .and_then(|epoch| epoch.trim().parse().ok())
+}
+
+/// Clamps a modification time to the [`Settings::source_date_epoch`](crate::Settings::source_date_epoch), if set,
+/// so the files created during the build do not embed the current time.
+#[allow(dead_code)]
+pub fn clamp_mtime(mtime: u64, source_date_epoch: Option<u64>) -> u64 {
+  source_date_epoch.map_or(mtime, |epoch| mtime.min(epoch))
+}
+
+/// Appends the file or directory at `src` to the tar archive as `dest`,
+/// with the content of directories sorted by file name.
+///
+/// Symbolic links are stored as links, unless `follow_symlinks` is set to store the files they point to.
+/// The entries are owned by root, with `0o755` or `0o644` permissions
+/// and modification times clamped to `source_date_epoch`, so the archive only depends on the file contents.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+pub fn append_to_tar<W: io::Write>(
+  builder: &mut tar::Builder<W>,
+  src: &Path,
+  dest: &Path,
+  source_date_epoch: Option<u64>,
+  follow_symlinks: bool,
+) -> crate::Result<()> {
+  use std::os::unix::fs::MetadataExt;
+
+  for entry in walkdir::WalkDir::new(src)
+    .follow_links(follow_symlinks)
+    .sort_by_file_name()
+  {
+    let entry = entry?;
+    let relative = entry.path().strip_prefix(src)?;
+    let dest_path = if relative.as_os_str().is_empty() {
+      dest.to_path_buf()
+    } else {
+      dest.join(relative)
+    };
+    if dest_path.as_os_str().is_empty() {
+      continue;
+    }
+
+    let metadata = if follow_symlinks {
+      entry.path().metadata()?
+    } else {
+      entry.path().symlink_metadata()?
+    };
+    let mut header = tar::Header::new_gnu();
+    header.set_metadata_in_mode(&metadata, tar::HeaderMode::Deterministic);
+    header.set_mtime(clamp_mtime(
+      metadata.mtime().max(0) as u64,
+      source_date_epoch,
+    ));
+
+    if metadata.file_type().is_symlink() {
+      let target = fs::read_link(entry.path())?;
+      builder.append_link(&mut header, &dest_path, target)?;
+    } else if metadata.is_dir() {
+      builder.append_data(&mut header, &dest_path, io::empty())?;
+    } else {
+      builder.append_data(&mut header, &dest_path, File::open(entry.path())?)?;
+    }
+  }
+  Ok(())
+}
+
 /// Makes a symbolic link to a directory.
 #[cfg(unix)]
 #[allow(dead_code)]
@@ -3,32 +3,103 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
+//! Builds the AppImage without network access.
+//!
+//! The Debian data folder is copied to an AppDir along with the GTK, WebKit and GStreamer runtime files
+//! and the shared libraries needed by every ELF file, found by walking their `DT_NEEDED` entries.
+//! The AppDir is then written to a squashfs image appended to the AppImage runtime,
+//! which is only downloaded when it is neither configured nor found in the tools directory.
+
+mod squashfs;
+
 use super::{
-  super::{
-    common::{self, CommandExt},
-    path_utils,
-  },
+  super::common,
   debian,
+  elf::{self, LibraryResolver},
+};
+use crate::{
+  bundle::{settings::Arch, windows::download},
+  Settings,
 };
-use crate::{bundle::settings::Arch, Settings};
 use anyhow::Context;
 use handlebars::Handlebars;
+use serde::Serialize;
 use std::{
-  collections::BTreeMap,
-  fs,
-  path::PathBuf,
-  process::{Command, Stdio},
+  fs::{self, File},
+  io::{self, Write},
+  os::unix::fs::{symlink, PermissionsExt},
+  path::{Path, PathBuf},
 };
 
+/// Libraries expected on every system, which must not be bundled.
+/// They are tied to the C library, the graphics drivers or the audio and display servers of the host.
+///
+/// Based on the [AppImage exclude list](https://github.com/AppImageCommunity/pkg2appimage/blob/master/excludelist).
+const EXCLUDED_LIBRARIES: &[&str] = &[
+  "ld-linux*.so.*",
+  "libanl.so.1",
+  "libBrokenLocale.so.1",
+  "libc.so.6",
+  "libdl.so.2",
+  "libm.so.6",
+  "libmvec.so.1",
+  "libnss_*.so.2",
+  "libpthread.so.0",
+  "libresolv.so.2",
+  "librt.so.1",
+  "libthread_db.so.1",
+  "libutil.so.1",
+  "libstdc++.so.6",
+  "libgcc_s.so.1",
+  "libGL.so.1",
+  "libGLX.so.0",
+  "libGLdispatch.so.0",
+  "libOpenGL.so.0",
+  "libEGL.so.1",
+  "libGLESv2.so.2",
+  "libdrm.so.2",
+  "libgbm.so.1",
+  "libvulkan.so.1",
+  "libX11.so.6",
+  "libX11-xcb.so.1",
+  "libxcb.so.1",
+  "libICE.so.6",
+  "libSM.so.6",
+  "libasound.so.2",
+  "libjack.so.0",
+  "libfontconfig.so.1",
+  "libfreetype.so.6",
+  "libharfbuzz.so.0",
+  "libfribidi.so.0",
+  "libexpat.so.1",
+  "libz.so.1",
+  "libcom_err.so.2",
+  "libgpg-error.so.0",
+  "libgmp.so.10",
+  "libp11-kit.so.0",
+  "libusb-1.0.so.0",
+  "libuuid.so.1",
+];
+
+/// The runtime files copied to the AppDir, configured by the AppRun script.
+#[derive(Default, Serialize)]
+struct RuntimeFiles<'a> {
+  main_binary: &'a str,
+  gsettings_schemas: bool,
+  gio_modules: bool,
+  gdk_pixbuf_loaders: bool,
+  gstreamer: bool,
+}
+
 /// Bundles the project.
 /// Returns a vector of PathBuf that shows where the AppImage was created.
 pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
   // generate the deb binary name
-  let arch: &str = match settings.binary_arch() {
-    Arch::X86_64 => "amd64",
-    Arch::X86 => "i386",
-    Arch::AArch64 => "aarch64",
-    Arch::Armhf => "armhf",
+  let (arch, runtime_arch) = match settings.binary_arch() {
+    Arch::X86_64 => ("amd64", "x86_64"),
+    Arch::X86 => ("i386", "i686"),
+    Arch::AArch64 => ("aarch64", "aarch64"),
+    Arch::Armhf => ("armhf", "armhf"),
     target => {
       return Err(crate::Error::ArchError(format!(
         "Unsupported architecture: {:?}",
@@ -72,13 +143,71 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
     arch
   );
   let appimage_path = output_path.join(&appimage_filename);
-  path_utils::create(app_dir_path, true)?;
 
-  // setup data to insert into shell script
-  let mut sh_map = BTreeMap::new();
-  sh_map.insert("arch", settings.target().split('-').next().unwrap());
-  sh_map.insert("product_name", settings.product_name());
-  sh_map.insert("appimage_filename", &appimage_filename);
+  // fail early, before copying the runtime files and libraries
+  let runtime_path = find_runtime(&settings, runtime_arch, &output_path)?;
+
+  log::info!(action = "Bundling"; "{} ({})", appimage_filename, appimage_path.display());
+
+  common::copy_dir(&data_dir.join("usr"), &app_dir_path.join("usr"))?;
+
+  let system = LibraryResolver::system();
+  let mut runtime_files = copy_runtime_files(&system, &app_dir_path)
+    .with_context(|| "Failed to copy the GTK and WebKit runtime files")?;
+  bundle_libraries(&settings, &system, &app_dir_path)
+    .with_context(|| "Failed to bundle the shared libraries")?;
+
+  runtime_files.main_binary = settings.main_binary_name()?;
+  generate_app_run(&runtime_files, &app_dir_path)?;
+
+  let larger_icon = icons
+    .iter()
+    .filter(|i| i.width == i.height)
+    .max_by_key(|i| i.width)
+    .expect("couldn't find a square icon to use as AppImage icon");
+  let larger_icon_path = larger_icon.path.strip_prefix(&data_dir)?;
+  fs::copy(&larger_icon.path, app_dir_path.join(".DirIcon"))?;
+  // the desktop entry references the icon by the main binary name
+  symlink(
+    larger_icon_path,
+    app_dir_path.join(format!("{}.png", settings.main_binary_name()?)),
+  )?;
+  let desktop_file_name = format!("{}.desktop", settings.product_name());
+  symlink(
+    Path::new("usr/share/applications").join(&desktop_file_name),
+    app_dir_path.join(&desktop_file_name),
+  )?;
+
+  let squashfs_path = output_path.join(format!("{appimage_filename}.squashfs"));
+  squashfs::create(&app_dir_path, &squashfs_path, settings.source_date_epoch())
+    .with_context(|| "Failed to create the AppImage filesystem")?;
+
+  // the runtime mounts the squashfs image appended to it
+  let mut appimage = common::create_file(&appimage_path)?;
+  io::copy(&mut File::open(&runtime_path)?, &mut appimage)?;
+  io::copy(&mut File::open(&squashfs_path)?, &mut appimage)?;
+  appimage.flush()?;
+  drop(appimage);
+  fs::set_permissions(&appimage_path, fs::Permissions::from_mode(0o755))?;
+
+  fs::remove_file(&squashfs_path)?;
+  fs::remove_dir_all(&package_dir)?;
+  Ok(vec![appimage_path])
+}
+
+/// Finds the AppImage runtime, either configured or in the tools directory,
+/// where the latest runtime is downloaded when it is missing.
+fn find_runtime(settings: &Settings, arch: &str, output_path: &Path) -> crate::Result<PathBuf> {
+  if let Some(runtime) = &settings.appimage().runtime {
+    return if runtime.is_file() {
+      Ok(runtime.clone())
+    } else {
+      Err(crate::Error::GenericError(format!(
+        "AppImage runtime {} does not exist",
+        runtime.display()
+      )))
+    };
+  }
 
   let tauri_tools_path = settings
     .local_tools_directory()
@@ -86,54 +215,254 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
     .unwrap_or_else(|| {
       dirs::cache_dir().map_or_else(|| output_path.to_path_buf(), |p| p.join("tauri"))
     });
+  let runtime = tauri_tools_path.join(format!("runtime-{arch}"));
+  if !runtime.is_file() {
+    let data = download(&format!(
+      "https://github.com/AppImage/type2-runtime/releases/download/continuous/runtime-{arch}"
+    ))
+    .with_context(|| {
+      format!(
+        "Failed to download the AppImage runtime. Download runtime-{arch} from https://github.com/AppImage/type2-runtime/releases and place it at {}, or set `bundle > linux > appimage > runtime`",
+        runtime.display()
+      )
+    })?;
+    fs::create_dir_all(&tauri_tools_path)?;
+    fs::write(&runtime, data)?;
+  }
+  Ok(runtime)
+}
 
-  std::fs::create_dir_all(&tauri_tools_path)?;
-  let tauri_tools_path_str = tauri_tools_path.to_string_lossy();
-  sh_map.insert("tauri_tools_path", &tauri_tools_path_str);
-  let larger_icon = icons
+/// Returns the first existing path relative to the given directories, with symlinks resolved.
+fn find_system_path(dirs: &[PathBuf], relative: &str) -> Option<PathBuf> {
+  dirs
     .iter()
-    .filter(|i| i.width == i.height)
-    .max_by_key(|i| i.width)
-    .expect("couldn't find a square icon to use as AppImage icon");
-  let larger_icon_path = larger_icon
-    .path
-    .strip_prefix(package_dir.join("data"))
-    .unwrap()
-    .to_string_lossy()
-    .to_string();
-  sh_map.insert("icon_path", &larger_icon_path);
-
-  // initialize shell script template.
+    .map(|dir| dir.join(relative))
+    .find(|path| path.exists())
+    .and_then(|path| path.canonicalize().ok())
+}
+
+/// Copies the files loaded at runtime by GTK, WebKit and GStreamer,
+/// and the optional tools and libraries requested by the CLI.
+fn copy_runtime_files<'a>(
+  system: &LibraryResolver,
+  app_dir: &Path,
+) -> crate::Result<RuntimeFiles<'a>> {
+  let mut runtime_files = RuntimeFiles::default();
+  let usr_lib = app_dir.join("usr/lib");
+
+  for (var, tool) in [
+    ("APPIMAGE_BUNDLE_XDG_OPEN", "xdg-open"),
+    ("APPIMAGE_BUNDLE_XDG_MIME", "xdg-mime"),
+  ] {
+    let path = Path::new("/usr/bin").join(tool);
+    if std::env::var(var).is_ok_and(|v| v != "0") && path.is_file() {
+      log::info!("Copying {}", path.display());
+      common::copy_file(&path, app_dir.join("usr/bin").join(tool))?;
+    }
+  }
+
+  if let Ok(tray_library) = std::env::var("TAURI_TRAY_LIBRARY_PATH") {
+    let tray_library = PathBuf::from(tray_library);
+    if let Some(soname) = tray_library.file_name().map(|n| n.to_string_lossy()) {
+      log::info!("Copying appindicator library {}", tray_library.display());
+      common::copy_file(&tray_library, usr_lib.join(&*soname))?;
+      // keep the name without the ABI version, expected by libappindicator-sys v0.7.1 and v0.7.2
+      if let Some(old_name) = soname.strip_suffix(".1") {
+        symlink(&*soname, usr_lib.join(old_name))?;
+      }
+    }
+  }
+
+  // the helper processes are kept at their system path, found by the patched WebKit library
+  let mut webkit_dirs: Vec<PathBuf> = system
+    .dirs()
+    .iter()
+    .chain(&[PathBuf::from("/usr/libexec")])
+    .map(|dir| dir.join("webkit2gtk-4.1"))
+    .filter(|dir| dir.is_dir())
+    .filter_map(|dir| dir.canonicalize().ok())
+    .collect();
+  webkit_dirs.sort();
+  webkit_dirs.dedup();
+  for webkit_dir in webkit_dirs {
+    let dest = app_dir.join(webkit_dir.strip_prefix("/")?);
+    if !dest.exists() {
+      common::copy_dir(&webkit_dir, &dest)?;
+    }
+  }
+
+  if let Some(loaders_dir) = find_system_path(system.dirs(), "gdk-pixbuf-2.0/2.10.0/loaders") {
+    let cache = fs::read_to_string(loaders_dir.with_file_name("loaders.cache"))?;
+    common::copy_dir(&loaders_dir, &usr_lib.join("gdk-pixbuf-2.0/loaders"))?;
+    fs::write(
+      usr_lib.join("gdk-pixbuf-2.0/loaders.cache"),
+      relocate_loaders_cache(&cache),
+    )?;
+    runtime_files.gdk_pixbuf_loaders = true;
+  }
+
+  if let Some(modules_dir) = find_system_path(system.dirs(), "gio/modules") {
+    common::copy_dir(&modules_dir, &usr_lib.join("gio/modules"))?;
+    runtime_files.gio_modules = true;
+  }
+
+  let schemas = Path::new("/usr/share/glib-2.0/schemas/gschemas.compiled");
+  if schemas.is_file() {
+    common::copy_file(
+      schemas,
+      app_dir.join("usr/share/glib-2.0/schemas/gschemas.compiled"),
+    )?;
+    runtime_files.gsettings_schemas = true;
+  }
+
+  if std::env::var("APPIMAGE_BUNDLE_GSTREAMER").is_ok_and(|v| v != "0") {
+    let plugins_dir = find_system_path(system.dirs(), "gstreamer-1.0").ok_or_else(|| {
+      crate::Error::GenericError("Could not find the GStreamer plugins directory".into())
+    })?;
+    common::copy_dir(&plugins_dir, &usr_lib.join("gstreamer-1.0"))?;
+
+    let mut scanner_dirs = system.dirs().to_vec();
+    scanner_dirs.push(PathBuf::from("/usr/libexec"));
+    let scanner = find_system_path(
+      &scanner_dirs,
+      "gstreamer1.0/gstreamer-1.0/gst-plugin-scanner",
+    )
+    .or_else(|| find_system_path(&scanner_dirs, "gstreamer-1.0/gst-plugin-scanner"))
+    .ok_or_else(|| {
+      crate::Error::GenericError("Could not find the GStreamer plugin scanner".into())
+    })?;
+    common::copy_file(
+      &scanner,
+      app_dir.join("usr/libexec/gstreamer-1.0/gst-plugin-scanner"),
+    )?;
+    runtime_files.gstreamer = true;
+  }
+
+  Ok(runtime_files)
+}
+
+/// Copies the shared libraries needed by the ELF files of the AppDir to `usr/lib`,
+/// except the excluded ones and the ones already bundled.
+fn bundle_libraries(
+  settings: &Settings,
+  system: &LibraryResolver,
+  app_dir: &Path,
+) -> crate::Result<()> {
+  let usr_lib = app_dir.join("usr/lib");
+  let excluded = &settings.appimage().exclude_libraries;
+
+  let mut files = Vec::new();
+  for entry in walkdir::WalkDir::new(app_dir) {
+    let entry = entry?;
+    if entry.file_type().is_file() {
+      files.push(entry.into_path());
+    }
+  }
+
+  let libraries = system.resolve(&files, |name| {
+    usr_lib.join(name).exists()
+      || EXCLUDED_LIBRARIES
+        .iter()
+        .copied()
+        .chain(excluded.iter().map(String::as_str))
+        .any(|pattern| elf::matches_wildcard(pattern, name))
+  })?;
+
+  for (name, path) in libraries {
+    log::debug!("Bundling {name} from {}", path.display());
+    let dest = usr_lib.join(&name);
+    common::copy_file(&path, &dest)?;
+
+    if name.starts_with("libwebkit2gtk-4.1.so") {
+      let mut library = fs::read(&dest)?;
+      relocate_usr_prefix(&mut library);
+      fs::write(&dest, library)?;
+    }
+  }
+
+  Ok(())
+}
+
+/// Replaces the `/usr` prefix of the paths embedded in the library by `././`, which has the same length,
+/// so they are resolved relative to the `usr` directory of the AppDir, the working directory set by AppRun.
+///
+/// Only the NUL-terminated strings starting with `/usr/` are patched, leaving other data and paths untouched.
+fn relocate_usr_prefix(library: &mut [u8]) {
+  const PREFIX: &[u8] = b"/usr/";
+  for i in 0..library.len() {
+    let starts_string = i == 0 || library[i - 1] == 0;
+    if starts_string && library[i..].starts_with(PREFIX) {
+      library[i..i + 4].copy_from_slice(b"././");
+    }
+  }
+}
+
+/// Makes the loader paths of a gdk-pixbuf `loaders.cache` file relative to `GDK_PIXBUF_MODULEDIR`.
+fn relocate_loaders_cache(cache: &str) -> String {
+  cache
+    .lines()
+    .map(|line| {
+      match line
+        .strip_prefix("\"/")
+        .and_then(|path| path.strip_suffix('"'))
+        .and_then(|path| path.rsplit('/').next())
+      {
+        Some(file_name) if file_name.ends_with(".so") => format!("\"{file_name}\"\n"),
+        _ => format!("{line}\n"),
+      }
+    })
+    .collect()
+}
+
+/// Writes the AppRun script, setting up the environment before running the main binary.
+fn generate_app_run(runtime_files: &RuntimeFiles<'_>, app_dir: &Path) -> crate::Result<()> {
   let mut handlebars = Handlebars::new();
   handlebars.register_escape_fn(handlebars::no_escape);
   handlebars
-    .register_template_string("appimage", include_str!("./appimage"))
+    .register_template_string("AppRun", include_str!("./AppRun"))
     .expect("Failed to register template for handlebars");
-  let temp = handlebars.render("appimage", &sh_map)?;
-
-  // create the shell script file in the target/ folder.
-  let sh_file = output_path.join("build_appimage.sh");
 
-  log::info!(action = "Bundling"; "{} ({})", appimage_filename, appimage_path.display());
+  let app_run_path = app_dir.join("AppRun");
+  fs::write(&app_run_path, handlebars.render("AppRun", runtime_files)?)?;
+  fs::set_permissions(&app_run_path, fs::Permissions::from_mode(0o755))?;
+  Ok(())
+}
 
-  fs::write(&sh_file, temp)?;
+#[cfg(test)]
+mod tests {
+  use super::{relocate_loaders_cache, relocate_usr_prefix};
 
-  // chmod script for execution
-  Command::new("chmod")
-    .arg("777")
-    .arg(&sh_file)
-    .current_dir(output_path.clone())
-    .stdout(Stdio::piped())
-    .stderr(Stdio::piped())
-    .output()
-    .expect("Failed to chmod script");
+  #[test]
+  fn relocates_webkit_paths() {
+    let mut library =
+      b"/usr/lib\0/usr/lib/x86_64-linux-gnu/webkit2gtk-4.1\0/usr/share/\0/opt/usr/lib\0".to_vec();
+    relocate_usr_prefix(&mut library);
+    assert_eq!(
+      library,
+      b"././/lib\0././/lib/x86_64-linux-gnu/webkit2gtk-4.1\0././/share/\0/opt/usr/lib\0"
+    );
 
-  // execute the shell script to build the appimage.
-  Command::new(&sh_file)
-    .current_dir(output_path)
-    .output_ok()
-    .context("error running build_appimage.sh")?;
+    // only the strings starting with the `/usr/` directory are paths to relocate
+    let mut library = b"\0/usr\0/usrlocal\0--prefix=/usr/\0\x8b/usr/\0".to_vec();
+    let unchanged = library.clone();
+    relocate_usr_prefix(&mut library);
+    assert_eq!(library, unchanged);
+  }
 
-  fs::remove_dir_all(&package_dir)?;
-  Ok(vec![appimage_path])
+  #[test]
+  fn relocates_loaders_cache() {
+    let cache = r#"# LoaderDir = /usr/lib/x86_64-linux-gnu/gdk-pixbuf-2.0/2.10.0/loaders
+"/usr/lib/x86_64-linux-gnu/gdk-pixbuf-2.0/2.10.0/loaders/libpixbufloader-png.so"
+"png" 5 "gdk-pixbuf" "PNG" "LGPL"
+"image/png" ""
+"#;
+    assert_eq!(
+      relocate_loaders_cache(cache),
+      r#"# LoaderDir = /usr/lib/x86_64-linux-gnu/gdk-pixbuf-2.0/2.10.0/loaders
+"libpixbufloader-png.so"
+"png" 5 "gdk-pixbuf" "PNG" "LGPL"
+"image/png" ""
+"#
+    );
+  }
 }
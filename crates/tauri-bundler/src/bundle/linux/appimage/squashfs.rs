@@ -0,0 +1,120 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{
+  fs::File,
+  io::{self, Read, Write},
+  os::unix::fs::{MetadataExt, PermissionsExt},
+  path::{Path, PathBuf},
+};
+
+use backhand::{compression::Compressor, FilesystemCompressor, FilesystemWriter, NodeHeader};
+
+use crate::bundle::common;
+
+/// A file opened on its first read and closed once read entirely,
+/// so writing a large AppDir does not exhaust the file descriptors.
+struct LazyFile {
+  path: PathBuf,
+  file: Option<File>,
+  done: bool,
+}
+
+impl Read for LazyFile {
+  fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    if self.done {
+      return Ok(0);
+    }
+    let file = match &mut self.file {
+      Some(file) => file,
+      None => self.file.insert(File::open(&self.path)?),
+    };
+    let read = file.read(buf)?;
+    if read == 0 && !buf.is_empty() {
+      self.done = true;
+      self.file = None;
+    }
+    Ok(read)
+  }
+}
+
+/// Writes the content of the directory to a gzip compressed squashfs image, sorted by file name and owned by root,
+/// with modification times clamped to `source_date_epoch`.
+pub fn create(dir: &Path, image_path: &Path, source_date_epoch: Option<u64>) -> crate::Result<()> {
+  let mut filesystem = FilesystemWriter::default();
+  filesystem.set_root_mode(0o755);
+  filesystem.set_compressor(FilesystemCompressor::new(Compressor::Gzip, None)?);
+  if let Some(epoch) = source_date_epoch {
+    filesystem.set_time(u32::try_from(epoch)?);
+  }
+
+  for entry in walkdir::WalkDir::new(dir).min_depth(1).sort_by_file_name() {
+    let entry = entry?;
+    let path = entry.path().strip_prefix(dir)?;
+    let metadata = entry.path().symlink_metadata()?;
+    let header = NodeHeader::new(
+      (metadata.permissions().mode() & 0o7777) as u16,
+      0,
+      0,
+      common::clamp_mtime(metadata.mtime().max(0) as u64, source_date_epoch)
+        .try_into()
+        .unwrap_or_default(),
+    );
+
+    if entry.file_type().is_symlink() {
+      let target = std::fs::read_link(entry.path())?;
+      filesystem.push_symlink(target.to_string_lossy().into_owned(), path, header)?;
+    } else if entry.file_type().is_dir() {
+      filesystem.push_dir(path, header)?;
+    } else {
+      let file = LazyFile {
+        path: entry.path().to_path_buf(),
+        file: None,
+        done: false,
+      };
+      filesystem.push_file(file, path, header)?;
+    }
+  }
+
+  let mut image = common::create_file(image_path)?;
+  filesystem.write(&mut image)?;
+  image.flush()?;
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::create;
+  use std::{
+    fs::{self, File},
+    os::unix::fs::symlink,
+    time::{Duration, SystemTime},
+  };
+
+  #[test]
+  fn creates_reproducibly() {
+    let temp = tempfile::tempdir().unwrap();
+    let app_dir = temp.path().join("Notes.AppDir");
+    fs::create_dir_all(app_dir.join("usr/bin")).unwrap();
+    let binary = app_dir.join("usr/bin/notes");
+    fs::write(&binary, b"notes binary").unwrap();
+    symlink("usr/bin/notes", app_dir.join("AppRun")).unwrap();
+    let image = temp.path().join("Notes.squashfs");
+
+    create(&app_dir, &image, Some(1700000000)).unwrap();
+    let first = fs::read(&image).unwrap();
+
+    // rebuilding the binary changes its modification time
+    File::options()
+      .write(true)
+      .open(&binary)
+      .unwrap()
+      .set_modified(SystemTime::now() + Duration::from_secs(60))
+      .unwrap();
+    create(&app_dir, &image, Some(1700000000)).unwrap();
+    let second = fs::read(&image).unwrap();
+
+    assert_eq!(first, second);
+  }
+}
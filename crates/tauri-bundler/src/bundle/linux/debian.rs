@@ -12,34 +12,52 @@
 //         md5sums                  # Checksums for files in data.tar.gz below
 //         postinst                 # Post-installation script (optional)
 //         prerm                    # Pre-uninstallation script (optional)
+//         postrm                   # Post-uninstallation script (optional)
 //     data.tar.gz             # Contains files to be installed:
 //         usr/bin/foobar                            # Binary executable file
 //         usr/share/applications/foobar.desktop     # Desktop file (for apps)
 //         usr/share/icons/hicolor/...               # Icon files (for apps)
+//         usr/share/mime/packages/foobar.xml        # MIME types of the file associations (optional)
 //         usr/lib/foobar/...                        # Other resource files
+//     _gpgorigin              # debsigs signature of the members above (optional)
+//
+// The control and data archives are compressed with xz or zstd instead of gzip
+// depending on the configured compression, changing their extension.
 //
 // For cargo-bundle, we put bundle resource files under /usr/lib/package_name/,
 // and then generate the desktop file and control file from the bundle
-// metadata, as well as generating the md5sums file.  Currently we do not
-// generate postinst or prerm files.
-
-use super::{super::common, freedesktop};
+// metadata, as well as generating the md5sums file.  The postinst and postrm
+// scripts refresh the MIME and desktop databases when the app declares MIME
+// types, in addition to the custom maintainer scripts.
+
+use super::{
+  super::common::{self, CommandExt},
+  depends::{self, PackageManager},
+  freedesktop,
+};
 use crate::{bundle::settings::Arch, Settings};
 use anyhow::Context;
 use flate2::{write::GzEncoder, Compression};
-use tar::HeaderMode;
+use tauri_utils::config::{DebCompression, DebSignatureStyle};
 use walkdir::WalkDir;
+use xz2::write::XzEncoder;
 
 use std::{
+  env,
   fs::{self, File, OpenOptions},
   io::{self, Write},
-  os::unix::fs::{MetadataExt, OpenOptionsExt},
+  os::unix::{
+    ffi::OsStrExt,
+    fs::{MetadataExt, OpenOptionsExt},
+  },
   path::{Path, PathBuf},
+  process::Command,
 };
 
 /// Bundles the project.
-/// Returns a vector of PathBuf that shows where the DEB was created.
-pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
+/// Returns a vector of PathBuf that shows where the DEB was created,
+/// and the packages it depends on, including the detected ones.
+pub fn bundle_project(settings: &Settings) -> crate::Result<(Vec<PathBuf>, Vec<String>)> {
   let arch = match settings.binary_arch() {
     Arch::X86_64 => "amd64",
     Arch::X86 => "i386",
@@ -78,7 +96,7 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
 
   // Generate control files.
   let control_dir = package_dir.join("control");
-  generate_control_file(settings, arch, &control_dir, &data_dir)
+  let dependencies = generate_control_file(settings, arch, &control_dir, &data_dir)
     .with_context(|| "Failed to create control file")?;
   generate_scripts(settings, &control_dir).with_context(|| "Failed to create control scripts")?;
   generate_md5sums(&control_dir, &data_dir).with_context(|| "Failed to create md5sums file")?;
@@ -89,17 +107,61 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
   create_file_with_data(&debian_binary_path, "2.0\n")
     .with_context(|| "Failed to create debian-binary file")?;
 
-  // Apply tar/gzip/ar to create the final package file.
-  let control_tar_gz_path =
-    tar_and_gzip_dir(control_dir).with_context(|| "Failed to tar/gzip control directory")?;
-  let data_tar_gz_path =
-    tar_and_gzip_dir(data_dir).with_context(|| "Failed to tar/gzip data directory")?;
-  create_archive(
-    vec![debian_binary_path, control_tar_gz_path, data_tar_gz_path],
-    &package_path,
+  // Apply tar/compression/ar to create the final package file.
+  let compression = settings.deb().compression;
+  let level = compression_level(compression, settings.deb().compression_level)?;
+  let control_tar_path = tar_and_compress_dir(
+    &control_dir,
+    compression,
+    level,
+    settings.source_date_epoch(),
   )
-  .with_context(|| "Failed to create package archive")?;
-  Ok(vec![package_path])
+  .with_context(|| "Failed to tar/compress control directory")?;
+  let data_tar_path =
+    tar_and_compress_dir(&data_dir, compression, level, settings.source_date_epoch())
+      .with_context(|| "Failed to tar/compress data directory")?;
+  let mut members = vec![debian_binary_path, control_tar_path, data_tar_path];
+
+  let signing_key = match env::var("TAURI_SIGNING_DEB_KEY") {
+    Ok(key) => Some(key),
+    Err(_) => settings
+      .deb()
+      .signing_key
+      .as_ref()
+      .map(|path| {
+        fs::read_to_string(path)
+          .with_context(|| format!("Failed to read signing key {}", path.display()))
+      })
+      .transpose()?,
+  };
+  let signature_style = settings.deb().signature_style;
+
+  // debsigs signs the concatenated members and adds the signature as the `_gpgorigin` member
+  if let (Some(key), DebSignatureStyle::Debsigs) = (&signing_key, signature_style) {
+    let signed_data_path = package_dir.join("signed-data");
+    let mut signed_data = common::create_file(&signed_data_path)?;
+    for member in &members {
+      io::copy(&mut File::open(member)?, &mut signed_data)?;
+    }
+    signed_data.flush()?;
+    let signature_path = package_dir.join("_gpgorigin");
+    gpg_sign(key, &signed_data_path, &signature_path, false)
+      .with_context(|| "Failed to sign package")?;
+    members.push(signature_path);
+  }
+
+  create_archive(members, &package_path, settings.source_date_epoch())
+    .with_context(|| "Failed to create package archive")?;
+  let mut bundles = vec![package_path.clone()];
+
+  if let (Some(key), DebSignatureStyle::Detached) = (&signing_key, signature_style) {
+    let signature_path = base_dir.join(format!("{package_name}.asc"));
+    gpg_sign(key, &package_path, &signature_path, true)
+      .with_context(|| "Failed to sign package")?;
+    bundles.push(signature_path);
+  }
+
+  Ok((bundles, dependencies))
 }
 
 /// Generate the debian data folders and files.
@@ -127,6 +189,15 @@ pub fn generate_data(
     .with_context(|| "Failed to create icon files")?;
   freedesktop::generate_desktop_file(settings, &settings.deb().desktop_template, &data_dir)
     .with_context(|| "Failed to create desktop file")?;
+  freedesktop::generate_metainfo_file(
+    settings,
+    settings.bundle_identifier(),
+    &format!("{}.desktop", settings.product_name()),
+    &data_dir,
+  )
+  .with_context(|| "Failed to create metainfo file")?;
+  freedesktop::generate_mime_file(settings, &data_dir)
+    .with_context(|| "Failed to create shared MIME-info file")?;
   generate_changelog_file(settings, &data_dir)
     .with_context(|| "Failed to create changelog.gz file")?;
 
@@ -152,12 +223,13 @@ fn generate_changelog_file(settings: &Settings, data_dir: &Path) -> crate::Resul
 }
 
 /// Generates the debian control file and stores it under the `control_dir`.
+/// Returns the packages listed in its `Depends` field.
 fn generate_control_file(
   settings: &Settings,
   arch: &str,
   control_dir: &Path,
   data_dir: &Path,
-) -> crate::Result<()> {
+) -> crate::Result<Vec<String>> {
   // For more information about the format of this file, see
   // https://www.debian.org/doc/debian-policy/ch-controlfields.html
   let dest_path = control_dir.join("control");
@@ -194,18 +266,26 @@ fn generate_control_file(
     writeln!(file, "Homepage: {}", homepage)?;
   }
 
-  let dependencies = settings.deb().depends.as_ref().cloned().unwrap_or_default();
+  let mut dependencies = settings.deb().depends.as_ref().cloned().unwrap_or_default();
+  if settings.deb().auto_depends {
+    let detected = depends::detect(
+      settings,
+      PackageManager::Dpkg,
+      settings.deb().auto_depends_mapping.as_deref(),
+    )?;
+    depends::merge(&mut dependencies, detected);
+  }
   if !dependencies.is_empty() {
     writeln!(file, "Depends: {}", dependencies.join(", "))?;
   }
-  let dependencies = settings
+  let recommends = settings
     .deb()
     .recommends
     .as_ref()
     .cloned()
     .unwrap_or_default();
-  if !dependencies.is_empty() {
-    writeln!(file, "Recommends: {}", dependencies.join(", "))?;
+  if !recommends.is_empty() {
+    writeln!(file, "Recommends: {}", recommends.join(", "))?;
   }
   let provides = settings
     .deb()
@@ -252,7 +332,7 @@ fn generate_control_file(
     }
   }
   file.flush()?;
-  Ok(())
+  Ok(dependencies)
 }
 
 fn generate_scripts(settings: &Settings, control_dir: &Path) -> crate::Result<()> {
@@ -261,9 +341,10 @@ fn generate_scripts(settings: &Settings, control_dir: &Path) -> crate::Result<()
     create_script_file_from_path(script_path, &dest_path)?
   }
 
-  if let Some(script_path) = &settings.deb().post_install_script {
+  let post_install_script = settings.deb().post_install_script.as_deref();
+  if let Some(script) = freedesktop::post_maintainer_script(settings, post_install_script)? {
     let dest_path = control_dir.join("postinst");
-    create_script_file_from_path(script_path, &dest_path)?
+    create_script_file(&script, &dest_path)?
   }
 
   if let Some(script_path) = &settings.deb().pre_remove_script {
@@ -271,13 +352,25 @@ fn generate_scripts(settings: &Settings, control_dir: &Path) -> crate::Result<()
     create_script_file_from_path(script_path, &dest_path)?
   }
 
-  if let Some(script_path) = &settings.deb().post_remove_script {
+  let post_remove_script = settings.deb().post_remove_script.as_deref();
+  if let Some(script) = freedesktop::post_maintainer_script(settings, post_remove_script)? {
     let dest_path = control_dir.join("postrm");
-    create_script_file_from_path(script_path, &dest_path)?
+    create_script_file(&script, &dest_path)?
   }
   Ok(())
 }
 
+fn create_script_file(script: &[u8], to: &PathBuf) -> crate::Result<()> {
+  let mut file = OpenOptions::new()
+    .create(true)
+    .truncate(true)
+    .write(true)
+    .mode(0o755)
+    .open(to)?;
+  file.write_all(script)?;
+  Ok(())
+}
+
 fn create_script_file_from_path(from: &PathBuf, to: &PathBuf) -> crate::Result<()> {
   let mut from = File::open(from)?;
   let mut file = OpenOptions::new()
@@ -295,7 +388,7 @@ fn create_script_file_from_path(from: &PathBuf, to: &PathBuf) -> crate::Result<(
 fn generate_md5sums(control_dir: &Path, data_dir: &Path) -> crate::Result<()> {
   let md5sums_path = control_dir.join("md5sums");
   let mut md5sums_file = common::create_file(&md5sums_path)?;
-  for entry in WalkDir::new(data_dir) {
+  for entry in WalkDir::new(data_dir).sort_by_file_name() {
     let entry = entry?;
     let path = entry.path();
     if path.is_dir() {
@@ -344,53 +437,274 @@ fn total_dir_size(dir: &Path) -> crate::Result<u64> {
 }
 
 /// Writes a tar file to the given writer containing the given directory.
-fn create_tar_from_dir<P: AsRef<Path>, W: Write>(src_dir: P, dest_file: W) -> crate::Result<W> {
-  let src_dir = src_dir.as_ref();
+fn create_tar_from_dir<P: AsRef<Path>, W: Write>(
+  src_dir: P,
+  dest_file: W,
+  source_date_epoch: Option<u64>,
+) -> crate::Result<W> {
   let mut tar_builder = tar::Builder::new(dest_file);
-  for entry in WalkDir::new(src_dir) {
-    let entry = entry?;
-    let src_path = entry.path();
-    if src_path == src_dir {
-      continue;
-    }
-    let dest_path = src_path.strip_prefix(src_dir)?;
-    let stat = fs::metadata(src_path)?;
-    let mut header = tar::Header::new_gnu();
-    header.set_metadata_in_mode(&stat, HeaderMode::Deterministic);
-    header.set_mtime(stat.mtime() as u64);
-
-    if entry.file_type().is_dir() {
-      tar_builder.append_data(&mut header, dest_path, &mut io::empty())?;
-    } else {
-      let mut src_file = fs::File::open(src_path)?;
-      tar_builder.append_data(&mut header, dest_path, &mut src_file)?;
-    }
-  }
+  common::append_to_tar(
+    &mut tar_builder,
+    src_dir.as_ref(),
+    Path::new(""),
+    source_date_epoch,
+    true,
+  )?;
   let dest_file = tar_builder.into_inner()?;
   Ok(dest_file)
 }
 
-/// Creates a `.tar.gz` file from the given directory (placing the new file
-/// within the given directory's parent directory), then deletes the original
-/// directory and returns the path to the new file.
-fn tar_and_gzip_dir<P: AsRef<Path>>(src_dir: P) -> crate::Result<PathBuf> {
-  let src_dir = src_dir.as_ref();
-  let dest_path = src_dir.with_extension("tar.gz");
+/// Validates the compression level, returning the default level of the algorithm if it is not set.
+fn compression_level(compression: DebCompression, level: Option<u32>) -> crate::Result<u32> {
+  let (levels, default) = match compression {
+    DebCompression::Gzip | DebCompression::Xz => (0..=9, 6),
+    DebCompression::Zstd => (1..=22, 3),
+  };
+  match level {
+    Some(level) if !levels.contains(&level) => Err(crate::Error::GenericError(format!(
+      "Invalid {compression:?} compression level {level}, expected a level between {} and {}",
+      levels.start(),
+      levels.end()
+    ))),
+    level => Ok(level.unwrap_or(default)),
+  }
+}
+
+/// Creates a compressed tar file from the given directory, placing the new file
+/// within the given directory's parent directory, and returns the path to the new file.
+fn tar_and_compress_dir(
+  src_dir: &Path,
+  compression: DebCompression,
+  level: u32,
+  source_date_epoch: Option<u64>,
+) -> crate::Result<PathBuf> {
+  let dest_path = src_dir.with_extension(match compression {
+    DebCompression::Gzip => "tar.gz",
+    DebCompression::Xz => "tar.xz",
+    DebCompression::Zstd => "tar.zst",
+  });
   let dest_file = common::create_file(&dest_path)?;
-  let gzip_encoder = GzEncoder::new(dest_file, Compression::default());
-  let gzip_encoder = create_tar_from_dir(src_dir, gzip_encoder)?;
-  let mut dest_file = gzip_encoder.finish()?;
+  let mut dest_file = match compression {
+    DebCompression::Gzip => create_tar_from_dir(
+      src_dir,
+      GzEncoder::new(dest_file, Compression::new(level)),
+      source_date_epoch,
+    )?
+    .finish()?,
+    DebCompression::Xz => {
+      create_tar_from_dir(src_dir, XzEncoder::new(dest_file, level), source_date_epoch)?.finish()?
+    }
+    DebCompression::Zstd => {
+      let encoder = zstd::Encoder::new(dest_file, i32::try_from(level)?)?;
+      create_tar_from_dir(src_dir, encoder, source_date_epoch)?.finish()?
+    }
+  };
   dest_file.flush()?;
   Ok(dest_path)
 }
 
+/// Writes a detached signature of the file made with the ASCII-armored GPG secret key,
+/// imported in a temporary keyring so the user keyring is left untouched.
+///
+/// The key passphrase is read from the `TAURI_SIGNING_DEB_KEY_PASSPHRASE` environment variable.
+fn gpg_sign(key: &str, src: &Path, dest: &Path, armor: bool) -> crate::Result<()> {
+  let home = tempfile::tempdir()?;
+  let key_path = home.path().join("key.asc");
+  fs::write(&key_path, key)?;
+  let passphrase_path = home.path().join("passphrase");
+  fs::write(
+    &passphrase_path,
+    env::var("TAURI_SIGNING_DEB_KEY_PASSPHRASE").unwrap_or_default(),
+  )?;
+
+  let gpg = || {
+    let mut cmd = Command::new("gpg");
+    cmd
+      .arg("--homedir")
+      .arg(home.path())
+      .args(["--batch", "--yes", "--pinentry-mode", "loopback"])
+      .arg("--passphrase-file")
+      .arg(&passphrase_path);
+    cmd
+  };
+
+  let result = gpg()
+    .arg("--import")
+    .arg(&key_path)
+    .output_ok()
+    .and_then(|_| {
+      let mut cmd = gpg();
+      if armor {
+        cmd.arg("--armor");
+      }
+      cmd
+        .arg("--output")
+        .arg(dest)
+        .arg("--detach-sign")
+        .arg(src)
+        .output_ok()
+    });
+
+  // stop the agent started for the temporary keyring
+  let _ = Command::new("gpgconf")
+    .arg("--homedir")
+    .arg(home.path())
+    .args(["--kill", "gpg-agent"])
+    .output();
+
+  result.map(|_| ())
+}
+
 /// Creates an `ar` archive from the given source files and writes it to the
 /// given destination path.
-fn create_archive(srcs: Vec<PathBuf>, dest: &Path) -> crate::Result<()> {
+fn create_archive(
+  srcs: Vec<PathBuf>,
+  dest: &Path,
+  source_date_epoch: Option<u64>,
+) -> crate::Result<()> {
   let mut builder = ar::Builder::new(common::create_file(dest)?);
   for path in &srcs {
-    builder.append_path(path)?;
+    let metadata = fs::metadata(path)?;
+    let name = path.file_name().expect("archive member has no file name");
+    let mut header = ar::Header::new(name.as_bytes().to_vec(), metadata.len());
+    header.set_mtime(common::clamp_mtime(
+      metadata.mtime().max(0) as u64,
+      source_date_epoch,
+    ));
+    header.set_uid(0);
+    header.set_gid(0);
+    header.set_mode(0o100644);
+    builder.append(&header, File::open(path)?)?;
   }
   builder.into_inner()?.flush()?;
   Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+  use super::{bundle_project, compression_level};
+  use crate::bundle::settings::{
+    test_settings, test_settings_builder, BundleSettings, DebianSettings,
+  };
+  use flate2::read::GzDecoder;
+  use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::Read,
+    time::{Duration, SystemTime},
+  };
+  use tauri_utils::config::DebCompression;
+
+  #[test]
+  fn bundles_reproducibly() {
+    let out_dir = tempfile::tempdir().unwrap();
+    let binary = out_dir.path().join("notes");
+    fs::write(&binary, b"notes binary").unwrap();
+    let settings = test_settings_builder(out_dir.path(), BundleSettings::default())
+      .source_date_epoch(1700000000)
+      .build()
+      .unwrap();
+
+    let first = fs::read(&bundle_project(&settings).unwrap().0[0]).unwrap();
+
+    // rebuilding the binary changes its modification time
+    File::options()
+      .write(true)
+      .open(&binary)
+      .unwrap()
+      .set_modified(SystemTime::now() + Duration::from_secs(60))
+      .unwrap();
+    let second = fs::read(&bundle_project(&settings).unwrap().0[0]).unwrap();
+
+    assert_eq!(first, second);
+  }
+
+  #[test]
+  fn validates_compression_levels() {
+    assert_eq!(compression_level(DebCompression::Gzip, None).unwrap(), 6);
+    assert_eq!(compression_level(DebCompression::Xz, Some(9)).unwrap(), 9);
+    assert_eq!(compression_level(DebCompression::Zstd, None).unwrap(), 3);
+    assert_eq!(
+      compression_level(DebCompression::Zstd, Some(19)).unwrap(),
+      19
+    );
+    assert!(compression_level(DebCompression::Gzip, Some(10)).is_err());
+    assert!(compression_level(DebCompression::Zstd, Some(0)).is_err());
+  }
+
+  #[test]
+  fn compresses_with_configured_algorithm() {
+    let out_dir = tempfile::tempdir().unwrap();
+    fs::write(out_dir.path().join("notes"), b"notes binary").unwrap();
+    let settings = test_settings(
+      out_dir.path(),
+      BundleSettings {
+        deb: DebianSettings {
+          compression: DebCompression::Zstd,
+          compression_level: Some(19),
+          ..Default::default()
+        },
+        ..Default::default()
+      },
+    );
+
+    let package = File::open(&bundle_project(&settings).unwrap().0[0]).unwrap();
+    let mut archive = ar::Archive::new(package);
+    let mut members = Vec::new();
+    while let Some(entry) = archive.next_entry() {
+      members.push(String::from_utf8(entry.unwrap().header().identifier().to_vec()).unwrap());
+    }
+    assert_eq!(
+      members,
+      ["debian-binary", "control.tar.zst", "data.tar.zst"]
+    );
+  }
+
+  #[test]
+  fn installs_mime_info() {
+    let out_dir = tempfile::tempdir().unwrap();
+    fs::write(out_dir.path().join("notes"), b"notes binary").unwrap();
+    let settings = test_settings(
+      out_dir.path(),
+      BundleSettings {
+        identifier: Some("com.tauri.app".into()),
+        file_associations: Some(
+          serde_json::from_str(r#"[{ "ext": ["doc"], "mimeType": "application/x-app-doc" }]"#)
+            .unwrap(),
+        ),
+        ..Default::default()
+      },
+    );
+
+    let package = File::open(&bundle_project(&settings).unwrap().0[0]).unwrap();
+    let mut archive = ar::Archive::new(package);
+    let mut control = BTreeMap::new();
+    let mut data = BTreeMap::new();
+    while let Some(entry) = archive.next_entry() {
+      let entry = entry.unwrap();
+      let files = match entry.header().identifier() {
+        b"control.tar.gz" => &mut control,
+        b"data.tar.gz" => &mut data,
+        _ => continue,
+      };
+      let mut tar = tar::Archive::new(GzDecoder::new(entry));
+      for file in tar.entries().unwrap() {
+        let mut file = file.unwrap();
+        let path = file.path().unwrap().to_string_lossy().into_owned();
+        let mut content = String::new();
+        file.read_to_string(&mut content).unwrap();
+        files.insert(path, content);
+      }
+    }
+
+    let mime_info = &data["usr/share/mime/packages/com.tauri.app.xml"];
+    assert!(mime_info.contains("<mime-type type=\"application/x-app-doc\">"));
+    assert!(mime_info.contains("<glob pattern=\"*.doc\" />"));
+    assert!(data["usr/share/applications/Notes.desktop"].contains("MimeType=application/x-app-doc"));
+    for script in ["postinst", "postrm"] {
+      assert!(control[script].starts_with("#!/bin/sh\n"));
+      assert!(control[script].contains("update-mime-database /usr/share/mime"));
+      assert!(control[script].contains("update-desktop-database -q /usr/share/applications"));
+    }
+  }
+}
@@ -0,0 +1,321 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Detection of the packages providing the shared libraries needed by the binaries.
+
+use std::{
+  collections::{BTreeMap, HashMap},
+  io::ErrorKind,
+  path::{Path, PathBuf},
+  process::Command,
+};
+
+use anyhow::Context;
+
+use super::elf::{self, LibraryResolver};
+use crate::Settings;
+
+/// The package manager queried for the package owning a library.
+#[derive(Debug, Clone, Copy)]
+pub enum PackageManager {
+  /// `dpkg -S`, for Debian packages.
+  Dpkg,
+  /// `rpm -qf`, for RPM packages.
+  Rpm,
+}
+
+impl PackageManager {
+  fn program(self) -> &'static str {
+    match self {
+      Self::Dpkg => "dpkg",
+      Self::Rpm => "rpm",
+    }
+  }
+
+  /// Returns the name of the package owning the file, if any.
+  fn owner(self, path: &Path) -> crate::Result<Option<String>> {
+    let mut command = Command::new(self.program());
+    match self {
+      Self::Dpkg => command.arg("-S"),
+      Self::Rpm => command.args(["-qf", "--queryformat", "%{NAME}\\n"]),
+    };
+    let output = match command.arg(path).output() {
+      Ok(output) => output,
+      Err(error) if error.kind() == ErrorKind::NotFound => {
+        return Err(crate::Error::GenericError(format!(
+          "`{}` was not found, it is needed by `autoDepends` to find the packages providing the libraries linked by the app; install it or list the packages in `autoDependsMapping`",
+          self.program()
+        )))
+      }
+      Err(error) => {
+        return Err(
+          anyhow::Error::new(error)
+            .context(format!("Failed to run `{}`", self.program()))
+            .into(),
+        )
+      }
+    };
+    if !output.status.success() {
+      return Ok(None);
+    }
+    let output = String::from_utf8_lossy(&output.stdout);
+
+    Ok(match self {
+      Self::Dpkg => parse_dpkg_search(&output),
+      Self::Rpm => output
+        .lines()
+        .next()
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty()),
+    })
+  }
+
+  /// Formats a dependency on at least the given version of the package.
+  fn at_least(self, package: &str, version: &str) -> String {
+    match self {
+      Self::Dpkg => format!("{package} (>= {version})"),
+      Self::Rpm => format!("{package} >= {version}"),
+    }
+  }
+}
+
+/// Parses a `GLIBC_2.34` symbol version into its version numbers.
+fn glibc_version(symbol_version: &str) -> Option<Vec<u32>> {
+  symbol_version
+    .strip_prefix("GLIBC_")?
+    .split('.')
+    .map(|number| number.parse().ok())
+    .collect()
+}
+
+/// Parses the output of `dpkg -S`, e.g. `libgtk-3-0t64:amd64: /usr/lib/x86_64-linux-gnu/libgtk-3.so.0`,
+/// returning the package name without its architecture qualifier.
+fn parse_dpkg_search(output: &str) -> Option<String> {
+  let line = output.lines().find(|line| !line.starts_with("diversion"))?;
+  let (packages, _path) = line.split_once(": ")?;
+  let package = packages.split(", ").next()?;
+  let package = package.split(':').next()?.trim();
+  (!package.is_empty()).then(|| package.to_string())
+}
+
+/// The paths the package database may know the library by, as `/lib` is a symlink to `/usr/lib` on merged-usr systems.
+fn candidate_paths(path: &Path) -> Vec<PathBuf> {
+  let mut candidates = vec![path.to_path_buf()];
+  candidates.extend(path.canonicalize().ok());
+  for candidate in candidates.clone() {
+    candidates.push(match candidate.strip_prefix("/usr") {
+      Ok(stripped) => Path::new("/").join(stripped),
+      Err(_) => Path::new("/usr").join(candidate.strip_prefix("/").unwrap_or(&candidate)),
+    });
+  }
+
+  let mut unique = Vec::new();
+  for candidate in candidates {
+    if !unique.contains(&candidate) {
+      unique.push(candidate);
+    }
+  }
+  unique
+}
+
+/// Finds the packages providing the shared libraries needed by the binaries and the sidecars,
+/// through the mapping file or by querying the package manager.
+///
+/// The package providing glibc gets a version constraint on the newest `GLIBC_` symbol version the binaries need.
+/// The libraries that could not be mapped to a package are logged.
+pub fn detect(
+  settings: &Settings,
+  package_manager: PackageManager,
+  mapping: Option<&Path>,
+) -> crate::Result<Vec<String>> {
+  let mapping: HashMap<String, Option<String>> = match mapping {
+    Some(path) => serde_json::from_str(
+      &std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read dependency mapping {}", path.display()))?,
+    )
+    .with_context(|| format!("Failed to parse dependency mapping {}", path.display()))?,
+    None => HashMap::new(),
+  };
+
+  let mut binaries: Vec<PathBuf> = settings
+    .binaries()
+    .iter()
+    .map(|bin| settings.binary_path(bin))
+    .collect();
+  for sidecar in settings.external_binaries() {
+    binaries.push(sidecar?);
+  }
+
+  let resolver = LibraryResolver::system();
+  // the packages with the newest glibc version needed from them
+  let mut packages = BTreeMap::<String, Option<Vec<u32>>>::new();
+  for binary in binaries {
+    let Some(info) = elf::read(&binary)? else {
+      continue;
+    };
+    for library in &info.needed {
+      let package = match mapping.get(library) {
+        Some(package) => package.clone(),
+        None => match resolver.find(library, &info) {
+          Some(path) => {
+            let mut owner = None;
+            for path in candidate_paths(&path) {
+              owner = package_manager.owner(&path)?;
+              if owner.is_some() {
+                break;
+              }
+            }
+            owner
+          }
+          None => None,
+        },
+      };
+      let Some(package) = package else {
+        if !mapping.contains_key(library) {
+          log::warn!(
+            "Could not find the package providing {library}, needed by {}",
+            binary.display()
+          );
+        }
+        continue;
+      };
+
+      log::debug!("{library} is provided by {package}");
+      let glibc = info
+        .needed_versions
+        .get(library)
+        .into_iter()
+        .flatten()
+        .filter_map(|version| glibc_version(version))
+        .max();
+      let newest = packages.entry(package).or_default();
+      if glibc > *newest {
+        *newest = glibc;
+      }
+    }
+  }
+
+  Ok(
+    packages
+      .into_iter()
+      .map(|(package, glibc)| match glibc {
+        Some(version) => {
+          let version = version
+            .iter()
+            .map(|number| number.to_string())
+            .collect::<Vec<_>>()
+            .join(".");
+          package_manager.at_least(&package, &version)
+        }
+        None => package,
+      })
+      .collect(),
+  )
+}
+
+/// Adds the detected packages to the dependencies, unless they are already listed, in which case the listed constraint wins.
+pub fn merge(dependencies: &mut Vec<String>, detected: Vec<String>) {
+  let name = |dependency: &str| {
+    dependency
+      .split(|c: char| c.is_whitespace() || c == '(' || c == '<' || c == '>' || c == '=')
+      .next()
+      .unwrap_or_default()
+      .to_string()
+  };
+  for package in detected {
+    if !dependencies.iter().any(|d| name(d) == name(&package)) {
+      dependencies.push(package);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{detect, glibc_version, merge, parse_dpkg_search, PackageManager};
+  use crate::{
+    bundle::{linux::elf, settings::test_settings},
+    BundleSettings,
+  };
+  use std::collections::HashMap;
+
+  #[test]
+  fn parses_dpkg_search() {
+    assert_eq!(
+      parse_dpkg_search("libgtk-3-0t64:amd64: /usr/lib/x86_64-linux-gnu/libgtk-3.so.0\n")
+        .as_deref(),
+      Some("libgtk-3-0t64")
+    );
+    assert_eq!(
+      parse_dpkg_search("libc6:amd64, libc6:i386: /usr/share/doc/libc6\n").as_deref(),
+      Some("libc6")
+    );
+    assert_eq!(
+      parse_dpkg_search(
+        "diversion by libc6 from: /lib64/ld-linux-x86-64.so.2\ndiversion by libc6 to: /lib64/ld-linux-x86-64.so.2.usr-is-merged\nlibc6:amd64: /lib64/ld-linux-x86-64.so.2\n"
+      )
+      .as_deref(),
+      Some("libc6")
+    );
+    assert_eq!(parse_dpkg_search(""), None);
+  }
+
+  #[test]
+  fn merges_with_user_dependencies() {
+    let mut dependencies = vec!["libc6 (>= 2.31)".to_string(), "glibc >= 2.28".to_string()];
+    merge(
+      &mut dependencies,
+      vec![
+        "glibc >= 2.34".into(),
+        "libc6 (>= 2.34)".into(),
+        "libssl3".into(),
+      ],
+    );
+    assert_eq!(
+      dependencies,
+      ["libc6 (>= 2.31)", "glibc >= 2.28", "libssl3"]
+    );
+  }
+
+  #[test]
+  fn constrains_glibc_version() {
+    assert_eq!(glibc_version("GLIBC_2.34"), Some(vec![2, 34]));
+    assert_eq!(glibc_version("GLIBC_2.2.5"), Some(vec![2, 2, 5]));
+    assert_eq!(glibc_version("GLIBC_PRIVATE"), None);
+    assert_eq!(glibc_version("GCC_3.0"), None);
+    assert!(glibc_version("GLIBC_2.34") > glibc_version("GLIBC_2.4"));
+
+    assert_eq!(
+      PackageManager::Dpkg.at_least("libc6", "2.34"),
+      "libc6 (>= 2.34)"
+    );
+    assert_eq!(
+      PackageManager::Rpm.at_least("glibc", "2.34"),
+      "glibc >= 2.34"
+    );
+  }
+
+  #[test]
+  fn detects_mapped_packages() {
+    let out_dir = tempfile::tempdir().unwrap();
+    let binary = out_dir.path().join("notes");
+    std::fs::copy(std::env::current_exe().unwrap(), &binary).unwrap();
+    let info = elf::read(&binary).unwrap().unwrap();
+    // every library is mapped, so the package manager of the host is not queried
+    let mapping: HashMap<_, _> = info
+      .needed
+      .iter()
+      .map(|library| {
+        let package = library.starts_with("libc.so").then_some("libc6");
+        (library.clone(), package)
+      })
+      .collect();
+    let mapping_path = out_dir.path().join("mapping.json");
+    std::fs::write(&mapping_path, serde_json::to_string(&mapping).unwrap()).unwrap();
+    let settings = test_settings(out_dir.path(), BundleSettings::default());
+
+    let detected = detect(&settings, PackageManager::Dpkg, Some(&mapping_path)).unwrap();
+    assert_eq!(detected.len(), 1);
+    assert!(detected[0].starts_with("libc6 (>= 2."), "{detected:?}");
+  }
+}
@@ -0,0 +1,312 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Resolution of the shared libraries needed by ELF files, following their `DT_NEEDED` entries
+//! the same way the dynamic linker does.
+
+use std::{
+  collections::{BTreeMap, VecDeque},
+  fs::{self, File},
+  io::{self, Read},
+  path::{Path, PathBuf},
+};
+
+use goblin::elf::Elf;
+
+const ELF_MAGIC: &[u8; 4] = b"\x7fELF";
+
+/// The dynamic linking information of an ELF file.
+#[derive(Debug)]
+pub struct ElfInfo {
+  /// The target machine, used to skip libraries built for another architecture.
+  pub machine: u16,
+  /// Whether the file is a 64-bit ELF file.
+  pub is_64: bool,
+  /// The `DT_NEEDED` entries.
+  pub needed: Vec<String>,
+  /// The symbol versions needed from each library, e.g. `GLIBC_2.34` from `libc.so.6`.
+  pub needed_versions: BTreeMap<String, Vec<String>>,
+  /// The `DT_RUNPATH` and `DT_RPATH` entries, with `$ORIGIN` expanded.
+  pub runpaths: Vec<PathBuf>,
+}
+
+/// Reads the dynamic linking information of the file at the given path.
+///
+/// Returns `None` if the file is not an ELF file or could not be parsed.
+pub fn read(path: &Path) -> crate::Result<Option<ElfInfo>> {
+  // only the magic number is read from the other files
+  let mut magic = [0; 4];
+  match File::open(path)?.read_exact(&mut magic) {
+    Ok(()) if &magic == ELF_MAGIC => {}
+    Ok(()) => return Ok(None),
+    Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+    Err(error) => return Err(error.into()),
+  }
+
+  let bytes = fs::read(path)?;
+  let elf = match Elf::parse(&bytes) {
+    Ok(elf) => elf,
+    Err(error) => {
+      log::debug!("Skipping {}: {error}", path.display());
+      return Ok(None);
+    }
+  };
+
+  let origin = path.parent().unwrap_or_else(|| Path::new("/"));
+  let runpaths = elf
+    .runpaths
+    .iter()
+    .chain(&elf.rpaths)
+    .flat_map(|paths| paths.split(':'))
+    .filter(|path| !path.is_empty())
+    .map(|path| {
+      let origin = origin.to_string_lossy();
+      PathBuf::from(
+        path
+          .replace("${ORIGIN}", &origin)
+          .replace("$ORIGIN", &origin),
+      )
+    })
+    .collect();
+
+  let mut needed_versions = BTreeMap::<String, Vec<String>>::new();
+  for verneed in elf.verneed.iter().flat_map(|section| section.iter()) {
+    let Some(library) = elf.dynstrtab.get_at(verneed.vn_file) else {
+      continue;
+    };
+    needed_versions
+      .entry(library.to_string())
+      .or_default()
+      .extend(
+        verneed
+          .iter()
+          .filter_map(|vernaux| elf.dynstrtab.get_at(vernaux.vna_name))
+          .map(|version| version.to_string()),
+      );
+  }
+
+  Ok(Some(ElfInfo {
+    machine: elf.header.e_machine,
+    is_64: elf.is_64,
+    needed: elf.libraries.iter().map(|s| s.to_string()).collect(),
+    needed_versions,
+    runpaths,
+  }))
+}
+
+/// Finds shared libraries in the library search path of the system.
+pub struct LibraryResolver {
+  dirs: Vec<PathBuf>,
+}
+
+impl LibraryResolver {
+  /// Uses `LD_LIBRARY_PATH`, the directories configured in `/etc/ld.so.conf` and the default directories.
+  pub fn system() -> Self {
+    let mut dirs: Vec<PathBuf> = std::env::var_os("LD_LIBRARY_PATH")
+      .map(|paths| std::env::split_paths(&paths).collect())
+      .unwrap_or_default();
+    dirs.extend(read_ld_so_conf(Path::new("/etc/ld.so.conf")));
+    dirs.extend(
+      ["/lib64", "/usr/lib64", "/lib", "/usr/lib"]
+        .iter()
+        .map(PathBuf::from),
+    );
+
+    let mut unique = Vec::new();
+    for dir in dirs {
+      if dir.is_dir() && !unique.contains(&dir) {
+        unique.push(dir);
+      }
+    }
+    Self { dirs: unique }
+  }
+
+  /// The directories searched for libraries, in order.
+  pub fn dirs(&self) -> &[PathBuf] {
+    &self.dirs
+  }
+
+  /// Finds the library needed by a file with the given ELF information.
+  pub fn find(&self, name: &str, needed_by: &ElfInfo) -> Option<PathBuf> {
+    // a name with a slash is a path to the library
+    if name.contains('/') {
+      return Some(PathBuf::from(name)).filter(|path| path.is_file());
+    }
+    needed_by
+      .runpaths
+      .iter()
+      .chain(&self.dirs)
+      .map(|dir| dir.join(name))
+      .find(|path| {
+        path.is_file()
+          && read(path).ok().flatten().is_some_and(|library| {
+            library.machine == needed_by.machine && library.is_64 == needed_by.is_64
+          })
+      })
+  }
+
+  /// Walks the `DT_NEEDED` entries of the given ELF files and their dependencies.
+  ///
+  /// Returns the resolved libraries by name, skipping the excluded ones and their dependencies.
+  /// The names of the libraries that could not be found are logged.
+  pub fn resolve(
+    &self,
+    files: &[PathBuf],
+    is_excluded: impl Fn(&str) -> bool,
+  ) -> crate::Result<BTreeMap<String, PathBuf>> {
+    let mut libraries = BTreeMap::new();
+    let mut queue: VecDeque<PathBuf> = files.iter().cloned().collect();
+
+    while let Some(path) = queue.pop_front() {
+      let Some(info) = read(&path)? else {
+        continue;
+      };
+      for name in &info.needed {
+        if libraries.contains_key(name) || is_excluded(name) {
+          continue;
+        }
+        match self.find(name, &info) {
+          Some(library) => {
+            queue.push_back(library.clone());
+            libraries.insert(name.clone(), library);
+          }
+          None => log::warn!(
+            "Could not find the {name} library needed by {}",
+            path.display()
+          ),
+        }
+      }
+    }
+
+    Ok(libraries)
+  }
+}
+
+/// Reads the library directories of a `ld.so.conf` file, following its `include` directives.
+fn read_ld_so_conf(path: &Path) -> Vec<PathBuf> {
+  let Ok(content) = fs::read_to_string(path) else {
+    return Vec::new();
+  };
+
+  let mut dirs = Vec::new();
+  for line in content.lines() {
+    let line = line.split('#').next().unwrap_or_default().trim();
+    if let Some(pattern) = line.strip_prefix("include") {
+      for include in expand_include(path, pattern.trim()) {
+        dirs.extend(read_ld_so_conf(&include));
+      }
+    } else if !line.is_empty() {
+      dirs.push(PathBuf::from(line));
+    }
+  }
+  dirs
+}
+
+/// Expands an `include` pattern such as `/etc/ld.so.conf.d/*.conf`.
+fn expand_include(conf: &Path, pattern: &str) -> Vec<PathBuf> {
+  let pattern = conf
+    .parent()
+    .map(|dir| dir.join(pattern))
+    .unwrap_or_else(|| PathBuf::from(pattern));
+  let (Some(dir), Some(file_pattern)) = (pattern.parent(), pattern.file_name()) else {
+    return Vec::new();
+  };
+  let file_pattern = file_pattern.to_string_lossy();
+
+  let mut files: Vec<PathBuf> = fs::read_dir(dir)
+    .map(|entries| {
+      entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+          path
+            .file_name()
+            .is_some_and(|name| matches_wildcard(&file_pattern, &name.to_string_lossy()))
+        })
+        .collect()
+    })
+    .unwrap_or_default();
+  files.sort();
+  files
+}
+
+/// Whether the name matches the pattern, where `*` matches any sequence of characters.
+pub fn matches_wildcard(pattern: &str, name: &str) -> bool {
+  let mut parts = pattern.split('*');
+  let first = parts.next().unwrap_or_default();
+  let Some(mut rest) = name.strip_prefix(first) else {
+    return false;
+  };
+
+  let mut parts = parts.peekable();
+  while let Some(part) = parts.next() {
+    if parts.peek().is_none() {
+      return rest.ends_with(part);
+    }
+    match rest.find(part) {
+      Some(index) => rest = &rest[index + part.len()..],
+      None => return false,
+    }
+  }
+  rest.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{matches_wildcard, read, read_ld_so_conf};
+
+  #[test]
+  fn matches_wildcards() {
+    assert!(matches_wildcard("libc.so.6", "libc.so.6"));
+    assert!(!matches_wildcard("libc.so.6", "libc.so.61"));
+    assert!(matches_wildcard("libGL*", "libGL.so.1"));
+    assert!(matches_wildcard("ld-linux*.so.*", "ld-linux-x86-64.so.2"));
+    assert!(matches_wildcard("*.conf", "x86_64-linux-gnu.conf"));
+    assert!(!matches_wildcard("*.conf", "README"));
+    assert!(!matches_wildcard("libX11*", "libXext.so.6"));
+  }
+
+  #[test]
+  fn reads_ld_so_conf_includes() {
+    let temp = tempfile::tempdir().unwrap();
+    let conf_d = temp.path().join("ld.so.conf.d");
+    std::fs::create_dir(&conf_d).unwrap();
+    std::fs::write(conf_d.join("b.conf"), "/opt/b/lib\n").unwrap();
+    std::fs::write(conf_d.join("a.conf"), "# comment\n/opt/a/lib\n").unwrap();
+    std::fs::write(conf_d.join("ignored.txt"), "/opt/ignored\n").unwrap();
+    let conf = temp.path().join("ld.so.conf");
+    std::fs::write(&conf, "include ld.so.conf.d/*.conf\n/usr/local/lib\n").unwrap();
+
+    assert_eq!(
+      read_ld_so_conf(&conf),
+      ["/opt/a/lib", "/opt/b/lib", "/usr/local/lib"]
+        .iter()
+        .map(std::path::PathBuf::from)
+        .collect::<Vec<_>>()
+    );
+  }
+
+  #[test]
+  fn reads_needed_libraries() {
+    let exe = std::env::current_exe().unwrap();
+    let info = read(&exe).unwrap().expect("test binary is an ELF file");
+    assert!(info.needed.iter().any(|name| name.starts_with("libc.so")));
+    assert!(info
+      .needed_versions
+      .iter()
+      .any(|(library, versions)| library.starts_with("libc.so")
+        && versions
+          .iter()
+          .any(|version| version.starts_with("GLIBC_2."))));
+
+    let temp = tempfile::NamedTempFile::new().unwrap();
+    std::fs::write(temp.path(), "#!/bin/sh").unwrap();
+    assert!(read(temp.path()).unwrap().is_none());
+    std::fs::write(temp.path(), "ab").unwrap();
+    assert!(read(temp.path()).unwrap().is_none());
+    // a truncated ELF file is skipped
+    std::fs::write(temp.path(), b"\x7fELF\x02\x01").unwrap();
+    assert!(read(temp.path()).unwrap().is_none());
+  }
+}
@@ -0,0 +1,305 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Bundles the app as a [Flatpak](https://docs.flatpak.org).
+//!
+//! The binaries, resources, icons, desktop entry and AppStream metainfo are staged
+//! in a directory mirroring the `/app` prefix, which is installed by the single module
+//! of a generated flatpak-builder manifest.
+//! `flatpak-builder` then exports the app to an OSTree repository, optionally turned into
+//! a single-file bundle with `flatpak build-bundle`.
+
+use super::freedesktop;
+use crate::{
+  bundle::{
+    common::{self, CommandExt},
+    settings::{Arch, FlatpakOutput, FlatpakSettings},
+  },
+  Settings,
+};
+
+use anyhow::Context;
+use serde_json::{json, Value};
+use std::{
+  fs,
+  path::{Path, PathBuf},
+  process::Command,
+};
+
+/// The sandbox permissions needed by the webview: display, GPU acceleration, network and audio.
+const DEFAULT_FINISH_ARGS: &[&str] = &[
+  "--share=ipc",
+  "--socket=wayland",
+  "--socket=fallback-x11",
+  "--device=dri",
+  "--share=network",
+  "--socket=pulseaudio",
+];
+
+/// Bundles the project.
+/// Returns a vector of PathBuf that shows where the Flatpak bundle or repository was created.
+pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
+  let arch = match settings.binary_arch() {
+    Arch::X86_64 => "x86_64",
+    Arch::AArch64 => "aarch64",
+    target => {
+      return Err(crate::Error::ArchError(format!(
+        "Unsupported architecture: {:?}",
+        target
+      )));
+    }
+  };
+
+  let flatpak = settings.flatpak();
+  let app_id = settings.bundle_identifier();
+  let package_base_name = format!(
+    "{}_{}_{}",
+    settings.product_name(),
+    settings.version_string(),
+    arch
+  );
+
+  let base_dir = settings.project_out_directory().join("bundle/flatpak");
+  let package_dir = base_dir.join(&package_base_name);
+  if package_dir.exists() {
+    fs::remove_dir_all(&package_dir)
+      .with_context(|| format!("Failed to remove old {package_base_name}"))?;
+  }
+  fs::create_dir_all(&package_dir)?;
+
+  let repo_dir = flatpak
+    .repo
+    .clone()
+    .unwrap_or_else(|| base_dir.join("repo"));
+  let bundle_path = base_dir.join(format!("{package_base_name}.flatpak"));
+
+  let output_path = match flatpak.output {
+    FlatpakOutput::Bundle => &bundle_path,
+    FlatpakOutput::Repo => &repo_dir,
+  };
+  log::info!(action = "Bundling"; "{} ({})", app_id, output_path.display());
+
+  let (data_dir, desktop_file) = generate_data(settings, &package_dir)
+    .with_context(|| "Failed to build data folders and files")?;
+
+  // set by the CLI when the app uses a tray icon
+  let has_tray = std::env::var_os("TAURI_TRAY_LIBRARY_PATH").is_some();
+  let manifest = manifest(
+    flatpak,
+    app_id,
+    settings.main_binary_name()?,
+    &desktop_file,
+    &data_dir,
+    has_tray,
+  );
+  let manifest_path = package_dir.join(format!("{app_id}.json"));
+  fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?)?;
+
+  Command::new("flatpak-builder")
+    .arg(format!("--arch={arch}"))
+    .arg(format!("--default-branch={}", flatpak.branch))
+    .arg(format!("--repo={}", repo_dir.display()))
+    // keep the downloads and build cache across builds
+    .arg(format!(
+      "--state-dir={}",
+      base_dir.join(".flatpak-builder").display()
+    ))
+    .arg("--force-clean")
+    .arg(package_dir.join("build"))
+    .arg(&manifest_path)
+    .output_ok()
+    .context("error running flatpak-builder, make sure it is installed along with the configured runtime and SDK")?;
+
+  if flatpak.output == FlatpakOutput::Bundle {
+    Command::new("flatpak")
+      .arg("build-bundle")
+      .arg(format!("--arch={arch}"))
+      .arg(&repo_dir)
+      .arg(&bundle_path)
+      .arg(app_id)
+      .arg(&flatpak.branch)
+      .output_ok()
+      .context("error running flatpak build-bundle")?;
+  }
+
+  fs::remove_dir_all(&package_dir)?;
+
+  Ok(vec![output_path.clone()])
+}
+
+/// Stages the app files under `package_dir/data`, mirroring the `/app` prefix.
+/// Returns the data directory and the file name of the desktop entry.
+fn generate_data(settings: &Settings, package_dir: &Path) -> crate::Result<(PathBuf, String)> {
+  let data_dir = package_dir.join("data");
+  let bin_dir = data_dir.join("usr/bin");
+
+  for bin in settings.binaries() {
+    let bin_path = settings.binary_path(bin);
+    common::copy_file(&bin_path, bin_dir.join(bin.name()))
+      .with_context(|| format!("Failed to copy binary from {bin_path:?}"))?;
+  }
+
+  settings
+    .copy_resources(&data_dir.join("usr/lib").join(settings.product_name()))
+    .with_context(|| "Failed to copy resource files")?;
+
+  settings
+    .copy_binaries(&bin_dir)
+    .with_context(|| "Failed to copy external binaries")?;
+
+  freedesktop::copy_icon_files(settings, &data_dir)
+    .with_context(|| "Failed to create icon files")?;
+  let (desktop_file, _) =
+    freedesktop::generate_desktop_file(settings, &settings.flatpak().desktop_template, &data_dir)
+      .with_context(|| "Failed to create desktop file")?;
+
+  // flatpak-builder renames the desktop entry after the app ID
+  let app_id = settings.bundle_identifier();
+  freedesktop::generate_metainfo_file(settings, app_id, &format!("{app_id}.desktop"), &data_dir)
+    .with_context(|| "Failed to create metainfo file")?;
+
+  common::copy_custom_files(&settings.flatpak().files, &data_dir.join("usr"))
+    .with_context(|| "Failed to copy custom files")?;
+
+  let desktop_file = desktop_file
+    .file_name()
+    .expect("desktop file has no name")
+    .to_string_lossy()
+    .into_owned();
+
+  Ok((data_dir, desktop_file))
+}
+
+/// Generates the flatpak-builder manifest, installing the staged `data_dir` in the `/app` prefix.
+fn manifest(
+  flatpak: &FlatpakSettings,
+  app_id: &str,
+  command: &str,
+  desktop_file: &str,
+  data_dir: &Path,
+  has_tray: bool,
+) -> Value {
+  let mut finish_args: Vec<&str> = match &flatpak.finish_args {
+    Some(finish_args) => finish_args.iter().map(String::as_str).collect(),
+    None => DEFAULT_FINISH_ARGS.to_vec(),
+  };
+  // allow the tray icon to reach the StatusNotifierItem host
+  let tray_arg = "--talk-name=org.kde.StatusNotifierWatcher";
+  if has_tray && !finish_args.contains(&tray_arg) {
+    finish_args.push(tray_arg);
+  }
+
+  let mut modules = flatpak.modules.clone();
+  modules.push(json!({
+    "name": command,
+    "buildsystem": "simple",
+    "build-commands": ["cp -a usr/. /app/"],
+    "sources": [{ "type": "dir", "path": data_dir }],
+  }));
+
+  json!({
+    "id": app_id,
+    "runtime": flatpak.runtime,
+    "runtime-version": flatpak.runtime_version,
+    "sdk": flatpak.sdk,
+    "command": command,
+    "rename-desktop-file": desktop_file,
+    // the icons are named after the main binary
+    "rename-icon": command,
+    "finish-args": finish_args,
+    "modules": modules,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use std::path::Path;
+
+  use serde_json::json;
+
+  use super::manifest;
+  use crate::bundle::settings::FlatpakSettings;
+
+  #[test]
+  fn generates_manifest() {
+    let settings = FlatpakSettings {
+      runtime: "org.gnome.Platform".into(),
+      runtime_version: "46".into(),
+      sdk: "org.gnome.Sdk".into(),
+      branch: "stable".into(),
+      finish_args: None,
+      modules: vec![json!({ "name": "libayatana-appindicator" })],
+      ..Default::default()
+    };
+
+    let manifest = manifest(
+      &settings,
+      "com.tauri.app",
+      "tauri-app",
+      "Tauri App.desktop",
+      Path::new("/tmp/data"),
+      true,
+    );
+
+    assert_eq!(manifest["id"], "com.tauri.app");
+    assert_eq!(manifest["command"], "tauri-app");
+    assert_eq!(manifest["rename-desktop-file"], "Tauri App.desktop");
+    assert_eq!(manifest["rename-icon"], "tauri-app");
+
+    assert_eq!(
+      manifest["finish-args"],
+      json!([
+        "--share=ipc",
+        "--socket=wayland",
+        "--socket=fallback-x11",
+        "--device=dri",
+        "--share=network",
+        "--socket=pulseaudio",
+        "--talk-name=org.kde.StatusNotifierWatcher",
+      ])
+    );
+
+    let modules = manifest["modules"].as_array().unwrap();
+    assert_eq!(modules.len(), 2);
+    assert_eq!(modules[0]["name"], "libayatana-appindicator");
+    assert_eq!(modules[1]["name"], "tauri-app");
+    assert_eq!(modules[1]["sources"][0]["path"], "/tmp/data");
+  }
+
+  #[test]
+  fn configured_finish_args_replace_the_defaults() {
+    let settings = FlatpakSettings {
+      finish_args: Some(vec![
+        "--socket=wayland".into(),
+        "--device=dri".into(),
+        "--filesystem=home".into(),
+      ]),
+      ..Default::default()
+    };
+    let manifest = |has_tray| {
+      manifest(
+        &settings,
+        "com.tauri.app",
+        "tauri-app",
+        "Tauri App.desktop",
+        Path::new("/tmp/data"),
+        has_tray,
+      )
+    };
+
+    assert_eq!(
+      manifest(false)["finish-args"],
+      json!(["--socket=wayland", "--device=dri", "--filesystem=home"])
+    );
+    assert_eq!(
+      manifest(true)["finish-args"],
+      json!([
+        "--socket=wayland",
+        "--device=dri",
+        "--filesystem=home",
+        "--talk-name=org.kde.StatusNotifierWatcher",
+      ])
+    );
+  }
+}
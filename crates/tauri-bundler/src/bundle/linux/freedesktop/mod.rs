@@ -8,12 +8,16 @@
 //!
 //! - Generation of [desktop entries] (`.desktop` files)
 //! - Copy of icons in the [icons file hierarchy]
+//! - Generation of [AppStream metainfo] files
+//! - Generation of [shared MIME-info] definitions for the file associations
 //!
 //! The specifications are developed and hosted at [freedesktop.org].
 //!
 //! [freedesktop.org]: https://www.freedesktop.org
 //! [desktop entries]: https://www.freedesktop.org/wiki/Specifications/desktop-entry-spec/
 //! [icons file hierarchy]: https://specifications.freedesktop.org/icon-theme-spec/icon-theme-spec-latest.html#icon_lookup
+//! [AppStream metainfo]: https://www.freedesktop.org/software/appstream/docs/chap-Metadata.html
+//! [shared MIME-info]: https://specifications.freedesktop.org/shared-mime-info-spec/latest/
 
 use std::collections::BTreeMap;
 use std::ffi::OsStr;
@@ -25,6 +29,7 @@ use anyhow::Context;
 use handlebars::Handlebars;
 use image::{self, codecs::png::PngDecoder, ImageDecoder};
 use serde::Serialize;
+use tauri_utils::config::FileAssociationMagic;
 
 use crate::bundle::common;
 use crate::Settings;
@@ -130,25 +135,7 @@ pub fn generate_desktop_file(
     long_description: String,
   }
 
-  let mut mime_type: Vec<String> = Vec::new();
-
-  if let Some(associations) = settings.file_associations() {
-    mime_type.extend(
-      associations
-        .iter()
-        .filter_map(|association| association.mime_type.clone()),
-    );
-  }
-
-  if let Some(protocols) = settings.deep_link_protocols() {
-    mime_type.extend(
-      protocols
-        .iter()
-        .flat_map(|protocol| &protocol.schemes)
-        .map(|s| format!("x-scheme-handler/{s}")),
-    );
-  }
-
+  let mime_type = mime_types(settings);
   let mime_type = (!mime_type.is_empty()).then_some(mime_type.join(";"));
 
   let bin_name_exec = if bin_name.contains(" ") {
@@ -180,3 +167,579 @@ pub fn generate_desktop_file(
 
   Ok((file_path, dest_path))
 }
+
+/// Generate the AppStream metainfo file of the application and store it under the `data_dir`.
+/// Returns the path of the resulting file (source path) and the destination
+/// path in the package.
+///
+/// The screenshots, releases and content rating come from the metainfo settings,
+/// and the MIME types of the file associations are listed as provided media types.
+///
+/// The `desktop_id` is the file name of the desktop entry launching the application.
+pub fn generate_metainfo_file(
+  settings: &Settings,
+  app_id: &str,
+  desktop_id: &str,
+  data_dir: &Path,
+) -> crate::Result<(PathBuf, PathBuf)> {
+  let path = PathBuf::from("usr/share/metainfo").join(format!("{app_id}.metainfo.xml"));
+  let dest_path = PathBuf::from("/").join(&path);
+  let file_path = data_dir.join(&path);
+  let file = &mut common::create_file(&file_path)?;
+
+  // the default escape function also covers the XML special characters
+  let mut handlebars = Handlebars::new();
+  handlebars
+    .register_template_string("metainfo.xml", include_str!("./metainfo.xml"))
+    .with_context(|| "Failed to setup metainfo handlebar template")?;
+
+  #[derive(Serialize)]
+  struct Screenshot<'a> {
+    image: &'a str,
+    caption: Option<&'a str>,
+  }
+
+  #[derive(Serialize)]
+  struct ContentAttribute<'a> {
+    id: &'a str,
+    value: &'a str,
+  }
+
+  #[derive(Serialize)]
+  struct Release<'a> {
+    version: &'a str,
+    date: Option<&'a str>,
+    timestamp: Option<u64>,
+    description: Vec<String>,
+  }
+
+  #[derive(Serialize)]
+  struct MetainfoTemplateParams<'a> {
+    id: &'a str,
+    name: &'a str,
+    summary: &'a str,
+    description: Vec<String>,
+    license: Option<String>,
+    homepage: Option<&'a str>,
+    desktop_id: &'a str,
+    categories: Vec<&'a str>,
+    screenshots: Vec<Screenshot<'a>>,
+    binary: &'a str,
+    mediatypes: Vec<&'a str>,
+    content_rating: Vec<ContentAttribute<'a>>,
+    releases: Vec<Release<'a>>,
+  }
+
+  let metainfo = settings.metainfo();
+  let summary = settings.short_description().trim();
+
+  let mut content_rating: Vec<ContentAttribute<'_>> = metainfo
+    .content_rating
+    .iter()
+    .map(|(id, value)| ContentAttribute { id, value })
+    .collect();
+  content_rating.sort_by_key(|attribute| attribute.id);
+
+  let releases = if metainfo.releases.is_empty() {
+    vec![Release {
+      version: settings.version_string(),
+      date: None,
+      timestamp: Some(settings.source_date_epoch().unwrap_or_else(|| {
+        std::time::SystemTime::now()
+          .duration_since(std::time::UNIX_EPOCH)
+          .map(|d| d.as_secs())
+          .unwrap_or_default()
+      })),
+      description: Vec::new(),
+    }]
+  } else {
+    metainfo
+      .releases
+      .iter()
+      .map(|release| Release {
+        version: &release.version,
+        date: Some(release.date.as_str()),
+        timestamp: None,
+        description: description_paragraphs(release.description.as_deref().unwrap_or_default()),
+      })
+      .collect()
+  };
+
+  handlebars.render_to_write(
+    "metainfo.xml",
+    &MetainfoTemplateParams {
+      id: app_id,
+      name: settings.product_name(),
+      summary: if summary.is_empty() {
+        settings.product_name()
+      } else {
+        summary
+      },
+      description: description_paragraphs(settings.long_description().unwrap_or_default()),
+      license: settings.license(),
+      homepage: settings.homepage_url(),
+      desktop_id,
+      categories: settings
+        .app_category()
+        .map(|app_category| app_category.freedesktop_categories())
+        .unwrap_or_default()
+        .split(';')
+        .filter(|category| !category.is_empty())
+        .collect(),
+      screenshots: metainfo
+        .screenshots
+        .iter()
+        .map(|screenshot| Screenshot {
+          image: &screenshot.image,
+          caption: screenshot.caption.as_deref(),
+        })
+        .collect(),
+      binary: settings.main_binary_name()?,
+      mediatypes: settings
+        .file_associations()
+        .into_iter()
+        .flatten()
+        .filter_map(|association| association.mime_type.as_deref())
+        .collect(),
+      content_rating,
+      releases,
+    },
+    file,
+  )?;
+
+  Ok((file_path, dest_path))
+}
+
+/// Generate the shared MIME-info definitions of the file association types and store them under the `data_dir`.
+/// Returns the path of the resulting file (source path) and the destination
+/// path in the package, or `None` if no file association declares a MIME type.
+pub fn generate_mime_file(
+  settings: &Settings,
+  data_dir: &Path,
+) -> crate::Result<Option<(PathBuf, PathBuf)>> {
+  #[derive(Serialize)]
+  struct MimeType<'a> {
+    mime_type: &'a str,
+    comment: Option<&'a str>,
+    icon: Option<&'a str>,
+    globs: Vec<&'a str>,
+    magic: &'a [FileAssociationMagic],
+  }
+
+  #[derive(Serialize)]
+  struct MimeTemplateParams<'a> {
+    mime_types: Vec<MimeType<'a>>,
+  }
+
+  let mime_types: Vec<MimeType<'_>> = settings
+    .file_associations()
+    .into_iter()
+    .flatten()
+    .filter_map(|association| {
+      Some(MimeType {
+        mime_type: association.mime_type.as_deref()?,
+        comment: association
+          .description
+          .as_deref()
+          .or(association.name.as_deref()),
+        icon: association.icon.as_deref(),
+        globs: association.ext.iter().map(|ext| ext.0.as_str()).collect(),
+        magic: &association.magic,
+      })
+    })
+    .collect();
+  if mime_types.is_empty() {
+    return Ok(None);
+  }
+  for mime_type in &mime_types {
+    for magic in mime_type.magic {
+      validate_magic(magic).with_context(|| {
+        format!(
+          "Invalid magic rule `{}` of the {} file association",
+          magic.value, mime_type.mime_type
+        )
+      })?;
+    }
+  }
+
+  let path =
+    PathBuf::from("usr/share/mime/packages").join(format!("{}.xml", settings.bundle_identifier()));
+  let dest_path = PathBuf::from("/").join(&path);
+  let file_path = data_dir.join(&path);
+  let file = &mut common::create_file(&file_path)?;
+
+  let mut handlebars = Handlebars::new();
+  handlebars
+    .register_template_string("mime.xml", include_str!("./mime.xml"))
+    .with_context(|| "Failed to setup shared MIME-info handlebar template")?;
+  handlebars.render_to_write("mime.xml", &MimeTemplateParams { mime_types }, file)?;
+
+  Ok(Some((file_path, dest_path)))
+}
+
+/// Checks that the value and mask of a magic rule can be parsed by shared-mime-info.
+fn validate_magic(magic: &FileAssociationMagic) -> crate::Result<()> {
+  let mask_size = match &magic.mask {
+    Some(mask) => {
+      let digits = mask
+        .strip_prefix("0x")
+        .filter(|digits| !digits.is_empty() && digits.chars().all(|c| c.is_ascii_hexdigit()))
+        .ok_or_else(|| {
+          crate::Error::GenericError(format!(
+            "mask `{mask}` is not a 0x prefixed hexadecimal number"
+          ))
+        })?;
+      Some(digits.len().div_ceil(2))
+    }
+    None => None,
+  };
+
+  match magic.kind.number_size() {
+    Some(size) => {
+      let value = match magic.value.strip_prefix("0x") {
+        Some(digits) => u64::from_str_radix(digits, 16),
+        None => magic.value.parse(),
+      }
+      .map_err(|_| crate::Error::GenericError("the value is not a number".into()))?;
+      if value >> (size * 8) != 0 || mask_size.is_some_and(|mask| mask > size as usize) {
+        return Err(crate::Error::GenericError(format!(
+          "the value or mask does not fit in {size} bytes"
+        )));
+      }
+    }
+    None => {
+      if magic.value.is_empty() {
+        return Err(crate::Error::GenericError("the value is empty".into()));
+      }
+    }
+  }
+  Ok(())
+}
+
+/// Returns the post-install or post-remove maintainer script of the package, refreshing
+/// the shared MIME-info and desktop entry databases when the app declares MIME types.
+///
+/// The commands are inserted after the shebang of the custom script at `script_path`, if any,
+/// which must be a `sh`, `bash` or `dash` script for them to be added.
+pub fn post_maintainer_script(
+  settings: &Settings,
+  script_path: Option<&Path>,
+) -> crate::Result<Option<Vec<u8>>> {
+  let script = script_path.map(std::fs::read).transpose()?;
+  if mime_types(settings).is_empty() {
+    return Ok(script);
+  }
+
+  let script = script.unwrap_or_default();
+  let (shebang, body) = if script.starts_with(b"#!") {
+    let (shebang, body) = match script.iter().position(|b| *b == b'\n') {
+      Some(end) => script.split_at(end + 1),
+      None => (script.as_slice(), &[][..]),
+    };
+    if !is_shell_shebang(shebang) {
+      return Err(crate::Error::GenericError(format!(
+        "the maintainer script {} is not a sh, bash or dash script, the commands refreshing the MIME database cannot be added to it: use a shell script or run `update-mime-database /usr/share/mime` and `update-desktop-database -q /usr/share/applications` from it",
+        script_path.map(tauri_utils::display_path).unwrap_or_default()
+      )));
+    }
+    (shebang.to_vec(), body)
+  } else {
+    (b"#!/bin/sh\n".to_vec(), script.as_slice())
+  };
+
+  let mut result = shebang;
+  if !result.ends_with(b"\n") {
+    result.push(b'\n');
+  }
+  result.extend_from_slice(UPDATE_DATABASES_SCRIPT.as_bytes());
+  result.extend_from_slice(body);
+  Ok(Some(result))
+}
+
+/// Whether the shebang line runs a shell the update commands can be spliced into,
+/// directly (`#!/bin/bash`) or through `env` (`#!/usr/bin/env bash`).
+fn is_shell_shebang(shebang: &[u8]) -> bool {
+  let line = String::from_utf8_lossy(&shebang[2..]);
+  let mut words = line.split_whitespace();
+  let Some(mut interpreter) = words.next() else {
+    return false;
+  };
+  if Path::new(interpreter).file_name() == Some(OsStr::new("env")) {
+    let Some(program) = words.find(|word| !word.starts_with('-')) else {
+      return false;
+    };
+    interpreter = program;
+  }
+  matches!(
+    Path::new(interpreter).file_name().and_then(OsStr::to_str),
+    Some("sh" | "bash" | "dash")
+  )
+}
+
+/// Refreshes the databases read by the desktop environments, ignoring the missing tools.
+const UPDATE_DATABASES_SCRIPT: &str = r#"if command -v update-mime-database >/dev/null 2>&1; then
+  update-mime-database /usr/share/mime || true
+fi
+if command -v update-desktop-database >/dev/null 2>&1; then
+  update-desktop-database -q /usr/share/applications || true
+fi
+"#;
+
+/// The MIME types handled by the app: the file association types and the deep link schemes.
+fn mime_types(settings: &Settings) -> Vec<String> {
+  let mut mime_types: Vec<String> = Vec::new();
+
+  if let Some(associations) = settings.file_associations() {
+    mime_types.extend(
+      associations
+        .iter()
+        .filter_map(|association| association.mime_type.clone()),
+    );
+  }
+
+  if let Some(protocols) = settings.deep_link_protocols() {
+    mime_types.extend(
+      protocols
+        .iter()
+        .flat_map(|protocol| &protocol.schemes)
+        .map(|s| format!("x-scheme-handler/{s}")),
+    );
+  }
+
+  mime_types
+}
+
+/// Splits a description into paragraphs separated by blank lines, joining their lines.
+fn description_paragraphs(description: &str) -> Vec<String> {
+  description
+    .split("\n\n")
+    .map(|paragraph| {
+      paragraph
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+    })
+    .filter(|paragraph| !paragraph.is_empty())
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{
+    description_paragraphs, generate_metainfo_file, generate_mime_file, is_shell_shebang,
+    post_maintainer_script, validate_magic,
+  };
+  use crate::bundle::settings::{
+    test_settings, BundleSettings, MetainfoRelease, MetainfoScreenshot, MetainfoSettings,
+  };
+  use tauri_utils::config::{FileAssociation, FileAssociationMagic};
+
+  #[test]
+  fn splits_description_paragraphs() {
+    assert_eq!(
+      description_paragraphs("A fast\n  editor.\n\n\nWith plugins.\n"),
+      vec!["A fast editor.", "With plugins."]
+    );
+    assert!(description_paragraphs("  \n").is_empty());
+  }
+
+  #[test]
+  fn generates_metainfo() {
+    let out_dir = tempfile::tempdir().unwrap();
+    let file_associations: Vec<FileAssociation> = serde_json::from_str(
+      r#"[{ "ext": ["md"], "mimeType": "text/markdown" }, { "ext": ["txt"] }]"#,
+    )
+    .unwrap();
+    let settings = test_settings(
+      out_dir.path(),
+      BundleSettings {
+        identifier: Some("com.tauri.notes".into()),
+        file_associations: Some(file_associations),
+        metainfo: MetainfoSettings {
+          screenshots: vec![
+            MetainfoScreenshot {
+              image: "https://example.com/main.png".into(),
+              caption: Some("The editor".into()),
+            },
+            MetainfoScreenshot {
+              image: "https://example.com/dark.png".into(),
+              caption: None,
+            },
+          ],
+          releases: vec![
+            MetainfoRelease {
+              version: "1.1.0".into(),
+              date: "2024-06-01".into(),
+              description: Some("Dark mode & search.".into()),
+            },
+            MetainfoRelease {
+              version: "1.0.0".into(),
+              date: "2024-01-15".into(),
+              description: None,
+            },
+          ],
+          content_rating: [("social-chat".to_string(), "mild".to_string())].into(),
+        },
+        ..Default::default()
+      },
+    );
+
+    let (path, dest_path) = generate_metainfo_file(
+      &settings,
+      "com.tauri.notes",
+      "Notes.desktop",
+      out_dir.path(),
+    )
+    .unwrap();
+    assert_eq!(
+      dest_path,
+      std::path::Path::new("/usr/share/metainfo/com.tauri.notes.metainfo.xml")
+    );
+
+    let metainfo = std::fs::read_to_string(path).unwrap();
+    for expected in [
+      "<summary>Take notes</summary>",
+      "<launchable type=\"desktop-id\">Notes.desktop</launchable>",
+      "<screenshot type=\"default\">\n      <image>https://example.com/main.png</image>\n      <caption>The editor</caption>",
+      "<screenshot>\n      <image>https://example.com/dark.png</image>\n    </screenshot>",
+      "<binary>notes</binary>\n    <mediatype>text/markdown</mediatype>\n  </provides>",
+      "<content_attribute id=\"social-chat\">mild</content_attribute>",
+      "<release version=\"1.1.0\" date=\"2024-06-01\">\n      <description>\n        <p>Dark mode &amp; search.</p>",
+      "<release version=\"1.0.0\" date=\"2024-01-15\" />",
+    ] {
+      assert!(metainfo.contains(expected), "{expected} not found in {metainfo}");
+    }
+  }
+
+  #[test]
+  fn generates_mime_info() {
+    let out_dir = tempfile::tempdir().unwrap();
+    let file_associations: Vec<FileAssociation> = serde_json::from_str(
+      r#"[
+        {
+          "ext": ["notes", "nts"],
+          "name": "Notes",
+          "description": "Notes & drafts",
+          "mimeType": "application/x-notes",
+          "magic": [
+            { "value": "NOTES\\x00", "offset": 4 },
+            { "value": "0xcafe", "type": "big16", "mask": "0xfffe" }
+          ],
+          "icon": "text-x-generic"
+        },
+        { "ext": ["txt"] }
+      ]"#,
+    )
+    .unwrap();
+    let settings = test_settings(
+      out_dir.path(),
+      BundleSettings {
+        identifier: Some("com.tauri.notes".into()),
+        file_associations: Some(file_associations),
+        ..Default::default()
+      },
+    );
+
+    let (path, dest_path) = generate_mime_file(&settings, out_dir.path())
+      .unwrap()
+      .unwrap();
+    assert_eq!(
+      dest_path,
+      std::path::Path::new("/usr/share/mime/packages/com.tauri.notes.xml")
+    );
+
+    let mime_info = std::fs::read_to_string(path).unwrap();
+    assert_eq!(
+      mime_info,
+      r#"<?xml version="1.0" encoding="UTF-8"?>
+<mime-info xmlns="http://www.freedesktop.org/standards/shared-mime-info">
+  <mime-type type="application/x-notes">
+    <comment>Notes &amp; drafts</comment>
+    <icon name="text-x-generic" />
+    <glob pattern="*.notes" />
+    <glob pattern="*.nts" />
+    <magic>
+      <match type="string" offset="4" value="NOTES\x00" />
+      <match type="big16" offset="0" value="0xcafe" mask="0xfffe" />
+    </magic>
+  </mime-type>
+</mime-info>
+"#
+    );
+
+    let script_path = out_dir.path().join("postinst.sh");
+    std::fs::write(&script_path, b"#!/usr/bin/env bash\necho \xff\n").unwrap();
+    let script = post_maintainer_script(&settings, Some(&script_path))
+      .unwrap()
+      .unwrap();
+    assert!(script.starts_with(b"#!/usr/bin/env bash\nif command -v update-mime-database"));
+    assert!(script
+      .ends_with(b"update-desktop-database -q /usr/share/applications || true\nfi\necho \xff\n"));
+
+    std::fs::write(&script_path, b"#!/usr/bin/python3\nprint('done')\n").unwrap();
+    assert!(post_maintainer_script(&settings, Some(&script_path)).is_err());
+  }
+
+  #[test]
+  fn validates_magic_rules() {
+    let magic = |json: &str| serde_json::from_str::<FileAssociationMagic>(json).unwrap();
+    for valid in [
+      r#"{ "value": "%PDF-" }"#,
+      r#"{ "value": "\\x7fELF", "mask": "0xffffffff" }"#,
+      r#"{ "value": "255", "type": "byte" }"#,
+      r#"{ "value": "0xcafebabe", "type": "little32", "mask": "0xffff0000" }"#,
+    ] {
+      assert!(validate_magic(&magic(valid)).is_ok(), "{valid}");
+    }
+    for invalid in [
+      r#"{ "value": "" }"#,
+      r#"{ "value": "256", "type": "byte" }"#,
+      r#"{ "value": "PK", "type": "host16" }"#,
+      r#"{ "value": "0xcafe", "type": "big16", "mask": "0xffffff" }"#,
+      r#"{ "value": "PK", "mask": "ffff" }"#,
+    ] {
+      assert!(validate_magic(&magic(invalid)).is_err(), "{invalid}");
+    }
+  }
+
+  #[test]
+  fn detects_shell_shebangs() {
+    for shebang in [
+      "#!/bin/sh\n",
+      "#! /bin/bash -e\n",
+      "#!/usr/bin/dash",
+      "#!/usr/bin/env -S bash\n",
+    ] {
+      assert!(is_shell_shebang(shebang.as_bytes()), "{shebang}");
+    }
+    for shebang in [
+      "#!/usr/bin/python3\n",
+      "#!/usr/bin/env zsh\n",
+      "#!/bin/shell\n",
+      "#!\n",
+    ] {
+      assert!(!is_shell_shebang(shebang.as_bytes()), "{shebang}");
+    }
+  }
+
+  #[test]
+  fn skips_mime_info_without_mime_types() {
+    let out_dir = tempfile::tempdir().unwrap();
+    let settings = test_settings(out_dir.path(), BundleSettings::default());
+
+    assert!(generate_mime_file(&settings, out_dir.path())
+      .unwrap()
+      .is_none());
+    assert_eq!(post_maintainer_script(&settings, None).unwrap(), None);
+    let script_path = out_dir.path().join("postinst.py");
+    std::fs::write(&script_path, b"#!/usr/bin/python3\nprint('done')\n").unwrap();
+    assert_eq!(
+      post_maintainer_script(&settings, Some(&script_path))
+        .unwrap()
+        .as_deref(),
+      Some(&b"#!/usr/bin/python3\nprint('done')\n"[..])
+    );
+  }
+}
@@ -5,5 +5,8 @@
 
 pub mod appimage;
 pub mod debian;
+mod depends;
+mod elf;
+pub mod flatpak;
 pub mod freedesktop;
 pub mod rpm;
@@ -13,11 +13,15 @@ use std::{
   path::{Path, PathBuf},
 };
 
-use super::freedesktop;
+use super::{
+  depends::{self, PackageManager},
+  freedesktop,
+};
 
 /// Bundles the project.
-/// Returns a vector of PathBuf that shows where the RPM was created.
-pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
+/// Returns a vector of PathBuf that shows where the RPM was created,
+/// and the packages it requires, including the detected ones.
+pub fn bundle_project(settings: &Settings) -> crate::Result<(Vec<PathBuf>, Vec<String>)> {
   let product_name = settings.product_name();
   let version = settings.version_string();
   let release = settings.rpm().release.as_str();
@@ -60,6 +64,11 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
     // This matches .deb compression. On a 240MB source binary the bundle will be 100KB larger than rpm's default while reducing build times by ~25%.
     .compression(rpm::CompressionWithLevel::Gzip(6));
 
+  // clamps the build time, the file modification times and the signature time
+  if let Some(epoch) = settings.source_date_epoch() {
+    builder = builder.source_date(u32::try_from(epoch)?);
+  }
+
   if let Some(description) = settings.long_description() {
     builder = builder.description(description);
   }
@@ -69,8 +78,17 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
   }
 
   // Add requirements
-  for dep in settings.rpm().depends.as_ref().cloned().unwrap_or_default() {
-    builder = builder.requires(Dependency::any(dep));
+  let mut dependencies = settings.rpm().depends.as_ref().cloned().unwrap_or_default();
+  if settings.rpm().auto_depends {
+    let detected = depends::detect(
+      settings,
+      PackageManager::Rpm,
+      settings.rpm().auto_depends_mapping.as_deref(),
+    )?;
+    depends::merge(&mut dependencies, detected);
+  }
+  for dep in &dependencies {
+    builder = builder.requires(dependency(dep)?);
   }
 
   // Add provides
@@ -81,7 +99,7 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
     .cloned()
     .unwrap_or_default()
   {
-    builder = builder.provides(Dependency::any(dep));
+    builder = builder.provides(dependency(&dep)?);
   }
 
   // Add recommends
@@ -92,7 +110,7 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
     .cloned()
     .unwrap_or_default()
   {
-    builder = builder.recommends(Dependency::any(dep));
+    builder = builder.recommends(dependency(&dep)?);
   }
 
   // Add conflicts
@@ -103,7 +121,7 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
     .cloned()
     .unwrap_or_default()
   {
-    builder = builder.conflicts(Dependency::any(dep));
+    builder = builder.conflicts(dependency(&dep)?);
   }
 
   // Add obsoletes
@@ -114,7 +132,7 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
     .cloned()
     .unwrap_or_default()
   {
-    builder = builder.obsoletes(Dependency::any(dep));
+    builder = builder.obsoletes(dependency(&dep)?);
   }
 
   // Add binaries
@@ -143,9 +161,9 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
     builder = builder.pre_install_script(script);
   }
 
-  if let Some(script_path) = &settings.rpm().post_install_script {
-    let script = fs::read_to_string(script_path)?;
-    builder = builder.post_install_script(script);
+  let post_install_script = settings.rpm().post_install_script.as_deref();
+  if let Some(script) = freedesktop::post_maintainer_script(settings, post_install_script)? {
+    builder = builder.post_install_script(scriptlet(script)?);
   }
 
   if let Some(script_path) = &settings.rpm().pre_remove_script {
@@ -153,9 +171,9 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
     builder = builder.pre_uninstall_script(script);
   }
 
-  if let Some(script_path) = &settings.rpm().post_remove_script {
-    let script = fs::read_to_string(script_path)?;
-    builder = builder.post_uninstall_script(script);
+  let post_remove_script = settings.rpm().post_remove_script.as_deref();
+  if let Some(script) = freedesktop::post_maintainer_script(settings, post_remove_script)? {
+    builder = builder.post_uninstall_script(scriptlet(script)?);
   }
 
   // Add resources
@@ -186,6 +204,28 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
     FileOptions::new(desktop_dest_path.to_string_lossy()),
   )?;
 
+  // Add AppStream metainfo file
+  let (metainfo_src_path, metainfo_dest_path) = freedesktop::generate_metainfo_file(
+    settings,
+    settings.bundle_identifier(),
+    &format!("{}.desktop", settings.product_name()),
+    &package_dir,
+  )?;
+  builder = builder.with_file(
+    metainfo_src_path,
+    FileOptions::new(metainfo_dest_path.to_string_lossy()),
+  )?;
+
+  // Add shared MIME-info file
+  if let Some((mime_src_path, mime_dest_path)) =
+    freedesktop::generate_mime_file(settings, &package_dir)?
+  {
+    builder = builder.with_file(
+      mime_src_path,
+      FileOptions::new(mime_dest_path.to_string_lossy()),
+    )?;
+  }
+
   // Add icons
   for (icon, src) in &freedesktop::list_icon_files(settings, &PathBuf::from("/"))? {
     builder = builder.with_file(src, FileOptions::new(icon.path.to_string_lossy()))?;
@@ -207,7 +247,20 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
     }
   }
 
-  let pkg = if let Ok(raw_secret_key) = env::var("TAURI_SIGNING_RPM_KEY") {
+  let signing_key = match env::var("TAURI_SIGNING_RPM_KEY") {
+    Ok(key) => Some(key),
+    Err(_) => settings
+      .rpm()
+      .signing_key
+      .as_ref()
+      .map(|path| {
+        fs::read_to_string(path)
+          .with_context(|| format!("Failed to read signing key {}", path.display()))
+      })
+      .transpose()?,
+  };
+
+  let pkg = if let Some(raw_secret_key) = signing_key {
     let mut signer = pgp::Signer::load_from_asc(&raw_secret_key)?;
     if let Ok(passphrase) = env::var("TAURI_SIGNING_RPM_KEY_PASSPHRASE") {
       signer = signer.with_key_passphrase(passphrase);
@@ -220,5 +273,121 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
   let mut f = fs::File::create(&package_path)?;
   pkg.write(&mut f)?;
 
-  Ok(vec![package_path])
+  Ok((vec![package_path], dependencies))
+}
+
+/// Parses a dependency like `glibc >= 2.34` into its name, operator and version.
+fn dependency(dep: &str) -> crate::Result<Dependency> {
+  let mut parts = dep.split_whitespace();
+  let name = parts.next().unwrap_or_default();
+  let dependency = match (parts.next(), parts.next(), parts.next()) {
+    (None, _, _) => Dependency::any(name),
+    (Some("="), Some(version), None) | (Some("=="), Some(version), None) => {
+      Dependency::eq(name, version)
+    }
+    (Some("<"), Some(version), None) => Dependency::less(name, version),
+    (Some("<="), Some(version), None) => Dependency::less_eq(name, version),
+    (Some(">"), Some(version), None) => Dependency::greater(name, version),
+    (Some(">="), Some(version), None) => Dependency::greater_eq(name, version),
+    _ => {
+      return Err(crate::Error::GenericError(format!(
+        "invalid RPM dependency `{dep}`, expected `name` or `name <operator> version`"
+      )))
+    }
+  };
+  Ok(dependency)
+}
+
+/// RPM scriptlets are stored as text in the package header.
+fn scriptlet(script: Vec<u8>) -> crate::Result<String> {
+  String::from_utf8(script)
+    .map_err(|_| crate::Error::GenericError("RPM maintainer scripts must be valid UTF-8".into()))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{bundle_project, dependency};
+  use crate::bundle::settings::{
+    test_settings, test_settings_builder, BundleSettings, RpmSettings,
+  };
+  use rpm::{Dependency, Package};
+  use std::{
+    fs::{self, File},
+    time::{Duration, SystemTime},
+  };
+
+  #[test]
+  fn bundles_reproducibly() {
+    let out_dir = tempfile::tempdir().unwrap();
+    let binary = out_dir.path().join("notes");
+    fs::write(&binary, b"notes binary").unwrap();
+    let settings = test_settings_builder(out_dir.path(), BundleSettings::default())
+      .source_date_epoch(1700000000)
+      .build()
+      .unwrap();
+
+    let first = fs::read(&bundle_project(&settings).unwrap().0[0]).unwrap();
+
+    // rebuilding the binary changes its modification time
+    File::options()
+      .write(true)
+      .open(&binary)
+      .unwrap()
+      .set_modified(SystemTime::now() + Duration::from_secs(60))
+      .unwrap();
+    let second = fs::read(&bundle_project(&settings).unwrap().0[0]).unwrap();
+
+    assert_eq!(first, second);
+  }
+
+  #[test]
+  fn parses_dependencies() {
+    assert_eq!(dependency("glibc").unwrap(), Dependency::any("glibc"));
+    assert_eq!(
+      dependency("glibc >= 2.34").unwrap(),
+      Dependency::greater_eq("glibc", "2.34")
+    );
+    assert_eq!(
+      dependency("libfoo.so.1()(64bit)").unwrap(),
+      Dependency::any("libfoo.so.1()(64bit)")
+    );
+    assert_eq!(
+      dependency("notes-data = 1.1.0-1").unwrap(),
+      Dependency::eq("notes-data", "1.1.0-1")
+    );
+    assert!(dependency("glibc >= ").is_err());
+    assert!(dependency("glibc ~ 2.34").is_err());
+  }
+
+  #[test]
+  fn writes_versioned_requires() {
+    let out_dir = tempfile::tempdir().unwrap();
+    fs::write(out_dir.path().join("notes"), b"notes binary").unwrap();
+    let settings = test_settings(
+      out_dir.path(),
+      BundleSettings {
+        rpm: RpmSettings {
+          depends: Some(vec!["glibc >= 2.34".into(), "gtk3".into()]),
+          ..Default::default()
+        },
+        ..Default::default()
+      },
+    );
+
+    let package = Package::open(&bundle_project(&settings).unwrap().0[0]).unwrap();
+    let requires: Vec<_> = package
+      .metadata
+      .get_requires()
+      .unwrap()
+      .into_iter()
+      .filter(|dependency| !dependency.name.starts_with("rpmlib("))
+      .collect();
+    assert_eq!(
+      requires,
+      [
+        Dependency::greater_eq("glibc", "2.34"),
+        Dependency::any("gtk3"),
+      ]
+    );
+  }
 }
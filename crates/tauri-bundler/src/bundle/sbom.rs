@@ -0,0 +1,678 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Software bill of materials of the bundles, as CycloneDX 1.5 or SPDX 2.3 JSON documents.
+//!
+//! A document lists the binaries, sidecars and resources shipped in the bundle with their hashes,
+//! the macOS frameworks, the system packages the Linux packages depend on,
+//! and the Rust crates and npm packages the app is built from.
+
+use std::{
+  fs::File,
+  io::{self, Write},
+  path::{Path, PathBuf},
+};
+
+use serde_json::{json, Value};
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+use uuid::Uuid;
+
+use super::{common, Bundle};
+use crate::{
+  PackageType, SbomEcosystem, SbomFormat, SbomHash, SbomHashAlgorithm, SbomSettings, Settings,
+};
+
+/// What a component of the bill of materials is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+  /// A Rust crate or npm package the app is built from.
+  Library,
+  /// A binary or resource shipped in the bundle.
+  File,
+  /// A macOS framework shipped in the bundle.
+  Framework,
+  /// A system package the bundle depends on, with the name of its package manager.
+  SystemPackage(&'static str),
+}
+
+/// A component of the bill of materials, independent of the document format.
+#[derive(Debug)]
+struct Component {
+  kind: Kind,
+  name: String,
+  version: Option<String>,
+  license: Option<String>,
+  purl: Option<String>,
+  hashes: Vec<SbomHash>,
+  /// The version constraint of a system package.
+  version_constraint: Option<String>,
+}
+
+impl Component {
+  fn new(kind: Kind, name: impl Into<String>) -> Self {
+    Self {
+      kind,
+      name: name.into(),
+      version: None,
+      license: None,
+      purl: None,
+      hashes: Vec::new(),
+      version_constraint: None,
+    }
+  }
+
+  /// The CycloneDX `bom-ref`, unique in the document.
+  fn bom_ref(&self) -> String {
+    match (&self.kind, &self.purl) {
+      (_, Some(purl)) => purl.clone(),
+      (Kind::File, None) => format!("file:{}", self.name),
+      (Kind::Framework, None) => format!("framework:{}", self.name),
+      (Kind::SystemPackage(package_manager), None) => format!("{package_manager}:{}", self.name),
+      (Kind::Library, None) => format!("library:{}", self.name),
+    }
+  }
+}
+
+/// The bundle artifact a document describes.
+struct Artifact {
+  /// The file name of the bundle.
+  name: String,
+  /// The hashes of the bundle, if it is a file.
+  hashes: Vec<SbomHash>,
+}
+
+/// Writes the bill of materials of each path of the bundle next to it,
+/// as `<bundle>.cdx.json` or `<bundle>.spdx.json`.
+///
+/// `system_dependencies` are the packages the Linux package depends on, as written in the package.
+///
+/// Returns the paths of the documents.
+pub fn bundle_project(
+  settings: &Settings,
+  sbom: &SbomSettings,
+  bundle: &Bundle,
+  system_dependencies: &[String],
+) -> crate::Result<Vec<PathBuf>> {
+  let components = components(settings, sbom, bundle.package_type, system_dependencies)?;
+  let epoch = settings
+    .source_date_epoch()
+    .and_then(|epoch| i64::try_from(epoch).ok())
+    .unwrap_or_else(|| OffsetDateTime::now_utc().unix_timestamp());
+  let timestamp = OffsetDateTime::from_unix_timestamp(epoch)
+    .map_err(time::error::Error::from)?
+    .format(&Rfc3339)
+    .map_err(time::error::Error::from)?;
+
+  let mut paths = Vec::new();
+  for path in &bundle.bundle_paths {
+    let artifact = Artifact {
+      name: file_name(path),
+      hashes: if path.is_file() {
+        vec![SbomHash {
+          algorithm: SbomHashAlgorithm::Sha256,
+          value: hash_file::<Sha256>(path)?,
+        }]
+      } else {
+        Vec::new()
+      },
+    };
+
+    let (extension, document) = match sbom.format {
+      SbomFormat::CycloneDx => (
+        "cdx.json",
+        cyclonedx(settings, &artifact, &components, &timestamp),
+      ),
+      SbomFormat::Spdx => (
+        "spdx.json",
+        spdx(settings, &artifact, &components, &timestamp),
+      ),
+    };
+
+    let sbom_path = path.with_file_name(format!("{}.{extension}", artifact.name));
+    log::info!(action = "Bundling"; "{}", tauri_utils::display_path(&sbom_path));
+    let mut file = common::create_file(&sbom_path)?;
+    serde_json::to_writer_pretty(&mut file, &document)?;
+    file.flush()?;
+    paths.push(sbom_path);
+  }
+
+  Ok(paths)
+}
+
+/// Lists the components of a bundle of the given type.
+fn components(
+  settings: &Settings,
+  sbom: &SbomSettings,
+  package_type: PackageType,
+  system_dependencies: &[String],
+) -> crate::Result<Vec<Component>> {
+  let mut components = Vec::new();
+
+  for binary in settings.binaries() {
+    let path = settings.binary_path(binary);
+    components.push(file_component(file_name(&path), &path)?);
+  }
+  for sidecar in settings.external_binaries() {
+    let path = sidecar?;
+    components.push(file_component(file_name(&path), &path)?);
+  }
+  for resource in settings.resource_files().iter() {
+    let resource = resource?;
+    components.push(file_component(
+      resource.target().to_string_lossy().replace('\\', "/"),
+      resource.path(),
+    )?);
+  }
+
+  if matches!(
+    package_type,
+    PackageType::MacOsBundle | PackageType::Dmg | PackageType::IosBundle
+  ) {
+    for framework in settings.macos().frameworks.iter().flatten() {
+      let name = Path::new(framework)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| framework.clone());
+      components.push(Component::new(Kind::Framework, name));
+    }
+  }
+
+  let package_manager = match package_type {
+    PackageType::Deb => Some("deb"),
+    PackageType::Rpm => Some("rpm"),
+    _ => None,
+  };
+  if let Some(package_manager) = package_manager {
+    for dependency in system_dependencies {
+      let (name, version_constraint) = split_version_constraint(dependency);
+      let mut component = Component::new(Kind::SystemPackage(package_manager), name);
+      component.version_constraint = version_constraint;
+      components.push(component);
+    }
+  }
+
+  for package in &sbom.packages {
+    let mut component = Component::new(Kind::Library, &package.name);
+    component.version = Some(package.version.clone());
+    component.license = package.license.clone();
+    component.purl = Some(purl(package.ecosystem, &package.name, &package.version));
+    component.hashes = package.hashes.clone();
+    components.push(component);
+  }
+
+  Ok(components)
+}
+
+fn file_component(name: String, path: &Path) -> crate::Result<Component> {
+  let mut component = Component::new(Kind::File, name);
+  component.hashes = vec![
+    SbomHash {
+      algorithm: SbomHashAlgorithm::Sha1,
+      value: hash_file::<Sha1>(path)?,
+    },
+    SbomHash {
+      algorithm: SbomHashAlgorithm::Sha256,
+      value: hash_file::<Sha256>(path)?,
+    },
+  ];
+  Ok(component)
+}
+
+fn file_name(path: &Path) -> String {
+  path
+    .file_name()
+    .unwrap_or_default()
+    .to_string_lossy()
+    .into_owned()
+}
+
+fn hash_file<D: Digest + Write>(path: &Path) -> crate::Result<String> {
+  let mut hasher = D::new();
+  io::copy(&mut File::open(path)?, &mut hasher)?;
+  Ok(hex::encode(hasher.finalize()))
+}
+
+/// Splits a package dependency such as `libc6 (>= 2.31)` or `glibc >= 2.28` into its name and version constraint.
+fn split_version_constraint(dependency: &str) -> (String, Option<String>) {
+  let dependency = dependency.trim();
+  let (name, constraint) = dependency
+    .split_once(|c: char| c.is_whitespace() || c == '(')
+    .unwrap_or((dependency, ""));
+  let constraint = constraint
+    .trim()
+    .trim_start_matches('(')
+    .trim_end_matches(')')
+    .trim();
+  (
+    name.to_string(),
+    (!constraint.is_empty()).then(|| constraint.to_string()),
+  )
+}
+
+/// The package URL of a Rust crate or npm package, see <https://github.com/package-url/purl-spec>.
+fn purl(ecosystem: SbomEcosystem, name: &str, version: &str) -> String {
+  let encode = |value: &str| {
+    value
+      .replace('%', "%25")
+      .replace('@', "%40")
+      .replace('+', "%2B")
+  };
+  match ecosystem {
+    SbomEcosystem::Cargo => format!("pkg:cargo/{}@{}", encode(name), encode(version)),
+    SbomEcosystem::Npm => {
+      let name = match name.split_once('/') {
+        Some((scope, name)) => format!("{}/{}", encode(scope), encode(name)),
+        None => encode(name),
+      };
+      format!("pkg:npm/{name}@{}", encode(version))
+    }
+  }
+}
+
+/// A stable identifier of the document, so rebuilding the same bundle produces the same document.
+fn document_uuid(settings: &Settings, artifact: &Artifact, format: &str) -> Uuid {
+  let hashes: Vec<&str> = artifact
+    .hashes
+    .iter()
+    .map(|hash| hash.value.as_str())
+    .collect();
+  Uuid::new_v5(
+    &Uuid::NAMESPACE_URL,
+    format!(
+      "{}/{}/{}/{}/{format}",
+      settings.bundle_identifier(),
+      settings.version_string(),
+      artifact.name,
+      hashes.join(",")
+    )
+    .as_bytes(),
+  )
+}
+
+fn cyclonedx(
+  settings: &Settings,
+  artifact: &Artifact,
+  components: &[Component],
+  timestamp: &str,
+) -> Value {
+  let hashes = |hashes: &[SbomHash]| -> Vec<Value> {
+    hashes
+      .iter()
+      .map(|hash| {
+        json!({
+          "alg": match hash.algorithm {
+            SbomHashAlgorithm::Sha1 => "SHA-1",
+            SbomHashAlgorithm::Sha256 => "SHA-256",
+            SbomHashAlgorithm::Sha512 => "SHA-512",
+          },
+          "content": hash.value,
+        })
+      })
+      .collect()
+  };
+
+  let mut root = json!({
+    "type": "application",
+    "bom-ref": "app",
+    "name": settings.product_name(),
+    "version": settings.version_string(),
+    "properties": [{ "name": "tauri:bundle", "value": artifact.name }],
+  });
+  if !artifact.hashes.is_empty() {
+    root["hashes"] = hashes(&artifact.hashes).into();
+  }
+
+  let mut dependencies = Vec::new();
+  let components: Vec<Value> = components
+    .iter()
+    .map(|component| {
+      let bom_ref = component.bom_ref();
+      let mut value = json!({
+        "type": match component.kind {
+          Kind::Library | Kind::SystemPackage(_) => "library",
+          Kind::File => "file",
+          Kind::Framework => "framework",
+        },
+        "bom-ref": bom_ref,
+        "name": component.name,
+      });
+      if let Some(version) = &component.version {
+        value["version"] = version.as_str().into();
+      }
+      if let Some(license) = &component.license {
+        value["licenses"] = json!([{ "expression": license }]);
+      }
+      if let Some(purl) = &component.purl {
+        value["purl"] = purl.as_str().into();
+      }
+      if !component.hashes.is_empty() {
+        value["hashes"] = hashes(&component.hashes).into();
+      }
+      if let Kind::SystemPackage(package_manager) = component.kind {
+        let mut properties =
+          vec![json!({ "name": "tauri:packageManager", "value": package_manager })];
+        if let Some(constraint) = &component.version_constraint {
+          properties.push(json!({ "name": "tauri:versionConstraint", "value": constraint }));
+        }
+        value["scope"] = "required".into();
+        value["properties"] = properties.into();
+      }
+      if matches!(component.kind, Kind::Library | Kind::SystemPackage(_)) {
+        dependencies.push(bom_ref);
+      }
+      value
+    })
+    .collect();
+
+  json!({
+    "bomFormat": "CycloneDX",
+    "specVersion": "1.5",
+    "serialNumber": format!("urn:uuid:{}", document_uuid(settings, artifact, "cyclonedx")),
+    "version": 1,
+    "metadata": {
+      "timestamp": timestamp,
+      "tools": {
+        "components": [{
+          "type": "application",
+          "name": "tauri-bundler",
+          "version": env!("CARGO_PKG_VERSION"),
+        }],
+      },
+      "component": root,
+    },
+    "components": components,
+    "dependencies": [{ "ref": "app", "dependsOn": dependencies }],
+  })
+}
+
+fn spdx(
+  settings: &Settings,
+  artifact: &Artifact,
+  components: &[Component],
+  timestamp: &str,
+) -> Value {
+  let checksums = |hashes: &[SbomHash]| -> Vec<Value> {
+    hashes
+      .iter()
+      .map(|hash| {
+        json!({
+          "algorithm": match hash.algorithm {
+            SbomHashAlgorithm::Sha1 => "SHA1",
+            SbomHashAlgorithm::Sha256 => "SHA256",
+            SbomHashAlgorithm::Sha512 => "SHA512",
+          },
+          "checksumValue": hash.value,
+        })
+      })
+      .collect()
+  };
+
+  let mut root = json!({
+    "SPDXID": "SPDXRef-App",
+    "name": settings.product_name(),
+    "versionInfo": settings.version_string(),
+    "packageFileName": artifact.name,
+    "downloadLocation": "NOASSERTION",
+    "filesAnalyzed": false,
+    "licenseConcluded": "NOASSERTION",
+    "licenseDeclared": "NOASSERTION",
+    "copyrightText": settings.copyright_string().unwrap_or("NOASSERTION"),
+    "primaryPackagePurpose": "APPLICATION",
+  });
+  if !artifact.hashes.is_empty() {
+    root["checksums"] = checksums(&artifact.hashes).into();
+  }
+
+  let mut packages = vec![root];
+  let mut files = Vec::new();
+  let mut relationships = vec![json!({
+    "spdxElementId": "SPDXRef-DOCUMENT",
+    "relationshipType": "DESCRIBES",
+    "relatedSpdxElement": "SPDXRef-App",
+  })];
+
+  for (index, component) in components.iter().enumerate() {
+    let (spdx_id, relationship_type) = if component.kind == Kind::File {
+      let spdx_id = format!("SPDXRef-File-{index}");
+      files.push(json!({
+        "SPDXID": spdx_id,
+        "fileName": format!("./{}", component.name),
+        "checksums": checksums(&component.hashes),
+        "licenseConcluded": "NOASSERTION",
+      }));
+      (spdx_id, "CONTAINS")
+    } else {
+      let spdx_id = format!("SPDXRef-Package-{index}");
+      let mut package = json!({
+        "SPDXID": spdx_id,
+        "name": component.name,
+        "downloadLocation": "NOASSERTION",
+        "filesAnalyzed": false,
+        "licenseConcluded": "NOASSERTION",
+        "licenseDeclared": component.license.as_deref().unwrap_or("NOASSERTION"),
+        "primaryPackagePurpose": if component.kind == Kind::Framework { "FRAMEWORK" } else { "LIBRARY" },
+      });
+      if let Some(version) = &component.version {
+        package["versionInfo"] = version.as_str().into();
+      }
+      if !component.hashes.is_empty() {
+        package["checksums"] = checksums(&component.hashes).into();
+      }
+      if let Some(purl) = &component.purl {
+        package["externalRefs"] = json!([{
+          "referenceCategory": "PACKAGE-MANAGER",
+          "referenceType": "purl",
+          "referenceLocator": purl,
+        }]);
+      }
+      if let Kind::SystemPackage(package_manager) = component.kind {
+        package["comment"] = match &component.version_constraint {
+          Some(constraint) => format!("{package_manager} package, version {constraint}"),
+          None => format!("{package_manager} package"),
+        }
+        .into();
+      }
+      packages.push(package);
+      // crates and npm packages are compiled or bundled into the app, system packages are installed next to it
+      let relationship_type = match component.kind {
+        Kind::SystemPackage(_) => "DEPENDS_ON",
+        _ => "CONTAINS",
+      };
+      (spdx_id, relationship_type)
+    };
+
+    relationships.push(json!({
+      "spdxElementId": "SPDXRef-App",
+      "relationshipType": relationship_type,
+      "relatedSpdxElement": spdx_id,
+    }));
+  }
+
+  let document_name = format!("{}-sbom", artifact.name);
+  json!({
+    "spdxVersion": "SPDX-2.3",
+    "dataLicense": "CC0-1.0",
+    "SPDXID": "SPDXRef-DOCUMENT",
+    "name": document_name,
+    "documentNamespace": format!(
+      "https://spdx.org/spdxdocs/{document_name}-{}",
+      document_uuid(settings, artifact, "spdx")
+    ),
+    "creationInfo": {
+      "created": timestamp,
+      "creators": [format!("Tool: tauri-bundler-{}", env!("CARGO_PKG_VERSION"))],
+    },
+    "packages": packages,
+    "files": files,
+    "relationships": relationships,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{
+    components, cyclonedx, purl, spdx, split_version_constraint, Artifact, Component, Kind,
+  };
+  use crate::{
+    BundleSettings, PackageSettings, PackageType, SbomEcosystem, SbomFormat, SbomHash,
+    SbomHashAlgorithm, SbomSettings, SettingsBuilder,
+  };
+
+  #[test]
+  fn encodes_purls() {
+    assert_eq!(
+      purl(SbomEcosystem::Cargo, "serde", "1.0.210"),
+      "pkg:cargo/serde@1.0.210"
+    );
+    assert_eq!(
+      purl(SbomEcosystem::Npm, "@tauri-apps/api", "2.0.0+build.1"),
+      "pkg:npm/%40tauri-apps/api@2.0.0%2Bbuild.1"
+    );
+  }
+
+  #[test]
+  fn splits_version_constraints() {
+    assert_eq!(
+      split_version_constraint("libc6 (>= 2.31)"),
+      ("libc6".into(), Some(">= 2.31".into()))
+    );
+    assert_eq!(
+      split_version_constraint("glibc >= 2.28"),
+      ("glibc".into(), Some(">= 2.28".into()))
+    );
+    assert_eq!(
+      split_version_constraint("libssl3"),
+      ("libssl3".into(), None)
+    );
+  }
+
+  #[test]
+  fn lists_components() {
+    let out_dir = tempfile::tempdir().unwrap();
+    let settings = SettingsBuilder::new()
+      .project_out_directory(out_dir.path())
+      .package_settings(PackageSettings {
+        product_name: "Notes".into(),
+        version: "1.1.0".into(),
+        description: "Take notes".into(),
+        homepage: None,
+        authors: None,
+        default_run: None,
+      })
+      .bundle_settings(BundleSettings {
+        identifier: Some("com.tauri.notes".into()),
+        ..Default::default()
+      })
+      .target("x86_64-unknown-linux-gnu".into())
+      .build()
+      .unwrap();
+
+    let mut file = Component::new(Kind::File, "notes");
+    file.hashes = vec![SbomHash {
+      algorithm: SbomHashAlgorithm::Sha256,
+      value: "ab".repeat(32),
+    }];
+    let mut library = Component::new(Kind::Library, "serde");
+    library.version = Some("1.0.210".into());
+    library.license = Some("MIT OR Apache-2.0".into());
+    library.purl = Some("pkg:cargo/serde@1.0.210".into());
+    let mut system_package = Component::new(Kind::SystemPackage("deb"), "libc6");
+    system_package.version_constraint = Some(">= 2.31".into());
+    let components = [file, library, system_package];
+    let artifact = Artifact {
+      name: "notes_1.1.0_amd64.deb".into(),
+      hashes: Vec::new(),
+    };
+
+    let document = cyclonedx(&settings, &artifact, &components, "2024-06-01T00:00:00Z");
+    assert_eq!(document["metadata"]["component"]["name"], "Notes");
+    assert_eq!(document["components"].as_array().unwrap().len(), 3);
+    assert_eq!(document["components"][0]["bom-ref"], "file:notes");
+    assert_eq!(
+      document["components"][1]["licenses"][0]["expression"],
+      "MIT OR Apache-2.0"
+    );
+    assert_eq!(
+      document["dependencies"][0]["dependsOn"],
+      serde_json::json!(["pkg:cargo/serde@1.0.210", "deb:libc6"])
+    );
+    assert_eq!(
+      document["serialNumber"],
+      cyclonedx(&settings, &artifact, &components, "2024-06-01T00:00:00Z")["serialNumber"]
+    );
+
+    let document = spdx(&settings, &artifact, &components, "2024-06-01T00:00:00Z");
+    assert_eq!(document["packages"].as_array().unwrap().len(), 3);
+    assert_eq!(document["files"][0]["fileName"], "./notes");
+    assert_eq!(
+      document["packages"][1]["externalRefs"][0]["referenceLocator"],
+      "pkg:cargo/serde@1.0.210"
+    );
+    assert_eq!(
+      document["packages"][2]["comment"],
+      "deb package, version >= 2.31"
+    );
+    let relationships: Vec<&str> = document["relationships"]
+      .as_array()
+      .unwrap()
+      .iter()
+      .map(|relationship| relationship["relationshipType"].as_str().unwrap())
+      .collect();
+    assert_eq!(
+      relationships,
+      ["DESCRIBES", "CONTAINS", "CONTAINS", "DEPENDS_ON"]
+    );
+  }
+
+  #[test]
+  fn lists_detected_system_dependencies() {
+    let out_dir = tempfile::tempdir().unwrap();
+    let settings = SettingsBuilder::new()
+      .project_out_directory(out_dir.path())
+      .package_settings(PackageSettings {
+        product_name: "Notes".into(),
+        version: "1.1.0".into(),
+        description: "Take notes".into(),
+        homepage: None,
+        authors: None,
+        default_run: None,
+      })
+      .bundle_settings(BundleSettings::default())
+      .target("x86_64-unknown-linux-gnu".into())
+      .build()
+      .unwrap();
+    let sbom = SbomSettings {
+      format: SbomFormat::Spdx,
+      packages: Vec::new(),
+    };
+    let dependencies = vec!["libc6 (>= 2.34)".to_string(), "libgtk-3-0".to_string()];
+
+    let listed = components(&settings, &sbom, PackageType::Rpm, &dependencies).unwrap();
+    let listed: Vec<_> = listed
+      .iter()
+      .map(|component| {
+        (
+          component.kind,
+          component.name.as_str(),
+          component.version_constraint.as_deref(),
+        )
+      })
+      .collect();
+    assert_eq!(
+      listed,
+      [
+        (Kind::SystemPackage("rpm"), "libc6", Some(">= 2.34")),
+        (Kind::SystemPackage("rpm"), "libgtk-3-0", None),
+      ]
+    );
+
+    assert!(
+      components(&settings, &sbom, PackageType::AppImage, &dependencies)
+        .unwrap()
+        .is_empty()
+    );
+  }
+}
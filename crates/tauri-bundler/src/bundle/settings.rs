@@ -8,7 +8,10 @@ use crate::bundle::{common, platform::target_triple};
 use anyhow::Context;
 pub use tauri_utils::config::WebviewInstallMode;
 use tauri_utils::{
-  config::{BundleType, DeepLinkProtocol, FileAssociation, NSISInstallerMode, NsisCompression},
+  config::{
+    BundleType, DebCompression, DebSignatureStyle, DeepLinkProtocol, FileAssociation,
+    NSISInstallerMode, NsisCompression,
+  },
   resources::{external_binaries, ResourcePaths},
 };
 
@@ -37,6 +40,8 @@ pub enum PackageType {
   AppImage,
   /// The macOS DMG bundle (.dmg).
   Dmg,
+  /// The Linux Flatpak bundle (.flatpak).
+  Flatpak,
   /// The Updater bundle.
   Updater,
 }
@@ -51,13 +56,14 @@ impl From<BundleType> for PackageType {
       BundleType::Nsis => Self::Nsis,
       BundleType::App => Self::MacOsBundle,
       BundleType::Dmg => Self::Dmg,
+      BundleType::Flatpak => Self::Flatpak,
     }
   }
 }
 
 impl PackageType {
   /// Maps a short name to a PackageType.
-  /// Possible values are "deb", "ios", "msi", "app", "rpm", "appimage", "dmg", "updater".
+  /// Possible values are "deb", "ios", "msi", "app", "rpm", "appimage", "dmg", "flatpak", "updater".
   pub fn from_short_name(name: &str) -> Option<PackageType> {
     // Other types we may eventually want to support: apk.
     match name {
@@ -69,6 +75,7 @@ impl PackageType {
       "rpm" => Some(PackageType::Rpm),
       "appimage" => Some(PackageType::AppImage),
       "dmg" => Some(PackageType::Dmg),
+      "flatpak" => Some(PackageType::Flatpak),
       "updater" => Some(PackageType::Updater),
       _ => None,
     }
@@ -86,6 +93,7 @@ impl PackageType {
       PackageType::Rpm => "rpm",
       PackageType::AppImage => "appimage",
       PackageType::Dmg => "dmg",
+      PackageType::Flatpak => "flatpak",
       PackageType::Updater => "updater",
     }
   }
@@ -110,6 +118,7 @@ impl PackageType {
       PackageType::Deb => 0,
       PackageType::Rpm => 0,
       PackageType::AppImage => 0,
+      PackageType::Flatpak => 0,
       PackageType::Dmg => 1,
       PackageType::Updater => 2,
     }
@@ -133,6 +142,8 @@ const ALL_PACKAGE_TYPES: &[PackageType] = &[
   PackageType::Dmg,
   #[cfg(target_os = "linux")]
   PackageType::AppImage,
+  #[cfg(target_os = "linux")]
+  PackageType::Flatpak,
   PackageType::Updater,
 ];
 
@@ -164,6 +175,68 @@ pub struct UpdaterSettings {
   pub msiexec_args: &'static [&'static str],
 }
 
+/// The format of the software bill of materials written next to each bundle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SbomFormat {
+  /// A CycloneDX 1.5 JSON document, written as `<bundle>.cdx.json`.
+  CycloneDx,
+  /// An SPDX 2.3 JSON document, written as `<bundle>.spdx.json`.
+  Spdx,
+}
+
+/// The package registry a dependency listed in the software bill of materials comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SbomEcosystem {
+  /// A Rust crate.
+  Cargo,
+  /// An npm package.
+  Npm,
+}
+
+/// The algorithm of a [`SbomHash`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SbomHashAlgorithm {
+  /// SHA-1.
+  Sha1,
+  /// SHA-256.
+  Sha256,
+  /// SHA-512.
+  Sha512,
+}
+
+/// A hash of a dependency, as recorded in its lockfile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SbomHash {
+  /// The hash algorithm.
+  pub algorithm: SbomHashAlgorithm,
+  /// The hash, hex encoded.
+  pub value: String,
+}
+
+/// A dependency of the app listed in the software bill of materials.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SbomPackage {
+  /// The package registry.
+  pub ecosystem: SbomEcosystem,
+  /// The package name.
+  pub name: String,
+  /// The package version.
+  pub version: String,
+  /// The declared license, as an SPDX license expression.
+  pub license: Option<String>,
+  /// The hashes of the package archive.
+  pub hashes: Vec<SbomHash>,
+}
+
+/// The software bill of materials settings.
+#[derive(Debug, Clone)]
+pub struct SbomSettings {
+  /// The document format.
+  pub format: SbomFormat,
+  /// The Rust crates and npm packages the app is built from.
+  pub packages: Vec<SbomPackage>,
+}
+
 /// The Linux debian bundle settings.
 #[derive(Clone, Debug, Default)]
 pub struct DebianSettings {
@@ -210,6 +283,20 @@ pub struct DebianSettings {
   /// Path to script that will be executed after the package is removed. See
   /// <https://www.debian.org/doc/debian-policy/ch-maintainerscripts.html>
   pub post_remove_script: Option<PathBuf>,
+  /// Whether to add the packages providing the shared libraries needed by the main binary
+  /// and the sidecars to the dependencies, found with the mapping file or `dpkg -S`.
+  pub auto_depends: bool,
+  /// Path to a JSON file mapping shared library names to the package providing them.
+  pub auto_depends_mapping: Option<PathBuf>,
+  /// The compression algorithm of the control and data archives.
+  pub compression: DebCompression,
+  /// The compression level. Defaults to 6 for gzip and xz and to 3 for zstd.
+  pub compression_level: Option<u32>,
+  /// Path to the ASCII-armored GPG secret key used to sign the package,
+  /// unless the `TAURI_SIGNING_DEB_KEY` environment variable is set.
+  pub signing_key: Option<PathBuf>,
+  /// How the package signature is stored.
+  pub signature_style: DebSignatureStyle,
 }
 
 /// The Linux AppImage bundle settings.
@@ -217,6 +304,89 @@ pub struct DebianSettings {
 pub struct AppImageSettings {
   /// The files to include in the Appimage Binary.
   pub files: HashMap<PathBuf, PathBuf>,
+  /// Path to the AppImage runtime the filesystem is appended to.
+  /// Defaults to the `runtime-<arch>` file of the tools directory, downloaded when missing.
+  pub runtime: Option<PathBuf>,
+  /// Shared libraries that must not be bundled, in addition to the ones expected on every system.
+  /// `*` matches any sequence of characters.
+  pub exclude_libraries: Vec<String>,
+}
+
+/// The output of the Flatpak bundle.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FlatpakOutput {
+  /// A single-file bundle (.flatpak).
+  #[default]
+  Bundle,
+  /// Only the OSTree repository.
+  Repo,
+}
+
+/// The Linux Flatpak bundle settings.
+#[derive(Clone, Debug, Default)]
+pub struct FlatpakSettings {
+  /// The runtime the app runs on, e.g. `org.gnome.Platform`.
+  pub runtime: String,
+  /// The version of the runtime.
+  pub runtime_version: String,
+  /// The SDK matching the runtime, e.g. `org.gnome.Sdk`.
+  pub sdk: String,
+  /// The branch of the app in the repository.
+  pub branch: String,
+  /// The sandbox permissions of the app, passed as the `finish-args` of the manifest.
+  ///
+  /// Defaults to the permissions needed by the webview: display, GPU acceleration, network and audio access.
+  /// Access to the tray icon host is added when the app uses a tray icon.
+  pub finish_args: Option<Vec<String>>,
+  /// Additional flatpak-builder modules built before the app.
+  pub modules: Vec<serde_json::Value>,
+  /// Whether to produce a single-file bundle or only the OSTree repository.
+  pub output: FlatpakOutput,
+  /// The path of the OSTree repository to export the app to.
+  pub repo: Option<PathBuf>,
+  /// List of custom files to add to the Flatpak.
+  /// Maps the path in the `/app` prefix to the path of the file to include (relative to the current working directory).
+  pub files: HashMap<PathBuf, PathBuf>,
+  /// Path to a custom desktop file Handlebars template.
+  ///
+  /// Available variables: `categories`, `comment` (optional), `exec`, `icon` and `name`.
+  ///
+  /// Default file contents:
+  /// ```text
+  #[doc = include_str!("./linux/freedesktop/main.desktop")]
+  /// ```
+  pub desktop_template: Option<PathBuf>,
+}
+
+/// A screenshot of the app in the AppStream metainfo file.
+#[derive(Clone, Debug)]
+pub struct MetainfoScreenshot {
+  /// The URL of the screenshot image.
+  pub image: String,
+  /// A short description of the screenshot.
+  pub caption: Option<String>,
+}
+
+/// A release of the app in the AppStream metainfo file.
+#[derive(Clone, Debug)]
+pub struct MetainfoRelease {
+  /// The released version.
+  pub version: String,
+  /// The release date, formatted as `YYYY-MM-DD`.
+  pub date: String,
+  /// The release notes, with paragraphs separated by blank lines.
+  pub description: Option<String>,
+}
+
+/// The settings of the AppStream metainfo file installed by the Linux bundles.
+#[derive(Clone, Debug, Default)]
+pub struct MetainfoSettings {
+  /// Screenshots of the app, the first one being the default screenshot.
+  pub screenshots: Vec<MetainfoScreenshot>,
+  /// The releases of the app, newest first. Defaults to the current version, released at build time.
+  pub releases: Vec<MetainfoRelease>,
+  /// The OARS content rating attributes of the app, e.g. `social-chat` to `intense`.
+  pub content_rating: HashMap<String, String>,
 }
 
 /// The RPM bundle settings.
@@ -262,6 +432,14 @@ pub struct RpmSettings {
   /// Path to script that will be executed after the package is removed. See
   /// <http://ftp.rpm.org/max-rpm/s1-rpm-inside-scripts.html>
   pub post_remove_script: Option<PathBuf>,
+  /// Whether to add the packages providing the shared libraries needed by the main binary
+  /// and the sidecars to the dependencies, found with the mapping file or `rpm -qf`.
+  pub auto_depends: bool,
+  /// Path to a JSON file mapping shared library names to the package providing them.
+  pub auto_depends_mapping: Option<PathBuf>,
+  /// Path to the ASCII-armored GPG secret key used to sign the package,
+  /// unless the `TAURI_SIGNING_RPM_KEY` environment variable is set.
+  pub signing_key: Option<PathBuf>,
 }
 
 /// Position coordinates struct.
@@ -632,6 +810,10 @@ pub struct BundleSettings {
   pub appimage: AppImageSettings,
   /// Rpm-specific settings.
   pub rpm: RpmSettings,
+  /// Flatpak-specific settings.
+  pub flatpak: FlatpakSettings,
+  /// AppStream metainfo settings, shared by the Linux bundles.
+  pub metainfo: MetainfoSettings,
   /// DMG-specific settings.
   pub dmg: DmgSettings,
   /// MacOS-specific settings.
@@ -734,6 +916,12 @@ pub struct Settings {
   /// the directory to place tools used by the bundler,
   /// if `None`, tools are placed in the current user's platform-specific cache directory.
   local_tools_directory: Option<PathBuf>,
+  /// the previous release updater artifacts to create delta patches from.
+  delta_from: Vec<PathBuf>,
+  /// the software bill of materials settings.
+  sbom: Option<SbomSettings>,
+  /// the timestamp used instead of the current time for reproducible builds.
+  source_date_epoch: Option<u64>,
   /// the bundle settings.
   bundle_settings: BundleSettings,
   /// the binaries to bundle.
@@ -753,6 +941,9 @@ pub struct SettingsBuilder {
   binaries: Vec<BundleBinary>,
   target: Option<String>,
   local_tools_directory: Option<PathBuf>,
+  delta_from: Vec<PathBuf>,
+  sbom: Option<SbomSettings>,
+  source_date_epoch: Option<u64>,
 }
 
 impl SettingsBuilder {
@@ -780,6 +971,29 @@ impl SettingsBuilder {
     self
   }
 
+  /// Sets the previous release updater artifacts to create delta patches from,
+  /// written next to the updater artifacts with a manifest of their hashes.
+  #[must_use]
+  pub fn delta_from(mut self, paths: Vec<PathBuf>) -> Self {
+    self.delta_from = paths;
+    self
+  }
+
+  /// Sets the software bill of materials written next to each bundle.
+  #[must_use]
+  pub fn sbom(mut self, sbom: SbomSettings) -> Self {
+    self.sbom.replace(sbom);
+    self
+  }
+
+  /// Sets the timestamp used instead of the current time, clamping the file modification times
+  /// and build timestamps of the packages. Defaults to the `SOURCE_DATE_EPOCH` environment variable.
+  #[must_use]
+  pub fn source_date_epoch(mut self, epoch: u64) -> Self {
+    self.source_date_epoch.replace(epoch);
+    self
+  }
+
   /// Sets the package types to create.
   #[must_use]
   pub fn package_types(mut self, package_types: Vec<PackageType>) -> Self {
@@ -844,6 +1058,11 @@ impl SettingsBuilder {
         .project_out_directory
         .ok_or_else(|| crate::Error::GenericError("out directory is required".into()))?,
       local_tools_directory: self.local_tools_directory,
+      delta_from: self.delta_from,
+      sbom: self.sbom,
+      source_date_epoch: self
+        .source_date_epoch
+        .or_else(super::common::source_date_epoch),
       binaries: self.binaries,
       bundle_settings: BundleSettings {
         external_bin: self
@@ -971,7 +1190,12 @@ impl Settings {
     let platform_types = match target_os.as_str() {
       "macos" => vec![PackageType::MacOsBundle, PackageType::Dmg],
       "ios" => vec![PackageType::IosBundle],
-      "linux" => vec![PackageType::Deb, PackageType::Rpm, PackageType::AppImage],
+      "linux" => vec![
+        PackageType::Deb,
+        PackageType::Rpm,
+        PackageType::AppImage,
+        PackageType::Flatpak,
+      ],
       "windows" => vec![PackageType::WindowsMsi, PackageType::Nsis],
       os => {
         return Err(crate::Error::GenericError(format!(
@@ -994,7 +1218,13 @@ impl Settings {
       }
       Ok(types)
     } else {
-      Ok(platform_types)
+      // Flatpak requires flatpak-builder and the runtime to be installed so it must be requested explicitly
+      Ok(
+        platform_types
+          .into_iter()
+          .filter(|t| *t != PackageType::Flatpak)
+          .collect(),
+      )
     }
   }
 
@@ -1157,6 +1387,21 @@ impl Settings {
     self.local_tools_directory.as_deref()
   }
 
+  /// Returns the previous release updater artifacts to create delta patches from.
+  pub fn delta_from(&self) -> &[PathBuf] {
+    &self.delta_from
+  }
+
+  /// Returns the software bill of materials settings, if enabled.
+  pub fn sbom(&self) -> Option<&SbomSettings> {
+    self.sbom.as_ref()
+  }
+
+  /// Returns the timestamp used instead of the current time for reproducible builds, if any.
+  pub fn source_date_epoch(&self) -> Option<u64> {
+    self.source_date_epoch
+  }
+
   /// Returns the debian settings.
   pub fn deb(&self) -> &DebianSettings {
     &self.bundle_settings.deb
@@ -1172,6 +1417,16 @@ impl Settings {
     &self.bundle_settings.rpm
   }
 
+  /// Returns the Flatpak settings.
+  pub fn flatpak(&self) -> &FlatpakSettings {
+    &self.bundle_settings.flatpak
+  }
+
+  /// Returns the AppStream metainfo settings.
+  pub fn metainfo(&self) -> &MetainfoSettings {
+    &self.bundle_settings.metainfo
+  }
+
   /// Returns the DMG settings.
   pub fn dmg(&self) -> &DmgSettings {
     &self.bundle_settings.dmg
@@ -1192,3 +1447,32 @@ impl Settings {
     self.bundle_settings.updater.as_ref()
   }
 }
+
+/// The settings of a `Notes` app with a `notes` binary, bundled in the given directory for x86_64 Linux.
+#[cfg(test)]
+pub(crate) fn test_settings(out_dir: &Path, bundle_settings: BundleSettings) -> Settings {
+  test_settings_builder(out_dir, bundle_settings)
+    .build()
+    .unwrap()
+}
+
+/// The builder of the [`test_settings`], to customize them further.
+#[cfg(test)]
+pub(crate) fn test_settings_builder(
+  out_dir: &Path,
+  bundle_settings: BundleSettings,
+) -> SettingsBuilder {
+  SettingsBuilder::new()
+    .project_out_directory(out_dir)
+    .package_settings(PackageSettings {
+      product_name: "Notes".into(),
+      version: "1.1.0".into(),
+      description: "Take notes".into(),
+      homepage: None,
+      authors: Some(vec!["Tauri Programme".into()]),
+      default_run: None,
+    })
+    .bundle_settings(bundle_settings)
+    .binaries(vec![BundleBinary::new("notes".into(), true)])
+    .target("x86_64-unknown-linux-gnu".into())
+}
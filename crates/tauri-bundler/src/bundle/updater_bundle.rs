@@ -40,9 +40,9 @@ pub fn bundle_project(settings: &Settings, bundles: &[Bundle]) -> crate::Result<
   }
 
   #[cfg(target_os = "macos")]
-  return bundle_update_macos(bundles);
+  return bundle_update_macos(settings, bundles);
   #[cfg(target_os = "linux")]
-  return bundle_update_linux(bundles);
+  return bundle_update_linux(settings, bundles);
 
   #[cfg(not(any(target_os = "macos", target_os = "linux")))]
   {
@@ -54,7 +54,7 @@ pub fn bundle_project(settings: &Settings, bundles: &[Bundle]) -> crate::Result<
 // Create simple update-macos.tar.gz
 // This is the Mac OS App packaged
 #[cfg(target_os = "macos")]
-fn bundle_update_macos(bundles: &[Bundle]) -> crate::Result<Vec<PathBuf>> {
+fn bundle_update_macos(settings: &Settings, bundles: &[Bundle]) -> crate::Result<Vec<PathBuf>> {
   use std::ffi::OsStr;
 
   // find our .app or rebuild our bundle
@@ -74,8 +74,12 @@ fn bundle_update_macos(bundles: &[Bundle]) -> crate::Result<Vec<PathBuf>> {
 
     // Create our gzip file (need to send parent)
     // as we walk the source directory (source isnt added)
-    create_tar(source_path, &osx_archived_path)
-      .with_context(|| "Failed to tar.gz update directory")?;
+    create_tar(
+      source_path,
+      &osx_archived_path,
+      settings.source_date_epoch(),
+    )
+    .with_context(|| "Failed to tar.gz update directory")?;
 
     log::info!(action = "Bundling"; "{} ({})", osx_archived, display_path(&osx_archived_path));
 
@@ -90,7 +94,7 @@ fn bundle_update_macos(bundles: &[Bundle]) -> crate::Result<Vec<PathBuf>> {
 // Right now in linux we hot replace the bin and request a restart
 // No assets are replaced
 #[cfg(target_os = "linux")]
-fn bundle_update_linux(bundles: &[Bundle]) -> crate::Result<Vec<PathBuf>> {
+fn bundle_update_linux(settings: &Settings, bundles: &[Bundle]) -> crate::Result<Vec<PathBuf>> {
   use std::ffi::OsStr;
 
   // build our app actually we support only appimage on linux
@@ -109,8 +113,12 @@ fn bundle_update_linux(bundles: &[Bundle]) -> crate::Result<Vec<PathBuf>> {
     let appimage_archived_path = PathBuf::from(&appimage_archived);
 
     // Create our gzip file
-    create_tar(source_path, &appimage_archived_path)
-      .with_context(|| "Failed to tar.gz update directory")?;
+    create_tar(
+      source_path,
+      &appimage_archived_path,
+      settings.source_date_epoch(),
+    )
+    .with_context(|| "Failed to tar.gz update directory")?;
 
     log::info!(action = "Bundling"; "{} ({})", appimage_archived, display_path(&appimage_archived_path));
 
@@ -232,64 +240,91 @@ pub fn create_zip(src_file: &Path, dst_file: &Path) -> crate::Result<PathBuf> {
 }
 
 #[cfg(any(target_os = "linux", target_os = "macos"))]
-fn create_tar(src_dir: &Path, dest_path: &Path) -> crate::Result<PathBuf> {
+fn create_tar(
+  src_dir: &Path,
+  dest_path: &Path,
+  source_date_epoch: Option<u64>,
+) -> crate::Result<PathBuf> {
   use flate2::{write::GzEncoder, Compression};
 
   let dest_file = common::create_file(dest_path)?;
   let gzip_encoder = GzEncoder::new(dest_file, Compression::default());
 
-  let gzip_encoder = create_tar_from_src(src_dir, gzip_encoder)?;
+  let gzip_encoder = create_tar_from_src(src_dir, gzip_encoder, source_date_epoch)?;
 
   let mut dest_file = gzip_encoder.finish()?;
   dest_file.flush()?;
   Ok(dest_path.to_owned())
 }
 
-#[cfg(target_os = "macos")]
-fn create_tar_from_src<P: AsRef<Path>, W: Write>(src_dir: P, dest_file: W) -> crate::Result<W> {
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn create_tar_from_src<P: AsRef<Path>, W: Write>(
+  src_dir: P,
+  dest_file: W,
+  source_date_epoch: Option<u64>,
+) -> crate::Result<W> {
   let src_dir = src_dir.as_ref();
   let mut builder = tar::Builder::new(dest_file);
-  builder.follow_symlinks(false);
-  builder.append_dir_all(src_dir.file_name().expect("Path has no file_name"), src_dir)?;
+  // the archive root is the bundle itself, e.g. `app.app/<...>` or `app.AppImage`,
+  // keeping the symbolic links of the macOS frameworks
+  common::append_to_tar(
+    &mut builder,
+    src_dir,
+    Path::new(src_dir.file_name().expect("Path has no file_name")),
+    source_date_epoch,
+    false,
+  )?;
   builder.into_inner().map_err(Into::into)
 }
 
-#[cfg(target_os = "linux")]
-fn create_tar_from_src<P: AsRef<Path>, W: Write>(src_dir: P, dest_file: W) -> crate::Result<W> {
-  let src_dir = src_dir.as_ref();
-  let mut tar_builder = tar::Builder::new(dest_file);
-
-  // validate source type
-  let file_type = fs::metadata(src_dir).expect("Can't read source directory");
-  // if it's a file don't need to walkdir
-  if file_type.is_file() {
-    let mut src_file = fs::File::open(src_dir)?;
-    let file_name = src_dir
-      .file_name()
-      .expect("Can't extract file name from path");
-
-    tar_builder.append_file(file_name, &mut src_file)?;
-  } else {
-    for entry in walkdir::WalkDir::new(src_dir) {
-      let entry = entry?;
-      let src_path = entry.path();
-      if src_path == src_dir {
-        continue;
-      }
-
-      // We add the .parent() because example if we send a path
-      // /dev/src-tauri/target/debug/bundle/osx/app.app
-      // We need a tar with app.app/<...> (source root folder should be included)
-      // safe to unwrap: the path has a parent
-      let dest_path = src_path.strip_prefix(src_dir.parent().unwrap())?;
-      if entry.file_type().is_dir() {
-        tar_builder.append_dir(dest_path, src_path)?;
-      } else {
-        let mut src_file = fs::File::open(src_path)?;
-        tar_builder.append_file(dest_path, &mut src_file)?;
-      }
-    }
+#[cfg(all(test, any(target_os = "linux", target_os = "macos")))]
+mod tests {
+  use super::create_tar;
+  use flate2::read::GzDecoder;
+  use std::{
+    fs::{self, File},
+    os::unix::fs::symlink,
+    path::Path,
+    time::{Duration, SystemTime},
+  };
+
+  #[test]
+  fn archives_reproducibly() {
+    let temp = tempfile::tempdir().unwrap();
+    let app = temp.path().join("Notes.app");
+    fs::create_dir_all(app.join("Contents/MacOS")).unwrap();
+    let binary = app.join("Contents/MacOS/notes");
+    fs::write(&binary, b"notes binary").unwrap();
+    symlink("MacOS/notes", app.join("Contents/notes")).unwrap();
+    let archive = temp.path().join("Notes.app.tar.gz");
+
+    create_tar(&app, &archive, Some(1700000000)).unwrap();
+    let first = fs::read(&archive).unwrap();
+
+    // rebuilding the binary changes its modification time
+    File::options()
+      .write(true)
+      .open(&binary)
+      .unwrap()
+      .set_modified(SystemTime::now() + Duration::from_secs(60))
+      .unwrap();
+    create_tar(&app, &archive, Some(1700000000)).unwrap();
+    let second = fs::read(&archive).unwrap();
+
+    assert_eq!(first, second);
+
+    // the symbolic links of the app bundle are kept
+    let mut tar = tar::Archive::new(GzDecoder::new(File::open(&archive).unwrap()));
+    let link = tar
+      .entries()
+      .unwrap()
+      .map(Result::unwrap)
+      .find(|entry| entry.path().unwrap() == Path::new("Notes.app/Contents/notes"))
+      .unwrap();
+    assert!(link.header().entry_type().is_symlink());
+    assert_eq!(
+      link.link_name().unwrap().as_deref(),
+      Some(Path::new("MacOS/notes"))
+    );
   }
-  let dest_file = tar_builder.into_inner()?;
-  Ok(dest_file)
 }
@@ -0,0 +1,324 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Delta updater artifacts: binary patches from previous release artifacts to the new updater artifacts,
+//! listed in a manifest of hashes so the updater can verify the patched result.
+//!
+//! A patch is a zstd frame compressed with the previous artifact as reference prefix,
+//! the format of `zstd --patch-from`. It is applied with
+//! `zstd -d --long=<windowLog> --patch-from=<previous artifact> <patch>`.
+//!
+//! Patches are made of the artifact files exactly as they were downloaded by the updater,
+//! so the updater must keep the previous artifact around to apply them.
+//! Compressed archives diff worse than their content,
+//! but unlike an archive recreated from the installed app their bytes are known to match the previous release.
+
+use std::{
+  fs::{self, File},
+  io::{self, BufReader, BufWriter, Read, Write},
+  path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use tauri_utils::display_path;
+
+use super::common;
+use crate::Settings;
+
+/// The zstd compression level of the patches.
+const PATCH_COMPRESSION_LEVEL: i32 = 19;
+
+/// The file name suffixes of the updater artifacts.
+/// A patch is only created between artifacts with the same suffix.
+const ARTIFACT_SUFFIXES: &[&str] = &[
+  ".AppImage.tar.gz",
+  ".app.tar.gz",
+  ".nsis.zip",
+  ".msi.zip",
+  ".AppImage",
+  "-setup.exe",
+  ".msi",
+];
+
+/// The delta manifest written next to an updater artifact, as `<artifact>.delta.json`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Manifest {
+  /// The app version the patches update to.
+  version: String,
+  /// The file name of the updater artifact.
+  artifact: String,
+  /// The size of the artifact, in bytes.
+  size: u64,
+  /// The SHA-256 hash of the artifact, to verify the patched result.
+  sha256: String,
+  /// The patches, one per previous release artifact.
+  patches: Vec<Patch>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Patch {
+  /// The file name of the patch, next to the manifest.
+  name: String,
+  /// The size of the patch file, in bytes.
+  size: u64,
+  /// The SHA-256 hash of the patch file.
+  sha256: String,
+  /// The zstd window log the patch must be decompressed with.
+  window_log: u32,
+  /// The previous release artifact the patch applies to.
+  from: PatchSource,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PatchSource {
+  /// The file name of the previous release artifact.
+  name: String,
+  /// The size of the previous artifact, in bytes.
+  size: u64,
+  /// The SHA-256 hash of the previous artifact, identifying the installed version.
+  sha256: String,
+}
+
+/// Creates the patches from the previous release artifacts set with [`Settings::delta_from`]
+/// to each of the given updater artifacts, and their manifests.
+///
+/// Returns the paths of the patches and manifests, to be signed like the updater artifacts.
+pub fn bundle_project(settings: &Settings, artifacts: &[PathBuf]) -> crate::Result<Vec<PathBuf>> {
+  let mut paths = Vec::new();
+
+  for artifact in artifacts {
+    let Some(suffix) = artifact_suffix(artifact) else {
+      continue;
+    };
+    let sources: Vec<&PathBuf> = settings
+      .delta_from()
+      .iter()
+      .filter(|source| artifact_suffix(source) == Some(suffix))
+      .collect();
+    if sources.is_empty() {
+      log::warn!(
+        "No previous release artifact matches {}, skipping its delta patches",
+        display_path(artifact)
+      );
+      continue;
+    }
+
+    let artifact_name = file_name(artifact);
+    let (size, artifact_sha256) = hash_file(artifact)?;
+
+    let mut patches = Vec::new();
+    for source in sources {
+      // the reference prefix must be in memory, the new artifact is streamed
+      let old = fs::read(source)?;
+      let old_sha256 = hex::encode(Sha256::digest(&old));
+      let window_log = window_log(old.len() as u64 + size);
+
+      let name = format!("{artifact_name}.{}.patch", &old_sha256[..12]);
+      let patch_path = artifact.with_file_name(&name);
+      log::info!(action = "Bundling"; "{}", display_path(&patch_path));
+      let patch = create_patch(
+        &old,
+        BufReader::new(File::open(artifact)?),
+        size,
+        window_log,
+        HashWriter::new(BufWriter::new(common::create_file(&patch_path)?)),
+      )
+      .with_context(|| format!("Failed to diff {}", display_path(source)))?;
+      let (mut patch_file, patch_size, patch_sha256) = patch.finish();
+      patch_file.flush()?;
+
+      patches.push(Patch {
+        name,
+        size: patch_size,
+        sha256: patch_sha256,
+        window_log,
+        from: PatchSource {
+          name: file_name(source),
+          size: old.len() as u64,
+          sha256: old_sha256,
+        },
+      });
+      paths.push(patch_path);
+    }
+
+    let manifest_path = artifact.with_file_name(format!("{artifact_name}.delta.json"));
+    let mut manifest_file = common::create_file(&manifest_path)?;
+    serde_json::to_writer_pretty(
+      &mut manifest_file,
+      &Manifest {
+        version: settings.version_string().to_string(),
+        artifact: artifact_name,
+        size,
+        sha256: artifact_sha256,
+        patches,
+      },
+    )?;
+    manifest_file.flush()?;
+    paths.push(manifest_path);
+  }
+
+  Ok(paths)
+}
+
+fn artifact_suffix(path: &Path) -> Option<&'static str> {
+  let name = file_name(path);
+  ARTIFACT_SUFFIXES
+    .iter()
+    .find(|suffix| name.ends_with(*suffix))
+    .copied()
+}
+
+fn file_name(path: &Path) -> String {
+  path
+    .file_name()
+    .unwrap_or_default()
+    .to_string_lossy()
+    .into_owned()
+}
+
+/// A writer computing the size and SHA-256 hash of the written data.
+struct HashWriter<W> {
+  inner: W,
+  hasher: Sha256,
+  size: u64,
+}
+
+impl<W: Write> HashWriter<W> {
+  fn new(inner: W) -> Self {
+    Self {
+      inner,
+      hasher: Sha256::new(),
+      size: 0,
+    }
+  }
+
+  /// Returns the inner writer, the size and the hex encoded hash of the written data.
+  fn finish(self) -> (W, u64, String) {
+    (self.inner, self.size, hex::encode(self.hasher.finalize()))
+  }
+}
+
+impl<W: Write> Write for HashWriter<W> {
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    let written = self.inner.write(buf)?;
+    self.hasher.update(&buf[..written]);
+    self.size += written as u64;
+    Ok(written)
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    self.inner.flush()
+  }
+}
+
+/// Returns the size and the hex encoded SHA-256 hash of the file, without reading it in memory.
+fn hash_file(path: &Path) -> crate::Result<(u64, String)> {
+  let mut writer = HashWriter::new(io::sink());
+  io::copy(&mut File::open(path)?, &mut writer)?;
+  let (_, size, sha256) = writer.finish();
+  Ok((size, sha256))
+}
+
+/// The zstd window must cover both artifacts for the new one to reference the whole previous one.
+fn window_log(size: u64) -> u32 {
+  (u64::BITS - size.leading_zeros()).clamp(10, 31)
+}
+
+/// Compresses the new artifact with the previous one as reference prefix, like `zstd --patch-from`,
+/// streaming the patch to the given writer.
+fn create_patch<R: Read, W: Write>(
+  old: &[u8],
+  mut new: R,
+  new_size: u64,
+  window_log: u32,
+  out: W,
+) -> crate::Result<W> {
+  let mut encoder =
+    zstd::stream::write::Encoder::with_ref_prefix(out, PATCH_COMPRESSION_LEVEL, old)?;
+  encoder.window_log(window_log)?;
+  encoder.long_distance_matching(true)?;
+  encoder.include_checksum(true)?;
+  encoder.set_pledged_src_size(Some(new_size))?;
+  io::copy(&mut new, &mut encoder)?;
+  encoder.finish().map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{artifact_suffix, create_patch, hash_file, window_log, HashWriter};
+  use sha2::{Digest, Sha256};
+  use std::{io::Read, path::Path};
+
+  #[test]
+  fn matches_artifact_suffixes() {
+    assert_eq!(
+      artifact_suffix(Path::new("app_1.0.0_amd64.AppImage")),
+      artifact_suffix(Path::new("app_1.1.0_amd64.AppImage"))
+    );
+    assert_eq!(
+      artifact_suffix(Path::new("app_1.0.0_amd64.AppImage.tar.gz")),
+      Some(".AppImage.tar.gz")
+    );
+    assert_eq!(
+      artifact_suffix(Path::new("app_1.0.0_x64-setup.exe")),
+      Some("-setup.exe")
+    );
+    assert_eq!(artifact_suffix(Path::new("app_1.0.0_amd64.deb")), None);
+  }
+
+  #[test]
+  fn patches_round_trip() {
+    // deterministic pseudo-random data, which does not compress on its own
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let old: Vec<u8> = (0..1 << 20)
+      .map(|_| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state as u8
+      })
+      .collect();
+    let mut new = old.clone();
+    new[1000..1100].fill(0);
+    let tail = new.split_off(500_000);
+    new.extend_from_slice(b"inserted");
+    new.extend_from_slice(&tail[..400_000]);
+
+    let window_log = window_log((old.len() + new.len()) as u64);
+    let (patch, size, sha256) = create_patch(
+      &old,
+      &new[..],
+      new.len() as u64,
+      window_log,
+      HashWriter::new(Vec::new()),
+    )
+    .unwrap()
+    .finish();
+    assert!(patch.len() < new.len() / 100);
+    assert_eq!(size, patch.len() as u64);
+    assert_eq!(sha256, hex::encode(Sha256::digest(&patch)));
+
+    let mut decoder = zstd::stream::read::Decoder::with_ref_prefix(&patch[..], &old).unwrap();
+    decoder.window_log_max(window_log).unwrap();
+    let mut patched = Vec::new();
+    decoder.read_to_end(&mut patched).unwrap();
+    assert_eq!(patched, new);
+  }
+
+  #[test]
+  fn hashes_artifact_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app_1.0.0_amd64.AppImage.tar.gz");
+    std::fs::write(&path, b"artifact").unwrap();
+    assert_eq!(
+      hash_file(&path).unwrap(),
+      (8, hex::encode(Sha256::digest(b"artifact")))
+    );
+  }
+}
@@ -107,7 +107,6 @@ pub enum Error {
   #[error("failed to sign app: {0}")]
   Sign(String),
   /// time error.
-  #[cfg(target_os = "macos")]
   #[error("`{0}`")]
   TimeError(#[from] time::error::Error),
   /// Plist error.
@@ -118,6 +117,14 @@ pub enum Error {
   #[cfg(target_os = "linux")]
   #[error("{0}")]
   RpmError(#[from] rpm::Error),
+  /// ELF parsing error.
+  #[cfg(target_os = "linux")]
+  #[error("`{0}`")]
+  ElfError(#[from] goblin::error::Error),
+  /// Squashfs error.
+  #[cfg(target_os = "linux")]
+  #[error("`{0}`")]
+  SquashfsError(#[from] backhand::BackhandError),
 }
 
 /// Convenient type alias of Result type.
@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: MIT
 
 use crate::{
-  bundle::BundleFormat,
+  bundle::{BundleFormat, SbomFormat},
   helpers::{
     self,
     app_paths::tauri_dir,
@@ -14,7 +14,7 @@ use crate::{
 };
 use anyhow::Context;
 use clap::{ArgAction, Parser};
-use std::env::set_current_dir;
+use std::{env::set_current_dir, path::PathBuf};
 use tauri_utils::platform::Target;
 
 #[derive(Debug, Clone, Parser)]
@@ -52,6 +52,14 @@ pub struct Options {
   pub config: Option<ConfigValue>,
   /// Command line arguments passed to the runner. Use `--` to explicitly mark the start of the arguments.
   pub args: Vec<String>,
+  /// Previous release updater artifacts to create delta patches from, e.g. the `.AppImage` or `.app.tar.gz` of the last releases.
+  ///
+  /// The patches and their manifests are written next to the updater artifacts and signed with them. Can be used multiple times.
+  #[clap(long)]
+  pub delta_from: Vec<PathBuf>,
+  /// Writes a software bill of materials next to each bundle, listing the Rust crates, the npm packages of a `package-lock.json` lockfile, the bundled binaries and resources with their hashes and the declared system dependencies.
+  #[clap(long, value_enum)]
+  pub sbom: Option<SbomFormat>,
   /// Skip prompting for values
   #[clap(long, env = "CI")]
   pub ci: bool,
@@ -47,6 +47,24 @@ impl ValueEnum for BundleFormat {
   }
 }
 
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum SbomFormat {
+  /// CycloneDX 1.5 JSON, written as `<bundle>.cdx.json`
+  #[value(name = "cyclonedx")]
+  CycloneDx,
+  /// SPDX 2.3 JSON, written as `<bundle>.spdx.json`
+  Spdx,
+}
+
+impl From<SbomFormat> for tauri_bundler::SbomFormat {
+  fn from(format: SbomFormat) -> Self {
+    match format {
+      SbomFormat::CycloneDx => Self::CycloneDx,
+      SbomFormat::Spdx => Self::Spdx,
+    }
+  }
+}
+
 #[derive(Debug, Parser, Clone)]
 #[clap(
   about = "Generate bundles and installers for your app (already built by `tauri build`)",
@@ -77,6 +95,14 @@ pub struct Options {
   /// Skip prompting for values
   #[clap(long, env = "CI")]
   pub ci: bool,
+  /// Previous release updater artifacts to create delta patches from, e.g. the `.AppImage` or `.app.tar.gz` of the last releases.
+  ///
+  /// The patches and their manifests are written next to the updater artifacts and signed with them. Can be used multiple times.
+  #[clap(long)]
+  pub delta_from: Vec<PathBuf>,
+  /// Writes a software bill of materials next to each bundle, listing the Rust crates, the npm packages of a `package-lock.json` lockfile, the bundled binaries and resources with their hashes and the declared system dependencies.
+  #[clap(long, value_enum)]
+  pub sbom: Option<SbomFormat>,
 }
 
 impl From<crate::build::Options> for Options {
@@ -88,6 +114,8 @@ impl From<crate::build::Options> for Options {
       debug: value.debug,
       ci: value.ci,
       config: value.config,
+      delta_from: value.delta_from,
+      sbom: value.sbom,
     }
   }
 }
@@ -15,6 +15,7 @@ pub struct CargoLockPackage {
   pub name: String,
   pub version: String,
   pub source: Option<String>,
+  pub checksum: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -14,6 +14,7 @@ pub mod npm;
 pub mod pbxproj;
 pub mod plugins;
 pub mod prompts;
+pub mod sbom;
 pub mod template;
 pub mod updater_signature;
 
@@ -0,0 +1,363 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{
+  collections::{BTreeSet, HashMap, VecDeque},
+  fs,
+  path::{Path, PathBuf},
+  process::Command,
+};
+
+use anyhow::Context;
+use base64::Engine;
+use serde::Deserialize;
+use tauri_bundler::{SbomEcosystem, SbomHash, SbomHashAlgorithm, SbomPackage};
+
+use super::{
+  app_paths::{app_dir, tauri_dir},
+  cargo_manifest::CargoLock,
+  npm::PackageManager,
+};
+
+#[derive(Deserialize)]
+struct CargoMetadata {
+  packages: Vec<CargoMetadataPackage>,
+  resolve: Option<CargoMetadataResolve>,
+  workspace_root: PathBuf,
+}
+
+#[derive(Deserialize)]
+struct CargoMetadataPackage {
+  id: String,
+  name: String,
+  version: String,
+  license: Option<String>,
+  manifest_path: PathBuf,
+}
+
+#[derive(Deserialize)]
+struct CargoMetadataResolve {
+  nodes: Vec<CargoMetadataNode>,
+}
+
+#[derive(Deserialize)]
+struct CargoMetadataNode {
+  id: String,
+  deps: Vec<CargoMetadataDep>,
+}
+
+#[derive(Deserialize)]
+struct CargoMetadataDep {
+  pkg: String,
+  dep_kinds: Vec<CargoMetadataDepKind>,
+}
+
+#[derive(Deserialize)]
+struct CargoMetadataDepKind {
+  kind: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct PackageLock {
+  #[serde(default)]
+  packages: HashMap<String, PackageLockPackage>,
+}
+
+#[derive(Deserialize)]
+struct PackageLockPackage {
+  name: Option<String>,
+  version: Option<String>,
+  license: Option<String>,
+  integrity: Option<String>,
+  #[serde(default)]
+  dev: bool,
+  #[serde(default)]
+  link: bool,
+}
+
+/// Lists the Rust crates the app is built from with the given features and target,
+/// and the npm packages of the frontend, for the software bill of materials.
+pub fn packages(features: &[String], target: &str) -> crate::Result<Vec<SbomPackage>> {
+  let mut packages = cargo_packages(features, target)?;
+
+  let lockfile = app_dir().join("package-lock.json");
+  if lockfile.exists() {
+    let lock: PackageLock = serde_json::from_str(
+      &fs::read_to_string(&lockfile).context("failed to read package-lock.json")?,
+    )
+    .context("failed to parse package-lock.json")?;
+    packages.extend(npm_packages(lock));
+  } else if !PackageManager::from_project(app_dir()).is_empty() {
+    log::warn!("The software bill of materials only lists the npm packages of a package-lock.json lockfile, the frontend packages are not listed");
+  }
+
+  Ok(packages)
+}
+
+fn cargo_packages(features: &[String], target: &str) -> crate::Result<Vec<SbomPackage>> {
+  let mut cmd = Command::new("cargo");
+  cmd
+    .args(["metadata", "--format-version", "1"])
+    .current_dir(tauri_dir());
+  if !features.iter().any(|feature| feature == "default") {
+    cmd.arg("--no-default-features");
+  }
+  let features: Vec<&str> = features
+    .iter()
+    .map(String::as_str)
+    .filter(|feature| *feature != "default")
+    .collect();
+  if !features.is_empty() {
+    cmd.arg("--features").arg(features.join(","));
+  }
+  // an universal macOS app includes the crates of both architectures
+  if target != "universal-apple-darwin" {
+    cmd.args(["--filter-platform", target]);
+  }
+  let output = cmd.output()?;
+  if !output.status.success() {
+    return Err(anyhow::anyhow!(
+      "cargo metadata command exited with a non zero exit code: {}",
+      String::from_utf8_lossy(&output.stderr)
+    ));
+  }
+  let metadata: CargoMetadata = serde_json::from_slice(&output.stdout)?;
+
+  let lock: Option<CargoLock> = fs::read_to_string(metadata.workspace_root.join("Cargo.lock"))
+    .ok()
+    .and_then(|s| toml::from_str(&s).ok());
+
+  Ok(resolve_cargo_packages(
+    &metadata,
+    &tauri_dir().join("Cargo.toml"),
+    lock.as_ref(),
+  ))
+}
+
+/// Walks the normal dependencies of the app package, leaving out the build and dev dependencies
+/// which are not part of the app binary.
+fn resolve_cargo_packages(
+  metadata: &CargoMetadata,
+  manifest_path: &Path,
+  lock: Option<&CargoLock>,
+) -> Vec<SbomPackage> {
+  let manifest_path =
+    dunce::canonicalize(manifest_path).unwrap_or_else(|_| manifest_path.to_path_buf());
+  let Some(app) = metadata.packages.iter().find(|package| {
+    dunce::canonicalize(&package.manifest_path).unwrap_or_else(|_| package.manifest_path.clone())
+      == manifest_path
+  }) else {
+    return Vec::new();
+  };
+  let Some(resolve) = &metadata.resolve else {
+    return Vec::new();
+  };
+  let nodes: HashMap<&str, &CargoMetadataNode> = resolve
+    .nodes
+    .iter()
+    .map(|node| (node.id.as_str(), node))
+    .collect();
+
+  let mut ids = BTreeSet::new();
+  let mut queue = VecDeque::from([app.id.as_str()]);
+  while let Some(id) = queue.pop_front() {
+    let Some(node) = nodes.get(id) else {
+      continue;
+    };
+    for dep in &node.deps {
+      let is_normal = dep.dep_kinds.iter().any(|kind| kind.kind.is_none());
+      if is_normal && ids.insert(dep.pkg.as_str()) {
+        queue.push_back(&dep.pkg);
+      }
+    }
+  }
+
+  let checksums: HashMap<(&str, &str), &str> = lock
+    .map(|lock| {
+      lock
+        .package
+        .iter()
+        .filter_map(|package| {
+          package
+            .checksum
+            .as_deref()
+            .map(|checksum| ((package.name.as_str(), package.version.as_str()), checksum))
+        })
+        .collect()
+    })
+    .unwrap_or_default();
+
+  metadata
+    .packages
+    .iter()
+    .filter(|package| package.id != app.id && ids.contains(package.id.as_str()))
+    .map(|package| SbomPackage {
+      ecosystem: SbomEcosystem::Cargo,
+      name: package.name.clone(),
+      version: package.version.clone(),
+      // `/` is the deprecated separator of alternative licenses
+      license: package
+        .license
+        .as_ref()
+        .map(|license| license.replace('/', " OR ")),
+      hashes: checksums
+        .get(&(package.name.as_str(), package.version.as_str()))
+        .map(|checksum| {
+          vec![SbomHash {
+            algorithm: SbomHashAlgorithm::Sha256,
+            value: checksum.to_string(),
+          }]
+        })
+        .unwrap_or_default(),
+    })
+    .collect()
+}
+
+/// Lists the packages installed in `node_modules` by a lockfile version 2 or 3,
+/// leaving out the dev dependencies which are not part of the frontend build output.
+fn npm_packages(lock: PackageLock) -> Vec<SbomPackage> {
+  let mut packages: Vec<SbomPackage> = lock
+    .packages
+    .into_iter()
+    .filter(|(path, package)| path.contains("node_modules/") && !package.dev && !package.link)
+    .filter_map(|(path, package)| {
+      let name = package.name.unwrap_or_else(|| {
+        path
+          .rsplit_once("node_modules/")
+          .map(|(_, name)| name.to_string())
+          .unwrap_or(path)
+      });
+      Some(SbomPackage {
+        ecosystem: SbomEcosystem::Npm,
+        name,
+        version: package.version?,
+        license: package.license,
+        hashes: package
+          .integrity
+          .as_deref()
+          .map(parse_integrity)
+          .unwrap_or_default(),
+      })
+    })
+    .collect();
+
+  packages.sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+  packages.dedup_by(|a, b| a.name == b.name && a.version == b.version);
+  packages
+}
+
+/// Parses a subresource integrity string such as `sha512-<base64>` into hex encoded hashes.
+fn parse_integrity(integrity: &str) -> Vec<SbomHash> {
+  integrity
+    .split_whitespace()
+    .filter_map(|hash| {
+      let (algorithm, value) = hash.split_once('-')?;
+      let algorithm = match algorithm {
+        "sha1" => SbomHashAlgorithm::Sha1,
+        "sha256" => SbomHashAlgorithm::Sha256,
+        "sha512" => SbomHashAlgorithm::Sha512,
+        _ => return None,
+      };
+      let value = base64::engine::general_purpose::STANDARD
+        .decode(value)
+        .ok()?
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+      Some(SbomHash { algorithm, value })
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{npm_packages, parse_integrity, resolve_cargo_packages, CargoMetadata, PackageLock};
+  use crate::helpers::cargo_manifest::CargoLock;
+  use tauri_bundler::{SbomEcosystem, SbomHashAlgorithm};
+
+  #[test]
+  fn resolves_normal_cargo_dependencies() {
+    let root = tempfile::tempdir().unwrap();
+    let manifest_path = root.path().join("Cargo.toml");
+    std::fs::write(&manifest_path, "").unwrap();
+    let metadata: CargoMetadata = serde_json::from_value(serde_json::json!({
+      "packages": [
+        { "id": "app", "name": "app", "version": "0.1.0", "license": null, "manifest_path": manifest_path },
+        { "id": "serde", "name": "serde", "version": "1.0.210", "license": "MIT OR Apache-2.0", "manifest_path": "/registry/serde/Cargo.toml" },
+        { "id": "libc", "name": "libc", "version": "0.2.159", "license": "MIT/Apache-2.0", "manifest_path": "/registry/libc/Cargo.toml" },
+        { "id": "cc", "name": "cc", "version": "1.1.28", "license": "MIT", "manifest_path": "/registry/cc/Cargo.toml" },
+        { "id": "unused", "name": "unused", "version": "1.0.0", "license": "MIT", "manifest_path": "/registry/unused/Cargo.toml" }
+      ],
+      "resolve": {
+        "nodes": [
+          { "id": "app", "deps": [
+            { "pkg": "serde", "dep_kinds": [{ "kind": null }] },
+            { "pkg": "cc", "dep_kinds": [{ "kind": "build" }] }
+          ] },
+          { "id": "serde", "deps": [{ "pkg": "libc", "dep_kinds": [{ "kind": null }, { "kind": "build" }] }] },
+          { "id": "libc", "deps": [] },
+          { "id": "cc", "deps": [{ "pkg": "unused", "dep_kinds": [{ "kind": null }] }] }
+        ]
+      },
+      "workspace_root": root.path()
+    }))
+    .unwrap();
+    let lock: CargoLock = toml::from_str(
+      r#"
+        [[package]]
+        name = "serde"
+        version = "1.0.210"
+        source = "registry+https://github.com/rust-lang/crates.io-index"
+        checksum = "c8e3592472072e6e22e0a54d5904d9febf8508f65fb8552499a1abc7d1078c3a"
+      "#,
+    )
+    .unwrap();
+
+    let packages = resolve_cargo_packages(&metadata, &manifest_path, Some(&lock));
+    let names: Vec<&str> = packages.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, ["serde", "libc"]);
+    assert_eq!(packages[0].ecosystem, SbomEcosystem::Cargo);
+    assert_eq!(
+      packages[0].hashes[0].value,
+      "c8e3592472072e6e22e0a54d5904d9febf8508f65fb8552499a1abc7d1078c3a"
+    );
+    assert_eq!(packages[1].license.as_deref(), Some("MIT OR Apache-2.0"));
+    assert!(packages[1].hashes.is_empty());
+  }
+
+  #[test]
+  fn lists_npm_packages() {
+    let lock: PackageLock = serde_json::from_str(
+      r#"{
+        "lockfileVersion": 3,
+        "packages": {
+          "": { "name": "app", "version": "0.1.0" },
+          "node_modules/@tauri-apps/api": { "version": "2.0.2", "license": "Apache-2.0 OR MIT", "integrity": "sha512-3wSwmG+1kr6WrgAFKK5ijkNFPp8TT3FLj3YHUb5EwMO+3FxX4uWlfSWkeeBy+Kc1RsKzugtYLuuya+98Flj+3w==" },
+          "node_modules/vite": { "version": "5.4.8", "dev": true },
+          "node_modules/a/node_modules/b": { "version": "1.0.0" },
+          "packages/lib": { "name": "lib", "version": "0.1.0" },
+          "node_modules/lib": { "resolved": "packages/lib", "link": true }
+        }
+      }"#,
+    )
+    .unwrap();
+
+    let packages = npm_packages(lock);
+    let names: Vec<(&str, &str)> = packages
+      .iter()
+      .map(|p| (p.name.as_str(), p.version.as_str()))
+      .collect();
+    assert_eq!(names, [("@tauri-apps/api", "2.0.2"), ("b", "1.0.0")]);
+    assert_eq!(packages[0].hashes[0].algorithm, SbomHashAlgorithm::Sha512);
+    assert_eq!(packages[0].hashes[0].value.len(), 128);
+  }
+
+  #[test]
+  fn parses_integrity() {
+    let hashes = parse_integrity("sha1-qUJTCgyRj5a+LzEZ4HR5AuyKtSE= md5-ignored");
+    assert_eq!(hashes.len(), 1);
+    assert_eq!(hashes[0].algorithm, SbomHashAlgorithm::Sha1);
+    assert_eq!(hashes[0].value, "a942530a0c918f96be2f3119e0747902ec8ab521");
+  }
+}
@@ -5,7 +5,7 @@
 use anyhow::Context;
 use base64::Engine;
 use minisign::{
-  sign, KeyPair as KP, PublicKey, PublicKeyBox, SecretKey, SecretKeyBox, SignatureBox,
+  sign, verify, KeyPair as KP, PublicKey, PublicKeyBox, SecretKey, SecretKeyBox, SignatureBox,
 };
 use std::{
   fs::{self, File, OpenOptions},
@@ -152,6 +152,25 @@ pub fn pub_key<S: AsRef<[u8]>>(public_key: S) -> crate::Result<PublicKey> {
   Ok(pk)
 }
 
+/// Verifies the base64 encoded signature at `signature_path`, as written by [`sign_file`], of the file at `bin_path`.
+///
+/// Returns the trusted comment of the signature.
+pub fn verify_file<P: AsRef<Path>>(
+  public_key: &PublicKey,
+  bin_path: P,
+  signature_path: &Path,
+) -> crate::Result<String> {
+  let encoded_signature = fs::read_to_string(signature_path)
+    .with_context(|| format!("failed to read {}", signature_path.display()))?;
+  let signature_box = SignatureBox::from_string(
+    &decode_key(encoded_signature.trim()).context("failed to decode base64 signature")?,
+  )
+  .context("failed to load updater signature")?;
+  let data_reader = open_data_file(bin_path)?;
+  verify(public_key, &signature_box, data_reader, true, false, false)?;
+  Ok(signature_box.trusted_comment()?)
+}
+
 fn unix_timestamp() -> u64 {
   let start = SystemTime::now();
   let since_the_epoch = start
@@ -186,4 +205,23 @@ mod tests {
       super::secret_key(PRIVATE_KEY, Some("".into())).expect("failed to resolve secret key");
     super::sign_file(&secret_key, &path).expect("failed to sign file");
   }
+
+  #[test]
+  fn verifies_signed_file() {
+    let path = std::env::temp_dir().join("minisign-verify-text.txt");
+    std::fs::write(&path, b"TAURI").expect("failed to write test file");
+
+    let key_pair = super::generate_key(Some("".into())).expect("failed to generate key pair");
+    let secret_key =
+      super::secret_key(&key_pair.sk, Some("".into())).expect("failed to resolve secret key");
+    let public_key = super::pub_key(&key_pair.pk).expect("failed to resolve public key");
+    let (signature_path, _) = super::sign_file(&secret_key, &path).expect("failed to sign file");
+
+    let trusted_comment =
+      super::verify_file(&public_key, &path, &signature_path).expect("invalid signature");
+    assert!(trusted_comment.ends_with("file:minisign-verify-text.txt"));
+
+    std::fs::write(&path, b"TAURI!").expect("failed to write test file");
+    assert!(super::verify_file(&public_key, &path, &signature_path).is_err());
+  }
 }
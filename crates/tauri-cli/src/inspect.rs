@@ -7,6 +7,8 @@ use clap::{Parser, Subcommand};
 
 use crate::interface::{AppInterface, AppSettings, Interface};
 
+mod bundle;
+
 #[derive(Debug, Parser)]
 #[clap(about = "Manage or create permissions for your app or plugin")]
 pub struct Cli {
@@ -18,11 +20,14 @@ pub struct Cli {
 enum Commands {
   /// Print the default Upgrade Code used by MSI installer derived from productName.
   WixUpgradeCode,
+  /// List the files and metadata of a .deb, .rpm, AppImage or .tar.gz updater bundle, verify its updater signature or compare it with another bundle.
+  Bundle(bundle::Options),
 }
 
 pub fn command(cli: Cli) -> Result<()> {
   match cli.command {
     Commands::WixUpgradeCode => wix_upgrade_code(),
+    Commands::Bundle(options) => bundle::command(options),
   }
 }
 
@@ -0,0 +1,857 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{
+  collections::BTreeMap,
+  fs::File,
+  io::{self, BufReader, Read, Seek, SeekFrom},
+  path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use backhand::{FilesystemReader, InnerNode};
+use clap::Parser;
+use flate2::read::GzDecoder;
+use sha2::{Digest, Sha256};
+use tauri_utils::display_path;
+
+use crate::{helpers::updater_signature, Result};
+
+#[derive(Debug, Parser)]
+pub struct Options {
+  /// Path to the .deb, .rpm, .AppImage or .tar.gz updater bundle to inspect.
+  artifact: PathBuf,
+  /// Path to another bundle to compare the artifact with, e.g. the same bundle of the previous release.
+  #[clap(long)]
+  diff: Option<PathBuf>,
+  /// Updater public key, or path to it, to verify the updater signature of the artifact with.
+  #[clap(long)]
+  pubkey: Option<String>,
+  /// Path to the updater signature of the artifact, defaults to `<artifact>.sig`.
+  #[clap(long, requires = "pubkey")]
+  signature: Option<PathBuf>,
+}
+
+/// The bundle formats that can be inspected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+  Deb,
+  Rpm,
+  AppImage,
+  TarGz,
+}
+
+impl Format {
+  fn from_path(path: &Path) -> Option<Self> {
+    let name = path.file_name()?.to_string_lossy().to_lowercase();
+    if name.ends_with(".deb") {
+      Some(Self::Deb)
+    } else if name.ends_with(".rpm") {
+      Some(Self::Rpm)
+    } else if name.ends_with(".appimage") {
+      Some(Self::AppImage)
+    } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+      Some(Self::TarGz)
+    } else {
+      None
+    }
+  }
+}
+
+/// A file, directory or link of a bundle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Entry {
+  File {
+    mode: u32,
+    size: u64,
+    sha256: String,
+  },
+  Dir {
+    mode: u32,
+  },
+  Link {
+    target: String,
+  },
+}
+
+/// The content of a bundle.
+#[derive(Debug, Default)]
+struct Bundle {
+  /// The package metadata, e.g. the fields of the Debian control file.
+  metadata: Vec<(String, String)>,
+  /// The entries by path, relative to the root of the bundle.
+  entries: BTreeMap<String, Entry>,
+  /// The content of the desktop entries by path.
+  desktop_entries: BTreeMap<String, String>,
+}
+
+impl Bundle {
+  fn add_dir(&mut self, path: String, mode: u32) {
+    self.entries.insert(path, Entry::Dir { mode });
+  }
+
+  fn add_link(&mut self, path: String, target: String) {
+    self.entries.insert(path, Entry::Link { target });
+  }
+
+  /// Adds a file, hashing its content and keeping the content of the desktop entries.
+  fn add_file(&mut self, path: String, mode: u32, mut reader: impl Read) -> Result<()> {
+    let mut hasher = Sha256::new();
+    let size = if path.ends_with(".desktop") {
+      let mut content = Vec::new();
+      reader.read_to_end(&mut content)?;
+      hasher.update(&content);
+      self
+        .desktop_entries
+        .insert(path.clone(), String::from_utf8_lossy(&content).into_owned());
+      content.len() as u64
+    } else {
+      io::copy(&mut reader, &mut hasher)?
+    };
+    self.entries.insert(
+      path,
+      Entry::File {
+        mode,
+        size,
+        sha256: hex::encode(hasher.finalize()),
+      },
+    );
+    Ok(())
+  }
+
+  /// The icons, following the freedesktop icon theme layout and the `.DirIcon` of AppImages.
+  fn icons(&self) -> impl Iterator<Item = (&String, &Entry)> {
+    self.entries.iter().filter(|(path, entry)| {
+      !matches!(entry, Entry::Dir { .. })
+        && (path.contains("share/icons/")
+          || path.contains("share/pixmaps/")
+          || path.as_str() == ".DirIcon")
+    })
+  }
+}
+
+pub fn command(options: Options) -> Result<()> {
+  if let Some(pubkey) = &options.pubkey {
+    verify_signature(&options.artifact, pubkey, options.signature.as_deref())?;
+    // the signature of the Windows installers and their zip archives can be verified without listing them
+    if Format::from_path(&options.artifact).is_none() && options.diff.is_none() {
+      return Ok(());
+    }
+  }
+
+  let bundle = read(&options.artifact)?;
+  match &options.diff {
+    Some(other) => print_diff(other, &read(other)?, &options.artifact, &bundle),
+    None => print(&options.artifact, &bundle),
+  }
+
+  Ok(())
+}
+
+fn verify_signature(artifact: &Path, pubkey: &str, signature: Option<&Path>) -> Result<()> {
+  // check if pubkey points to a file...
+  let maybe_path = Path::new(pubkey);
+  let pubkey = if maybe_path.exists() {
+    std::fs::read_to_string(maybe_path)?
+  } else {
+    pubkey.to_string()
+  };
+  let public_key = updater_signature::pub_key(pubkey.trim())?;
+
+  let signature_path = match signature {
+    Some(path) => path.to_path_buf(),
+    None => {
+      let mut name = artifact.file_name().unwrap_or_default().to_os_string();
+      name.push(".sig");
+      artifact.with_file_name(name)
+    }
+  };
+
+  let trusted_comment = updater_signature::verify_file(&public_key, artifact, &signature_path)
+    .with_context(|| {
+      format!(
+        "The updater signature {} does not match {} and the public key",
+        display_path(&signature_path),
+        display_path(artifact)
+      )
+    })?;
+  log::info!(action = "Verified"; "updater signature {} ({trusted_comment})", display_path(&signature_path));
+  Ok(())
+}
+
+fn read(path: &Path) -> Result<Bundle> {
+  let format = Format::from_path(path).with_context(|| {
+    format!(
+      "Cannot inspect {}, only .deb, .rpm, .AppImage and .tar.gz bundles are supported",
+      display_path(path)
+    )
+  })?;
+  let open = || File::open(path).with_context(|| format!("failed to open {}", display_path(path)));
+
+  let mut bundle = Bundle::default();
+  match format {
+    Format::Deb => read_deb(open()?, &mut bundle),
+    Format::Rpm => read_rpm(path, &mut bundle),
+    Format::AppImage => read_appimage(open()?, &mut bundle),
+    Format::TarGz => read_tar(GzDecoder::new(open()?), &mut bundle),
+  }
+  .with_context(|| format!("failed to read {}", display_path(path)))?;
+  Ok(bundle)
+}
+
+/// Strips the leading `./` and `/` of archive paths, the root directory becoming an empty path.
+fn normalize_path(path: &str) -> String {
+  let path = path
+    .trim_start_matches("./")
+    .trim_start_matches('/')
+    .trim_end_matches('/');
+  if path == "." {
+    String::new()
+  } else {
+    path.to_string()
+  }
+}
+
+/// Wraps the reader with the decoder of the given compressed tar extension, e.g. `.gz` for `data.tar.gz`.
+fn decompress<'a>(extension: &str, reader: impl Read + 'a) -> Result<Box<dyn Read + 'a>> {
+  Ok(match extension {
+    "" => Box::new(reader),
+    ".gz" => Box::new(GzDecoder::new(reader)),
+    ".xz" => Box::new(xz2::read::XzDecoder::new(reader)),
+    ".zst" => Box::new(zstd::stream::read::Decoder::new(reader)?),
+    _ => anyhow::bail!("unsupported compression {extension}"),
+  })
+}
+
+fn read_tar(reader: impl Read, bundle: &mut Bundle) -> Result<()> {
+  let mut archive = tar::Archive::new(reader);
+  for entry in archive.entries()? {
+    let entry = entry?;
+    let path = normalize_path(&entry.path()?.to_string_lossy());
+    if path.is_empty() {
+      continue;
+    }
+    let entry_type = entry.header().entry_type();
+    let mode = entry.header().mode()?;
+    if entry_type.is_dir() {
+      bundle.add_dir(path, mode);
+    } else if entry_type.is_symlink() || entry_type.is_hard_link() {
+      let target = entry
+        .link_name()?
+        .map(|target| target.to_string_lossy().into_owned())
+        .unwrap_or_default();
+      bundle.add_link(path, target);
+    } else if entry_type.is_file() {
+      bundle.add_file(path, mode, entry)?;
+    }
+  }
+  Ok(())
+}
+
+fn read_deb(file: File, bundle: &mut Bundle) -> Result<()> {
+  let mut archive = ar::Archive::new(file);
+  while let Some(entry) = archive.next_entry() {
+    let entry = entry?;
+    let name = String::from_utf8_lossy(entry.header().identifier()).into_owned();
+    if let Some(extension) = name.strip_prefix("control.tar") {
+      let mut control = tar::Archive::new(decompress(extension, entry)?);
+      for file in control.entries()? {
+        let mut file = file?;
+        if normalize_path(&file.path()?.to_string_lossy()) == "control" {
+          let mut content = String::new();
+          file.read_to_string(&mut content)?;
+          bundle.metadata = parse_control(&content);
+        }
+      }
+    } else if let Some(extension) = name.strip_prefix("data.tar") {
+      read_tar(decompress(extension, entry)?, bundle)?;
+    }
+  }
+  Ok(())
+}
+
+/// Parses the fields of a Debian control file, joining the continuation lines.
+fn parse_control(content: &str) -> Vec<(String, String)> {
+  let mut fields: Vec<(String, String)> = Vec::new();
+  for line in content.lines() {
+    if line.starts_with(' ') || line.starts_with('\t') {
+      if let Some((_, value)) = fields.last_mut() {
+        value.push('\n');
+        value.push_str(line.trim());
+      }
+    } else if let Some((field, value)) = line.split_once(':') {
+      fields.push((field.trim().to_string(), value.trim().to_string()));
+    }
+  }
+  fields
+}
+
+fn read_rpm(path: &Path, bundle: &mut Bundle) -> Result<()> {
+  let package = rpm::Package::open(path)?;
+  let metadata = &package.metadata;
+
+  let fields = [
+    ("Name", metadata.get_name()),
+    ("Version", metadata.get_version()),
+    ("Release", metadata.get_release()),
+    ("Arch", metadata.get_arch()),
+    ("Summary", metadata.get_summary()),
+    ("License", metadata.get_license()),
+    ("URL", metadata.get_url()),
+    ("Vendor", metadata.get_vendor()),
+    ("Description", metadata.get_description()),
+  ];
+  for (field, value) in fields {
+    if let Ok(value) = value {
+      if !value.is_empty() {
+        bundle.metadata.push((field.to_string(), value.to_string()));
+      }
+    }
+  }
+
+  let requires: Vec<String> = metadata
+    .get_requires()
+    .unwrap_or_default()
+    .into_iter()
+    .filter(|dependency| !dependency.name.starts_with("rpmlib("))
+    .map(|dependency| {
+      let flags = dependency.flags;
+      let operator = match (
+        flags.contains(rpm::DependencyFlags::LESS),
+        flags.contains(rpm::DependencyFlags::GREATER),
+        flags.contains(rpm::DependencyFlags::EQUAL),
+      ) {
+        (true, false, true) => "<=",
+        (true, false, false) => "<",
+        (false, true, true) => ">=",
+        (false, true, false) => ">",
+        (false, false, true) => "=",
+        _ => "",
+      };
+      if operator.is_empty() || dependency.version.is_empty() {
+        dependency.name
+      } else {
+        format!("{} {operator} {}", dependency.name, dependency.version)
+      }
+    })
+    .collect();
+  if !requires.is_empty() {
+    bundle
+      .metadata
+      .push(("Requires".to_string(), requires.join(", ")));
+  }
+
+  let payload = decompress_payload(&package.content)?;
+  read_cpio(payload, bundle)
+}
+
+/// Wraps the RPM payload with the decoder of its compression, detected from its magic bytes.
+fn decompress_payload(payload: &[u8]) -> Result<Box<dyn Read + '_>> {
+  let extension = if payload.starts_with(&[0x1f, 0x8b]) {
+    ".gz"
+  } else if payload.starts_with(&[0xfd, b'7', b'z', b'X', b'Z', 0]) {
+    ".xz"
+  } else if payload.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+    ".zst"
+  } else {
+    ""
+  };
+  decompress(extension, payload)
+}
+
+/// Reads a cpio archive in the `newc` format, the payload format of RPM packages.
+fn read_cpio(mut reader: impl Read, bundle: &mut Bundle) -> Result<()> {
+  const HEADER_LEN: usize = 110;
+  const S_IFMT: u32 = 0o170000;
+  const S_IFDIR: u32 = 0o040000;
+  const S_IFLNK: u32 = 0o120000;
+  const S_IFREG: u32 = 0o100000;
+  // PATH_MAX on Linux, including the trailing nul byte
+  const MAX_NAME_LEN: usize = 4096;
+
+  let padding = |len: u64| (4 - len % 4) % 4;
+
+  loop {
+    let mut header = [0; HEADER_LEN];
+    reader.read_exact(&mut header)?;
+    if &header[..6] != b"070701" && &header[..6] != b"070702" {
+      anyhow::bail!("invalid cpio header");
+    }
+    let field = |index: usize| -> Result<u32> {
+      let start = 6 + index * 8;
+      let value = std::str::from_utf8(&header[start..start + 8])?;
+      Ok(u32::from_str_radix(value, 16)?)
+    };
+    let mode = field(1)?;
+    let size = u64::from(field(6)?);
+    let name_len = field(11)? as usize;
+    if name_len > MAX_NAME_LEN {
+      anyhow::bail!("invalid cpio entry name length {name_len}");
+    }
+
+    let mut name = vec![0; name_len];
+    reader.read_exact(&mut name)?;
+    io::copy(
+      &mut (&mut reader).take(padding((HEADER_LEN + name_len) as u64)),
+      &mut io::sink(),
+    )?;
+    let name = String::from_utf8_lossy(name.strip_suffix(&[0]).unwrap_or(&name)).into_owned();
+    if name == "TRAILER!!!" {
+      break;
+    }
+
+    let path = normalize_path(&name);
+    let mut data = (&mut reader).take(size);
+    match mode & S_IFMT {
+      S_IFDIR => bundle.add_dir(path, mode & 0o7777),
+      S_IFLNK => {
+        let mut target = String::new();
+        data.read_to_string(&mut target)?;
+        bundle.add_link(path, target);
+      }
+      S_IFREG => bundle.add_file(path, mode & 0o7777, &mut data)?,
+      _ => {}
+    }
+    io::copy(&mut data, &mut io::sink())?;
+    io::copy(&mut (&mut reader).take(padding(size)), &mut io::sink())?;
+  }
+  Ok(())
+}
+
+/// The size of the ELF file at the start of the reader, from the end of its section header table.
+///
+/// The squashfs filesystem of an AppImage follows its ELF runtime.
+fn elf_size(mut reader: impl Read) -> Result<u64> {
+  let mut header = [0; 64];
+  reader.read_exact(&mut header[..52])?;
+  if &header[..4] != b"\x7fELF" {
+    anyhow::bail!("the AppImage runtime is not an ELF file");
+  }
+  let is_64 = header[4] == 2;
+  if is_64 {
+    reader.read_exact(&mut header[52..])?;
+  }
+  let big_endian = header[5] == 2;
+  let read = |offset: usize, len: usize| -> u64 {
+    let bytes = &header[offset..offset + len];
+    let fold = |value: u64, byte: &u8| (value << 8) | u64::from(*byte);
+    if big_endian {
+      bytes.iter().fold(0, fold)
+    } else {
+      bytes.iter().rev().fold(0, fold)
+    }
+  };
+
+  let (section_headers_offset, section_header_size, section_header_count) = if is_64 {
+    (read(0x28, 8), read(0x3a, 2), read(0x3c, 2))
+  } else {
+    (read(0x20, 4), read(0x2e, 2), read(0x30, 2))
+  };
+  Ok(section_headers_offset + section_header_size * section_header_count)
+}
+
+fn read_appimage(mut file: File, bundle: &mut Bundle) -> Result<()> {
+  let offset = elf_size(&mut file)?;
+  file.seek(SeekFrom::Start(0))?;
+  bundle
+    .metadata
+    .push(("Runtime".to_string(), format!("{offset} bytes")));
+
+  let filesystem = FilesystemReader::from_reader_with_offset(BufReader::new(file), offset)?;
+  for node in filesystem.files() {
+    let path = normalize_path(&node.fullpath.to_string_lossy());
+    if path.is_empty() {
+      continue;
+    }
+    let mode = u32::from(node.header.permissions);
+    match &node.inner {
+      InnerNode::File(file) => {
+        bundle.add_file(path, mode, filesystem.file(&file.basic).reader())?
+      }
+      InnerNode::Symlink(link) => {
+        bundle.add_link(path, link.link.to_string_lossy().into_owned());
+      }
+      InnerNode::Dir(_) => bundle.add_dir(path, mode),
+      _ => {}
+    }
+  }
+  Ok(())
+}
+
+/// Formats the type and permissions like `ls -l`, e.g. `-rwxr-xr-x`.
+fn format_mode(entry: &Entry) -> String {
+  let (kind, mode) = match entry {
+    Entry::File { mode, .. } => ('-', *mode),
+    Entry::Dir { mode } => ('d', *mode),
+    Entry::Link { .. } => ('l', 0o777),
+  };
+  let mut formatted = String::from(kind);
+  for shift in [6, 3, 0] {
+    let bits = (mode >> shift) & 0o7;
+    formatted.push(if bits & 0o4 != 0 { 'r' } else { '-' });
+    formatted.push(if bits & 0o2 != 0 { 'w' } else { '-' });
+    formatted.push(if bits & 0o1 != 0 { 'x' } else { '-' });
+  }
+  formatted
+}
+
+fn print(path: &Path, bundle: &Bundle) {
+  println!("{}", display_path(path));
+
+  if !bundle.metadata.is_empty() {
+    println!("\nMetadata:");
+    for (field, value) in &bundle.metadata {
+      println!("  {field}: {}", value.replace('\n', "\n    "));
+    }
+  }
+
+  if !bundle.desktop_entries.is_empty() {
+    println!("\nDesktop entries:");
+    for (path, content) in &bundle.desktop_entries {
+      println!("  {path}");
+      for line in content.lines() {
+        println!("    {line}");
+      }
+    }
+  }
+
+  let icons: Vec<_> = bundle.icons().collect();
+  if !icons.is_empty() {
+    println!("\nIcons:");
+    for (path, entry) in icons {
+      match entry {
+        Entry::File { size, .. } => println!("  {path} ({size} bytes)"),
+        Entry::Link { target } => println!("  {path} -> {target}"),
+        Entry::Dir { .. } => {}
+      }
+    }
+  }
+
+  let total_size: u64 = bundle
+    .entries
+    .values()
+    .map(|entry| match entry {
+      Entry::File { size, .. } => *size,
+      _ => 0,
+    })
+    .sum();
+  println!(
+    "\nFiles ({} entries, {total_size} bytes):",
+    bundle.entries.len()
+  );
+  for (path, entry) in &bundle.entries {
+    let mode = format_mode(entry);
+    match entry {
+      Entry::File { size, sha256, .. } => println!("  {mode} {size:>12} {sha256} {path}"),
+      Entry::Dir { .. } => println!("  {mode} {:>12} {:64} {path}/", "-", "-"),
+      Entry::Link { target } => println!("  {mode} {:>12} {:64} {path} -> {target}", "-", "-"),
+    }
+  }
+}
+
+/// A difference between two bundles.
+#[derive(Debug, PartialEq, Eq)]
+enum Change<'a> {
+  Metadata {
+    field: &'a str,
+    old: Option<&'a str>,
+    new: Option<&'a str>,
+  },
+  Added(&'a str, &'a Entry),
+  Removed(&'a str, &'a Entry),
+  Changed(&'a str, &'a Entry, &'a Entry),
+}
+
+fn diff<'a>(old: &'a Bundle, new: &'a Bundle) -> Vec<Change<'a>> {
+  let mut changes = Vec::new();
+
+  let old_metadata: BTreeMap<&str, &str> = old
+    .metadata
+    .iter()
+    .map(|(field, value)| (field.as_str(), value.as_str()))
+    .collect();
+  let new_metadata: BTreeMap<&str, &str> = new
+    .metadata
+    .iter()
+    .map(|(field, value)| (field.as_str(), value.as_str()))
+    .collect();
+  for &field in old_metadata.keys().chain(new_metadata.keys()) {
+    let (old, new) = (
+      old_metadata.get(field).copied(),
+      new_metadata.get(field).copied(),
+    );
+    let change = Change::Metadata { field, old, new };
+    if old != new && !changes.contains(&change) {
+      changes.push(change);
+    }
+  }
+
+  for (path, old_entry) in &old.entries {
+    match new.entries.get(path) {
+      None => changes.push(Change::Removed(path, old_entry)),
+      Some(new_entry) if new_entry != old_entry => {
+        changes.push(Change::Changed(path, old_entry, new_entry))
+      }
+      Some(_) => {}
+    }
+  }
+  for (path, new_entry) in &new.entries {
+    if !old.entries.contains_key(path) {
+      changes.push(Change::Added(path, new_entry));
+    }
+  }
+  changes.sort_by_key(|change| match change {
+    Change::Metadata { .. } => None,
+    Change::Added(path, _) | Change::Removed(path, _) | Change::Changed(path, _, _) => Some(*path),
+  });
+
+  changes
+}
+
+fn describe(entry: &Entry) -> String {
+  match entry {
+    Entry::File { mode, size, sha256 } => format!("{size} bytes, {mode:o}, {}", &sha256[..12]),
+    Entry::Dir { mode } => format!("directory, {mode:o}"),
+    Entry::Link { target } => format!("-> {target}"),
+  }
+}
+
+fn print_diff(old_path: &Path, old: &Bundle, new_path: &Path, new: &Bundle) {
+  println!("--- {}", display_path(old_path));
+  println!("+++ {}", display_path(new_path));
+
+  let changes = diff(old, new);
+  if changes.is_empty() {
+    println!("\nThe bundles have the same metadata and files");
+    return;
+  }
+
+  let (mut added, mut removed, mut changed) = (0, 0, 0);
+  for change in &changes {
+    match change {
+      Change::Metadata { field, old, new } => println!(
+        "~ {field}: {} -> {}",
+        old.unwrap_or("(none)"),
+        new.unwrap_or("(none)")
+      ),
+      Change::Added(path, entry) => {
+        added += 1;
+        println!("+ {path} ({})", describe(entry));
+      }
+      Change::Removed(path, entry) => {
+        removed += 1;
+        println!("- {path} ({})", describe(entry));
+      }
+      Change::Changed(path, old, new) => {
+        changed += 1;
+        println!("~ {path} ({} -> {})", describe(old), describe(new));
+      }
+    }
+  }
+  println!("\n{added} added, {removed} removed, {changed} changed");
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{diff, elf_size, parse_control, read_cpio, read_deb, Bundle, Change, Entry};
+  use std::io::Write;
+
+  fn tar_gz(files: &[(&str, &[u8], u32)]) -> Vec<u8> {
+    let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+      Vec::new(),
+      flate2::Compression::default(),
+    ));
+    for (path, content, mode) in files {
+      let mut header = tar::Header::new_gnu();
+      header.set_size(content.len() as u64);
+      header.set_mode(*mode);
+      header.set_entry_type(tar::EntryType::Regular);
+      builder.append_data(&mut header, path, *content).unwrap();
+    }
+    builder.into_inner().unwrap().finish().unwrap()
+  }
+
+  #[test]
+  fn reads_deb() {
+    let control = tar_gz(&[(
+      "./control",
+      b"Package: notes\nVersion: 1.1.0\nDescription: Take notes\n Quickly.\n",
+      0o644,
+    )]);
+    let data = tar_gz(&[
+      ("./usr/bin/notes", b"\x7fELF", 0o755),
+      (
+        "./usr/share/applications/Notes.desktop",
+        b"[Desktop Entry]\nName=Notes\n",
+        0o644,
+      ),
+      (
+        "./usr/share/icons/hicolor/32x32/apps/notes.png",
+        b"png",
+        0o644,
+      ),
+    ]);
+
+    let mut deb = tempfile::NamedTempFile::new().unwrap();
+    {
+      let mut builder = ar::Builder::new(deb.as_file_mut());
+      for (name, content) in [
+        ("debian-binary", b"2.0\n".to_vec()),
+        ("control.tar.gz", control),
+        ("data.tar.gz", data),
+      ] {
+        let header = ar::Header::new(name.as_bytes().to_vec(), content.len() as u64);
+        builder.append(&header, content.as_slice()).unwrap();
+      }
+    }
+    deb.flush().unwrap();
+
+    let mut bundle = Bundle::default();
+    read_deb(deb.reopen().unwrap(), &mut bundle).unwrap();
+    assert_eq!(bundle.metadata[1], ("Version".into(), "1.1.0".into()));
+    assert_eq!(
+      bundle.metadata[2],
+      ("Description".into(), "Take notes\nQuickly.".into())
+    );
+    assert_eq!(
+      bundle.entries["usr/bin/notes"],
+      Entry::File {
+        mode: 0o755,
+        size: 4,
+        sha256: "3bdbb4fe8397cd2b842430b39ccff01a8663c751945ef5e9a09e267fb8b1d359".into()
+      }
+    );
+    assert_eq!(
+      bundle.desktop_entries["usr/share/applications/Notes.desktop"],
+      "[Desktop Entry]\nName=Notes\n"
+    );
+    assert_eq!(bundle.icons().count(), 1);
+  }
+
+  #[test]
+  fn reads_cpio() {
+    let mut cpio = Vec::new();
+    let mut append = |name: &str, mode: u32, data: &[u8]| {
+      let name_len = name.len() + 1;
+      write!(
+        cpio,
+        "070701{:08x}{mode:08x}{:08x}{:08x}{:08x}{:08x}{:08x}{:08x}{:08x}{:08x}{:08x}{name_len:08x}{:08x}{name}\0",
+        0, 0, 0, 1, 0, data.len(), 0, 0, 0, 0, 0
+      )
+      .unwrap();
+      cpio.resize(cpio.len() + (4 - (110 + name_len) % 4) % 4, 0);
+      cpio.extend_from_slice(data);
+      cpio.resize(cpio.len() + (4 - data.len() % 4) % 4, 0);
+    };
+    append("./usr/bin", 0o040755, b"");
+    append("./usr/bin/notes", 0o100755, b"notes");
+    append("./usr/bin/notes-link", 0o120777, b"notes");
+    append("TRAILER!!!", 0, b"");
+
+    let mut bundle = Bundle::default();
+    read_cpio(cpio.as_slice(), &mut bundle).unwrap();
+    assert_eq!(bundle.entries["usr/bin"], Entry::Dir { mode: 0o755 });
+    assert!(matches!(
+      bundle.entries["usr/bin/notes"],
+      Entry::File {
+        mode: 0o755,
+        size: 5,
+        ..
+      }
+    ));
+    assert_eq!(
+      bundle.entries["usr/bin/notes-link"],
+      Entry::Link {
+        target: "notes".into()
+      }
+    );
+  }
+
+  #[test]
+  fn rejects_oversized_cpio_names() {
+    let mut cpio = format!(
+      "070701{:08x}{:08x}{:08x}{:08x}{:08x}{:08x}{:08x}{:08x}{:08x}{:08x}{:08x}{:08x}{:08x}",
+      0,
+      0o100644,
+      0,
+      0,
+      1,
+      0,
+      0,
+      0,
+      0,
+      0,
+      0,
+      u32::MAX,
+      0
+    )
+    .into_bytes();
+    cpio.extend_from_slice(b"name\0");
+    let error = read_cpio(cpio.as_slice(), &mut Bundle::default()).unwrap_err();
+    assert!(error.to_string().contains("name length"), "{error}");
+  }
+
+  #[test]
+  fn reads_elf_size() {
+    let mut header = vec![0u8; 64];
+    header[..4].copy_from_slice(b"\x7fELF");
+    header[4] = 2;
+    header[5] = 1;
+    header[0x28..0x30].copy_from_slice(&1000u64.to_le_bytes());
+    header[0x3a..0x3c].copy_from_slice(&64u16.to_le_bytes());
+    header[0x3c..0x3e].copy_from_slice(&3u16.to_le_bytes());
+    assert_eq!(elf_size(header.as_slice()).unwrap(), 1192);
+
+    header[4] = 1;
+    header[5] = 2;
+    header[0x20..0x24].copy_from_slice(&500u32.to_be_bytes());
+    header[0x2e..0x30].copy_from_slice(&40u16.to_be_bytes());
+    header[0x30..0x32].copy_from_slice(&2u16.to_be_bytes());
+    assert_eq!(elf_size(header.as_slice()).unwrap(), 580);
+
+    assert!(elf_size(&b"#!/bin/sh\n"[..]).is_err());
+  }
+
+  #[test]
+  fn parses_control() {
+    assert_eq!(
+      parse_control("Package: notes\nDepends: libc6, libgtk-3-0\n"),
+      [
+        ("Package".to_string(), "notes".to_string()),
+        ("Depends".to_string(), "libc6, libgtk-3-0".to_string())
+      ]
+    );
+  }
+
+  #[test]
+  fn diffs_bundles() {
+    let file = |size| Entry::File {
+      mode: 0o644,
+      size,
+      sha256: "0".repeat(64),
+    };
+    let mut old = Bundle::default();
+    old.metadata.push(("Version".into(), "1.0.0".into()));
+    old.entries.insert("a".into(), file(1));
+    old.entries.insert("b".into(), file(2));
+    let mut new = Bundle::default();
+    new.metadata.push(("Version".into(), "1.1.0".into()));
+    new.entries.insert("b".into(), file(3));
+    new.entries.insert("c".into(), file(4));
+
+    assert_eq!(
+      diff(&old, &new),
+      [
+        Change::Metadata {
+          field: "Version",
+          old: Some("1.0.0"),
+          new: Some("1.1.0")
+        },
+        Change::Removed("a", &file(1)),
+        Change::Changed("b", &file(2), &file(3)),
+        Change::Added("c", &file(4)),
+      ]
+    );
+  }
+}
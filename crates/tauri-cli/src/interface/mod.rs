@@ -66,8 +66,17 @@ pub trait AppSettings {
       .bundle_settings(self.get_bundle_settings(config, &enabled_features)?)
       .binaries(bins)
       .project_out_directory(out_dir)
-      .target(target)
-      .package_types(package_types);
+      .target(target.clone())
+      .package_types(package_types)
+      .delta_from(options.delta_from);
+
+    if let Some(format) = options.sbom {
+      settings_builder = settings_builder.sbom(tauri_bundler::SbomSettings {
+        format: format.into(),
+        packages: crate::helpers::sbom::packages(&enabled_features, &target)
+          .context("failed to list the packages of the software bill of materials")?,
+      });
+    }
 
     if config.bundle.use_local_tools_dir {
       settings_builder = settings_builder.local_tools_directory(
@@ -22,12 +22,14 @@ use notify_debouncer_mini::new_debouncer;
 use serde::{Deserialize, Deserializer};
 use tauri_bundler::{
   AppCategory, AppImageSettings, BundleBinary, BundleSettings, DebianSettings, DmgSettings,
-  MacOsSettings, PackageSettings, Position, RpmSettings, Size, UpdaterSettings, WindowsSettings,
+  FlatpakOutput, FlatpakSettings, MacOsSettings, MetainfoRelease, MetainfoScreenshot,
+  MetainfoSettings, PackageSettings, Position, RpmSettings, Size, UpdaterSettings, WindowsSettings,
 };
 use tauri_utils::config::{parse::is_configuration_file, DeepLinkProtocol, Updater};
 
 use super::{AppSettings, DevProcess, ExitReason, Interface};
 use crate::{
+  bundle::SbomFormat,
   helpers::{
     app_paths::{app_dir, tauri_dir},
     config::{nsis_settings, reload as reload_config, wix_settings, BundleResources, Config},
@@ -53,6 +55,8 @@ pub struct Options {
   pub args: Vec<String>,
   pub config: Option<ConfigValue>,
   pub no_watch: bool,
+  pub delta_from: Vec<PathBuf>,
+  pub sbom: Option<SbomFormat>,
 }
 
 impl From<crate::build::Options> for Options {
@@ -65,6 +69,8 @@ impl From<crate::build::Options> for Options {
       args: options.args,
       config: options.config,
       no_watch: true,
+      delta_from: options.delta_from,
+      sbom: options.sbom,
     }
   }
 }
@@ -77,6 +83,8 @@ impl From<crate::bundle::Options> for Options {
       target: options.target,
       features: options.features,
       no_watch: true,
+      delta_from: options.delta_from,
+      sbom: options.sbom,
       ..Default::default()
     }
   }
@@ -92,6 +100,8 @@ impl From<crate::dev::Options> for Options {
       args: options.args,
       config: options.config,
       no_watch: options.no_watch,
+      delta_from: Vec::new(),
+      sbom: None,
     }
   }
 }
@@ -1359,9 +1369,17 @@ fn tauri_config_to_bundle_settings(
       post_install_script: config.linux.deb.post_install_script,
       pre_remove_script: config.linux.deb.pre_remove_script,
       post_remove_script: config.linux.deb.post_remove_script,
+      auto_depends: config.linux.deb.auto_depends,
+      auto_depends_mapping: config.linux.deb.auto_depends_mapping,
+      compression: config.linux.deb.compression,
+      compression_level: config.linux.deb.compression_level,
+      signing_key: config.linux.deb.signing_key,
+      signature_style: config.linux.deb.signature_style,
     },
     appimage: AppImageSettings {
       files: config.linux.appimage.files,
+      runtime: config.linux.appimage.runtime,
+      exclude_libraries: config.linux.appimage.exclude_libraries,
     },
     rpm: RpmSettings {
       depends: if depends_rpm.is_empty() {
@@ -1381,6 +1399,48 @@ fn tauri_config_to_bundle_settings(
       post_install_script: config.linux.rpm.post_install_script,
       pre_remove_script: config.linux.rpm.pre_remove_script,
       post_remove_script: config.linux.rpm.post_remove_script,
+      auto_depends: config.linux.rpm.auto_depends,
+      auto_depends_mapping: config.linux.rpm.auto_depends_mapping,
+      signing_key: config.linux.rpm.signing_key,
+    },
+    flatpak: FlatpakSettings {
+      runtime: config.linux.flatpak.runtime,
+      runtime_version: config.linux.flatpak.runtime_version,
+      sdk: config.linux.flatpak.sdk,
+      branch: config.linux.flatpak.branch,
+      finish_args: config.linux.flatpak.finish_args,
+      modules: config.linux.flatpak.modules,
+      output: match config.linux.flatpak.output {
+        crate::helpers::config::FlatpakOutput::Bundle => FlatpakOutput::Bundle,
+        crate::helpers::config::FlatpakOutput::Repo => FlatpakOutput::Repo,
+      },
+      repo: config.linux.flatpak.repo,
+      files: config.linux.flatpak.files,
+      desktop_template: config.linux.flatpak.desktop_template,
+    },
+    metainfo: MetainfoSettings {
+      screenshots: config
+        .linux
+        .metainfo
+        .screenshots
+        .into_iter()
+        .map(|screenshot| MetainfoScreenshot {
+          image: screenshot.image,
+          caption: screenshot.caption,
+        })
+        .collect(),
+      releases: config
+        .linux
+        .metainfo
+        .releases
+        .into_iter()
+        .map(|release| MetainfoRelease {
+          version: release.version,
+          date: release.date,
+          description: release.description,
+        })
+        .collect(),
+      content_rating: config.linux.metainfo.content_rating,
     },
     dmg: DmgSettings {
       background: config.macos.dmg.background,
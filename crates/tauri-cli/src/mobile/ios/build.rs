@@ -120,6 +120,8 @@ impl From<Options> for BuildOptions {
       no_bundle: false,
       config: options.config,
       args: Vec::new(),
+      delta_from: Vec::new(),
+      sbom: None,
       ci: options.ci,
     }
   }
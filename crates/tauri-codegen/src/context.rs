@@ -2,14 +2,15 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 use std::convert::identity;
 use std::path::{Path, PathBuf};
 use std::{ffi::OsStr, str::FromStr};
 
 use crate::{
   embedded_assets::{
-    ensure_out_dir, AssetOptions, CspHashes, EmbeddedAssets, EmbeddedAssetsResult,
+    ensure_out_dir, AssetOptions, CspHashes, EmbeddedAssets, EmbeddedAssetsError,
+    EmbeddedAssetsResult,
   },
   image::CachedIcon,
 };
@@ -24,7 +25,7 @@ use tauri_utils::{
   acl::manifest::Manifest,
   acl::resolved::Resolved,
   assets::AssetKey,
-  config::{CapabilityEntry, Config, FrontendDist, PatternKind},
+  config::{CapabilityEntry, Config, FrontendDist, MenuItemConfig, PatternKind},
   html::{inject_nonce_token, parse as parse_html, serialize_node as serialize_html_node, NodeRef},
   platform::Target,
   tokens::{map_lit, str_lit},
@@ -298,6 +299,28 @@ pub fn context_codegen(data: ContextData) -> EmbeddedAssetsResult<TokenStream> {
     quote!()
   };
 
+  let with_menu_icons_code = if target.is_desktop() {
+    let mut icons = Vec::new();
+    let menus = config
+      .app
+      .menu
+      .iter()
+      .chain(config.app.tray_icon.iter().filter_map(|t| t.menu.as_ref()));
+    for menu in menus {
+      collect_menu_icons(menu, target, &mut HashSet::new(), &mut icons)?;
+    }
+
+    let mut setters = Vec::new();
+    for path in icons {
+      let icon = CachedIcon::new(&root, &config_parent.join(&path))?;
+      let path = path.to_string_lossy();
+      setters.push(quote!(context.set_menu_icon(#path, #icon);));
+    }
+    quote!(#(#setters)*)
+  } else {
+    quote!()
+  };
+
   #[cfg(target_os = "macos")]
   let maybe_embed_plist_block = if target == Target::MacOS && dev && !running_tests {
     let info_plist_path = config_parent.join("Info.plist");
@@ -483,6 +506,7 @@ pub fn context_codegen(data: ContextData) -> EmbeddedAssetsResult<TokenStream> {
     );
 
     #with_tray_icon_code
+    #with_menu_icons_code
     #maybe_config_parent_setter
 
     context
@@ -505,6 +529,31 @@ pub fn context_codegen(data: ContextData) -> EmbeddedAssetsResult<TokenStream> {
   }))
 }
 
+/// Collects the icons of the menu items added on the given target
+/// and ensures the item identifiers are unique so menu events can be matched.
+fn collect_menu_icons(
+  items: &[MenuItemConfig],
+  target: Target,
+  ids: &mut HashSet<String>,
+  icons: &mut Vec<PathBuf>,
+) -> EmbeddedAssetsResult<()> {
+  for item in items.iter().filter(|i| i.is_supported(target)) {
+    if let Some(id) = item.id() {
+      if !ids.insert(id.to_string()) {
+        return Err(EmbeddedAssetsError::DuplicateMenuItemId(id.to_string()));
+      }
+    }
+
+    match item {
+      MenuItemConfig::Icon(item) if !icons.contains(&item.icon) => icons.push(item.icon.clone()),
+      MenuItemConfig::Submenu(submenu) => collect_menu_icons(&submenu.items, target, ids, icons)?,
+      _ => (),
+    }
+  }
+
+  Ok(())
+}
+
 fn find_icon(
   config: &Config,
   config_parent: &Path,
@@ -61,6 +61,9 @@ pub enum EmbeddedAssetsError {
 
   #[error("version error: {0}")]
   Version(#[from] semver::Error),
+
+  #[error("menu item identifier `{0}` is used more than once in the same menu")]
+  DuplicateMenuItemId(String),
 }
 
 pub type EmbeddedAssetsResult<T> = Result<T, EmbeddedAssetsError>;
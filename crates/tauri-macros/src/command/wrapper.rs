@@ -237,7 +237,7 @@ pub fn wrapper(attributes: TokenStream, item: TokenStream) -> TokenStream {
   let col = loc.column;
 
   let maybe_span = if cfg!(feature = "tracing") {
-    quote!({
+    quote!(
       let _span = tracing::debug_span!(
         "ipc::request::handler",
         cmd = #message.command(),
@@ -247,7 +247,7 @@ pub fn wrapper(attributes: TokenStream, item: TokenStream) -> TokenStream {
         is_internal = false,
       )
       .entered();
-    })
+    )
   } else {
     quote!()
   };
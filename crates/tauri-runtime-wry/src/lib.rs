@@ -115,11 +115,14 @@ use std::{
     Arc, Mutex, Weak,
   },
   thread::{current as current_thread, ThreadId},
+  time::{Duration, Instant},
 };
 
 pub type WebviewId = u32;
 type IpcHandler = dyn Fn(Request<String>) + 'static;
 
+mod navigation;
+mod permission;
 #[cfg(any(
   windows,
   target_os = "linux",
@@ -132,6 +135,7 @@ mod undecorated_resizing;
 mod webview;
 mod window;
 
+use navigation::NavigationExt as _;
 pub use webview::Webview;
 use window::WindowExt as _;
 
@@ -380,6 +384,48 @@ pub enum ActiveTracingSpan {
   },
 }
 
+type TimerTask = Box<dyn FnOnce() + Send>;
+
+/// Tasks to run on the main thread once their deadline is reached.
+#[derive(Clone, Default)]
+pub struct TimerStore(Rc<RefCell<Vec<(Instant, TimerTask)>>>);
+
+impl fmt::Debug for TimerStore {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("TimerStore")
+      .field("pending", &self.0.borrow().len())
+      .finish()
+  }
+}
+
+impl TimerStore {
+  fn insert(&self, deadline: Instant, task: TimerTask) {
+    self.0.borrow_mut().push((deadline, task));
+  }
+
+  fn next_deadline(&self) -> Option<Instant> {
+    self.0.borrow().iter().map(|(deadline, _)| *deadline).min()
+  }
+
+  /// Runs the tasks whose deadline is reached and returns the deadline of the next one.
+  fn run_due(&self) -> Option<Instant> {
+    let now = Instant::now();
+    let due = {
+      let mut timers = self.0.borrow_mut();
+      let (due, pending) = timers
+        .drain(..)
+        .partition::<Vec<_>, _>(|(deadline, _)| *deadline <= now);
+      *timers = pending;
+      due
+    };
+    // tasks might schedule new timers so the store must not be borrowed while they run
+    for (_, task) in due {
+      task();
+    }
+    self.next_deadline()
+  }
+}
+
 #[derive(Debug)]
 pub struct WindowsStore(RefCell<BTreeMap<WindowId, WindowWrapper>>);
 
@@ -397,6 +443,7 @@ pub struct DispatcherMainThreadContext<T: UserEvent> {
   pub web_context: WebContextStore,
   // changing this to an Rc will cause frequent app crashes.
   pub windows: Arc<WindowsStore>,
+  pub timers: TimerStore,
   #[cfg(feature = "tracing")]
   pub active_tracing_spans: ActiveTraceSpanStore,
 }
@@ -1248,6 +1295,10 @@ pub enum WebviewMessage {
   WebviewEvent(WebviewEvent),
   SynthesizedWindowEvent(SynthesizedWindowEvent),
   Navigate(Url),
+  GoBack,
+  GoForward,
+  Reload(bool),
+  SyncNavigationState,
   Print,
   Close,
   Show,
@@ -1265,6 +1316,8 @@ pub enum WebviewMessage {
   Bounds(Sender<Result<tauri_runtime::Rect>>),
   Position(Sender<Result<PhysicalPosition<i32>>>),
   Size(Sender<Result<PhysicalSize<u32>>>),
+  CanGoBack(Sender<bool>),
+  CanGoForward(Sender<bool>),
   WithWebview(Box<dyn FnOnce(Webview) + Send>),
   // Devtools
   #[cfg(any(debug_assertions, feature = "devtools"))]
@@ -1286,6 +1339,7 @@ pub type CreateWebviewClosure = Box<dyn FnOnce(&Window) -> Result<WebviewWrapper
 
 pub enum Message<T: 'static> {
   Task(Box<dyn FnOnce() + Send>),
+  Timer(Instant, Box<dyn FnOnce() + Send>),
   #[cfg(target_os = "macos")]
   SetActivationPolicy(ActivationPolicy),
   RequestExit(i32),
@@ -1397,6 +1451,14 @@ impl<T: UserEvent> WebviewDispatch<T> for WryWebviewDispatcher<T> {
     webview_getter!(self, WebviewMessage::Size)?
   }
 
+  fn can_go_back(&self) -> Result<bool> {
+    webview_getter!(self, WebviewMessage::CanGoBack)
+  }
+
+  fn can_go_forward(&self) -> Result<bool> {
+    webview_getter!(self, WebviewMessage::CanGoForward)
+  }
+
   // Setters
 
   fn navigate(&self, url: Url) -> Result<()> {
@@ -1410,6 +1472,39 @@ impl<T: UserEvent> WebviewDispatch<T> for WryWebviewDispatcher<T> {
     )
   }
 
+  fn go_back(&self) -> Result<()> {
+    send_user_message(
+      &self.context,
+      Message::Webview(
+        *self.window_id.lock().unwrap(),
+        self.webview_id,
+        WebviewMessage::GoBack,
+      ),
+    )
+  }
+
+  fn go_forward(&self) -> Result<()> {
+    send_user_message(
+      &self.context,
+      Message::Webview(
+        *self.window_id.lock().unwrap(),
+        self.webview_id,
+        WebviewMessage::GoForward,
+      ),
+    )
+  }
+
+  fn reload(&self, bypass_cache: bool) -> Result<()> {
+    send_user_message(
+      &self.context,
+      Message::Webview(
+        *self.window_id.lock().unwrap(),
+        self.webview_id,
+        WebviewMessage::Reload(bypass_cache),
+      ),
+    )
+  }
+
   fn print(&self) -> Result<()> {
     send_user_message(
       &self.context,
@@ -2304,6 +2399,19 @@ impl<T: UserEvent> RuntimeHandle<T> for WryHandle<T> {
     send_user_message(&self.context, Message::Task(Box::new(f)))
   }
 
+  fn run_on_main_thread_after<F: FnOnce() + Send + 'static>(
+    &self,
+    delay: Duration,
+    f: F,
+  ) -> Result<()> {
+    // always go through the event loop so it wakes up at the deadline, even when called from the main thread
+    self
+      .context
+      .proxy
+      .send_event(Message::Timer(Instant::now() + delay, Box::new(f)))
+      .map_err(|_| Error::FailedToSendMessage)
+  }
+
   fn display_handle(&self) -> std::result::Result<DisplayHandle, raw_window_handle::HandleError> {
     self.context.main_thread.window_target.display_handle()
   }
@@ -2430,6 +2538,7 @@ impl<T: UserEvent> Wry<T> {
         window_target: event_loop.deref().clone(),
         web_context,
         windows,
+        timers: Default::default(),
         #[cfg(feature = "tracing")]
         active_tracing_spans: Default::default(),
       },
@@ -2666,6 +2775,7 @@ impl<T: UserEvent> Runtime<T> for Wry<T> {
     use tao::platform::run_return::EventLoopExtRunReturn;
     let windows = self.context.main_thread.windows.clone();
     let window_id_map = self.context.window_id_map.clone();
+    let timers = self.context.main_thread.timers.clone();
     let web_context = &self.context.main_thread.web_context;
     let plugins = self.context.plugins.clone();
 
@@ -2692,6 +2802,7 @@ impl<T: UserEvent> Runtime<T> for Wry<T> {
               callback: &mut callback,
               window_id_map: window_id_map.clone(),
               windows: windows.clone(),
+              timers: timers.clone(),
               #[cfg(feature = "tracing")]
               active_tracing_spans: active_tracing_spans.clone(),
             },
@@ -2709,6 +2820,7 @@ impl<T: UserEvent> Runtime<T> for Wry<T> {
           EventLoopIterationContext {
             callback: &mut callback,
             windows: windows.clone(),
+            timers: timers.clone(),
             window_id_map: window_id_map.clone(),
             #[cfg(feature = "tracing")]
             active_tracing_spans: active_tracing_spans.clone(),
@@ -2720,6 +2832,7 @@ impl<T: UserEvent> Runtime<T> for Wry<T> {
   fn run<F: FnMut(RunEvent<T>) + 'static>(self, mut callback: F) {
     let windows = self.context.main_thread.windows.clone();
     let window_id_map = self.context.window_id_map.clone();
+    let timers = self.context.main_thread.timers.clone();
     let web_context = self.context.main_thread.web_context;
     let plugins = self.context.plugins.clone();
 
@@ -2738,6 +2851,7 @@ impl<T: UserEvent> Runtime<T> for Wry<T> {
             callback: &mut callback,
             window_id_map: window_id_map.clone(),
             windows: windows.clone(),
+            timers: timers.clone(),
             #[cfg(feature = "tracing")]
             active_tracing_spans: active_tracing_spans.clone(),
           },
@@ -2755,6 +2869,7 @@ impl<T: UserEvent> Runtime<T> for Wry<T> {
           callback: &mut callback,
           window_id_map: window_id_map.clone(),
           windows: windows.clone(),
+          timers: timers.clone(),
           #[cfg(feature = "tracing")]
           active_tracing_spans: active_tracing_spans.clone(),
         },
@@ -2767,6 +2882,7 @@ pub struct EventLoopIterationContext<'a, T: UserEvent> {
   pub callback: &'a mut (dyn FnMut(RunEvent<T>) + 'static),
   pub window_id_map: WindowIdStore,
   pub windows: Arc<WindowsStore>,
+  pub timers: TimerStore,
   #[cfg(feature = "tracing")]
   pub active_tracing_spans: ActiveTraceSpanStore,
 }
@@ -2792,6 +2908,7 @@ fn handle_user_message<T: UserEvent>(
       event_loop.set_activation_policy_at_runtime(tao_activation_policy(activation_policy))
     }
     Message::RequestExit(_code) => panic!("cannot handle RequestExit on the main thread"),
+    Message::Timer(..) => panic!("timers are handled by the event loop"),
     #[cfg(target_os = "macos")]
     Message::Application(application_message) => match application_message {
       ApplicationMessage::Show => {
@@ -3128,6 +3245,7 @@ fn handle_user_message<T: UserEvent>(
           WebviewMessage::WebviewEvent(_) => { /* already handled */ }
           WebviewMessage::SynthesizedWindowEvent(_) => { /* already handled */ }
           WebviewMessage::Reparent(_window_id, _tx) => { /* already handled */ }
+          WebviewMessage::SyncNavigationState => { /* already handled */ }
           WebviewMessage::AddEventListener(id, listener) => {
             webview
               .webview_event_listeners
@@ -3155,6 +3273,15 @@ fn handle_user_message<T: UserEvent>(
               log::error!("failed to navigate to url {}: {}", url, e);
             }
           }
+          WebviewMessage::GoBack => {
+            webview.go_back();
+          }
+          WebviewMessage::GoForward => {
+            webview.go_forward();
+          }
+          WebviewMessage::Reload(bypass_cache) => {
+            webview.reload(bypass_cache);
+          }
           WebviewMessage::Show => {
             if let Err(e) = webview.set_visible(true) {
               log::error!("failed to change webview visibility: {e}");
@@ -3288,6 +3415,12 @@ fn handle_user_message<T: UserEvent>(
             )
             .unwrap();
           }
+          WebviewMessage::CanGoBack(tx) => {
+            tx.send(webview.can_go_back()).unwrap();
+          }
+          WebviewMessage::CanGoForward(tx) => {
+            tx.send(webview.can_go_forward()).unwrap();
+          }
           WebviewMessage::SetFocus => {
             if let Err(e) = webview.focus() {
               log::error!("failed to focus webview: {e}");
@@ -3477,14 +3610,27 @@ fn handle_event_loop<T: UserEvent>(
     callback,
     window_id_map,
     windows,
+    timers,
     #[cfg(feature = "tracing")]
     active_tracing_spans,
   } = context;
   if *control_flow != ControlFlow::Exit {
-    *control_flow = ControlFlow::Wait;
+    *control_flow = match timers.run_due() {
+      Some(deadline) => ControlFlow::WaitUntil(deadline),
+      None => ControlFlow::Wait,
+    };
   }
 
   match event {
+    Event::UserEvent(Message::Timer(deadline, task)) => {
+      timers.insert(deadline, task);
+      if *control_flow != ControlFlow::Exit {
+        if let Some(deadline) = timers.next_deadline() {
+          *control_flow = ControlFlow::WaitUntil(deadline);
+        }
+      }
+    }
+
     Event::NewEvents(StartCause::Init) => {
       callback(RunEvent::Ready);
     }
@@ -3548,6 +3694,42 @@ fn handle_event_loop<T: UserEvent>(
       }
     }
 
+    Event::UserEvent(Message::Webview(
+      window_id,
+      webview_id,
+      WebviewMessage::SyncNavigationState,
+    )) => {
+      let windows_ref = windows.0.borrow();
+      if let Some(window) = windows_ref.get(&window_id) {
+        if let Some(webview) = window.webviews.iter().find(|w| w.id == webview_id) {
+          let label = webview.label.clone();
+          let webview_event_listeners = webview.webview_event_listeners.clone();
+          let url = webview.url().ok().and_then(|url| url.parse().ok());
+          let can_go_back = webview.can_go_back();
+          let can_go_forward = webview.can_go_forward();
+
+          drop(windows_ref);
+
+          if let Some(url) = url {
+            let event = WebviewEvent::NavigationStateChanged {
+              url,
+              can_go_back,
+              can_go_forward,
+            };
+            callback(RunEvent::WebviewEvent {
+              label,
+              event: event.clone(),
+            });
+            let listeners = webview_event_listeners.lock().unwrap();
+            let handlers = listeners.values();
+            for handler in handlers {
+              handler(&event);
+            }
+          }
+        }
+      }
+    }
+
     Event::UserEvent(Message::Webview(
       window_id,
       _webview_id,
@@ -4071,6 +4253,13 @@ fn create_webview<T: UserEvent>(
     });
   }
 
+  let permission_request_handler = pending.permission_request_handler;
+
+  if let Some(new_window_handler) = pending.new_window_handler {
+    webview_builder = webview_builder
+      .with_new_window_req_handler(move |url| url.parse().map(&new_window_handler).unwrap_or(true));
+  }
+
   let webview_bounds = if let Some(bounds) = webview_attributes.bounds {
     let bounds: RectWrapper = bounds.into();
     let bounds = bounds.0;
@@ -4130,8 +4319,20 @@ fn create_webview<T: UserEvent>(
     });
   }
 
-  if let Some(page_load_handler) = pending.on_page_load_handler {
-    webview_builder = webview_builder.with_on_page_load_handler(move |event, url| {
+  let page_load_handler = pending.on_page_load_handler;
+  let proxy = context.proxy.clone();
+  let window_id_ = window_id.clone();
+  webview_builder = webview_builder.with_on_page_load_handler(move |event, url| {
+    // the history state is only reliable once the navigation has been committed
+    if matches!(event, wry::PageLoadEvent::Finished) {
+      let _ = proxy.send_event(Message::Webview(
+        *window_id_.lock().unwrap(),
+        id,
+        WebviewMessage::SyncNavigationState,
+      ));
+    }
+
+    if let Some(page_load_handler) = &page_load_handler {
       let _ = url.parse().map(|url| {
         page_load_handler(
           url,
@@ -4141,8 +4342,8 @@ fn create_webview<T: UserEvent>(
           },
         )
       });
-    });
-  }
+    }
+  });
 
   if let Some(user_agent) = webview_attributes.user_agent {
     webview_builder = webview_builder.with_user_agent(&user_agent);
@@ -4178,14 +4379,16 @@ fn create_webview<T: UserEvent>(
       .with_browser_extensions_enabled(webview_attributes.browser_extensions_enabled);
   }
 
-  webview_builder = webview_builder.with_ipc_handler(create_ipc_handler(
-    kind,
-    window_id.clone(),
-    id,
-    context.clone(),
-    label.clone(),
-    ipc_handler,
-  ));
+  webview_builder = webview_builder
+    .with_ipc_handler(create_ipc_handler(
+      kind,
+      window_id.clone(),
+      id,
+      context.clone(),
+      label.clone(),
+      ipc_handler,
+    ))
+    .with_initialization_script(navigation::HISTORY_CHANGE_SCRIPT);
 
   for script in webview_attributes.initialization_scripts {
     webview_builder = webview_builder.with_initialization_script(&script);
@@ -4283,6 +4486,10 @@ fn create_webview<T: UserEvent>(
   }
   .map_err(|e| Error::CreateWebview(Box::new(dbg!(e))))?;
 
+  if let Some(handler) = permission_request_handler {
+    permission::attach_permission_request_handler(&webview, handler);
+  }
+
   if kind == WebviewKind::WindowContent {
     #[cfg(any(
       target_os = "linux",
@@ -4360,6 +4567,15 @@ fn create_ipc_handler<T: UserEvent>(
   ipc_handler: Option<WebviewIpcHandler<T, Wry<T>>>,
 ) -> Box<IpcHandler> {
   Box::new(move |request| {
+    if request.body() == navigation::NAVIGATION_STATE_CHANGED_MESSAGE {
+      let _ = context.proxy.send_event(Message::Webview(
+        *window_id.lock().unwrap(),
+        webview_id,
+        WebviewMessage::SyncNavigationState,
+      ));
+      return;
+    }
+
     if let Some(handler) = &ipc_handler {
       handler(
         DetachedWebview {
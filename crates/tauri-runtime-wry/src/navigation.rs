@@ -0,0 +1,245 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use wry::WebView;
+
+/// The IPC message posted by [`HISTORY_CHANGE_SCRIPT`].
+pub const NAVIGATION_STATE_CHANGED_MESSAGE: &str = "__TAURI_NAVIGATION_STATE_CHANGED__";
+
+/// Notifies the runtime of same-document navigations, which do not trigger page load events:
+/// `history.pushState`, `history.replaceState`, going back and forward, and fragment navigations.
+pub const HISTORY_CHANGE_SCRIPT: &str = r#"
+(function () {
+  let pending = false
+  function notify() {
+    // popstate and hashchange are both fired for fragment navigations
+    if (!pending) {
+      pending = true
+      setTimeout(function () {
+        pending = false
+        window.ipc.postMessage('__TAURI_NAVIGATION_STATE_CHANGED__')
+      })
+    }
+  }
+  for (const method of ['pushState', 'replaceState']) {
+    const original = window.history[method]
+    window.history[method] = function () {
+      const result = original.apply(this, arguments)
+      notify()
+      return result
+    }
+  }
+  window.addEventListener('popstate', notify)
+  window.addEventListener('hashchange', notify)
+})()
+"#;
+
+pub trait NavigationExt {
+  /// Navigates back in the webview history.
+  fn go_back(&self);
+
+  /// Navigates forward in the webview history.
+  fn go_forward(&self);
+
+  /// Reloads the current page, ignoring cached resources when `bypass_cache` is set.
+  fn reload(&self, bypass_cache: bool);
+
+  /// Whether there's a previous entry in the webview history.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Android**: Unsupported, always returns `false`.
+  fn can_go_back(&self) -> bool;
+
+  /// Whether there's a next entry in the webview history.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Android**: Unsupported, always returns `false`.
+  fn can_go_forward(&self) -> bool;
+}
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+impl NavigationExt for WebView {
+  fn go_back(&self) {
+    use webkit2gtk::WebViewExt;
+    use wry::WebViewExtUnix;
+    self.webview().go_back();
+  }
+
+  fn go_forward(&self) {
+    use webkit2gtk::WebViewExt;
+    use wry::WebViewExtUnix;
+    self.webview().go_forward();
+  }
+
+  fn reload(&self, bypass_cache: bool) {
+    use webkit2gtk::WebViewExt;
+    use wry::WebViewExtUnix;
+    let webview = self.webview();
+    if bypass_cache {
+      webview.reload_bypass_cache();
+    } else {
+      webview.reload();
+    }
+  }
+
+  fn can_go_back(&self) -> bool {
+    use webkit2gtk::WebViewExt;
+    use wry::WebViewExtUnix;
+    self.webview().can_go_back()
+  }
+
+  fn can_go_forward(&self) -> bool {
+    use webkit2gtk::WebViewExt;
+    use wry::WebViewExtUnix;
+    self.webview().can_go_forward()
+  }
+}
+
+#[cfg(windows)]
+impl NavigationExt for WebView {
+  fn go_back(&self) {
+    use wry::WebViewExtWindows;
+    if let Err(e) = unsafe { self.controller().CoreWebView2().and_then(|w| w.GoBack()) } {
+      log::error!("failed to navigate back: {e}");
+    }
+  }
+
+  fn go_forward(&self) {
+    use wry::WebViewExtWindows;
+    if let Err(e) = unsafe { self.controller().CoreWebView2().and_then(|w| w.GoForward()) } {
+      log::error!("failed to navigate forward: {e}");
+    }
+  }
+
+  fn reload(&self, bypass_cache: bool) {
+    use webview2_com::CallDevToolsProtocolMethodCompletedHandler;
+    use windows::core::w;
+    use wry::WebViewExtWindows;
+
+    let result = unsafe {
+      self.controller().CoreWebView2().and_then(|w| {
+        if bypass_cache {
+          // WebView2 has no dedicated API to ignore the cache on reload,
+          // so we go through the DevTools protocol instead
+          let handler = CallDevToolsProtocolMethodCompletedHandler::create(Box::new(|_, _| Ok(())));
+          w.CallDevToolsProtocolMethod(w!("Page.reload"), w!(r#"{"ignoreCache":true}"#), &handler)
+        } else {
+          w.Reload()
+        }
+      })
+    };
+    if let Err(e) = result {
+      log::error!("failed to reload webview: {e}");
+    }
+  }
+
+  fn can_go_back(&self) -> bool {
+    use windows::Win32::Foundation::BOOL;
+    use wry::WebViewExtWindows;
+    let mut can_go_back = BOOL::default();
+    unsafe {
+      self
+        .controller()
+        .CoreWebView2()
+        .and_then(|w| w.CanGoBack(&mut can_go_back))
+        .map(|_| can_go_back.as_bool())
+        .unwrap_or_default()
+    }
+  }
+
+  fn can_go_forward(&self) -> bool {
+    use windows::Win32::Foundation::BOOL;
+    use wry::WebViewExtWindows;
+    let mut can_go_forward = BOOL::default();
+    unsafe {
+      self
+        .controller()
+        .CoreWebView2()
+        .and_then(|w| w.CanGoForward(&mut can_go_forward))
+        .map(|_| can_go_forward.as_bool())
+        .unwrap_or_default()
+    }
+  }
+}
+
+#[cfg(target_vendor = "apple")]
+impl NavigationExt for WebView {
+  fn go_back(&self) {
+    let webview = apple_webview(self);
+    let _: *mut objc2::runtime::AnyObject = unsafe { objc2::msg_send![&*webview, goBack] };
+  }
+
+  fn go_forward(&self) {
+    let webview = apple_webview(self);
+    let _: *mut objc2::runtime::AnyObject = unsafe { objc2::msg_send![&*webview, goForward] };
+  }
+
+  fn reload(&self, bypass_cache: bool) {
+    let webview = apple_webview(self);
+    let _: *mut objc2::runtime::AnyObject = if bypass_cache {
+      unsafe { objc2::msg_send![&*webview, reloadFromOrigin] }
+    } else {
+      unsafe { objc2::msg_send![&*webview, reload] }
+    };
+  }
+
+  fn can_go_back(&self) -> bool {
+    let webview = apple_webview(self);
+    unsafe { objc2::msg_send![&*webview, canGoBack] }
+  }
+
+  fn can_go_forward(&self) -> bool {
+    let webview = apple_webview(self);
+    unsafe { objc2::msg_send![&*webview, canGoForward] }
+  }
+}
+
+#[cfg(target_os = "macos")]
+fn apple_webview(webview: &WebView) -> objc2::rc::Retained<wry::WryWebView> {
+  use wry::WebViewExtMacOS;
+  webview.webview()
+}
+
+#[cfg(target_os = "ios")]
+fn apple_webview(webview: &WebView) -> objc2::rc::Retained<wry::WryWebView> {
+  use wry::WebViewExtIOS;
+  webview.webview()
+}
+
+#[cfg(target_os = "android")]
+impl NavigationExt for WebView {
+  fn go_back(&self) {
+    if let Err(e) = self.evaluate_script("window.history.back()") {
+      log::error!("failed to navigate back: {e}");
+    }
+  }
+
+  fn go_forward(&self) {
+    if let Err(e) = self.evaluate_script("window.history.forward()") {
+      log::error!("failed to navigate forward: {e}");
+    }
+  }
+
+  fn reload(&self, _bypass_cache: bool) {
+    if let Err(e) = self.evaluate_script("window.location.reload()") {
+      log::error!("failed to reload webview: {e}");
+    }
+  }
+
+  fn can_go_back(&self) -> bool {
+    false
+  }
+
+  fn can_go_forward(&self) -> bool {
+    false
+  }
+}
@@ -0,0 +1,241 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use tauri_runtime::webview::PermissionResponse;
+use tauri_utils::config::WebPermissionKind;
+use url::Url;
+use wry::WebView;
+
+pub type PermissionRequestHandler =
+  Box<dyn Fn(&Url, WebPermissionKind) -> PermissionResponse + Send>;
+
+/// Denies the permission requests of cross-origin subframes before they reach the webview.
+///
+/// WebKitGTK does not tell which frame made a permission request,
+/// so the handler only sees the URL of the top-level page and would otherwise grant its permissions to embedded content.
+/// Injected into every frame, the script is a no-op in the top-level page and in same-origin subframes.
+#[cfg(any(
+  test,
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+pub const SUBFRAME_PERMISSION_GUARD_SCRIPT: &str = r#"
+(function () {
+  if (window === window.top) return
+  try {
+    if (window.top.location.origin === window.location.origin) return
+  } catch (_) {
+    // accessing the location of a cross-origin top-level page throws
+  }
+  function deny(target, name, value) {
+    if (target) {
+      Object.defineProperty(target, name, { value, writable: false, configurable: false })
+    }
+  }
+  function notAllowed() {
+    return Promise.reject(new DOMException('Permission denied', 'NotAllowedError'))
+  }
+  function positionError(_success, error) {
+    if (typeof error === 'function') {
+      setTimeout(function () {
+        error({ code: 1, PERMISSION_DENIED: 1, message: 'Permission denied' })
+      })
+    }
+    return 0
+  }
+  deny(window.MediaDevices && window.MediaDevices.prototype, 'getUserMedia', notAllowed)
+  deny(window.Navigator && window.Navigator.prototype, 'webkitGetUserMedia', function () {
+    const error = arguments[2]
+    if (typeof error === 'function') setTimeout(function () { error(new DOMException('Permission denied', 'NotAllowedError')) })
+  })
+  deny(window.Geolocation && window.Geolocation.prototype, 'getCurrentPosition', positionError)
+  deny(window.Geolocation && window.Geolocation.prototype, 'watchPosition', positionError)
+  deny(window.Notification, 'requestPermission', function (callback) {
+    if (typeof callback === 'function') setTimeout(function () { callback('denied') })
+    return Promise.resolve('denied')
+  })
+})()
+"#;
+
+/// Forwards the web API permission requests of the webview to the given handler.
+///
+/// The handler is called synchronously on the main thread and receives the URL of the top-level page.
+/// Requests of cross-origin subframes are denied by [`SUBFRAME_PERMISSION_GUARD_SCRIPT`].
+///
+/// ## Platform-specific:
+///
+/// - **Windows / macOS / Android / iOS**: Unsupported, the webview default behavior is applied.
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+pub fn attach_permission_request_handler(webview: &WebView, handler: PermissionRequestHandler) {
+  use gtk::glib::{Cast, ObjectExt};
+  use webkit2gtk::{
+    GeolocationPermissionRequest, NotificationPermissionRequest, PermissionRequestExt,
+    UserContentInjectedFrames, UserContentManagerExt, UserMediaPermissionRequest,
+    UserMediaPermissionRequestExt, UserScript, UserScriptInjectionTime, WebViewExt,
+  };
+  use wry::WebViewExtUnix;
+
+  if let Some(manager) = webview.webview().user_content_manager() {
+    manager.add_script(&UserScript::new(
+      SUBFRAME_PERMISSION_GUARD_SCRIPT,
+      UserContentInjectedFrames::AllFrames,
+      UserScriptInjectionTime::Start,
+      &[],
+      &[],
+    ));
+  }
+
+  webview
+    .webview()
+    .connect_permission_request(move |webview, request| {
+      let Some(url) = webview.uri().and_then(|uri| Url::parse(&uri).ok()) else {
+        return false;
+      };
+
+      let permissions = if let Some(request) = request.downcast_ref::<UserMediaPermissionRequest>()
+      {
+        let mut permissions = Vec::new();
+        if request.is_for_video_device() {
+          permissions.push(WebPermissionKind::Camera);
+        }
+        if request.is_for_audio_device() {
+          permissions.push(WebPermissionKind::Microphone);
+        }
+        permissions
+      } else if request.is::<GeolocationPermissionRequest>() {
+        vec![WebPermissionKind::Geolocation]
+      } else if request.is::<NotificationPermissionRequest>() {
+        vec![WebPermissionKind::Notifications]
+      } else {
+        return false;
+      };
+
+      match combine(
+        permissions
+          .into_iter()
+          .map(|permission| handler(&url, permission)),
+      ) {
+        PermissionResponse::Allow => request.allow(),
+        PermissionResponse::Deny => request.deny(),
+        PermissionResponse::Default => return false,
+      }
+      true
+    });
+}
+
+#[cfg(not(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+)))]
+pub fn attach_permission_request_handler(_webview: &WebView, _handler: PermissionRequestHandler) {}
+
+/// Combines the responses for a request covering several permissions, e.g. camera and microphone.
+///
+/// The request is denied if any permission is denied and granted only if all permissions are granted.
+#[cfg(any(
+  test,
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+fn combine(responses: impl Iterator<Item = PermissionResponse>) -> PermissionResponse {
+  let mut combined = None;
+  for response in responses {
+    combined = match (combined, response) {
+      (_, PermissionResponse::Deny) => return PermissionResponse::Deny,
+      (None, response) => Some(response),
+      (Some(PermissionResponse::Allow), PermissionResponse::Allow) => {
+        Some(PermissionResponse::Allow)
+      }
+      _ => Some(PermissionResponse::Default),
+    };
+  }
+  combined.unwrap_or(PermissionResponse::Default)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{combine, SUBFRAME_PERMISSION_GUARD_SCRIPT};
+  use tauri_runtime::webview::PermissionResponse::{self, Allow, Default, Deny};
+  use tauri_utils::config::WebPermissionKind;
+
+  /// The web APIs overridden by the script for each permission forwarded to the handler.
+  const SUBFRAME_GUARDED_APIS: &[(WebPermissionKind, &[&str])] = &[
+    (
+      WebPermissionKind::Camera,
+      &["getUserMedia", "webkitGetUserMedia"],
+    ),
+    (
+      WebPermissionKind::Microphone,
+      &["getUserMedia", "webkitGetUserMedia"],
+    ),
+    (
+      WebPermissionKind::Geolocation,
+      &["getCurrentPosition", "watchPosition"],
+    ),
+    (WebPermissionKind::Notifications, &["requestPermission"]),
+  ];
+
+  #[test]
+  fn guards_forwarded_permissions_in_subframes() {
+    // every permission the handler can grant must be denied to cross-origin iframes
+    for permission in [
+      WebPermissionKind::Camera,
+      WebPermissionKind::Microphone,
+      WebPermissionKind::Geolocation,
+      WebPermissionKind::Notifications,
+    ] {
+      let (_, apis) = SUBFRAME_GUARDED_APIS
+        .iter()
+        .find(|(kind, _)| *kind == permission)
+        .unwrap_or_else(|| panic!("{permission:?} is not guarded in subframes"));
+      for api in *apis {
+        assert!(
+          SUBFRAME_PERMISSION_GUARD_SCRIPT.contains(&format!("'{api}'")),
+          "{api} is not overridden in subframes"
+        );
+      }
+    }
+    // the top-level page and same-origin iframes keep the native APIs
+    assert!(SUBFRAME_PERMISSION_GUARD_SCRIPT.contains("if (window === window.top) return"));
+    assert!(SUBFRAME_PERMISSION_GUARD_SCRIPT
+      .contains("if (window.top.location.origin === window.location.origin) return"));
+  }
+
+  #[test]
+  fn combines_camera_and_microphone_responses() {
+    let cases: &[(&[PermissionResponse], PermissionResponse)] = &[
+      (&[], Default),
+      (&[Allow], Allow),
+      (&[Allow, Allow], Allow),
+      (&[Allow, Default], Default),
+      (&[Default, Allow], Default),
+      (&[Default, Default], Default),
+      (&[Allow, Deny], Deny),
+      (&[Deny, Allow], Deny),
+      (&[Default, Deny], Deny),
+    ];
+    for (responses, expected) in cases {
+      assert_eq!(
+        combine(responses.iter().copied()),
+        *expected,
+        "{responses:?}"
+      );
+    }
+  }
+}
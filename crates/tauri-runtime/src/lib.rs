@@ -17,7 +17,7 @@
 
 use raw_window_handle::DisplayHandle;
 use serde::{Deserialize, Serialize};
-use std::{borrow::Cow, fmt::Debug, sync::mpsc::Sender};
+use std::{borrow::Cow, fmt::Debug, sync::mpsc::Sender, time::Duration};
 use tauri_utils::Theme;
 use url::Url;
 use webview::{DetachedWebview, PendingWebview};
@@ -303,6 +303,23 @@ pub trait RuntimeHandle<T: UserEvent>: Debug + Clone + Send + Sync + Sized + 'st
   /// Run a task on the main thread.
   fn run_on_main_thread<F: FnOnce() + Send + 'static>(&self, f: F) -> Result<()>;
 
+  /// Run a task on the main thread once the given delay has elapsed.
+  ///
+  /// The default implementation waits on a new thread before calling [`Self::run_on_main_thread`],
+  /// runtimes should override it to measure the delay with their event loop instead.
+  fn run_on_main_thread_after<F: FnOnce() + Send + 'static>(
+    &self,
+    delay: Duration,
+    f: F,
+  ) -> Result<()> {
+    let handle = self.clone();
+    std::thread::spawn(move || {
+      std::thread::sleep(delay);
+      let _ = handle.run_on_main_thread(f);
+    });
+    Ok(())
+  }
+
   fn display_handle(&self) -> std::result::Result<DisplayHandle, raw_window_handle::HandleError>;
 
   fn primary_monitor(&self) -> Option<Monitor>;
@@ -482,11 +499,43 @@ pub trait WebviewDispatch<T: UserEvent>: Debug + Clone + Send + Sync + Sized + '
   /// Returns the physical size of the webviews's client area.
   fn size(&self) -> Result<PhysicalSize<u32>>;
 
+  /// Whether the webview can navigate back in its history.
+  ///
+  /// Returns `false` unless implemented by the runtime.
+  fn can_go_back(&self) -> Result<bool> {
+    Ok(false)
+  }
+
+  /// Whether the webview can navigate forward in its history.
+  ///
+  /// Returns `false` unless implemented by the runtime.
+  fn can_go_forward(&self) -> Result<bool> {
+    Ok(false)
+  }
+
   // SETTER
 
   /// Navigate to the given URL.
   fn navigate(&self, url: Url) -> Result<()>;
 
+  /// Navigates back to the previous page in the webview's history.
+  fn go_back(&self) -> Result<()> {
+    self.eval_script("window.history.back()")
+  }
+
+  /// Navigates forward to the next page in the webview's history.
+  fn go_forward(&self) -> Result<()> {
+    self.eval_script("window.history.forward()")
+  }
+
+  /// Reloads the current page, optionally ignoring cached resources.
+  ///
+  /// `bypass_cache` is ignored unless implemented by the runtime.
+  #[allow(unused_variables)]
+  fn reload(&self, bypass_cache: bool) -> Result<()> {
+    self.eval_script("window.location.reload()")
+  }
+
   /// Opens the dialog to prints the contents of the webview.
   fn print(&self) -> Result<()>;
 
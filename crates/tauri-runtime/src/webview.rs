@@ -7,7 +7,7 @@
 use crate::{window::is_label_valid, Rect, Runtime, UserEvent};
 
 use http::Request;
-use tauri_utils::config::{WebviewUrl, WindowConfig, WindowEffectsConfig};
+use tauri_utils::config::{WebPermissionKind, WebviewUrl, WindowConfig, WindowEffectsConfig};
 use url::Url;
 
 use std::{
@@ -28,6 +28,10 @@ type WebResourceRequestHandler =
 
 type NavigationHandler = dyn Fn(&Url) -> bool + Send;
 
+type NewWindowHandler = dyn Fn(Url) -> bool + Send;
+
+type PermissionRequestHandler = dyn Fn(&Url, WebPermissionKind) -> PermissionResponse + Send;
+
 type OnPageLoadHandler = dyn Fn(Url, PageLoadEvent) + Send;
 
 type DownloadHandler = dyn Fn(DownloadEvent) -> bool + Send + Sync;
@@ -70,6 +74,17 @@ pub enum PageLoadEvent {
   Finished,
 }
 
+/// The response to a web API permission request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PermissionResponse {
+  /// Grants the permission.
+  Allow,
+  /// Denies the permission.
+  Deny,
+  /// Lets the webview apply its default behavior.
+  Default,
+}
+
 /// A webview that has yet to be built.
 pub struct PendingWebview<T: UserEvent, R: Runtime<T>> {
   /// The label that the webview will be named.
@@ -86,6 +101,12 @@ pub struct PendingWebview<T: UserEvent, R: Runtime<T>> {
   /// A handler to decide if incoming url is allowed to navigate.
   pub navigation_handler: Option<Box<NavigationHandler>>,
 
+  /// A handler to decide if a request to open a new window is allowed.
+  pub new_window_handler: Option<Box<NewWindowHandler>>,
+
+  /// A handler to decide if a web API permission requested by the page is granted.
+  pub permission_request_handler: Option<Box<PermissionRequestHandler>>,
+
   /// The resolved URL to load on the webview.
   pub url: String,
 
@@ -117,6 +138,8 @@ impl<T: UserEvent, R: Runtime<T>> PendingWebview<T, R> {
         label,
         ipc_handler: None,
         navigation_handler: None,
+        new_window_handler: None,
+        permission_request_handler: None,
         url: "tauri://localhost".to_string(),
         #[cfg(target_os = "android")]
         on_webview_created: None,
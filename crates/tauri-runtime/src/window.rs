@@ -63,9 +63,26 @@ pub enum WindowEvent {
 
 /// An event from a window.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub enum WebviewEvent {
   /// An event associated with the drag and drop action.
   DragDrop(DragDropEvent),
+  /// The webview navigation history has changed.
+  ///
+  /// Emitted when a page finishes loading and on same-document navigations
+  /// such as `history.pushState`, `history.replaceState` and fragment changes.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Android**: `can_go_back` and `can_go_forward` are always `false`.
+  NavigationStateChanged {
+    /// The current URL of the webview.
+    url: url::Url,
+    /// Whether the webview can navigate back in its history.
+    can_go_back: bool,
+    /// Whether the webview can navigate forward in its history.
+    can_go_forward: bool,
+  },
 }
 
 /// The drag drop event payload.
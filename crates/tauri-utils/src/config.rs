@@ -129,10 +129,14 @@ pub enum BundleType {
   App,
   /// The Apple Disk Image bundle (.dmg).
   Dmg,
+  /// The Flatpak bundle (.flatpak).
+  ///
+  /// Not included in the `all` target since it requires `flatpak-builder` and the configured runtime to be installed.
+  Flatpak,
 }
 
 impl BundleType {
-  /// All bundle types.
+  /// All bundle types built by the `all` target.
   fn all() -> &'static [Self] {
     &[
       BundleType::Deb,
@@ -159,6 +163,7 @@ impl Display for BundleType {
         Self::Nsis => "nsis",
         Self::App => "app",
         Self::Dmg => "dmg",
+        Self::Flatpak => "flatpak",
       }
     )
   }
@@ -187,6 +192,7 @@ impl<'de> Deserialize<'de> for BundleType {
       "nsis" => Ok(Self::Nsis),
       "app" => Ok(Self::App),
       "dmg" => Ok(Self::Dmg),
+      "flatpak" => Ok(Self::Flatpak),
       _ => Err(DeError::custom(format!("unknown bundle target '{s}'"))),
     }
   }
@@ -283,6 +289,7 @@ impl<'de> Deserialize<'de> for BundleTarget {
         "invalid bundle type {t}, expected one of `all`, {}",
         BundleType::all()
           .iter()
+          .chain([&BundleType::Flatpak])
           .map(|b| format!("`{b}`"))
           .collect::<Vec<_>>()
           .join(", ")
@@ -308,6 +315,7 @@ impl BundleTarget {
 /// Configuration for AppImage bundles.
 ///
 /// See more: <https://v2.tauri.app/reference/config/#appimageconfig>
+#[skip_serializing_none]
 #[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
@@ -319,6 +327,19 @@ pub struct AppImageConfig {
   /// The files to include in the Appimage Binary.
   #[serde(default)]
   pub files: HashMap<PathBuf, PathBuf>,
+  /// Path to the AppImage runtime the filesystem is appended to.
+  /// See <https://github.com/AppImage/type2-runtime/releases>.
+  ///
+  /// Defaults to the `runtime-<arch>` file (e.g. `runtime-x86_64`) in the Tauri tools directory,
+  /// `.tauri` in the project directory when `useLocalToolsDir` is enabled or the `tauri` cache directory otherwise.
+  /// The latest runtime is downloaded there when the file does not exist.
+  pub runtime: Option<PathBuf>,
+  /// Shared libraries that must not be bundled, in addition to the ones expected on every system
+  /// such as the C library and the graphics drivers.
+  ///
+  /// `*` matches any sequence of characters, e.g. `libgtk-3.so.*`.
+  #[serde(default, alias = "exclude-libraries")]
+  pub exclude_libraries: Vec<String>,
 }
 
 /// Configuration for Debian (.deb) bundles.
@@ -371,6 +392,62 @@ pub struct DebConfig {
   /// <https://www.debian.org/doc/debian-policy/ch-maintainerscripts.html>
   #[serde(alias = "post-remove-script")]
   pub post_remove_script: Option<PathBuf>,
+  /// Adds the packages providing the shared libraries needed by the main binary and the sidecars to `depends`.
+  ///
+  /// The libraries are read from the `DT_NEEDED` entries of the binaries
+  /// and mapped to packages with `autoDependsMapping` or by querying the local package database with `dpkg -S`.
+  #[serde(default, alias = "auto-depends")]
+  pub auto_depends: bool,
+  /// Path to a JSON file mapping shared library names to the package providing them,
+  /// e.g. `{ "libssl.so.3": "libssl3" }`, used by `autoDepends` before querying the package database.
+  ///
+  /// Map a library to `null` to skip it.
+  #[serde(alias = "auto-depends-mapping")]
+  pub auto_depends_mapping: Option<PathBuf>,
+  /// The compression algorithm of the control and data archives of the package.
+  #[serde(default)]
+  pub compression: DebCompression,
+  /// The compression level, from 0 to 9 for gzip and xz, and from 1 to 22 for zstd.
+  ///
+  /// Defaults to 6 for gzip and xz and to 3 for zstd.
+  #[serde(alias = "compression-level")]
+  pub compression_level: Option<u32>,
+  /// Path to the ASCII-armored GPG secret key used to sign the package.
+  ///
+  /// The `TAURI_SIGNING_DEB_KEY` environment variable takes precedence over this option,
+  /// and the key passphrase is read from the `TAURI_SIGNING_DEB_KEY_PASSPHRASE` environment variable.
+  /// Signing requires `gpg`.
+  #[serde(alias = "signing-key")]
+  pub signing_key: Option<PathBuf>,
+  /// How the package signature is stored.
+  #[serde(default, alias = "signature-style")]
+  pub signature_style: DebSignatureStyle,
+}
+
+/// Compression algorithms used for the control and data archives of Debian packages.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub enum DebCompression {
+  /// gzip (default), supported by every dpkg version.
+  #[default]
+  Gzip,
+  /// xz, smaller packages at the cost of a slower compression.
+  Xz,
+  /// zstd, fast compression and decompression. Requires dpkg 1.21.18 or later to install the package.
+  Zstd,
+}
+
+/// How the GPG signature of a Debian package is stored.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub enum DebSignatureStyle {
+  /// A `_gpgorigin` member added to the package, as created by `debsigs` and verified by `debsig-verify`.
+  #[default]
+  Debsigs,
+  /// An ASCII-armored detached signature written next to the package, as `<package>.deb.asc`.
+  Detached,
 }
 
 /// Configuration for Linux bundles.
@@ -390,6 +467,66 @@ pub struct LinuxConfig {
   /// Configuration for the RPM bundle.
   #[serde(default)]
   pub rpm: RpmConfig,
+  /// Configuration for the Flatpak bundle.
+  #[serde(default)]
+  pub flatpak: FlatpakConfig,
+  /// Configuration for the AppStream metainfo file installed by the Linux bundles.
+  #[serde(default)]
+  pub metainfo: MetainfoConfig,
+}
+
+/// Configuration for the AppStream metainfo file installed by the Linux bundles,
+/// which software centers such as GNOME Software and KDE Discover use to present the app.
+///
+/// The name, summary, description, homepage, license, categories and version are read from the bundle configuration,
+/// and the MIME types of the file associations are listed as the media types the app provides.
+///
+/// See more: <https://www.freedesktop.org/software/appstream/docs/chap-Metadata.html>
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct MetainfoConfig {
+  /// Screenshots of the app, the first one being the default screenshot.
+  #[serde(default)]
+  pub screenshots: Vec<MetainfoScreenshot>,
+  /// The releases of the app, newest first.
+  ///
+  /// Defaults to the current version, released at build time.
+  #[serde(default)]
+  pub releases: Vec<MetainfoRelease>,
+  /// The [OARS](https://hughsie.github.io/oars/) content rating attributes of the app,
+  /// e.g. `{ "social-chat": "intense" }`.
+  ///
+  /// The values are `none`, `mild`, `moderate` or `intense`. Attributes that are not listed are rated `none`.
+  #[serde(default, alias = "content-rating")]
+  pub content_rating: HashMap<String, String>,
+}
+
+/// A screenshot of the app in the AppStream metainfo file.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct MetainfoScreenshot {
+  /// The URL of the screenshot image.
+  pub image: String,
+  /// A short description of the screenshot.
+  pub caption: Option<String>,
+}
+
+/// A release of the app in the AppStream metainfo file.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct MetainfoRelease {
+  /// The released version.
+  pub version: String,
+  /// The release date, formatted as `YYYY-MM-DD`.
+  pub date: String,
+  /// The release notes, with paragraphs separated by blank lines.
+  pub description: Option<String>,
 }
 
 /// Configuration for RPM bundles.
@@ -440,6 +577,24 @@ pub struct RpmConfig {
   /// <http://ftp.rpm.org/max-rpm/s1-rpm-inside-scripts.html>
   #[serde(alias = "post-remove-script")]
   pub post_remove_script: Option<PathBuf>,
+  /// Adds the packages providing the shared libraries needed by the main binary and the sidecars to `depends`.
+  ///
+  /// The libraries are read from the `DT_NEEDED` entries of the binaries
+  /// and mapped to packages with `autoDependsMapping` or by querying the local package database with `rpm -qf`.
+  #[serde(default, alias = "auto-depends")]
+  pub auto_depends: bool,
+  /// Path to a JSON file mapping shared library names to the package providing them,
+  /// e.g. `{ "libssl.so.3": "openssl-libs" }`, used by `autoDepends` before querying the package database.
+  ///
+  /// Map a library to `null` to skip it.
+  #[serde(alias = "auto-depends-mapping")]
+  pub auto_depends_mapping: Option<PathBuf>,
+  /// Path to the ASCII-armored GPG secret key used to sign the package.
+  ///
+  /// The `TAURI_SIGNING_RPM_KEY` environment variable takes precedence over this option,
+  /// and the key passphrase is read from the `TAURI_SIGNING_RPM_KEY_PASSPHRASE` environment variable.
+  #[serde(alias = "signing-key")]
+  pub signing_key: Option<PathBuf>,
 }
 
 impl Default for RpmConfig {
@@ -458,6 +613,9 @@ impl Default for RpmConfig {
       post_install_script: None,
       pre_remove_script: None,
       post_remove_script: None,
+      auto_depends: false,
+      auto_depends_mapping: None,
+      signing_key: None,
     }
   }
 }
@@ -466,6 +624,98 @@ fn default_release() -> String {
   "1".into()
 }
 
+/// The output of the Flatpak bundler.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum FlatpakOutput {
+  /// A single-file bundle (.flatpak) that can be installed with `flatpak install`.
+  #[default]
+  Bundle,
+  /// Only the OSTree repository, to be published as a Flatpak remote.
+  Repo,
+}
+
+/// Configuration for Flatpak bundles.
+///
+/// The app ID is the [`Config::identifier`], which must be a valid D-Bus name.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct FlatpakConfig {
+  /// The runtime the app runs on. It must provide webkit2gtk-4.1, like the GNOME runtime does.
+  #[serde(default = "default_flatpak_runtime")]
+  pub runtime: String,
+  /// The version of the runtime.
+  #[serde(default = "default_flatpak_runtime_version", alias = "runtime-version")]
+  pub runtime_version: String,
+  /// The SDK matching the runtime.
+  #[serde(default = "default_flatpak_sdk")]
+  pub sdk: String,
+  /// The branch of the app in the repository.
+  #[serde(default = "default_flatpak_branch")]
+  pub branch: String,
+  /// The sandbox permissions of the app, e.g. `["--share=ipc", "--socket=wayland", "--filesystem=home"]`.
+  ///
+  /// Replaces the default permissions needed by the webview (display, GPU, network and audio access),
+  /// so they must be listed again if the app still needs them.
+  /// Access to the tray icon host is always added when the tray icon is enabled.
+  #[serde(alias = "finish-args")]
+  pub finish_args: Option<Vec<String>>,
+  /// Additional [flatpak-builder modules](https://docs.flatpak.org/en/latest/flatpak-builder-command-reference.html#module-properties)
+  /// built before the app, e.g. libraries missing from the runtime such as libayatana-appindicator.
+  #[serde(default)]
+  pub modules: Vec<JsonValue>,
+  /// Whether to produce a single-file bundle or only the OSTree repository.
+  #[serde(default)]
+  pub output: FlatpakOutput,
+  /// The path of the OSTree repository to export the app to.
+  /// Defaults to the `repo` directory next to the bundle.
+  pub repo: Option<PathBuf>,
+  /// The files to include in the app, relative to its `/app` prefix.
+  #[serde(default)]
+  pub files: HashMap<PathBuf, PathBuf>,
+  /// Path to a custom desktop file Handlebars template.
+  ///
+  /// Available variables: `categories`, `comment` (optional), `exec`, `icon` and `name`.
+  #[serde(alias = "desktop-template")]
+  pub desktop_template: Option<PathBuf>,
+}
+
+impl Default for FlatpakConfig {
+  fn default() -> Self {
+    Self {
+      runtime: default_flatpak_runtime(),
+      runtime_version: default_flatpak_runtime_version(),
+      sdk: default_flatpak_sdk(),
+      branch: default_flatpak_branch(),
+      finish_args: None,
+      modules: Vec::new(),
+      output: FlatpakOutput::default(),
+      repo: None,
+      files: Default::default(),
+      desktop_template: None,
+    }
+  }
+}
+
+fn default_flatpak_runtime() -> String {
+  "org.gnome.Platform".into()
+}
+
+fn default_flatpak_runtime_version() -> String {
+  "46".into()
+}
+
+fn default_flatpak_sdk() -> String {
+  "org.gnome.Sdk".into()
+}
+
+fn default_flatpak_branch() -> String {
+  "stable".into()
+}
+
 /// Position coordinates struct.
 #[derive(Default, Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
@@ -1080,6 +1330,73 @@ pub struct FileAssociation {
   /// The mime-type e.g. 'image/png' or 'text/plain'. Linux-only.
   #[serde(alias = "mime-type")]
   pub mime_type: Option<String>,
+  /// Magic rules recognizing the files of this type by their content. Linux-only.
+  ///
+  /// Written to the shared MIME-info definition of `mimeType`, which is only generated when `mimeType` is set.
+  #[serde(default)]
+  pub magic: Vec<FileAssociationMagic>,
+  /// The name of the icon of this type in the icon theme, e.g. 'text-x-generic'. Linux-only.
+  pub icon: Option<String>,
+}
+
+/// A magic rule of a file association, matching a value at an offset of the file content.
+///
+/// See more: <https://specifications.freedesktop.org/shared-mime-info-spec/latest/ar01s02.html>
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct FileAssociationMagic {
+  /// The value to match.
+  ///
+  /// For the `string` type, the string to match, e.g. '%PDF-', where arbitrary bytes can be written as escape sequences such as `\x7f`.
+  /// For the number types, a decimal or `0x` prefixed hexadecimal number, e.g. '0xcafe'.
+  pub value: String,
+  /// The offset of the value in the file.
+  #[serde(default)]
+  pub offset: u32,
+  /// How the value is compared to the file content.
+  #[serde(rename = "type", default)]
+  pub kind: FileAssociationMagicType,
+  /// A `0x` prefixed hexadecimal mask applied to the file content before the comparison, e.g. '0xff00'.
+  ///
+  /// For the `string` type, it must be as long as the value.
+  pub mask: Option<String>,
+}
+
+/// The type of the value of a [`FileAssociationMagic`].
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum FileAssociationMagicType {
+  /// A sequence of bytes.
+  #[default]
+  String,
+  /// A single byte.
+  Byte,
+  /// A 16-bit number in the byte order of the host.
+  Host16,
+  /// A 32-bit number in the byte order of the host.
+  Host32,
+  /// A big-endian 16-bit number.
+  Big16,
+  /// A big-endian 32-bit number.
+  Big32,
+  /// A little-endian 16-bit number.
+  Little16,
+  /// A little-endian 32-bit number.
+  Little32,
+}
+
+impl FileAssociationMagicType {
+  /// The size of the number in bytes, or `None` for the `string` type.
+  pub fn number_size(self) -> Option<u32> {
+    match self {
+      Self::String => None,
+      Self::Byte => Some(1),
+      Self::Host16 | Self::Big16 | Self::Little16 => Some(2),
+      Self::Host32 | Self::Big32 | Self::Little32 => Some(4),
+    }
+  }
 }
 
 /// Deep link protocol configuration.
@@ -1486,6 +1803,13 @@ pub struct WindowConfig {
   /// - **MacOS / Linux / iOS / Android** - Unsupported.
   #[serde(default)]
   pub browser_extensions_enabled: bool,
+  /// Declarative layout of child webviews inside this window.
+  ///
+  /// When set, the window is created without its own webview and every `webview` node of the layout
+  /// is created as a child webview, with its bounds managed by the layout as the window is resized.
+  ///
+  /// Requires the `unstable` Cargo feature.
+  pub layout: Option<LayoutConfig>,
 }
 
 impl Default for WindowConfig {
@@ -1534,6 +1858,7 @@ impl Default for WindowConfig {
       proxy_url: None,
       zoom_hotkeys_enabled: false,
       browser_extensions_enabled: false,
+      layout: None,
     }
   }
 }
@@ -1554,6 +1879,113 @@ fn default_title() -> String {
   "Tauri App".to_string()
 }
 
+/// The axis the children of a layout container are laid out along.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum LayoutDirection {
+  /// Children are laid out from left to right.
+  #[default]
+  Horizontal,
+  /// Children are laid out from top to bottom.
+  Vertical,
+}
+
+/// A node of a declarative window layout.
+///
+/// See [`WindowConfig::layout`].
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct LayoutConfig {
+  /// Identifier of this node, used to address it at runtime e.g. to move a splitter or switch tabs.
+  pub id: Option<String>,
+  /// The relative size of this node in a `split` container, defaults to `1`.
+  ///
+  /// The space of the container is divided between its children proportionally to their weight.
+  /// Ignored in the other containers.
+  pub weight: Option<f64>,
+  /// The fixed logical size of this node along the axis of a `stack` container.
+  ///
+  /// Nodes without a size share the space left by the fixed ones. Ignored in the other containers.
+  pub size: Option<f64>,
+  /// The minimum logical width of this node.
+  #[serde(default)]
+  pub min_width: f64,
+  /// The minimum logical height of this node.
+  #[serde(default)]
+  pub min_height: f64,
+  /// The kind of this node.
+  #[serde(flatten)]
+  pub kind: LayoutKind,
+}
+
+/// The kind of a [`LayoutConfig`] node.
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum LayoutKind {
+  /// Divides the available space between its children proportionally to their weight,
+  /// separated by splitters that can be moved at runtime.
+  ///
+  /// When the node has an `id`, the user can drag its splitters from the edges of the adjacent webviews,
+  /// which requires the `core:window:allow-move-layout-splitter` permission.
+  #[serde(rename_all = "camelCase")]
+  Split {
+    /// The axis the children are laid out along.
+    #[serde(default)]
+    direction: LayoutDirection,
+    /// The logical size of the gap between two children.
+    #[serde(default = "default_splitter_size")]
+    splitter_size: f64,
+    /// The child nodes.
+    children: Vec<LayoutConfig>,
+  },
+  /// Lays out its children one after another, e.g. a tab strip above its content.
+  #[serde(rename_all = "camelCase")]
+  Stack {
+    /// The axis the children are laid out along.
+    #[serde(default)]
+    direction: LayoutDirection,
+    /// The child nodes.
+    children: Vec<LayoutConfig>,
+  },
+  /// Shows only the active child, using all the available space.
+  #[serde(rename_all = "camelCase")]
+  Tabs {
+    /// The index of the visible child.
+    #[serde(default)]
+    active: usize,
+    /// The child nodes.
+    children: Vec<LayoutConfig>,
+  },
+  /// A child webview.
+  #[serde(rename_all = "camelCase")]
+  Webview {
+    /// The webview label. It must be unique across the app.
+    label: String,
+    /// The webview URL.
+    #[serde(default)]
+    url: WebviewUrl,
+    /// The user agent for the webview.
+    user_agent: Option<String>,
+    /// Whether the drag and drop is enabled or not on the webview. By default it is enabled.
+    #[serde(default = "default_true")]
+    drag_drop_enabled: bool,
+    /// Whether the webview is transparent or not.
+    #[serde(default)]
+    transparent: bool,
+    /// Whether or not the webview should be launched in incognito mode.
+    #[serde(default)]
+    incognito: bool,
+  },
+}
+
+fn default_splitter_size() -> f64 {
+  4f64
+}
+
 /// A Content-Security-Policy directive source list.
 /// See <https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/Sources#sources>.
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
@@ -1821,6 +2253,64 @@ pub struct SecurityConfig {
   /// If the list is empty, all capabilities are included.
   #[serde(default)]
   pub capabilities: Vec<CapabilityEntry>,
+  /// Persistent decisions for the web API permissions requested by web content,
+  /// such as camera, microphone or geolocation access.
+  ///
+  /// The first rule matching the origin and the permission of a request is applied,
+  /// requests without a matching rule are forwarded to the webview permission request handler.
+  #[serde(default, alias = "web-permissions")]
+  pub web_permissions: Vec<WebPermissionRule>,
+}
+
+/// A web API permission that can be requested by web content.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum WebPermissionKind {
+  /// Access to the camera.
+  Camera,
+  /// Access to the microphone.
+  Microphone,
+  /// Access to the device location.
+  Geolocation,
+  /// Permission to show notifications.
+  Notifications,
+}
+
+/// The decision of a [`WebPermissionRule`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum WebPermissionDecision {
+  /// Grants the permission.
+  Allow,
+  /// Denies the permission.
+  Deny,
+}
+
+/// A persistent decision for web API permission requests.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct WebPermissionRule {
+  /// The origin this rule applies to, e.g. `https://meet.example.com` or `tauri://localhost`.
+  ///
+  /// Use `*` to match any origin.
+  pub origin: String,
+  /// The permissions this rule applies to.
+  pub permissions: Vec<WebPermissionKind>,
+  /// Whether the permissions are granted or denied.
+  pub decision: WebPermissionDecision,
+}
+
+impl WebPermissionRule {
+  /// Whether this rule applies to the given permission requested by the given URL.
+  pub fn matches(&self, url: &Url, permission: WebPermissionKind) -> bool {
+    self.permissions.contains(&permission)
+      && (self.origin == "*"
+        || self.origin.trim_end_matches('/') == url.origin().ascii_serialization())
+  }
 }
 
 /// A capability entry which can be either an inlined capability or a reference to a capability defined on its own file.
@@ -1867,6 +2357,41 @@ impl Default for PatternKind {
   }
 }
 
+/// Overrides of the app directories resolved by the path resolver.
+///
+/// Relative paths are resolved from the directory of the executable,
+/// or from the directory containing the `.app` bundle on macOS.
+/// The `TAURI_APP_CONFIG_DIR`, `TAURI_APP_DATA_DIR`, `TAURI_APP_LOCAL_DATA_DIR`, `TAURI_APP_CACHE_DIR`
+/// and `TAURI_APP_LOG_DIR` environment variables take precedence over this configuration.
+///
+/// ## Platform-specific
+///
+/// - **Android:** Unsupported.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AppDirectoriesConfig {
+  /// Stores the app directories next to the executable,
+  /// in the `config`, `data`, `local-data`, `cache` and `logs` folders.
+  ///
+  /// Portable mode is also enabled when a file named `portable` is next to the executable.
+  /// On macOS, the folders and the `portable` file are next to the `.app` bundle instead.
+  #[serde(default)]
+  pub portable: bool,
+  /// Overrides the app config directory.
+  pub config: Option<PathBuf>,
+  /// Overrides the app data directory.
+  pub data: Option<PathBuf>,
+  /// Overrides the app local data directory.
+  #[serde(alias = "local-data")]
+  pub local_data: Option<PathBuf>,
+  /// Overrides the app cache directory.
+  pub cache: Option<PathBuf>,
+  /// Overrides the app log directory.
+  pub log: Option<PathBuf>,
+}
+
 /// The App configuration object.
 ///
 /// See more: <https://v2.tauri.app/reference/config/#appconfig>
@@ -1884,6 +2409,10 @@ pub struct AppConfig {
   /// Configuration for app tray icon.
   #[serde(alias = "tray-icon")]
   pub tray_icon: Option<TrayIconConfig>,
+  /// The application menu, set to all windows on Windows and Linux and as the app menu on macOS.
+  ///
+  /// The menu set with `tauri::Builder::menu` takes precedence.
+  pub menu: Option<Vec<MenuItemConfig>>,
   /// MacOS private API configuration. Enables the transparent background API and sets the `fullScreenEnabled` preference to `true`.
   #[serde(rename = "macOSPrivateApi", alias = "macos-private-api", default)]
   pub macos_private_api: bool,
@@ -1893,6 +2422,9 @@ pub struct AppConfig {
   /// If set to true "identifier" will be set as GTK app ID (on systems that use GTK).
   #[serde(rename = "enableGTKAppId", alias = "enable-gtk-app-id", default)]
   pub enable_gtk_app_id: bool,
+  /// Overrides of the app directories, such as the app data directory, or portable mode.
+  #[serde(default)]
+  pub directories: AppDirectoriesConfig,
 }
 
 impl AppConfig {
@@ -1955,6 +2487,199 @@ pub struct TrayIconConfig {
   pub title: Option<String>,
   /// Tray icon tooltip on Windows and macOS
   pub tooltip: Option<String>,
+  /// The tray icon menu.
+  pub menu: Option<Vec<MenuItemConfig>>,
+}
+
+/// A menu item defined in the configuration.
+///
+/// Menu events of the items are emitted with the configured `id`, see `tauri::Builder::on_menu_event`.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(untagged)]
+// Note, order matters for untagged enum deserialization
+pub enum MenuItemConfig {
+  /// A predefined menu item that has a predefined behavior by the OS, e.g. `copy` or `quit`.
+  Predefined(PredefinedMenuItemConfig),
+  /// A submenu.
+  Submenu(SubmenuConfig),
+  /// A menu item with a checkbox.
+  Check(CheckMenuItemConfig),
+  /// A menu item with an icon.
+  Icon(IconMenuItemConfig),
+  /// A menu item with a text.
+  Normal(NormalMenuItemConfig),
+}
+
+impl MenuItemConfig {
+  /// The platforms this item is added on, all platforms if `None`.
+  pub fn platforms(&self) -> Option<&[crate::platform::Target]> {
+    match self {
+      Self::Predefined(i) => i.platforms.as_deref(),
+      Self::Submenu(i) => i.platforms.as_deref(),
+      Self::Check(i) => i.platforms.as_deref(),
+      Self::Icon(i) => i.platforms.as_deref(),
+      Self::Normal(i) => i.platforms.as_deref(),
+    }
+  }
+
+  /// Whether this item is added on the given platform.
+  pub fn is_supported(&self, target: crate::platform::Target) -> bool {
+    self
+      .platforms()
+      .map(|platforms| platforms.contains(&target))
+      .unwrap_or(true)
+  }
+
+  /// The item identifier, if any.
+  pub fn id(&self) -> Option<&str> {
+    match self {
+      Self::Predefined(_) => None,
+      Self::Submenu(i) => i.id.as_deref(),
+      Self::Check(i) => Some(&i.id),
+      Self::Icon(i) => Some(&i.id),
+      Self::Normal(i) => Some(&i.id),
+    }
+  }
+}
+
+/// A menu item with a text.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct NormalMenuItemConfig {
+  /// The item identifier, used to match its menu events.
+  pub id: String,
+  /// The item text.
+  pub text: String,
+  /// Whether the item is enabled or not.
+  #[serde(default = "default_true")]
+  pub enabled: bool,
+  /// The item keyboard shortcut, e.g. `CmdOrCtrl+Q`.
+  pub accelerator: Option<String>,
+  /// Limit which target platforms this item is added on. By default all platforms are targeted.
+  pub platforms: Option<Vec<crate::platform::Target>>,
+}
+
+/// A menu item with a checkbox.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct CheckMenuItemConfig {
+  /// The item identifier, used to match its menu events.
+  pub id: String,
+  /// The item text.
+  pub text: String,
+  /// Whether the item is initially checked or not.
+  pub checked: bool,
+  /// Whether the item is enabled or not.
+  #[serde(default = "default_true")]
+  pub enabled: bool,
+  /// The item keyboard shortcut, e.g. `CmdOrCtrl+Q`.
+  pub accelerator: Option<String>,
+  /// Limit which target platforms this item is added on. By default all platforms are targeted.
+  pub platforms: Option<Vec<crate::platform::Target>>,
+}
+
+/// A menu item with an icon.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct IconMenuItemConfig {
+  /// The item identifier, used to match its menu events.
+  pub id: String,
+  /// The item text.
+  pub text: String,
+  /// Path to the item icon, relative to the configuration file.
+  ///
+  /// Note: this stores the image in raw pixels to the final binary,
+  /// so keep the icon size (width and height) small
+  /// or else it's going to bloat your final executable
+  pub icon: PathBuf,
+  /// Whether the item is enabled or not.
+  #[serde(default = "default_true")]
+  pub enabled: bool,
+  /// The item keyboard shortcut, e.g. `CmdOrCtrl+Q`.
+  pub accelerator: Option<String>,
+  /// Limit which target platforms this item is added on. By default all platforms are targeted.
+  pub platforms: Option<Vec<crate::platform::Target>>,
+}
+
+/// A submenu.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SubmenuConfig {
+  /// The submenu identifier, used to reference it later.
+  pub id: Option<String>,
+  /// The submenu text.
+  pub text: String,
+  /// Whether the submenu is enabled or not.
+  #[serde(default = "default_true")]
+  pub enabled: bool,
+  /// The submenu items.
+  pub items: Vec<MenuItemConfig>,
+  /// Limit which target platforms this submenu is added on. By default all platforms are targeted.
+  pub platforms: Option<Vec<crate::platform::Target>>,
+}
+
+/// A predefined menu item that has a predefined behavior by the OS.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct PredefinedMenuItemConfig {
+  /// The predefined item.
+  pub item: PredefinedMenuItemKind,
+  /// The item text, defaults to the OS text.
+  pub text: Option<String>,
+  /// Limit which target platforms this item is added on. By default all platforms are targeted.
+  pub platforms: Option<Vec<crate::platform::Target>>,
+}
+
+/// The kind of a predefined menu item.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum PredefinedMenuItemKind {
+  /// A separator.
+  Separator,
+  /// Copy the selected text.
+  Copy,
+  /// Cut the selected text.
+  Cut,
+  /// Paste text from the clipboard.
+  Paste,
+  /// Select all the text.
+  SelectAll,
+  /// Undo the last action.
+  Undo,
+  /// Redo the last undone action.
+  Redo,
+  /// Minimize the window.
+  Minimize,
+  /// Maximize the window.
+  Maximize,
+  /// Toggle the window fullscreen state.
+  Fullscreen,
+  /// Hide the application.
+  Hide,
+  /// Hide the other applications.
+  HideOthers,
+  /// Show all the applications.
+  ShowAll,
+  /// Close the window.
+  CloseWindow,
+  /// Quit the application.
+  Quit,
+  /// Show the about dialog with the application name, version, copyright and publisher.
+  About,
+  /// The services submenu on macOS.
+  Services,
 }
 
 /// General configuration for the iOS target.
@@ -2505,6 +3230,7 @@ mod build {
       let parent = opt_str_lit(self.parent.as_ref());
       let zoom_hotkeys_enabled = self.zoom_hotkeys_enabled;
       let browser_extensions_enabled = self.browser_extensions_enabled;
+      let layout = opt_lit(self.layout.as_ref());
 
       literal_struct!(
         tokens,
@@ -2551,11 +3277,94 @@ mod build {
         incognito,
         parent,
         zoom_hotkeys_enabled,
-        browser_extensions_enabled
+        browser_extensions_enabled,
+        layout
       );
     }
   }
 
+  impl ToTokens for LayoutDirection {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let prefix = quote! { ::tauri::utils::config::LayoutDirection };
+
+      tokens.append_all(match self {
+        Self::Horizontal => quote! { #prefix::Horizontal },
+        Self::Vertical => quote! { #prefix::Vertical },
+      })
+    }
+  }
+
+  impl ToTokens for LayoutConfig {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let id = opt_str_lit(self.id.as_ref());
+      let weight = opt_lit(self.weight.as_ref());
+      let size = opt_lit(self.size.as_ref());
+      let min_width = self.min_width;
+      let min_height = self.min_height;
+      let kind = &self.kind;
+
+      literal_struct!(
+        tokens,
+        ::tauri::utils::config::LayoutConfig,
+        id,
+        weight,
+        size,
+        min_width,
+        min_height,
+        kind
+      );
+    }
+  }
+
+  impl ToTokens for LayoutKind {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let prefix = quote! { ::tauri::utils::config::LayoutKind };
+
+      tokens.append_all(match self {
+        Self::Split {
+          direction,
+          splitter_size,
+          children,
+        } => {
+          let children = vec_lit(children, |c| c);
+          quote! { #prefix::Split { direction: #direction, splitter_size: #splitter_size, children: #children } }
+        }
+        Self::Stack {
+          direction,
+          children,
+        } => {
+          let children = vec_lit(children, |c| c);
+          quote! { #prefix::Stack { direction: #direction, children: #children } }
+        }
+        Self::Tabs { active, children } => {
+          let children = vec_lit(children, |c| c);
+          quote! { #prefix::Tabs { active: #active, children: #children } }
+        }
+        Self::Webview {
+          label,
+          url,
+          user_agent,
+          drag_drop_enabled,
+          transparent,
+          incognito,
+        } => {
+          let label = str_lit(label);
+          let user_agent = opt_str_lit(user_agent.as_ref());
+          quote! {
+            #prefix::Webview {
+              label: #label,
+              url: #url,
+              user_agent: #user_agent,
+              drag_drop_enabled: #drag_drop_enabled,
+              transparent: #transparent,
+              incognito: #incognito,
+            }
+          }
+        }
+      })
+    }
+  }
+
   impl ToTokens for PatternKind {
     fn to_tokens(&self, tokens: &mut TokenStream) {
       let prefix = quote! { ::tauri::utils::config::PatternKind };
@@ -2783,6 +3592,7 @@ mod build {
       let asset_protocol = &self.asset_protocol;
       let pattern = &self.pattern;
       let capabilities = vec_lit(&self.capabilities, identity);
+      let web_permissions = vec_lit(&self.web_permissions, identity);
 
       literal_struct!(
         tokens,
@@ -2793,7 +3603,48 @@ mod build {
         dangerous_disable_asset_csp_modification,
         asset_protocol,
         pattern,
-        capabilities
+        capabilities,
+        web_permissions
+      );
+    }
+  }
+
+  impl ToTokens for WebPermissionKind {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let prefix = quote! { ::tauri::utils::config::WebPermissionKind };
+
+      tokens.append_all(match self {
+        Self::Camera => quote! { #prefix::Camera },
+        Self::Microphone => quote! { #prefix::Microphone },
+        Self::Geolocation => quote! { #prefix::Geolocation },
+        Self::Notifications => quote! { #prefix::Notifications },
+      })
+    }
+  }
+
+  impl ToTokens for WebPermissionDecision {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let prefix = quote! { ::tauri::utils::config::WebPermissionDecision };
+
+      tokens.append_all(match self {
+        Self::Allow => quote! { #prefix::Allow },
+        Self::Deny => quote! { #prefix::Deny },
+      })
+    }
+  }
+
+  impl ToTokens for WebPermissionRule {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let origin = str_lit(&self.origin);
+      let permissions = vec_lit(&self.permissions, identity);
+      let decision = &self.decision;
+
+      literal_struct!(
+        tokens,
+        ::tauri::utils::config::WebPermissionRule,
+        origin,
+        permissions,
+        decision
       );
     }
   }
@@ -2806,6 +3657,7 @@ mod build {
       let icon_path = path_buf_lit(&self.icon_path);
       let title = opt_str_lit(self.title.as_ref());
       let tooltip = opt_str_lit(self.tooltip.as_ref());
+      let menu = opt_vec_lit(self.menu.as_ref(), identity);
       literal_struct!(
         tokens,
         ::tauri::utils::config::TrayIconConfig,
@@ -2814,11 +3666,147 @@ mod build {
         icon_as_template,
         menu_on_left_click,
         title,
-        tooltip
+        tooltip,
+        menu
+      );
+    }
+  }
+
+  impl ToTokens for MenuItemConfig {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let prefix = quote! { ::tauri::utils::config::MenuItemConfig };
+
+      tokens.append_all(match self {
+        Self::Predefined(item) => quote! { #prefix::Predefined(#item) },
+        Self::Submenu(item) => quote! { #prefix::Submenu(#item) },
+        Self::Check(item) => quote! { #prefix::Check(#item) },
+        Self::Icon(item) => quote! { #prefix::Icon(#item) },
+        Self::Normal(item) => quote! { #prefix::Normal(#item) },
+      })
+    }
+  }
+
+  impl ToTokens for NormalMenuItemConfig {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let id = str_lit(&self.id);
+      let text = str_lit(&self.text);
+      let enabled = self.enabled;
+      let accelerator = opt_str_lit(self.accelerator.as_ref());
+      let platforms = opt_vec_lit(self.platforms.as_ref(), identity);
+      literal_struct!(
+        tokens,
+        ::tauri::utils::config::NormalMenuItemConfig,
+        id,
+        text,
+        enabled,
+        accelerator,
+        platforms
+      );
+    }
+  }
+
+  impl ToTokens for CheckMenuItemConfig {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let id = str_lit(&self.id);
+      let text = str_lit(&self.text);
+      let checked = self.checked;
+      let enabled = self.enabled;
+      let accelerator = opt_str_lit(self.accelerator.as_ref());
+      let platforms = opt_vec_lit(self.platforms.as_ref(), identity);
+      literal_struct!(
+        tokens,
+        ::tauri::utils::config::CheckMenuItemConfig,
+        id,
+        text,
+        checked,
+        enabled,
+        accelerator,
+        platforms
+      );
+    }
+  }
+
+  impl ToTokens for IconMenuItemConfig {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let id = str_lit(&self.id);
+      let text = str_lit(&self.text);
+      let icon = path_buf_lit(&self.icon);
+      let enabled = self.enabled;
+      let accelerator = opt_str_lit(self.accelerator.as_ref());
+      let platforms = opt_vec_lit(self.platforms.as_ref(), identity);
+      literal_struct!(
+        tokens,
+        ::tauri::utils::config::IconMenuItemConfig,
+        id,
+        text,
+        icon,
+        enabled,
+        accelerator,
+        platforms
+      );
+    }
+  }
+
+  impl ToTokens for SubmenuConfig {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let id = opt_str_lit(self.id.as_ref());
+      let text = str_lit(&self.text);
+      let enabled = self.enabled;
+      let items = vec_lit(&self.items, identity);
+      let platforms = opt_vec_lit(self.platforms.as_ref(), identity);
+      literal_struct!(
+        tokens,
+        ::tauri::utils::config::SubmenuConfig,
+        id,
+        text,
+        enabled,
+        items,
+        platforms
+      );
+    }
+  }
+
+  impl ToTokens for PredefinedMenuItemConfig {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let item = &self.item;
+      let text = opt_str_lit(self.text.as_ref());
+      let platforms = opt_vec_lit(self.platforms.as_ref(), identity);
+      literal_struct!(
+        tokens,
+        ::tauri::utils::config::PredefinedMenuItemConfig,
+        item,
+        text,
+        platforms
       );
     }
   }
 
+  impl ToTokens for PredefinedMenuItemKind {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let prefix = quote! { ::tauri::utils::config::PredefinedMenuItemKind };
+
+      tokens.append_all(match self {
+        Self::Separator => quote! { #prefix::Separator },
+        Self::Copy => quote! { #prefix::Copy },
+        Self::Cut => quote! { #prefix::Cut },
+        Self::Paste => quote! { #prefix::Paste },
+        Self::SelectAll => quote! { #prefix::SelectAll },
+        Self::Undo => quote! { #prefix::Undo },
+        Self::Redo => quote! { #prefix::Redo },
+        Self::Minimize => quote! { #prefix::Minimize },
+        Self::Maximize => quote! { #prefix::Maximize },
+        Self::Fullscreen => quote! { #prefix::Fullscreen },
+        Self::Hide => quote! { #prefix::Hide },
+        Self::HideOthers => quote! { #prefix::HideOthers },
+        Self::ShowAll => quote! { #prefix::ShowAll },
+        Self::CloseWindow => quote! { #prefix::CloseWindow },
+        Self::Quit => quote! { #prefix::Quit },
+        Self::About => quote! { #prefix::About },
+        Self::Services => quote! { #prefix::Services },
+      })
+    }
+  }
+
   impl ToTokens for FsScope {
     fn to_tokens(&self, tokens: &mut TokenStream) {
       let prefix = quote! { ::tauri::utils::config::FsScope };
@@ -2850,9 +3838,11 @@ mod build {
       let windows = vec_lit(&self.windows, identity);
       let security = &self.security;
       let tray_icon = opt_lit(self.tray_icon.as_ref());
+      let menu = opt_vec_lit(self.menu.as_ref(), identity);
       let macos_private_api = self.macos_private_api;
       let with_global_tauri = self.with_global_tauri;
       let enable_gtk_app_id = self.enable_gtk_app_id;
+      let directories = &self.directories;
 
       literal_struct!(
         tokens,
@@ -2860,9 +3850,33 @@ mod build {
         windows,
         security,
         tray_icon,
+        menu,
         macos_private_api,
         with_global_tauri,
-        enable_gtk_app_id
+        enable_gtk_app_id,
+        directories
+      );
+    }
+  }
+
+  impl ToTokens for AppDirectoriesConfig {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let portable = self.portable;
+      let config = opt_lit(self.config.as_ref().map(path_buf_lit).as_ref());
+      let data = opt_lit(self.data.as_ref().map(path_buf_lit).as_ref());
+      let local_data = opt_lit(self.local_data.as_ref().map(path_buf_lit).as_ref());
+      let cache = opt_lit(self.cache.as_ref().map(path_buf_lit).as_ref());
+      let log = opt_lit(self.log.as_ref().map(path_buf_lit).as_ref());
+
+      literal_struct!(
+        tokens,
+        ::tauri::utils::config::AppDirectoriesConfig,
+        portable,
+        config,
+        data,
+        local_data,
+        cache,
+        log
       );
     }
   }
@@ -2937,11 +3951,14 @@ mod test {
         asset_protocol: AssetProtocolConfig::default(),
         pattern: Default::default(),
         capabilities: Vec::new(),
+        web_permissions: Vec::new(),
       },
       tray_icon: None,
+      menu: None,
       macos_private_api: false,
       with_global_tauri: false,
       enable_gtk_app_id: false,
+      directories: Default::default(),
     };
 
     // create a build config
@@ -2986,4 +4003,132 @@ mod test {
     assert_eq!(d_bundle, bundle);
     assert_eq!(d_windows, app.windows);
   }
+
+  #[test]
+  fn parse_window_layout() {
+    let window: WindowConfig = serde_json::from_value(serde_json::json!({
+      "layout": {
+        "type": "split",
+        "id": "main",
+        "children": [
+          { "type": "webview", "label": "sidebar", "url": "sidebar.html", "weight": 1, "minWidth": 200 },
+          {
+            "type": "tabs",
+            "id": "editors",
+            "active": 1,
+            "weight": 3,
+            "children": [
+              { "type": "webview", "label": "first" },
+              { "type": "webview", "label": "second", "dragDropEnabled": false }
+            ]
+          }
+        ]
+      }
+    }))
+    .unwrap();
+
+    let layout = window.layout.unwrap();
+    assert_eq!(layout.id.as_deref(), Some("main"));
+    let LayoutKind::Split {
+      direction,
+      splitter_size,
+      children,
+    } = layout.kind
+    else {
+      panic!("expected a split layout");
+    };
+    assert_eq!(direction, LayoutDirection::Horizontal);
+    assert_eq!(splitter_size, default_splitter_size());
+    assert_eq!(children.len(), 2);
+    assert_eq!(children[0].min_width, 200.);
+    assert_eq!(children[0].weight, Some(1.));
+    assert_eq!(children[1].weight, Some(3.));
+    assert_eq!(children[1].size, None);
+    assert_eq!(
+      children[0].kind,
+      LayoutKind::Webview {
+        label: "sidebar".into(),
+        url: WebviewUrl::App("sidebar.html".into()),
+        user_agent: None,
+        drag_drop_enabled: true,
+        transparent: false,
+        incognito: false,
+      }
+    );
+    assert!(matches!(
+      &children[1].kind,
+      LayoutKind::Tabs { active: 1, children } if children.len() == 2
+    ));
+  }
+
+  #[test]
+  fn web_permission_rule_matches() {
+    let security: SecurityConfig = serde_json::from_value(serde_json::json!({
+      "webPermissions": [
+        { "origin": "https://meet.example.com/", "permissions": ["camera", "microphone"], "decision": "allow" },
+        { "origin": "*", "permissions": ["geolocation"], "decision": "deny" }
+      ]
+    }))
+    .unwrap();
+
+    let [meet, any] = security.web_permissions.as_slice() else {
+      panic!("expected two rules");
+    };
+    assert_eq!(meet.decision, WebPermissionDecision::Allow);
+
+    let url: Url = "https://meet.example.com/room/1".parse().unwrap();
+    assert!(meet.matches(&url, WebPermissionKind::Camera));
+    assert!(!meet.matches(&url, WebPermissionKind::Notifications));
+    assert!(!meet.matches(
+      &"https://example.com".parse().unwrap(),
+      WebPermissionKind::Camera
+    ));
+    assert!(any.matches(&url, WebPermissionKind::Geolocation));
+  }
+
+  #[test]
+  fn menu_config() {
+    use crate::platform::Target;
+
+    let items: Vec<MenuItemConfig> = serde_json::from_value(serde_json::json!([
+      {
+        "text": "File",
+        "items": [
+          { "id": "new", "text": "New", "accelerator": "CmdOrCtrl+N" },
+          { "id": "autosave", "text": "Auto Save", "checked": true },
+          { "id": "export", "text": "Export", "icon": "icons/export.png", "enabled": false },
+          { "item": "separator" },
+          { "item": "quit", "text": "Exit", "platforms": ["windows", "linux"] }
+        ]
+      }
+    ]))
+    .unwrap();
+
+    let [MenuItemConfig::Submenu(file)] = items.as_slice() else {
+      panic!("expected a submenu");
+    };
+    let [MenuItemConfig::Normal(new), MenuItemConfig::Check(autosave), MenuItemConfig::Icon(export), MenuItemConfig::Predefined(separator), quit] =
+      file.items.as_slice()
+    else {
+      panic!("unexpected submenu items {:?}", file.items);
+    };
+
+    assert_eq!(new.accelerator.as_deref(), Some("CmdOrCtrl+N"));
+    assert!(new.enabled);
+    assert!(autosave.checked);
+    assert_eq!(export.icon, PathBuf::from("icons/export.png"));
+    assert!(!export.enabled);
+    assert_eq!(separator.item, PredefinedMenuItemKind::Separator);
+    assert!(quit.is_supported(Target::Windows));
+    assert!(!quit.is_supported(Target::MacOS));
+    assert_eq!(quit.id(), None);
+
+    // unknown fields are rejected instead of falling back to another item kind
+    assert!(serde_json::from_value::<MenuItemConfig>(serde_json::json!({
+      "id": "new",
+      "text": "New",
+      "shortcut": "CmdOrCtrl+N"
+    }))
+    .is_err());
+  }
 }
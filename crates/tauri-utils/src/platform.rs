@@ -315,6 +315,9 @@ fn resource_dir_from<P: AsRef<std::path::Path>>(
         .join(format!("../lib/{}", package_info.name))
         .canonicalize()
         .map_err(Into::into)
+    } else if curr_dir == "/app/bin" {
+      // running from the Flatpak sandbox
+      Ok(PathBuf::from(format!("/app/lib/{}", package_info.name)))
     } else if let Some(appdir) = &env.appdir {
       let appdir: &std::path::Path = appdir.as_ref();
       Ok(PathBuf::from(format!(
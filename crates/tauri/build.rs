@@ -109,6 +109,8 @@ const PLUGINS: &[(&str, &[(&str, bool)])] = &[
       ("set_title_bar_style", false),
       ("set_theme", false),
       ("toggle_maximize", false),
+      ("move_layout_splitter", false),
+      ("set_layout_active_tab", false),
       // internal
       ("internal_toggle_maximize", true),
     ],
@@ -122,6 +124,8 @@ const PLUGINS: &[(&str, &[(&str, bool)])] = &[
       ("get_all_webviews", true),
       ("webview_position", true),
       ("webview_size", true),
+      ("webview_can_go_back", true),
+      ("webview_can_go_forward", true),
       // setters
       ("webview_close", false),
       ("set_webview_size", false),
@@ -133,7 +137,11 @@ const PLUGINS: &[(&str, &[(&str, bool)])] = &[
       ("print", false),
       ("reparent", false),
       ("clear_all_browsing_data", false),
+      ("webview_go_back", false),
+      ("webview_go_forward", false),
+      ("webview_reload", false),
       // internal
+      ("internal_on_new_window", false),
       ("internal_toggle_devtools", true),
     ],
   ),
@@ -147,6 +155,7 @@ const PLUGINS: &[(&str, &[(&str, bool)])] = &[
       ("app_hide", false),
       ("default_window_icon", false),
       ("set_app_theme", false),
+      ("locale", true),
     ],
   ),
   (
@@ -155,11 +164,23 @@ const PLUGINS: &[(&str, &[(&str, bool)])] = &[
       ("new", true),
       ("from_bytes", true),
       ("from_path", true),
+      ("from_ico_for_scale_factor", true),
       ("rgba", true),
       ("size", true),
+      ("resize", true),
+      ("crop", true),
     ],
   ),
   ("core:resources", &[("close", true)]),
+  (
+    "core:message-port",
+    &[
+      ("create", false),
+      ("connect", true),
+      ("post", true),
+      ("close", true),
+    ],
+  ),
   (
     "core:menu",
     &[
@@ -185,6 +206,7 @@ const PLUGINS: &[(&str, &[(&str, bool)])] = &[
       ("is_checked", true),
       ("set_checked", true),
       ("set_icon", true),
+      ("dispatch_accelerator", false),
     ],
   ),
   (
@@ -194,6 +216,9 @@ const PLUGINS: &[(&str, &[(&str, bool)])] = &[
       ("get_by_id", true),
       ("remove_by_id", true),
       ("set_icon", true),
+      ("set_badge", true),
+      ("start_animation", true),
+      ("stop_animation", true),
       ("set_menu", true),
       ("set_tooltip", true),
       ("set_title", true),
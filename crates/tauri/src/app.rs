@@ -8,6 +8,7 @@ use crate::{
     channel::ChannelDataIpcQueue, CallbackFn, CommandArg, CommandItem, Invoke, InvokeError,
     InvokeHandler, InvokeResponseBody,
   },
+  locale::{Catalog, LOCALE_CHANGED_EVENT},
   manager::{webview::UriSchemeProtocol, AppManager, Asset},
   plugin::{Plugin, PluginStore},
   resources::ResourceTable,
@@ -171,12 +172,42 @@ impl From<RuntimeWindowEvent> for WindowEvent {
 pub enum WebviewEvent {
   /// An event associated with the drag and drop action.
   DragDrop(DragDropEvent),
+  /// The webview navigation history has changed.
+  ///
+  /// Emitted when a page finishes loading and on same-document navigations
+  /// such as `history.pushState`, `history.replaceState` and fragment changes.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Android**: `can_go_back` and `can_go_forward` are always `false`.
+  NavigationStateChanged {
+    /// The current URL of the webview.
+    url: url::Url,
+    /// Whether the webview can navigate back in its history.
+    can_go_back: bool,
+    /// Whether the webview can navigate forward in its history.
+    can_go_forward: bool,
+  },
 }
 
-impl From<RuntimeWebviewEvent> for WebviewEvent {
-  fn from(event: RuntimeWebviewEvent) -> Self {
+/// Converts the events known to this version of Tauri,
+/// returning the events added to the runtime afterwards unchanged.
+impl TryFrom<RuntimeWebviewEvent> for WebviewEvent {
+  type Error = RuntimeWebviewEvent;
+
+  fn try_from(event: RuntimeWebviewEvent) -> std::result::Result<Self, Self::Error> {
     match event {
-      RuntimeWebviewEvent::DragDrop(e) => Self::DragDrop(e),
+      RuntimeWebviewEvent::DragDrop(e) => Ok(Self::DragDrop(e)),
+      RuntimeWebviewEvent::NavigationStateChanged {
+        url,
+        can_go_back,
+        can_go_forward,
+      } => Ok(Self::NavigationStateChanged {
+        url,
+        can_go_back,
+        can_go_forward,
+      }),
+      event => Err(event),
     }
   }
 }
@@ -238,6 +269,22 @@ pub enum RunEvent {
   #[cfg(all(desktop, feature = "tray-icon"))]
   #[cfg_attr(docsrs, doc(cfg(all(desktop, feature = "tray-icon"))))]
   TrayIconEvent(crate::tray::TrayIconEvent),
+  /// Another instance of the app was launched while [`Builder::single_instance`] is enabled.
+  ///
+  /// The other instance exits right after forwarding its arguments, so this is usually where the main window gets focused.
+  #[non_exhaustive]
+  #[cfg(desktop)]
+  #[cfg_attr(docsrs, doc(cfg(desktop)))]
+  SecondInstance {
+    /// The command line arguments of the instance, including the executable.
+    args: Vec<String>,
+    /// The working directory of the instance.
+    cwd: std::path::PathBuf,
+  },
+  /// An arbitrary message sent by another instance of the app with [`crate::instance::InstanceClient::send_message`].
+  #[cfg(desktop)]
+  #[cfg_attr(docsrs, doc(cfg(desktop)))]
+  InstanceMessage(serde_json::Value),
   /// Emitted when the NSApplicationDelegate's applicationShouldHandleReopen gets called
   #[non_exhaustive]
   #[cfg(target_os = "macos")]
@@ -259,6 +306,18 @@ impl From<EventLoopMessage> for RunEvent {
   }
 }
 
+#[cfg(desktop)]
+impl From<crate::instance::InstanceMessage> for RunEvent {
+  fn from(message: crate::instance::InstanceMessage) -> Self {
+    match message {
+      crate::instance::InstanceMessage::Launched { args, cwd } => {
+        Self::SecondInstance { args, cwd }
+      }
+      crate::instance::InstanceMessage::Custom { payload } => Self::InstanceMessage(payload),
+    }
+  }
+}
+
 /// The asset resolver is a helper to access the [`tauri_utils::assets::Assets`] interface.
 #[derive(Debug, Clone)]
 pub struct AssetResolver<R: Runtime> {
@@ -717,6 +776,46 @@ macro_rules! shared_app_impl {
         self.manager.window.default_icon.as_ref()
       }
 
+      /// Registers the message catalog of a locale, replacing the one previously registered for it.
+      ///
+      /// The catalogs translate the menu items and tray icon tooltips bound to a message key,
+      /// see [`Self::set_locale`].
+      pub fn register_catalog<L: Into<String>>(&self, locale: L, catalog: Catalog) {
+        self
+          .manager
+          .locale
+          .catalogs_lock()
+          .insert(locale.into(), catalog);
+      }
+
+      /// Returns the active locale, set with [`Self::set_locale`].
+      pub fn locale(&self) -> Option<String> {
+        self.manager.locale.locale_lock().clone()
+      }
+
+      /// Sets the active locale and emits the `tauri://locale-changed` event to the frontend.
+      ///
+      /// All live menu items and tray icon tooltips bound to a message key are relabeled.
+      /// A message missing from the catalog of the locale is looked up in the catalog
+      /// of its language (`fr` for `fr-CA`), otherwise menu items get back the text they had
+      /// before being bound to a message key and tray icon tooltips keep their current text.
+      pub fn set_locale<L: Into<String>>(&self, locale: L) -> crate::Result<()> {
+        let locale = locale.into();
+        self.manager.locale.locale_lock().replace(locale.clone());
+
+        #[cfg(desktop)]
+        crate::menu::relabel(self.app_handle())?;
+
+        #[cfg(all(desktop, feature = "tray-icon"))]
+        for (tray, key) in self.manager.tray.localized_tooltips() {
+          if let Some(tooltip) = self.manager.locale.translate(&key) {
+            tray.set_tooltip(Some(tooltip))?;
+          }
+        }
+
+        self.emit(LOCALE_CHANGED_EVENT, locale)
+      }
+
       /// Returns the app-wide menu.
       #[cfg(desktop)]
       pub fn menu(&self) -> Option<Menu<R>> {
@@ -745,6 +844,8 @@ macro_rules! shared_app_impl {
               window.menu_lock().replace(crate::window::WindowMenu {
                 is_app_wide: true,
                 menu: menu.clone(),
+                #[cfg(feature = "test")]
+                visible: true,
               });
             }
           }
@@ -868,6 +969,8 @@ macro_rules! shared_app_impl {
       /// Runs necessary cleanup tasks before exiting the process.
       /// **You should always exit the tauri app immediately after this function returns and not use any tauri-related APIs.**
       pub fn cleanup_before_exit(&self) {
+        #[cfg(desktop)]
+        self.manager.instance.lock().unwrap().take();
         #[cfg(all(desktop, feature = "tray-icon"))]
         self.manager.tray.icons.lock().unwrap().clear();
         self.manager.resources_table().clear();
@@ -1041,6 +1144,7 @@ impl<R: Runtime> App<R> {
     self.handle.plugin(crate::app::plugin::init())?;
     self.handle.plugin(crate::resources::plugin::init())?;
     self.handle.plugin(crate::image::plugin::init())?;
+    self.handle.plugin(crate::ipc::message_port::init())?;
     #[cfg(desktop)]
     self.handle.plugin(crate::menu::plugin::init())?;
     #[cfg(all(desktop, feature = "tray-icon"))]
@@ -1131,17 +1235,26 @@ impl<R: Runtime> App<R> {
         if let Err(e) = setup(&mut self) {
           panic!("Failed to setup app: {e}");
         }
-        let event = on_event_loop_event(&app_handle, RuntimeRunEvent::Ready, &manager);
-        callback(&app_handle, event);
+        if let Some(event) = on_event_loop_event(&app_handle, RuntimeRunEvent::Ready, &manager) {
+          callback(&app_handle, event);
+        }
       }
       RuntimeRunEvent::Exit => {
-        let event = on_event_loop_event(&app_handle, RuntimeRunEvent::Exit, &manager);
-        callback(&app_handle, event);
+        if let Some(event) = on_event_loop_event(&app_handle, RuntimeRunEvent::Exit, &manager) {
+          callback(&app_handle, event);
+        }
         app_handle.cleanup_before_exit();
       }
       _ => {
-        let event = on_event_loop_event(&app_handle, event, &manager);
-        callback(&app_handle, event);
+        #[cfg(desktop)]
+        if matches!(event, RuntimeRunEvent::MainEventsCleared) {
+          for event in on_instance_messages(&app_handle, &manager) {
+            callback(&app_handle, event);
+          }
+        }
+        if let Some(event) = on_event_loop_event(&app_handle, event, &manager) {
+          callback(&app_handle, event);
+        }
       }
     });
   }
@@ -1180,8 +1293,14 @@ impl<R: Runtime> App<R> {
     }
 
     self.runtime.as_mut().unwrap().run_iteration(move |event| {
-      let event = on_event_loop_event(&app_handle, event, &manager);
-      callback(&app_handle, event);
+      if matches!(event, RuntimeRunEvent::MainEventsCleared) {
+        for event in on_instance_messages(&app_handle, &manager) {
+          callback(&app_handle, event);
+        }
+      }
+      if let Some(event) = on_event_loop_event(&app_handle, event, &manager) {
+        callback(&app_handle, event);
+      }
     })
   }
 }
@@ -1236,6 +1355,10 @@ pub struct Builder<R: Runtime> {
   #[allow(unused)]
   enable_macos_default_menu: bool,
 
+  /// Whether other instances of the app forward their arguments to the running one and exit.
+  #[cfg(desktop)]
+  single_instance: bool,
+
   /// Window event handlers that listens to all windows.
   window_event_listeners: Vec<GlobalWindowEventListener<R>>,
 
@@ -1305,6 +1428,8 @@ impl<R: Runtime> Builder<R> {
       #[cfg(desktop)]
       menu_event_listeners: Vec::new(),
       enable_macos_default_menu: true,
+      #[cfg(desktop)]
+      single_instance: false,
       window_event_listeners: Vec::new(),
       webview_event_listeners: Vec::new(),
       device_event_filter: Default::default(),
@@ -1614,6 +1739,8 @@ tauri::Builder::default()
 
   /// Sets the menu to use on all windows.
   ///
+  /// Takes precedence over the menu defined in the `app > menu` configuration.
+  ///
   /// # Examples
   /// ```
   /// use tauri::menu::{Menu, MenuItem, PredefinedMenuItem, Submenu};
@@ -1678,6 +1805,43 @@ tauri::Builder::default()
     self
   }
 
+  /// Allows a single instance of the app to run.
+  ///
+  /// When another instance is launched, it forwards its command line arguments and working directory
+  /// to the running instance, which receives them as [`RunEvent::SecondInstance`],
+  /// and [`Builder::build`] returns [`Error::SecondInstance`](crate::Error::SecondInstance) so it can exit.
+  /// See the [`instance`](crate::instance) module for the platform support and to send arbitrary messages.
+  ///
+  /// # Examples
+  /// ```
+  /// use tauri::{Manager, RunEvent};
+  ///
+  /// let app = match tauri::Builder::default()
+  ///   .single_instance(true)
+  ///   .build(tauri::generate_context!("test/fixture/src-tauri/tauri.conf.json"))
+  /// {
+  ///   Ok(app) => app,
+  ///   Err(tauri::Error::SecondInstance) => return,
+  ///   Err(e) => panic!("error while building tauri application: {e}"),
+  /// };
+  ///
+  /// app.run(|app, event| {
+  ///   if let RunEvent::SecondInstance { args, .. } = event {
+  ///     println!("launched again with {args:?}");
+  ///     if let Some(window) = app.get_webview_window("main") {
+  ///       let _ = window.unminimize();
+  ///       let _ = window.set_focus();
+  ///     }
+  ///   }
+  /// });
+  /// ```
+  #[cfg(desktop)]
+  #[must_use]
+  pub fn single_instance(mut self, enable: bool) -> Self {
+    self.single_instance = enable;
+    self
+  }
+
   /// Registers a window event handler for all windows.
   ///
   /// # Examples
@@ -1850,12 +2014,38 @@ tauri::Builder::default()
   }
 
   /// Builds the application.
+  ///
+  /// Fails with [`Error::SecondInstance`](crate::Error::SecondInstance) if [`Builder::single_instance`] is enabled and another instance is running.
   #[allow(clippy::type_complexity, unused_mut)]
   #[cfg_attr(
     feature = "tracing",
     tracing::instrument(name = "app::build", skip_all)
   )]
   pub fn build(mut self, context: Context<R>) -> crate::Result<App<R>> {
+    #[cfg(desktop)]
+    let instance = if self.single_instance {
+      match crate::instance::acquire(&context.config.identifier)? {
+        crate::instance::Instance::Primary(guard) => Some(guard),
+        crate::instance::Instance::Secondary(client) => {
+          if let Some(mut client) = client {
+            client.send(&crate::instance::InstanceMessage::launched())?;
+          }
+          return Err(crate::Error::SecondInstance);
+        }
+      }
+    } else {
+      None
+    };
+
+    #[cfg(desktop)]
+    if self.menu.is_none() {
+      if let Some(items) = context.config.app.menu.clone() {
+        self.menu = Some(Box::new(move |app_handle| {
+          crate::menu::Menu::from_config(app_handle, &items)
+        }));
+      }
+    }
+
     #[cfg(target_os = "macos")]
     if self.menu.is_none() && self.enable_macos_default_menu {
       self.menu = Some(Box::new(|app_handle| {
@@ -1950,6 +2140,18 @@ tauri::Builder::default()
           let _ = proxy.send_event(EventLoopMessage::TrayIconEvent(e.into()));
         }));
       }
+
+      // forward the messages of the other instances
+      if let Some(instance) = instance {
+        let messages = manager.instance_messages.clone();
+        let runtime_handle = runtime.handle();
+        instance.listen(move |message| {
+          messages.lock().unwrap().push(message);
+          // wake up the event loop so the message is delivered
+          let _ = runtime_handle.run_on_main_thread(|| {});
+        })?;
+        manager.instance.lock().unwrap().replace(instance);
+      }
     }
 
     runtime.set_device_event_filter(self.device_event_filter);
@@ -2038,6 +2240,9 @@ tauri::Builder::default()
         if let Some(tooltip) = &tray_config.tooltip {
           tray = tray.tooltip(tooltip);
         }
+        if let Some(items) = &tray_config.menu {
+          tray = tray.menu(&crate::menu::Menu::from_config(handle, items)?);
+        }
         tray.build(handle)?;
       }
     }
@@ -2048,8 +2253,15 @@ tauri::Builder::default()
   }
 
   /// Runs the configured Tauri application.
+  ///
+  /// Returns `Ok(())` right away if [`Builder::single_instance`] is enabled and another instance is running.
   pub fn run(self, context: Context<R>) -> crate::Result<()> {
-    self.build(context)?.run(|_, _| {});
+    match self.build(context) {
+      Ok(app) => app.run(|_, _| {}),
+      #[cfg(desktop)]
+      Err(crate::Error::SecondInstance) => {}
+      Err(e) => return Err(e),
+    }
     Ok(())
   }
 }
@@ -2124,7 +2336,14 @@ fn setup<R: Runtime>(app: &mut App<R>) -> crate::Result<()> {
   app.ran_setup = true;
 
   for window_config in app.config().app.windows.iter().filter(|w| w.create) {
-    WebviewWindowBuilder::from_config(app.handle(), window_config)?.build()?;
+    if window_config.layout.is_some() {
+      #[cfg(all(desktop, feature = "unstable"))]
+      crate::window::layout::build_window(app.handle(), window_config)?;
+      #[cfg(not(all(desktop, feature = "unstable")))]
+      return Err(crate::Error::UnstableFeatureNotSupported);
+    } else {
+      WebviewWindowBuilder::from_config(app.handle(), window_config)?.build()?;
+    }
   }
 
   app.manager.assets.setup(app);
@@ -2136,11 +2355,31 @@ fn setup<R: Runtime>(app: &mut App<R>) -> crate::Result<()> {
   Ok(())
 }
 
+/// Takes the messages received from the other instances of the app as run events, notifying the plugins.
+#[cfg(desktop)]
+fn on_instance_messages<R: Runtime>(
+  app_handle: &AppHandle<R>,
+  manager: &AppManager<R>,
+) -> Vec<RunEvent> {
+  let messages = std::mem::take(&mut *manager.instance_messages.lock().unwrap());
+  let mut plugins = manager.plugins.lock().expect("poisoned plugin store");
+  messages
+    .into_iter()
+    .map(|message| {
+      let event = RunEvent::from(message);
+      plugins.on_event(app_handle, &event);
+      event
+    })
+    .collect()
+}
+
+/// Converts the event loop event to a [`RunEvent`] and notifies the plugins,
+/// returning `None` for the webview events this version of Tauri does not know.
 fn on_event_loop_event<R: Runtime>(
   app_handle: &AppHandle<R>,
   event: RuntimeRunEvent<EventLoopMessage>,
   manager: &AppManager<R>,
-) -> RunEvent {
+) -> Option<RunEvent> {
   if let RuntimeRunEvent::WindowEvent {
     label,
     event: RuntimeWindowEvent::Destroyed,
@@ -2161,7 +2400,7 @@ fn on_event_loop_event<R: Runtime>(
     },
     RuntimeRunEvent::WebviewEvent { label, event } => RunEvent::WebviewEvent {
       label,
-      event: event.into(),
+      event: event.try_into().ok()?,
     },
     RuntimeRunEvent::Ready => {
       // set the app icon in development
@@ -2245,7 +2484,7 @@ fn on_event_loop_event<R: Runtime>(
     .expect("poisoned plugin store")
     .on_event(app_handle, &event);
 
-  event
+  Some(event)
 }
 
 #[cfg(test)]
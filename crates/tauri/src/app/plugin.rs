@@ -57,6 +57,11 @@ pub async fn set_app_theme<R: Runtime>(app: AppHandle<R>, theme: Option<Theme>)
   app.set_theme(theme);
 }
 
+#[command(root = "crate")]
+pub fn locale<R: Runtime>(app: AppHandle<R>) -> Option<String> {
+  app.locale()
+}
+
 pub fn init<R: Runtime>() -> TauriPlugin<R> {
   Builder::new("app")
     .invoke_handler(crate::generate_handler![
@@ -67,6 +72,7 @@ pub fn init<R: Runtime>() -> TauriPlugin<R> {
       app_hide,
       default_window_icon,
       set_app_theme,
+      locale,
     ])
     .build()
 }
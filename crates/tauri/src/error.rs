@@ -82,9 +82,13 @@ pub enum Error {
   #[error("invalid glob pattern: {0}")]
   GlobPattern(#[from] glob::PatternError),
   /// Image error.
-  #[cfg(any(feature = "image-png", feature = "image-ico"))]
+  #[cfg(feature = "image")]
   #[error("failed to process image: {0}")]
   Image(#[from] image::error::ImageError),
+  /// SVG image error.
+  #[cfg(feature = "image-svg")]
+  #[error("failed to render SVG image: {0}")]
+  Svg(#[from] resvg::usvg::Error),
   /// The Window's raw handle is invalid for the platform.
   #[error("Unexpected `raw_window_handle` for the current platform")]
   InvalidWindowHandle,
@@ -99,6 +103,14 @@ pub enum Error {
   #[error("menu error: {0}")]
   #[cfg(desktop)]
   Menu(#[from] muda::Error),
+  /// Invalid accelerator.
+  #[error("invalid accelerator `{0}`: {1}")]
+  #[cfg(desktop)]
+  InvalidAccelerator(String, String),
+  /// The accelerator is already registered on the window.
+  #[error("accelerator `{0}` is already registered on this window")]
+  #[cfg(desktop)]
+  AcceleratorConflict(String),
   /// Bad menu icon error.
   #[error(transparent)]
   #[cfg(desktop)]
@@ -160,6 +172,32 @@ pub enum Error {
   /// Bad `__TAURI_INVOKE_KEY__` value received in ipc message.
   #[error("bad __TAURI_INVOKE_KEY__ value received in ipc message")]
   InvokeKey,
+  /// Window layout node not found.
+  #[error("layout node `{0}` not found")]
+  LayoutNodeNotFound(String),
+  /// Window layout node does not have a child at the given index.
+  #[error("layout node `{0}` has no child at index {1}")]
+  LayoutChildOutOfBounds(String, usize),
+  /// Message port not found or closed.
+  #[error("message port {0} not found")]
+  MessagePortNotFound(u32),
+  /// Too many messages are waiting for the webview owning the message port to connect to it.
+  #[error("too many pending messages on message port {0}")]
+  MessagePortQueueFull(u32),
+  /// A message was received twice or too many messages are waiting for a lost one,
+  /// so the channel of the message port was closed.
+  #[error("message {1} of message port {0} is out of sequence, the channel was closed")]
+  MessagePortSequence(u32, u64),
+  /// The message catalog could not be parsed or resolved.
+  #[error("invalid message catalog: {0}")]
+  InvalidCatalog(String),
+  /// Another instance of the app is running and received the command line arguments of this one.
+  ///
+  /// Returned by [`Builder::build`](crate::Builder::build) when [`Builder::single_instance`](crate::Builder::single_instance) is enabled.
+  /// The app should exit after cleaning up.
+  #[cfg(desktop)]
+  #[error("another instance of the app is running")]
+  SecondInstance,
 }
 
 impl From<getrandom::Error> for Error {
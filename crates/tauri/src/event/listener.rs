@@ -204,6 +204,10 @@ impl Listeners {
           let handlers = handlers.filter(|(_, h)| match_any_or_filter(&h.target, &filter));
           for (&id, Handler { callback, .. }) in handlers {
             maybe_pending = true;
+            #[cfg(feature = "tracing")]
+            let _span =
+              tracing::debug_span!("event::dispatch", event = emit_args.event_name, id = id)
+                .entered();
             (callback)(Event::new(id, emit_args.payload.clone()))
           }
         }
@@ -286,6 +290,14 @@ impl Listeners {
           .filter(|handler| match_any_or_filter(&handler.target, &filter))
           .map(|handler| handler.id)
           .collect::<Vec<_>>();
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!(
+          "event::dispatch_js",
+          event = event,
+          webview = webview.label(),
+          listeners = ids.len()
+        )
+        .entered();
         webview.emit_js(emit_args, &ids)?;
       }
 
@@ -0,0 +1,98 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Selection of an entry in a multi-resolution `ico` file.
+
+const HEADER_SIZE: usize = 6;
+const ENTRY_SIZE: usize = 16;
+
+fn invalid(message: &str) -> crate::Error {
+  crate::Error::InvalidIcon(std::io::Error::new(
+    std::io::ErrorKind::InvalidData,
+    message.to_string(),
+  ))
+}
+
+/// Returns an `ico` file holding only the entry that best fits the target size in pixels:
+/// the smallest one at least as large, or the largest one if none is.
+pub(crate) fn best_entry(bytes: &[u8], target: u32) -> crate::Result<Vec<u8>> {
+  let header = bytes
+    .get(..HEADER_SIZE)
+    .ok_or_else(|| invalid("truncated ico header"))?;
+  if header[..4] != [0, 0, 1, 0] {
+    return Err(invalid("not an ico file"));
+  }
+  let count = u16::from_le_bytes([header[4], header[5]]) as usize;
+
+  let entries = (0..count)
+    .map(|i| {
+      let offset = HEADER_SIZE + i * ENTRY_SIZE;
+      bytes
+        .get(offset..offset + ENTRY_SIZE)
+        .ok_or_else(|| invalid("truncated ico directory"))
+    })
+    .collect::<crate::Result<Vec<_>>>()?;
+
+  // a width of 0 means 256 pixels
+  let size = |entry: &[u8]| if entry[0] == 0 { 256 } else { entry[0] as u32 };
+  let entry = entries
+    .iter()
+    .filter(|entry| size(entry) >= target)
+    .min_by_key(|entry| size(entry))
+    .or_else(|| entries.iter().max_by_key(|entry| size(entry)))
+    .ok_or_else(|| invalid("empty ico file"))?;
+
+  let len = u32::from_le_bytes(entry[8..12].try_into().unwrap()) as usize;
+  let offset = u32::from_le_bytes(entry[12..16].try_into().unwrap()) as usize;
+  let data = offset
+    .checked_add(len)
+    .and_then(|end| bytes.get(offset..end))
+    .ok_or_else(|| invalid("truncated ico image"))?;
+
+  let mut ico = Vec::with_capacity(HEADER_SIZE + ENTRY_SIZE + data.len());
+  ico.extend_from_slice(&[0, 0, 1, 0, 1, 0]);
+  ico.extend_from_slice(&entry[..12]);
+  ico.extend_from_slice(&((HEADER_SIZE + ENTRY_SIZE) as u32).to_le_bytes());
+  ico.extend_from_slice(data);
+  Ok(ico)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::best_entry;
+
+  /// Builds an ico directory with fake image data holding the entry size.
+  fn ico(sizes: &[u8]) -> Vec<u8> {
+    let mut bytes = vec![0, 0, 1, 0, sizes.len() as u8, 0];
+    let data_start = 6 + sizes.len() * 16;
+    for (i, size) in sizes.iter().enumerate() {
+      bytes.extend_from_slice(&[*size, *size, 0, 0, 1, 0, 32, 0]);
+      bytes.extend_from_slice(&1u32.to_le_bytes());
+      bytes.extend_from_slice(&((data_start + i) as u32).to_le_bytes());
+    }
+    bytes.extend_from_slice(sizes);
+    bytes
+  }
+
+  fn selected(bytes: &[u8], target: u32) -> u8 {
+    let entry = best_entry(bytes, target).unwrap();
+    assert_eq!(&entry[..6], &[0, 0, 1, 0, 1, 0]);
+    assert_eq!(entry.len(), 6 + 16 + 1);
+    entry[22]
+  }
+
+  #[test]
+  fn picks_smallest_entry_fitting_target() {
+    let bytes = ico(&[16, 32, 0, 48]);
+    assert_eq!(selected(&bytes, 16), 16);
+    assert_eq!(selected(&bytes, 24), 32);
+    assert_eq!(selected(&bytes, 40), 48);
+    assert_eq!(selected(&bytes, 64), 0);
+    assert_eq!(selected(&bytes, 512), 0);
+
+    assert!(best_entry(&ico(&[]), 16).is_err());
+    assert!(best_entry(b"\x89PNG", 16).is_err());
+    assert!(best_entry(&bytes[..30], 16).is_err());
+  }
+}
@@ -4,8 +4,16 @@
 
 //! Image types used by this crate and also referenced by the JavaScript API layer.
 
+#[cfg(feature = "image-ico")]
+mod ico;
+#[cfg(feature = "image")]
+mod ops;
 pub(crate) mod plugin;
 
+#[cfg(feature = "image")]
+#[cfg_attr(docsrs, doc(cfg(feature = "image")))]
+pub use ops::ResizeFilter;
+
 use std::borrow::Cow;
 use std::sync::Arc;
 
@@ -32,6 +40,25 @@ impl Image<'static> {
       height,
     }
   }
+
+  /// Creates a new Image using RGBA data, in row-major order from top to bottom, and with specified width and height.
+  ///
+  /// Unlike [`Self::new_owned`], fails if the data is not `width * height * 4` bytes long.
+  pub fn try_new_owned(rgba: Vec<u8>, width: u32, height: u32) -> crate::Result<Self> {
+    let expected = (width as usize)
+      .checked_mul(height as usize)
+      .and_then(|pixels| pixels.checked_mul(4));
+    if expected != Some(rgba.len()) {
+      return Err(crate::Error::InvalidIcon(std::io::Error::new(
+        std::io::ErrorKind::InvalidInput,
+        format!(
+          "{} bytes of RGBA data do not match the {width}x{height} image size",
+          rgba.len()
+        ),
+      )));
+    }
+    Ok(Self::new_owned(rgba, width, height))
+  }
 }
 
 impl<'a> Image<'a> {
@@ -44,36 +71,114 @@ impl<'a> Image<'a> {
     }
   }
 
-  /// Creates a new image using the provided bytes.
+  /// Creates a new image using the provided bytes, inferring the file format.
   ///
-  /// Only `ico` and `png` are supported (based on activated feature flag).
-  #[cfg(any(feature = "image-ico", feature = "image-png"))]
-  #[cfg_attr(docsrs, doc(cfg(any(feature = "image-ico", feature = "image-png"))))]
+  /// Only `ico`, `png`, `jpeg`, `webp`, `bmp` and `svg` are supported (based on activated feature flag).
+  /// SVG images are rendered at their intrinsic size, see [`Self::from_svg`] to choose it.
+  #[cfg(feature = "image")]
+  #[cfg_attr(
+    docsrs,
+    doc(cfg(any(
+      feature = "image-ico",
+      feature = "image-png",
+      feature = "image-jpeg",
+      feature = "image-webp",
+      feature = "image-bmp",
+      feature = "image-svg"
+    )))
+  )]
   pub fn from_bytes(bytes: &[u8]) -> crate::Result<Self> {
-    use image::GenericImageView;
+    #[cfg(feature = "image-svg")]
+    if is_svg(bytes) {
+      return Self::from_svg(bytes, None);
+    }
 
     let img = image::load_from_memory(bytes)?;
-    let pixels = img
-      .pixels()
-      .flat_map(|(_, _, pixel)| pixel.0)
-      .collect::<Vec<_>>();
-    Ok(Self {
-      rgba: Cow::Owned(pixels),
-      width: img.width(),
-      height: img.height(),
-    })
+    Ok(Self::from_dynamic_image(img))
   }
 
   /// Creates a new image using the provided path.
   ///
-  /// Only `ico` and `png` are supported (based on activated feature flag).
-  #[cfg(any(feature = "image-ico", feature = "image-png"))]
-  #[cfg_attr(docsrs, doc(cfg(any(feature = "image-ico", feature = "image-png"))))]
+  /// Only `ico`, `png`, `jpeg`, `webp`, `bmp` and `svg` are supported (based on activated feature flag).
+  #[cfg(feature = "image")]
+  #[cfg_attr(
+    docsrs,
+    doc(cfg(any(
+      feature = "image-ico",
+      feature = "image-png",
+      feature = "image-jpeg",
+      feature = "image-webp",
+      feature = "image-bmp",
+      feature = "image-svg"
+    )))
+  )]
   pub fn from_path<P: AsRef<std::path::Path>>(path: P) -> crate::Result<Self> {
     let bytes = std::fs::read(path)?;
     Self::from_bytes(&bytes)
   }
 
+  /// Creates a new image from the entry of a multi-resolution `ico` file that best fits
+  /// a logical size at the given scale factor.
+  ///
+  /// Picks the smallest entry at least `size * scale_factor` pixels wide, or the largest one if none is.
+  #[cfg(feature = "image-ico")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "image-ico")))]
+  pub fn from_ico_for_scale_factor(
+    bytes: &[u8],
+    size: u32,
+    scale_factor: f64,
+  ) -> crate::Result<Self> {
+    let target = (size as f64 * scale_factor).ceil() as u32;
+    let entry = ico::best_entry(bytes, target)?;
+    let img = image::load_from_memory_with_format(&entry, image::ImageFormat::Ico)?;
+    Ok(Self::from_dynamic_image(img))
+  }
+
+  /// Renders an SVG image.
+  ///
+  /// The image is scaled to fit the given size, or rendered at its intrinsic size if `None`.
+  #[cfg(feature = "image-svg")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "image-svg")))]
+  pub fn from_svg(bytes: &[u8], size: Option<(u32, u32)>) -> crate::Result<Self> {
+    use resvg::{tiny_skia, usvg};
+
+    let tree = usvg::Tree::from_data(bytes, &usvg::Options::default())?;
+    let intrinsic = tree.size();
+    let (width, height) = size.unwrap_or((
+      intrinsic.width().ceil() as u32,
+      intrinsic.height().ceil() as u32,
+    ));
+    let mut pixmap = tiny_skia::Pixmap::new(width, height).ok_or_else(|| {
+      crate::Error::InvalidIcon(std::io::Error::new(
+        std::io::ErrorKind::InvalidInput,
+        format!("invalid SVG size {width}x{height}"),
+      ))
+    })?;
+    let scale = (width as f32 / intrinsic.width()).min(height as f32 / intrinsic.height());
+    resvg::render(
+      &tree,
+      tiny_skia::Transform::from_scale(scale, scale),
+      &mut pixmap.as_mut(),
+    );
+
+    // the pixmap is premultiplied
+    let rgba = pixmap
+      .pixels()
+      .iter()
+      .flat_map(|pixel| {
+        let color = pixel.demultiply();
+        [color.red(), color.green(), color.blue(), color.alpha()]
+      })
+      .collect();
+    Ok(Image::new_owned(rgba, width, height))
+  }
+
+  #[cfg(feature = "image")]
+  fn from_dynamic_image(img: image::DynamicImage) -> Image<'static> {
+    let (width, height) = (img.width(), img.height());
+    Image::new_owned(img.into_rgba8().into_raw(), width, height)
+  }
+
   /// Returns the RGBA data for this image, in row-major order from top to bottom.
   pub fn rgba(&'a self) -> &'a [u8] {
     &self.rgba
@@ -173,10 +278,10 @@ impl JsImage {
   pub fn into_img(self, resources_table: &ResourceTable) -> crate::Result<Arc<Image<'_>>> {
     match self {
       Self::Resource(rid) => resources_table.get::<Image<'static>>(rid),
-      #[cfg(any(feature = "image-ico", feature = "image-png"))]
+      #[cfg(feature = "image")]
       Self::Path(path) => Image::from_path(path).map(Arc::new).map_err(Into::into),
 
-      #[cfg(any(feature = "image-ico", feature = "image-png"))]
+      #[cfg(feature = "image")]
       Self::Bytes(bytes) => Image::from_bytes(&bytes).map(Arc::new).map_err(Into::into),
 
       Self::Rgba {
@@ -185,7 +290,7 @@ impl JsImage {
         height,
       } => Ok(Arc::new(Image::new_owned(rgba, width, height))),
 
-      #[cfg(not(any(feature = "image-ico", feature = "image-png")))]
+      #[cfg(not(feature = "image"))]
       _ => Err(
         std::io::Error::new(
           std::io::ErrorKind::InvalidInput,
@@ -203,3 +308,13 @@ impl JsImage {
     }
   }
 }
+
+/// Whether the bytes look like an SVG document rather than a raster image.
+#[cfg(feature = "image-svg")]
+fn is_svg(bytes: &[u8]) -> bool {
+  let start = &bytes[..bytes.len().min(1024)];
+  let text = String::from_utf8_lossy(start);
+  let text = text.trim_start_matches('\u{feff}').trim_start();
+  (text.starts_with("<?xml") || text.starts_with("<svg") || text.starts_with("<!--"))
+    && text.contains("<svg")
+}
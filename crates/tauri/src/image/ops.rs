@@ -0,0 +1,159 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use image::{imageops, RgbaImage};
+use serde::Deserialize;
+
+use super::Image;
+
+/// The largest number of pixels of a resized image, 8192x8192 or 256MiB of RGBA data.
+const MAX_RESIZE_PIXELS: u64 = 8192 * 8192;
+
+/// The filter used to sample pixels when resizing an image.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ResizeFilter {
+  /// Nearest neighbor, keeps hard edges such as in pixel art.
+  Nearest,
+  /// Linear interpolation.
+  Triangle,
+  /// Cubic interpolation.
+  CatmullRom,
+  /// Gaussian blur, smooth but less sharp.
+  Gaussian,
+  /// Lanczos with a window of 3, the sharpest filter.
+  #[default]
+  Lanczos3,
+}
+
+impl From<ResizeFilter> for imageops::FilterType {
+  fn from(filter: ResizeFilter) -> Self {
+    match filter {
+      ResizeFilter::Nearest => Self::Nearest,
+      ResizeFilter::Triangle => Self::Triangle,
+      ResizeFilter::CatmullRom => Self::CatmullRom,
+      ResizeFilter::Gaussian => Self::Gaussian,
+      ResizeFilter::Lanczos3 => Self::Lanczos3,
+    }
+  }
+}
+
+impl Image<'_> {
+  fn to_rgba_image(&self) -> crate::Result<RgbaImage> {
+    RgbaImage::from_raw(self.width, self.height, self.rgba.to_vec()).ok_or_else(|| {
+      crate::Error::InvalidIcon(std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        format!(
+          "{} bytes of RGBA data do not match the {}x{} image size",
+          self.rgba.len(),
+          self.width,
+          self.height
+        ),
+      ))
+    })
+  }
+
+  /// Returns a copy of this image resized to the exact given size, ignoring the aspect ratio.
+  ///
+  /// Fails if the size is empty or has more pixels than 8192x8192,
+  /// or if the RGBA data does not match the size of the image.
+  #[cfg_attr(docsrs, doc(cfg(feature = "image")))]
+  pub fn resize(
+    &self,
+    width: u32,
+    height: u32,
+    filter: ResizeFilter,
+  ) -> crate::Result<Image<'static>> {
+    if width == 0 || height == 0 || u64::from(width) * u64::from(height) > MAX_RESIZE_PIXELS {
+      return Err(crate::Error::InvalidIcon(std::io::Error::new(
+        std::io::ErrorKind::InvalidInput,
+        format!("cannot resize the image to {width}x{height}, it must be at least 1x1 and at most {MAX_RESIZE_PIXELS} pixels"),
+      )));
+    }
+
+    let resized = imageops::resize(&self.to_rgba_image()?, width, height, filter.into());
+    Ok(Image::new_owned(resized.into_raw(), width, height))
+  }
+
+  /// Returns a copy of the given region of this image.
+  ///
+  /// Fails if the region is empty or is not contained in the image,
+  /// or if the RGBA data does not match the size of the image.
+  #[cfg_attr(docsrs, doc(cfg(feature = "image")))]
+  pub fn crop(&self, x: u32, y: u32, width: u32, height: u32) -> crate::Result<Image<'static>> {
+    let fits =
+      |start: u32, len: u32, max: u32| start.checked_add(len).is_some_and(|end| end <= max);
+    if width == 0 || height == 0 || !fits(x, width, self.width) || !fits(y, height, self.height) {
+      return Err(crate::Error::InvalidIcon(std::io::Error::new(
+        std::io::ErrorKind::InvalidInput,
+        format!(
+          "crop region {width}x{height} at ({x}, {y}) is outside of the {}x{} image",
+          self.width, self.height
+        ),
+      )));
+    }
+
+    let cropped = imageops::crop_imm(&self.to_rgba_image()?, x, y, width, height).to_image();
+    Ok(Image::new_owned(cropped.into_raw(), width, height))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{Image, ResizeFilter};
+
+  fn checkerboard() -> Image<'static> {
+    let rgba = (0..4 * 4)
+      .flat_map(|i| {
+        let (x, y) = (i % 4, i / 4);
+        if (x + y) % 2 == 0 {
+          [0xff, 0xff, 0xff, 0xff]
+        } else {
+          [0, 0, 0, 0xff]
+        }
+      })
+      .collect();
+    Image::new_owned(rgba, 4, 4)
+  }
+
+  #[test]
+  fn resize_and_crop() {
+    let image = checkerboard();
+
+    let resized = image.resize(8, 8, ResizeFilter::Nearest).unwrap();
+    assert_eq!((resized.width(), resized.height()), (8, 8));
+    assert_eq!(resized.rgba().len(), 8 * 8 * 4);
+    assert_eq!(
+      &resized.rgba()[..8],
+      &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]
+    );
+
+    let cropped = image.crop(1, 2, 2, 1).unwrap();
+    assert_eq!((cropped.width(), cropped.height()), (2, 1));
+    assert_eq!(cropped.rgba(), &[0, 0, 0, 0xff, 0xff, 0xff, 0xff, 0xff]);
+
+    assert!(image.resize(0, 8, ResizeFilter::Nearest).is_err());
+    assert!(image.resize(8, 0, ResizeFilter::Nearest).is_err());
+    assert!(image
+      .resize(u32::MAX, u32::MAX, ResizeFilter::Nearest)
+      .is_err());
+    assert!(image.resize(8193, 8192, ResizeFilter::Nearest).is_err());
+
+    assert!(image.crop(3, 0, 2, 1).is_err());
+    assert!(image.crop(0, 0, 0, 1).is_err());
+    assert!(image.crop(u32::MAX, 0, 2, 1).is_err());
+  }
+
+  #[test]
+  fn rejects_mismatched_rgba_data() {
+    assert!(Image::try_new_owned(vec![0; 4 * 4 * 4], 4, 4).is_ok());
+    assert!(Image::try_new_owned(vec![0; 4 * 4 * 4 - 1], 4, 4).is_err());
+    assert!(Image::try_new_owned(vec![0; 4], u32::MAX, u32::MAX).is_err());
+
+    // images built with the unchecked constructors fail instead of panicking
+    let image = Image::new_owned(vec![0; 3], 4, 4);
+    assert!(image.resize(2, 2, ResizeFilter::Nearest).is_err());
+    assert!(image.crop(0, 0, 1, 1).is_err());
+  }
+}
@@ -15,13 +15,13 @@ fn new<R: Runtime>(
   width: u32,
   height: u32,
 ) -> crate::Result<ResourceId> {
-  let image = Image::new_owned(rgba, width, height);
+  let image = Image::try_new_owned(rgba, width, height)?;
   let mut resources_table = webview.resources_table();
   let rid = resources_table.add(image);
   Ok(rid)
 }
 
-#[cfg(any(feature = "image-ico", feature = "image-png"))]
+#[cfg(feature = "image")]
 #[command(root = "crate")]
 fn from_bytes<R: Runtime>(webview: Webview<R>, bytes: Vec<u8>) -> crate::Result<ResourceId> {
   let image = Image::from_bytes(&bytes)?.to_owned();
@@ -30,13 +30,13 @@ fn from_bytes<R: Runtime>(webview: Webview<R>, bytes: Vec<u8>) -> crate::Result<
   Ok(rid)
 }
 
-#[cfg(not(any(feature = "image-ico", feature = "image-png")))]
+#[cfg(not(feature = "image"))]
 #[command(root = "crate")]
 fn from_bytes() -> std::result::Result<(), &'static str> {
-  Err("from_bytes is only supported if the `image-ico` or `image-png` Cargo features are enabled")
+  Err("from_bytes is only supported if one of the `image-*` Cargo features is enabled")
 }
 
-#[cfg(any(feature = "image-ico", feature = "image-png"))]
+#[cfg(feature = "image")]
 #[command(root = "crate")]
 fn from_path<R: Runtime>(
   webview: Webview<R>,
@@ -48,10 +48,30 @@ fn from_path<R: Runtime>(
   Ok(rid)
 }
 
-#[cfg(not(any(feature = "image-ico", feature = "image-png")))]
+#[cfg(not(feature = "image"))]
 #[command(root = "crate")]
 fn from_path() -> std::result::Result<(), &'static str> {
-  Err("from_path is only supported if the `image-ico` or `image-png` Cargo features are enabled")
+  Err("from_path is only supported if one of the `image-*` Cargo features is enabled")
+}
+
+#[cfg(feature = "image-ico")]
+#[command(root = "crate")]
+fn from_ico_for_scale_factor<R: Runtime>(
+  webview: Webview<R>,
+  bytes: Vec<u8>,
+  size: u32,
+  scale_factor: f64,
+) -> crate::Result<ResourceId> {
+  let image = Image::from_ico_for_scale_factor(&bytes, size, scale_factor)?;
+  let mut resources_table = webview.resources_table();
+  let rid = resources_table.add(image);
+  Ok(rid)
+}
+
+#[cfg(not(feature = "image-ico"))]
+#[command(root = "crate")]
+fn from_ico_for_scale_factor() -> std::result::Result<(), &'static str> {
+  Err("from_ico_for_scale_factor is only supported if the `image-ico` Cargo feature is enabled")
 }
 
 #[command(root = "crate")]
@@ -77,11 +97,61 @@ fn size<R: Runtime>(webview: Webview<R>, rid: ResourceId) -> crate::Result<Size>
   })
 }
 
+#[cfg(feature = "image")]
+#[command(root = "crate")]
+fn resize<R: Runtime>(
+  webview: Webview<R>,
+  rid: ResourceId,
+  width: u32,
+  height: u32,
+  filter: Option<super::ResizeFilter>,
+) -> crate::Result<ResourceId> {
+  let mut resources_table = webview.resources_table();
+  let image = resources_table.get::<Image<'_>>(rid)?;
+  let resized = image.resize(width, height, filter.unwrap_or_default())?;
+  Ok(resources_table.add(resized))
+}
+
+#[cfg(not(feature = "image"))]
+#[command(root = "crate")]
+fn resize() -> std::result::Result<(), &'static str> {
+  Err("resize is only supported if one of the `image-*` Cargo features is enabled")
+}
+
+#[cfg(feature = "image")]
+#[command(root = "crate")]
+fn crop<R: Runtime>(
+  webview: Webview<R>,
+  rid: ResourceId,
+  x: u32,
+  y: u32,
+  width: u32,
+  height: u32,
+) -> crate::Result<ResourceId> {
+  let mut resources_table = webview.resources_table();
+  let image = resources_table.get::<Image<'_>>(rid)?;
+  let cropped = image.crop(x, y, width, height)?;
+  Ok(resources_table.add(cropped))
+}
+
+#[cfg(not(feature = "image"))]
+#[command(root = "crate")]
+fn crop() -> std::result::Result<(), &'static str> {
+  Err("crop is only supported if one of the `image-*` Cargo features is enabled")
+}
+
 /// Initializes the plugin.
 pub fn init<R: Runtime>() -> TauriPlugin<R> {
   Builder::new("image")
     .invoke_handler(crate::generate_handler![
-      new, from_bytes, from_path, rgba, size
+      new,
+      from_bytes,
+      from_path,
+      from_ico_for_scale_factor,
+      rgba,
+      size,
+      resize,
+      crop
     ])
     .build()
 }
@@ -0,0 +1,665 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Coordination between multiple instances of the app.
+//!
+//! The first instance of the app listens on a local socket named after the bundle identifier.
+//! The other instances connect to it with [`InstanceClient`] to forward their command line arguments
+//! or send arbitrary messages, received as [`RunEvent::SecondInstance`](crate::RunEvent::SecondInstance)
+//! and [`RunEvent::InstanceMessage`](crate::RunEvent::InstanceMessage).
+//! Only the processes of the same user can exchange messages.
+//!
+//! The running instance reads the connections one at a time, closing the ones
+//! sending a message larger than 1 MiB or idle for more than 5 seconds.
+//!
+//! See [`Builder::single_instance`](crate::Builder::single_instance) to enable it.
+//!
+//! ## Platform-specific
+//!
+//! - **Linux / macOS:** Unix domain socket in [`BaseDirectory::Runtime`](crate::path::BaseDirectory::Runtime),
+//!   or in a `/tmp/tauri-<uid>` directory only accessible to the user when there is none.
+//!   An exclusive lock on a file next to the socket is held while the instance runs,
+//!   and the user of the peer process is checked on both ends of the connection.
+//! - **Windows:** Named pipe only writable by the user, whose server process is checked to belong to the same user.
+//!   Idle connections are not closed.
+
+use std::{
+  io::{BufRead, BufReader, Read},
+  path::PathBuf,
+  time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+/// The maximum length of a message, the connection is closed when it is exceeded.
+const MAX_MESSAGE_LEN: u64 = 1024 * 1024;
+/// How long the running instance waits for data on a connection before closing it.
+#[cfg_attr(windows, allow(dead_code))]
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+/// How long another instance waits for the running instance to accept connections.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// A message sent by another instance of the app.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum InstanceMessage {
+  /// Another instance of the app was launched.
+  Launched {
+    /// The command line arguments of the instance, including the executable.
+    args: Vec<String>,
+    /// The working directory of the instance.
+    cwd: PathBuf,
+  },
+  /// An arbitrary message.
+  Custom {
+    /// The message payload.
+    payload: serde_json::Value,
+  },
+}
+
+impl InstanceMessage {
+  /// The message sent by the current process when it is launched as a second instance.
+  pub fn launched() -> Self {
+    Self::Launched {
+      args: std::env::args_os()
+        .map(|arg| arg.to_string_lossy().into_owned())
+        .collect(),
+      cwd: std::env::current_dir().unwrap_or_default(),
+    }
+  }
+}
+
+/// Reads the newline-delimited messages of a connection until it is closed, fails or exceeds [`MAX_MESSAGE_LEN`].
+fn read_messages<R: Read>(reader: R, handler: &dyn Fn(InstanceMessage)) {
+  let mut reader = BufReader::new(reader);
+  loop {
+    let mut line = Vec::new();
+    match (&mut reader)
+      .take(MAX_MESSAGE_LEN + 1)
+      .read_until(b'\n', &mut line)
+    {
+      Ok(0) | Err(_) => break,
+      Ok(len) if len as u64 > MAX_MESSAGE_LEN => {
+        log::warn!("message from another instance exceeds {MAX_MESSAGE_LEN} bytes");
+        break;
+      }
+      Ok(_) => match serde_json::from_slice(&line) {
+        Ok(message) => handler(message),
+        Err(e) => log::warn!("invalid message from another instance: {e}"),
+      },
+    }
+  }
+}
+
+/// A connection to the running instance of the app.
+#[derive(Debug)]
+pub struct InstanceClient {
+  #[cfg(unix)]
+  stream: std::os::unix::net::UnixStream,
+  #[cfg(windows)]
+  pipe: std::fs::File,
+}
+
+impl InstanceClient {
+  /// Connects to the running instance of the app with the given bundle identifier.
+  ///
+  /// Returns `None` if there is no running instance.
+  pub fn connect(identifier: &str) -> crate::Result<Option<Self>> {
+    #[cfg(unix)]
+    return Self::connect_to(&unix::socket_path(&unix::socket_dir()?, identifier));
+    #[cfg(windows)]
+    return Self::connect_to(&windows::pipe_name(identifier));
+  }
+
+  #[cfg(unix)]
+  fn connect_to(path: &std::path::Path) -> crate::Result<Option<Self>> {
+    use std::io::ErrorKind;
+    let stream = match std::os::unix::net::UnixStream::connect(path) {
+      Ok(stream) => stream,
+      Err(e) if matches!(e.kind(), ErrorKind::NotFound | ErrorKind::ConnectionRefused) => {
+        return Ok(None)
+      }
+      Err(e) => return Err(e.into()),
+    };
+    if unix::peer_uid(&stream)? != unix::current_uid() {
+      return Err(unix::other_user_error(path).into());
+    }
+    Ok(Some(Self { stream }))
+  }
+
+  #[cfg(windows)]
+  fn connect_to(name: &str) -> crate::Result<Option<Self>> {
+    Ok(windows::connect(name)?.map(|pipe| Self { pipe }))
+  }
+
+  /// Sends a message to the running instance.
+  pub fn send(&mut self, message: &InstanceMessage) -> crate::Result<()> {
+    use std::io::Write;
+    let mut line = serde_json::to_vec(message)?;
+    line.push(b'\n');
+    #[cfg(unix)]
+    self.stream.write_all(&line)?;
+    #[cfg(windows)]
+    self.pipe.write_all(&line)?;
+    Ok(())
+  }
+
+  /// Sends an arbitrary message to the running instance,
+  /// received as [`RunEvent::InstanceMessage`](crate::RunEvent::InstanceMessage).
+  pub fn send_message<T: Serialize>(&mut self, payload: &T) -> crate::Result<()> {
+    self.send(&InstanceMessage::Custom {
+      payload: serde_json::to_value(payload)?,
+    })
+  }
+}
+
+/// The outcome of trying to become the running instance.
+pub(crate) enum Instance {
+  /// This process is the running instance.
+  Primary(InstanceGuard),
+  /// Another instance is running, with a connection to it unless it stopped accepting them.
+  Secondary(Option<InstanceClient>),
+}
+
+/// Holds the socket and lock file, or the named pipe, of the running instance.
+pub(crate) struct InstanceGuard {
+  #[cfg(unix)]
+  path: PathBuf,
+  #[cfg(unix)]
+  listener: std::os::unix::net::UnixListener,
+  /// Released after the socket is removed, as the fields are dropped after [`Drop::drop`].
+  #[cfg(unix)]
+  _lock: std::fs::File,
+  #[cfg(windows)]
+  name: String,
+  /// The first instance of the pipe, moved to the listener thread which always keeps one open.
+  #[cfg(windows)]
+  pipe: std::sync::Mutex<Option<std::os::windows::io::OwnedHandle>>,
+}
+
+impl InstanceGuard {
+  /// Calls the handler with the messages of the other instances on a background thread.
+  pub(crate) fn listen<F: Fn(InstanceMessage) + Send + 'static>(
+    &self,
+    handler: F,
+  ) -> crate::Result<()> {
+    #[cfg(unix)]
+    {
+      let listener = self.listener.try_clone()?;
+      std::thread::spawn(move || {
+        let uid = unix::current_uid();
+        for stream in listener.incoming() {
+          let Ok(stream) = stream else {
+            continue;
+          };
+          if !matches!(unix::peer_uid(&stream), Ok(peer) if peer == uid) {
+            log::warn!("rejected a connection from another user");
+            continue;
+          }
+          if stream.set_read_timeout(Some(READ_TIMEOUT)).is_ok() {
+            read_messages(stream, &handler);
+          }
+        }
+      });
+    }
+    #[cfg(windows)]
+    {
+      let Some(pipe) = self.pipe.lock().unwrap().take() else {
+        return Ok(());
+      };
+      let name = self.name.clone();
+      std::thread::spawn(move || {
+        if let Err(e) = windows::serve(&name, pipe, &handler) {
+          log::error!("failed to listen for other instances: {e}");
+        }
+      });
+    }
+    Ok(())
+  }
+}
+
+#[cfg(unix)]
+impl Drop for InstanceGuard {
+  fn drop(&mut self) {
+    let _ = std::fs::remove_file(&self.path);
+  }
+}
+
+/// Tries to become the running instance of the app with the given bundle identifier.
+pub(crate) fn acquire(identifier: &str) -> crate::Result<Instance> {
+  #[cfg(unix)]
+  return unix::acquire(&unix::socket_dir()?, identifier);
+  #[cfg(windows)]
+  return windows::acquire(identifier);
+}
+
+#[cfg(unix)]
+mod unix {
+  use std::{
+    fs::{self, DirBuilder, OpenOptions},
+    io::{self, ErrorKind},
+    os::unix::{
+      fs::{DirBuilderExt, MetadataExt, OpenOptionsExt},
+      io::AsRawFd,
+      net::{UnixListener, UnixStream},
+    },
+    path::{Path, PathBuf},
+  };
+
+  use super::{Instance, InstanceClient, InstanceGuard, CONNECT_TIMEOUT};
+
+  /// The size of `sockaddr_un::sun_path`, including the NUL terminator.
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  const SUN_PATH_LEN: usize = 108;
+  #[cfg(not(any(target_os = "linux", target_os = "android")))]
+  const SUN_PATH_LEN: usize = 104;
+
+  pub(super) fn current_uid() -> u32 {
+    // SAFETY: getuid cannot fail
+    unsafe { libc::getuid() }
+  }
+
+  /// The directory of the socket, only accessible to the current user.
+  pub(super) fn socket_dir() -> io::Result<PathBuf> {
+    let uid = current_uid();
+    let dir = match dirs::runtime_dir() {
+      Some(dir) => dir,
+      None => {
+        let dir = Path::new("/tmp").join(format!("tauri-{uid}"));
+        match DirBuilder::new().mode(0o700).create(&dir) {
+          Err(e) if e.kind() != ErrorKind::AlreadyExists => return Err(e),
+          _ => dir,
+        }
+      }
+    };
+    check_private_dir(&dir, uid)?;
+    Ok(dir)
+  }
+
+  /// Ensures that the directory is owned by the user and not accessible to the others.
+  fn check_private_dir(dir: &Path, uid: u32) -> io::Result<()> {
+    let metadata = fs::symlink_metadata(dir)?;
+    if !metadata.is_dir() || metadata.uid() != uid || metadata.mode() & 0o077 != 0 {
+      return Err(io::Error::new(
+        ErrorKind::PermissionDenied,
+        format!(
+          "{} must be a directory only accessible to the current user",
+          dir.display()
+        ),
+      ));
+    }
+    Ok(())
+  }
+
+  /// The socket path, using a hash of the identifier when it would not fit in `sun_path`.
+  pub(super) fn socket_path(dir: &Path, identifier: &str) -> PathBuf {
+    let path = dir.join(format!("{identifier}.sock"));
+    if path.as_os_str().len() < SUN_PATH_LEN {
+      path
+    } else {
+      // FNV-1a, stable across builds unlike the std hasher
+      let hash = identifier
+        .bytes()
+        .fold(0xcbf29ce484222325u64, |hash, byte| {
+          (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+      dir.join(format!("{hash:016x}.sock"))
+    }
+  }
+
+  /// The user of the process on the other end of the connection.
+  pub(super) fn peer_uid(stream: &UnixStream) -> io::Result<u32> {
+    let fd = stream.as_raw_fd();
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+      // SAFETY: the credentials are written to a buffer of the given length
+      let mut cred: libc::ucred = unsafe { std::mem::zeroed() };
+      let mut len = std::mem::size_of::<libc::ucred>() as libc::socklen_t;
+      let result = unsafe {
+        libc::getsockopt(
+          fd,
+          libc::SOL_SOCKET,
+          libc::SO_PEERCRED,
+          &mut cred as *mut libc::ucred as *mut libc::c_void,
+          &mut len,
+        )
+      };
+      if result != 0 {
+        return Err(io::Error::last_os_error());
+      }
+      Ok(cred.uid)
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    {
+      let mut uid = 0;
+      let mut gid = 0;
+      // SAFETY: the ids are written to valid pointers
+      if unsafe { libc::getpeereid(fd, &mut uid, &mut gid) } != 0 {
+        return Err(io::Error::last_os_error());
+      }
+      Ok(uid)
+    }
+  }
+
+  pub(super) fn other_user_error(path: &Path) -> io::Error {
+    io::Error::new(
+      ErrorKind::PermissionDenied,
+      format!("{} is served by another user", path.display()),
+    )
+  }
+
+  pub(super) fn acquire(dir: &Path, identifier: &str) -> crate::Result<Instance> {
+    let lock = OpenOptions::new()
+      .write(true)
+      .create(true)
+      .truncate(false)
+      .mode(0o600)
+      .open(dir.join(format!("{identifier}.lock")))?;
+    let path = socket_path(dir, identifier);
+
+    // SAFETY: the file descriptor is valid while `lock` is alive
+    if unsafe { libc::flock(lock.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } == 0 {
+      // the lock is held for the lifetime of the running instance,
+      // so a socket left behind can only come from an instance that did not exit cleanly
+      match fs::remove_file(&path) {
+        Err(e) if e.kind() != ErrorKind::NotFound => return Err(e.into()),
+        _ => {}
+      }
+      let listener = UnixListener::bind(&path)?;
+      return Ok(Instance::Primary(InstanceGuard {
+        path,
+        listener,
+        _lock: lock,
+      }));
+    }
+
+    let error = io::Error::last_os_error();
+    if error.kind() != ErrorKind::WouldBlock {
+      return Err(error.into());
+    }
+
+    // the running instance may not have bound its socket yet
+    let started = std::time::Instant::now();
+    loop {
+      if let Some(client) = InstanceClient::connect_to(&path)? {
+        return Ok(Instance::Secondary(Some(client)));
+      }
+      if started.elapsed() > CONNECT_TIMEOUT {
+        return Ok(Instance::Secondary(None));
+      }
+      std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+  }
+}
+
+#[cfg(windows)]
+mod windows {
+  use std::{
+    fs::{File, OpenOptions},
+    io::{self, ErrorKind},
+    os::windows::io::{AsRawHandle, FromRawHandle, OwnedHandle},
+  };
+
+  use windows::{
+    core::HSTRING,
+    Win32::{
+      Foundation::{
+        ERROR_ACCESS_DENIED, ERROR_PIPE_BUSY, ERROR_PIPE_CONNECTED, HANDLE, INVALID_HANDLE_VALUE,
+      },
+      Security::{GetLengthSid, GetTokenInformation, TokenUser, TOKEN_QUERY, TOKEN_USER},
+      Storage::FileSystem::{FILE_FLAG_FIRST_PIPE_INSTANCE, PIPE_ACCESS_INBOUND},
+      System::{
+        Pipes::{
+          ConnectNamedPipe, CreateNamedPipeW, GetNamedPipeServerProcessId, WaitNamedPipeW,
+          PIPE_READMODE_BYTE, PIPE_REJECT_REMOTE_CLIENTS, PIPE_TYPE_BYTE, PIPE_UNLIMITED_INSTANCES,
+          PIPE_WAIT,
+        },
+        Threading::{
+          GetCurrentProcess, OpenProcess, OpenProcessToken, PROCESS_QUERY_LIMITED_INFORMATION,
+        },
+      },
+    },
+  };
+
+  use super::{read_messages, Instance, InstanceClient, InstanceGuard, InstanceMessage};
+
+  pub(super) fn pipe_name(identifier: &str) -> String {
+    format!(
+      r"\\.\pipe\{identifier}-{}",
+      std::env::var("USERNAME").unwrap_or_default()
+    )
+  }
+
+  /// Creates an instance of the pipe, failing with [`ERROR_ACCESS_DENIED`] if `first` is set and the pipe exists.
+  ///
+  /// The default security descriptor of the pipe only grants write access to the current user.
+  fn create_pipe(name: &str, first: bool) -> io::Result<OwnedHandle> {
+    let mut open_mode = PIPE_ACCESS_INBOUND;
+    if first {
+      open_mode |= FILE_FLAG_FIRST_PIPE_INSTANCE;
+    }
+    // SAFETY: the name is a valid null-terminated string
+    let handle = unsafe {
+      CreateNamedPipeW(
+        &HSTRING::from(name),
+        open_mode,
+        PIPE_TYPE_BYTE | PIPE_READMODE_BYTE | PIPE_WAIT | PIPE_REJECT_REMOTE_CLIENTS,
+        PIPE_UNLIMITED_INSTANCES,
+        0,
+        super::MAX_MESSAGE_LEN as u32,
+        0,
+        None,
+      )
+    };
+    if handle == INVALID_HANDLE_VALUE {
+      return Err(io::Error::last_os_error());
+    }
+    // SAFETY: the handle was just created and is owned by nothing else
+    Ok(unsafe { OwnedHandle::from_raw_handle(handle.0) })
+  }
+
+  pub(super) fn acquire(identifier: &str) -> crate::Result<Instance> {
+    let name = pipe_name(identifier);
+    match create_pipe(&name, true) {
+      Ok(pipe) => Ok(Instance::Primary(InstanceGuard {
+        name,
+        pipe: std::sync::Mutex::new(Some(pipe)),
+      })),
+      Err(e) if e.raw_os_error() == Some(ERROR_ACCESS_DENIED.0 as i32) => Ok(Instance::Secondary(
+        connect(&name)?.map(|pipe| InstanceClient { pipe }),
+      )),
+      Err(e) => Err(e.into()),
+    }
+  }
+
+  /// Accepts the connections one at a time, always keeping an instance of the pipe open so its name stays reserved.
+  pub(super) fn serve(
+    name: &str,
+    mut pipe: OwnedHandle,
+    handler: &dyn Fn(InstanceMessage),
+  ) -> io::Result<()> {
+    loop {
+      // SAFETY: the handle is a valid pipe instance
+      match unsafe { ConnectNamedPipe(HANDLE(pipe.as_raw_handle()), None) } {
+        Err(e) if e.code() != ERROR_PIPE_CONNECTED.to_hresult() => {
+          pipe = create_pipe(name, false)?;
+          continue;
+        }
+        _ => {}
+      }
+      let next = create_pipe(name, false)?;
+      read_messages(File::from(std::mem::replace(&mut pipe, next)), handler);
+    }
+  }
+
+  /// Connects to the pipe, returning `None` if it does not exist.
+  pub(super) fn connect(name: &str) -> io::Result<Option<File>> {
+    let started = std::time::Instant::now();
+    let pipe = loop {
+      match OpenOptions::new().write(true).open(name) {
+        Ok(pipe) => break pipe,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(e)
+          if e.raw_os_error() == Some(ERROR_PIPE_BUSY.0 as i32)
+            && started.elapsed() < super::CONNECT_TIMEOUT =>
+        {
+          // SAFETY: the name is a valid null-terminated string
+          let _ = unsafe { WaitNamedPipeW(&HSTRING::from(name), 100) };
+        }
+        Err(e) => return Err(e),
+      }
+    };
+
+    let mut server = 0;
+    // SAFETY: the handle is a valid pipe client
+    unsafe { GetNamedPipeServerProcessId(HANDLE(pipe.as_raw_handle()), &mut server)? };
+    // SAFETY: the process handle is closed when dropped
+    let process = unsafe {
+      OwnedHandle::from_raw_handle(OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, server)?.0)
+    };
+    // SAFETY: the current process pseudo handle does not need to be closed
+    if process_user(HANDLE(process.as_raw_handle()))?
+      != process_user(unsafe { GetCurrentProcess() })?
+    {
+      return Err(io::Error::new(
+        ErrorKind::PermissionDenied,
+        format!("{name} is served by another user"),
+      ));
+    }
+    Ok(Some(pipe))
+  }
+
+  /// The security identifier of the user running the process.
+  fn process_user(process: HANDLE) -> io::Result<Vec<u8>> {
+    // SAFETY: the token handle is closed when dropped, and the token information
+    // is written to a buffer of the requested length aligned for `TOKEN_USER`
+    unsafe {
+      let mut token = HANDLE::default();
+      OpenProcessToken(process, TOKEN_QUERY, &mut token)?;
+      let token = OwnedHandle::from_raw_handle(token.0);
+      let mut len = 0;
+      let _ = GetTokenInformation(HANDLE(token.as_raw_handle()), TokenUser, None, 0, &mut len);
+      let mut buffer = vec![0u64; (len as usize).div_ceil(8)];
+      GetTokenInformation(
+        HANDLE(token.as_raw_handle()),
+        TokenUser,
+        Some(buffer.as_mut_ptr().cast()),
+        len,
+        &mut len,
+      )?;
+      let sid = (*buffer.as_ptr().cast::<TOKEN_USER>()).User.Sid;
+      Ok(std::slice::from_raw_parts(sid.0 as *const u8, GetLengthSid(sid) as usize).to_vec())
+    }
+  }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+  use std::{
+    os::unix::{fs::PermissionsExt, net::UnixStream},
+    sync::mpsc::channel,
+    time::Duration,
+  };
+
+  use super::{read_messages, unix, Instance, InstanceMessage, MAX_MESSAGE_LEN};
+
+  fn private_dir() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::set_permissions(dir.path(), std::fs::Permissions::from_mode(0o700)).unwrap();
+    dir
+  }
+
+  #[test]
+  fn forwards_messages_to_running_instance() {
+    let dir = private_dir();
+    let path = unix::socket_path(dir.path(), "com.tauri.test");
+    // a socket left behind by a crashed instance
+    std::fs::write(&path, "").unwrap();
+
+    let Instance::Primary(guard) = unix::acquire(dir.path(), "com.tauri.test").unwrap() else {
+      panic!("expected to be the running instance");
+    };
+    let (tx, rx) = channel();
+    guard
+      .listen(move |message| tx.send(message).unwrap())
+      .unwrap();
+
+    let Instance::Secondary(Some(mut client)) =
+      unix::acquire(dir.path(), "com.tauri.test").unwrap()
+    else {
+      panic!("expected another instance to be running");
+    };
+    let launched = InstanceMessage::Launched {
+      args: vec!["app".into(), "--open".into(), "file.txt".into()],
+      cwd: "/home/user".into(),
+    };
+    client.send(&launched).unwrap();
+    client
+      .send_message(&serde_json::json!({ "action": "refresh" }))
+      .unwrap();
+
+    let timeout = Duration::from_secs(5);
+    assert_eq!(rx.recv_timeout(timeout).unwrap(), launched);
+    assert_eq!(
+      rx.recv_timeout(timeout).unwrap(),
+      InstanceMessage::Custom {
+        payload: serde_json::json!({ "action": "refresh" })
+      }
+    );
+
+    drop(guard);
+    assert!(!path.exists());
+    assert!(matches!(
+      unix::acquire(dir.path(), "com.tauri.test").unwrap(),
+      Instance::Primary(_)
+    ));
+  }
+
+  #[test]
+  fn elects_a_single_running_instance() {
+    let dir = private_dir();
+    let instances = std::thread::scope(|scope| {
+      let handles: Vec<_> = (0..8)
+        .map(|_| scope.spawn(|| unix::acquire(dir.path(), "com.tauri.race").unwrap()))
+        .collect();
+      handles
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .collect::<Vec<_>>()
+    });
+    assert_eq!(
+      instances
+        .iter()
+        .filter(|instance| matches!(instance, Instance::Primary(_)))
+        .count(),
+      1
+    );
+  }
+
+  #[test]
+  fn stops_reading_oversized_messages() {
+    let (mut writer, reader) = UnixStream::pair().unwrap();
+    let handle = std::thread::spawn(move || {
+      use std::io::Write;
+      let _ = writer.write_all(&vec![b'a'; MAX_MESSAGE_LEN as usize + 2]);
+      let _ = writer.write_all(b"\n{\"kind\":\"custom\",\"payload\":1}\n");
+    });
+    let (tx, rx) = channel();
+    read_messages(reader, &move |message| tx.send(message).unwrap());
+    assert!(rx.try_recv().is_err());
+    handle.join().unwrap();
+  }
+
+  #[test]
+  fn shortens_long_socket_paths() {
+    let dir = std::path::Path::new("/tmp/tauri-1000");
+    assert_eq!(
+      unix::socket_path(dir, "com.tauri.app"),
+      dir.join("com.tauri.app.sock")
+    );
+    let long = unix::socket_path(dir, &"com.tauri.".repeat(12));
+    assert!(long.as_os_str().len() < 104);
+    assert_eq!(long, unix::socket_path(dir, &"com.tauri.".repeat(12)));
+  }
+}
@@ -108,6 +108,11 @@ impl JavaScriptChannelId {
         }
       }
 
+      #[cfg(any(test, feature = "test"))]
+      if let Some(recorder) = webview.try_state::<crate::test::IpcRecorder>() {
+        recorder.record_channel_message(webview.label(), callback_id.0, &body);
+      }
+
       let data_id = CHANNEL_DATA_COUNTER.fetch_add(1, Ordering::Relaxed);
 
       webview
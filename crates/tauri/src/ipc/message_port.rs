@@ -0,0 +1,527 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Direct message channels between webviews.
+
+use std::{
+  collections::{BTreeMap, HashMap},
+  sync::{
+    atomic::{AtomicU32, Ordering},
+    Arc, Mutex,
+  },
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  command,
+  event::EventTarget,
+  plugin::{Builder as PluginBuilder, TauriPlugin},
+  sealed::ManagerBase,
+  Emitter, Manager, Runtime, State, Webview,
+};
+
+use super::{Channel, InvokeBody, InvokeError, InvokeResponseBody, Request};
+
+pub(crate) const MESSAGE_PORT_EVENT: &str = "tauri://message-port";
+pub(crate) const MESSAGE_PORT_HEADER_NAME: &str = "Tauri-Message-Port";
+pub(crate) const MESSAGE_PORT_SEQUENCE_HEADER_NAME: &str = "Tauri-Message-Port-Sequence";
+
+/// The maximum number of messages kept for a webview that did not connect to its port yet.
+const MAX_QUEUED_MESSAGES: usize = 1024;
+/// The maximum number of messages waiting for a missing sequence number before the channel is closed.
+const MAX_OUT_OF_ORDER_MESSAGES: usize = 64;
+
+static MESSAGE_PORT_COUNTER: AtomicU32 = AtomicU32::new(0);
+
+/// One end of a message channel between two webviews.
+///
+/// Created with [`Webview::create_message_channel`]. Each end belongs to a webview,
+/// which is the only one allowed to connect to it, post messages through it and close it.
+///
+/// On the JavaScript side, the port is available as a `WebviewMessagePort` from `@tauri-apps/api/webview`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MessagePort(u32);
+
+impl MessagePort {
+  /// The port identifier.
+  pub fn id(&self) -> u32 {
+    self.0
+  }
+
+  /// Closes both ends of the channel this port belongs to.
+  pub fn close<R: Runtime, M: Manager<R>>(&self, manager: &M) {
+    if let Some(ports) = manager.try_state::<MessagePorts>() {
+      ports.close(self.0);
+    }
+  }
+}
+
+/// Payload of the event emitted to a webview when it receives a message port.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MessagePortPayload {
+  port: MessagePort,
+  /// Label of the webview that owns the other end of the channel.
+  peer: String,
+}
+
+struct Port {
+  /// Label of the webview that owns this port.
+  owner: String,
+  peer: u32,
+  /// The channel used to deliver messages to the owner webview, set when it connects to the port.
+  receiver: Option<Channel>,
+  /// Messages received before the owner webview connected.
+  queue: Vec<InvokeResponseBody>,
+  /// Sequence number of the next message this port is expected to send.
+  next_sequence: u64,
+  /// Messages sent through this port that arrived before the previous ones.
+  out_of_order: BTreeMap<u64, InvokeResponseBody>,
+  /// Held while messages are delivered to the owner webview, so they are sent in order
+  /// without holding the registry lock.
+  delivery: Arc<Mutex<()>>,
+}
+
+impl Port {
+  fn new(owner: String, peer: u32) -> Self {
+    Self {
+      owner,
+      peer,
+      receiver: None,
+      queue: Default::default(),
+      next_sequence: 0,
+      out_of_order: Default::default(),
+      delivery: Default::default(),
+    }
+  }
+
+  /// Takes the messages sent through this port that can be delivered in sequence order.
+  ///
+  /// Fails if the message was already received, if [`MAX_OUT_OF_ORDER_MESSAGES`] messages are waiting
+  /// for a lost one or if the sequence numbers are exhausted, since the following messages cannot be ordered.
+  fn sequence(
+    &mut self,
+    port: u32,
+    sequence: u64,
+    message: InvokeResponseBody,
+  ) -> crate::Result<Vec<InvokeResponseBody>> {
+    let error = || crate::Error::MessagePortSequence(port, sequence);
+    if sequence < self.next_sequence
+      || self.out_of_order.contains_key(&sequence)
+      || (sequence != self.next_sequence && self.out_of_order.len() >= MAX_OUT_OF_ORDER_MESSAGES)
+    {
+      return Err(error());
+    }
+    self.out_of_order.insert(sequence, message);
+
+    let mut messages = Vec::new();
+    while let Some(message) = self.out_of_order.remove(&self.next_sequence) {
+      messages.push(message);
+      self.next_sequence = self.next_sequence.checked_add(1).ok_or_else(error)?;
+    }
+    Ok(messages)
+  }
+}
+
+/// The registry of open message ports.
+#[derive(Default)]
+pub(crate) struct MessagePorts(Mutex<HashMap<u32, Port>>);
+
+impl MessagePorts {
+  /// Creates a pair of linked ports owned by the given webviews.
+  pub(crate) fn link(&self, first: &str, second: &str) -> (MessagePort, MessagePort) {
+    let first_id = MESSAGE_PORT_COUNTER.fetch_add(1, Ordering::Relaxed);
+    let second_id = MESSAGE_PORT_COUNTER.fetch_add(1, Ordering::Relaxed);
+
+    let mut ports = self.0.lock().unwrap();
+    ports.insert(first_id, Port::new(first.into(), second_id));
+    ports.insert(second_id, Port::new(second.into(), first_id));
+
+    (MessagePort(first_id), MessagePort(second_id))
+  }
+
+  /// The label of the webview that owns the given port.
+  pub(crate) fn owner(&self, port: u32) -> Option<String> {
+    self.0.lock().unwrap().get(&port).map(|p| p.owner.clone())
+  }
+
+  /// Transfers the given port to another webview.
+  pub(crate) fn set_owner(&self, port: u32, owner: &str) {
+    if let Some(port) = self.0.lock().unwrap().get_mut(&port) {
+      port.owner = owner.into();
+    }
+  }
+
+  /// Sets the channel used to deliver the messages of the given port, flushing the pending ones.
+  fn connect(&self, owner: &str, port: u32, receiver: Channel) -> crate::Result<()> {
+    let mut ports = self.0.lock().unwrap();
+    let port = ports
+      .get_mut(&port)
+      .filter(|p| p.owner == owner)
+      .ok_or(crate::Error::MessagePortNotFound(port))?;
+
+    let messages = std::mem::take(&mut port.queue);
+    port.receiver.replace(receiver.clone());
+
+    // the delivery lock is taken before releasing the registry so messages posted meanwhile are sent after these
+    let delivery = port.delivery.clone();
+    let _delivery = delivery.lock().unwrap();
+    drop(ports);
+
+    for message in messages {
+      receiver.send(message)?;
+    }
+
+    Ok(())
+  }
+
+  /// Sends a message through the given port.
+  ///
+  /// When a sequence number is provided, messages are delivered to the peer in sequence order
+  /// regardless of the order they reached the Rust side, and the channel is closed if the sequence is broken.
+  fn post(
+    &self,
+    owner: &str,
+    port: u32,
+    sequence: Option<u64>,
+    message: InvokeResponseBody,
+  ) -> crate::Result<()> {
+    let mut ports = self.0.lock().unwrap();
+    let sender = ports
+      .get_mut(&port)
+      .filter(|p| p.owner == owner)
+      .ok_or(crate::Error::MessagePortNotFound(port))?;
+
+    let peer = sender.peer;
+    let messages = match sequence {
+      Some(sequence) => match sender.sequence(port, sequence, message) {
+        Ok(messages) => messages,
+        Err(e) => {
+          ports.remove(&port);
+          ports.remove(&peer);
+          return Err(e);
+        }
+      },
+      None => vec![message],
+    };
+
+    let receiver = ports
+      .get_mut(&peer)
+      .ok_or(crate::Error::MessagePortNotFound(port))?;
+
+    let Some(channel) = receiver.receiver.clone() else {
+      if receiver.queue.len() + messages.len() > MAX_QUEUED_MESSAGES {
+        return Err(crate::Error::MessagePortQueueFull(peer));
+      }
+      receiver.queue.extend(messages);
+      return Ok(());
+    };
+
+    let delivery = receiver.delivery.clone();
+    let _delivery = delivery.lock().unwrap();
+    drop(ports);
+
+    for message in messages {
+      channel.send(message)?;
+    }
+
+    Ok(())
+  }
+
+  /// Closes the given port and its peer.
+  fn close(&self, port: u32) {
+    let mut ports = self.0.lock().unwrap();
+    if let Some(port) = ports.remove(&port) {
+      ports.remove(&port.peer);
+    }
+  }
+
+  /// Closes every channel with an end owned by the given webview, called when it is destroyed.
+  pub(crate) fn close_owned_by(&self, owner: &str) {
+    let mut ports = self.0.lock().unwrap();
+    let closed = ports
+      .iter()
+      .filter(|(_, p)| p.owner == owner)
+      .flat_map(|(id, p)| [*id, p.peer])
+      .collect::<Vec<_>>();
+    for id in closed {
+      ports.remove(&id);
+    }
+  }
+}
+
+impl<R: Runtime> Webview<R> {
+  /// Creates a message channel between this webview and `other`.
+  ///
+  /// Each webview receives its end of the channel through the `tauri://message-port` event,
+  /// which can be listened to with `getCurrentWebview().onMessagePort()` from `@tauri-apps/api/webview`.
+  /// Messages posted on one end are delivered in order to the other end without going through the event system,
+  /// and binary payloads are forwarded as is.
+  ///
+  /// Returns the ports owned by this webview and by `other`, respectively.
+  pub fn create_message_channel(
+    &self,
+    other: &Webview<R>,
+  ) -> crate::Result<(MessagePort, MessagePort)> {
+    let (port, peer_port) = self
+      .state::<MessagePorts>()
+      .link(self.label(), other.label());
+
+    self.emit_to(
+      EventTarget::webview(self.label()),
+      MESSAGE_PORT_EVENT,
+      MessagePortPayload {
+        port,
+        peer: other.label().into(),
+      },
+    )?;
+    self.emit_to(
+      EventTarget::webview(other.label()),
+      MESSAGE_PORT_EVENT,
+      MessagePortPayload {
+        port: peer_port,
+        peer: self.label().into(),
+      },
+    )?;
+
+    Ok((port, peer_port))
+  }
+}
+
+#[command(root = "crate")]
+fn create<R: Runtime>(
+  webview: Webview<R>,
+  ports: State<'_, MessagePorts>,
+  target: String,
+) -> crate::Result<MessagePortPayload> {
+  let target = webview
+    .manager()
+    .get_webview(&target)
+    .ok_or(crate::Error::WebviewNotFound)?;
+
+  let (port, peer_port) = ports.link(webview.label(), target.label());
+  webview.emit_to(
+    EventTarget::webview(target.label()),
+    MESSAGE_PORT_EVENT,
+    MessagePortPayload {
+      port: peer_port,
+      peer: webview.label().into(),
+    },
+  )?;
+
+  Ok(MessagePortPayload {
+    port,
+    peer: target.label().into(),
+  })
+}
+
+#[command(root = "crate")]
+fn connect<R: Runtime>(
+  webview: Webview<R>,
+  ports: State<'_, MessagePorts>,
+  port: u32,
+  on_message: Channel,
+) -> crate::Result<()> {
+  ports.connect(webview.label(), port, on_message)
+}
+
+#[command(root = "crate")]
+fn post<R: Runtime>(
+  webview: Webview<R>,
+  request: Request<'_>,
+  ports: State<'_, MessagePorts>,
+) -> Result<(), InvokeError> {
+  let header = |name| {
+    request
+      .headers()
+      .get(name)
+      .and_then(|v| v.to_str().ok())
+      .and_then(|v| v.parse::<u64>().ok())
+  };
+  let port = header(MESSAGE_PORT_HEADER_NAME)
+    .and_then(|port| u32::try_from(port).ok())
+    .ok_or("missing message port header")?;
+  let sequence = header(MESSAGE_PORT_SEQUENCE_HEADER_NAME);
+
+  let message = match request.body() {
+    // JSON messages are wrapped in an object since the IPC only accepts objects as JSON payloads
+    InvokeBody::Json(payload) => InvokeResponseBody::Json(
+      serde_json::to_string(&payload["message"]).map_err(crate::Error::from)?,
+    ),
+    InvokeBody::Raw(bytes) => InvokeResponseBody::Raw(bytes.clone()),
+  };
+
+  ports
+    .post(webview.label(), port, sequence, message)
+    .map_err(Into::into)
+}
+
+#[command(root = "crate")]
+fn close<R: Runtime>(
+  webview: Webview<R>,
+  ports: State<'_, MessagePorts>,
+  port: u32,
+) -> crate::Result<()> {
+  let owned = ports
+    .0
+    .lock()
+    .unwrap()
+    .get(&port)
+    .is_some_and(|p| p.owner == webview.label());
+  if owned {
+    ports.close(port);
+    Ok(())
+  } else {
+    Err(crate::Error::MessagePortNotFound(port))
+  }
+}
+
+pub(crate) fn init<R: Runtime>() -> TauriPlugin<R> {
+  PluginBuilder::new("message-port")
+    .setup(|app, _api| {
+      app.manage(MessagePorts::default());
+      Ok(())
+    })
+    .invoke_handler(crate::generate_handler![create, connect, post, close])
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use std::sync::Arc;
+
+  fn receiver() -> (Channel, Arc<Mutex<Vec<String>>>) {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let received_ = received.clone();
+    let channel = Channel::new(move |body| {
+      if let InvokeResponseBody::Json(message) = body {
+        received_.lock().unwrap().push(message);
+      }
+      Ok(())
+    });
+    (channel, received)
+  }
+
+  fn message(value: &str) -> InvokeResponseBody {
+    InvokeResponseBody::Json(value.into())
+  }
+
+  #[test]
+  fn delivers_in_sequence_order() {
+    let ports = MessagePorts::default();
+    let (first, second) = ports.link("first", "second");
+
+    // messages posted before the peer connects are queued
+    ports
+      .post("first", first.id(), Some(1), message("b"))
+      .unwrap();
+    ports
+      .post("first", first.id(), Some(0), message("a"))
+      .unwrap();
+
+    let (channel, received) = receiver();
+    ports.connect("second", second.id(), channel).unwrap();
+    assert_eq!(*received.lock().unwrap(), vec!["a", "b"]);
+
+    ports
+      .post("first", first.id(), Some(3), message("d"))
+      .unwrap();
+    assert_eq!(received.lock().unwrap().len(), 2);
+    ports
+      .post("first", first.id(), Some(2), message("c"))
+      .unwrap();
+    assert_eq!(*received.lock().unwrap(), vec!["a", "b", "c", "d"]);
+  }
+
+  #[test]
+  fn ports_are_scoped_to_their_owner() {
+    let ports = MessagePorts::default();
+    let (first, second) = ports.link("first", "second");
+
+    assert!(matches!(
+      ports.post("second", first.id(), None, message("a")),
+      Err(crate::Error::MessagePortNotFound(_))
+    ));
+    let (channel, _) = receiver();
+    assert!(ports.connect("first", second.id(), channel).is_err());
+
+    ports.close_owned_by("second");
+    assert!(ports.post("first", first.id(), None, message("a")).is_err());
+  }
+
+  #[test]
+  fn bounds_pending_messages() {
+    let ports = MessagePorts::default();
+    let (first, second) = ports.link("first", "second");
+
+    for _ in 0..MAX_QUEUED_MESSAGES {
+      ports.post("first", first.id(), None, message("a")).unwrap();
+    }
+    assert!(matches!(
+      ports.post("first", first.id(), None, message("a")),
+      Err(crate::Error::MessagePortQueueFull(port)) if port == second.id()
+    ));
+
+    let (channel, received) = receiver();
+    ports.connect("second", second.id(), channel).unwrap();
+    assert_eq!(received.lock().unwrap().len(), MAX_QUEUED_MESSAGES);
+    ports.post("first", first.id(), None, message("b")).unwrap();
+    assert_eq!(received.lock().unwrap().len(), MAX_QUEUED_MESSAGES + 1);
+  }
+
+  #[test]
+  fn closes_broken_sequences() {
+    let ports = MessagePorts::default();
+    let (first, second) = ports.link("first", "second");
+    let (channel, received) = receiver();
+    ports.connect("second", second.id(), channel).unwrap();
+
+    // the message with sequence number 0 never arrives
+    for sequence in 1..=MAX_OUT_OF_ORDER_MESSAGES as u64 {
+      ports
+        .post("first", first.id(), Some(sequence), message("a"))
+        .unwrap();
+    }
+    let last = MAX_OUT_OF_ORDER_MESSAGES as u64 + 1;
+    assert!(matches!(
+      ports.post("first", first.id(), Some(last), message("b")),
+      Err(crate::Error::MessagePortSequence(port, sequence)) if port == first.id() && sequence == last
+    ));
+    assert!(received.lock().unwrap().is_empty());
+    assert!(ports.owner(first.id()).is_none());
+    assert!(ports.owner(second.id()).is_none());
+
+    // a message received twice
+    let (first, second) = ports.link("first", "second");
+    ports
+      .post("first", first.id(), Some(0), message("a"))
+      .unwrap();
+    assert!(ports
+      .post("first", first.id(), Some(0), message("a"))
+      .is_err());
+    assert!(ports.owner(second.id()).is_none());
+  }
+
+  #[test]
+  fn closes_exhausted_sequences() {
+    let ports = MessagePorts::default();
+    let (first, second) = ports.link("first", "second");
+    ports
+      .0
+      .lock()
+      .unwrap()
+      .get_mut(&first.id())
+      .unwrap()
+      .next_sequence = u64::MAX;
+
+    assert!(matches!(
+      ports.post("first", first.id(), Some(u64::MAX), message("a")),
+      Err(crate::Error::MessagePortSequence(_, u64::MAX))
+    ));
+    assert!(ports.owner(second.id()).is_none());
+  }
+}
@@ -25,6 +25,7 @@ mod authority;
 pub(crate) mod channel;
 mod command;
 pub(crate) mod format_callback;
+pub(crate) mod message_port;
 pub(crate) mod protocol;
 
 pub use authority::{
@@ -33,6 +34,7 @@ pub use authority::{
 };
 pub use channel::{Channel, JavaScriptChannelId};
 pub use command::{private, CommandArg, CommandItem};
+pub use message_port::MessagePort;
 
 /// A closure that is run every time Tauri receives a message it doesn't explicitly handle.
 pub type InvokeHandler<R> = dyn Fn(Invoke<R>) -> bool + Send + Sync + 'static;
@@ -175,6 +177,8 @@ pub trait IpcResponse {
 
 impl<T: Serialize> IpcResponse for T {
   fn body(self) -> crate::Result<InvokeResponseBody> {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::trace_span!("ipc::request::serialize_response").entered();
     serde_json::to_string(&self)
       .map(Into::into)
       .map_err(Into::into)
@@ -321,7 +325,7 @@ impl<R: Runtime> InvokeResolver<R> {
     T: IpcResponse,
     F: Future<Output = Result<T, InvokeError>> + Send + 'static,
   {
-    crate::async_runtime::spawn(async move {
+    let future = async move {
       Self::return_task(
         self.webview,
         self.responder,
@@ -331,7 +335,11 @@ impl<R: Runtime> InvokeResolver<R> {
         self.error,
       )
       .await;
-    });
+    };
+    // keep the task in the trace of the request
+    #[cfg(feature = "tracing")]
+    let future = tracing::Instrument::in_current_span(future);
+    crate::async_runtime::spawn(future);
   }
 
   /// Reply to the invoke promise with an async task which is already serialized.
@@ -339,7 +347,7 @@ impl<R: Runtime> InvokeResolver<R> {
   where
     F: Future<Output = Result<InvokeResponseBody, InvokeError>> + Send + 'static,
   {
-    crate::async_runtime::spawn(async move {
+    let future = async move {
       let response = match task.await {
         Ok(ok) => InvokeResponse::Ok(ok),
         Err(err) => InvokeResponse::Err(err),
@@ -352,7 +360,10 @@ impl<R: Runtime> InvokeResolver<R> {
         self.callback,
         self.error,
       )
-    });
+    };
+    #[cfg(feature = "tracing")]
+    let future = tracing::Instrument::in_current_span(future);
+    crate::async_runtime::spawn(future);
   }
 
   /// Reply to the invoke promise with a serializable value.
@@ -29,6 +29,11 @@ const TAURI_RESPONSE_HEADER_NAME: &str = "Tauri-Response";
 const TAURI_RESPONSE_HEADER_ERROR: &str = "error";
 const TAURI_RESPONSE_HEADER_OK: &str = "ok";
 
+/// The [W3C Trace Context](https://www.w3.org/TR/trace-context/#traceparent-header) header
+/// the frontend can set to find the command execution of its requests in the logs.
+#[cfg(feature = "tracing")]
+const TRACEPARENT_HEADER_NAME: &str = "traceparent";
+
 pub fn message_handler<R: Runtime>(
   manager: Arc<AppManager<R>>,
 ) -> crate::runtime::webview::WebviewIpcHandler<crate::EventLoopMessage, R> {
@@ -61,6 +66,8 @@ pub fn get<R: Runtime>(manager: Arc<AppManager<R>>) -> UriSchemeProtocolHandler
     match *request.method() {
       Method::POST => {
         if let Some(webview) = manager.get_webview(label) {
+          #[cfg(feature = "tracing")]
+          let payload_size = request.body().len();
           match parse_invoke_request(&manager, request) {
             Ok(request) => {
               #[cfg(feature = "tracing")]
@@ -72,7 +79,9 @@ pub fn get<R: Runtime>(manager: Arc<AppManager<R>>) -> UriSchemeProtocolHandler
                 },
               );
               #[cfg(feature = "tracing")]
-              let request_span = tracing::trace_span!("ipc::request::handle", cmd = request.cmd);
+              let request_span = handle_span(&request, label, payload_size);
+              #[cfg(feature = "tracing")]
+              let _handle_span = request_span.clone().entered();
 
               webview.on_message(
                 request,
@@ -303,6 +312,9 @@ fn handle_ipc_message<R: Runtime>(request: Request<String>, manager: &AppManager
       serde_json::from_str::<Message>(request.body()).map_err(Into::into)
     });
 
+    #[cfg(feature = "tracing")]
+    let payload_size = request.body().len();
+
     match message {
       Ok(message) => {
         let options = message.options.unwrap_or_default();
@@ -318,7 +330,9 @@ fn handle_ipc_message<R: Runtime>(request: Request<String>, manager: &AppManager
         };
 
         #[cfg(feature = "tracing")]
-        let request_span = tracing::trace_span!("ipc::request::handle", cmd = request.cmd);
+        let request_span = handle_span(&request, label, payload_size);
+        #[cfg(feature = "tracing")]
+        let _handle_span = request_span.clone().entered();
 
         webview.on_message(
           request,
@@ -444,6 +458,94 @@ fn handle_ipc_message<R: Runtime>(request: Request<String>, manager: &AppManager
   }
 }
 
+/// Creates the span of an IPC request.
+///
+/// The trace ID and parent span ID of the `traceparent` header, if any, are recorded as fields of the span
+/// to correlate the logs. With the `tracing-opentelemetry` feature, the frontend span is also set as
+/// the remote OpenTelemetry parent of the span.
+#[cfg(feature = "tracing")]
+fn handle_span(request: &InvokeRequest, webview: &str, payload_size: usize) -> tracing::Span {
+  let span = tracing::trace_span!(
+    "ipc::request::handle",
+    cmd = request.cmd,
+    webview = webview,
+    payload_size = payload_size,
+    trace_id = tracing::field::Empty,
+    parent_span_id = tracing::field::Empty,
+  );
+
+  if let Some((trace_id, parent_span_id, flags)) = request
+    .headers
+    .get(TRACEPARENT_HEADER_NAME)
+    .and_then(|value| value.to_str().ok())
+    .and_then(parse_traceparent)
+  {
+    span.record("trace_id", trace_id);
+    span.record("parent_span_id", parent_span_id);
+
+    #[cfg(feature = "tracing-opentelemetry")]
+    {
+      use opentelemetry::trace::{
+        SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState,
+      };
+      use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+      // the fields were validated as lowercase hexadecimal
+      if let (Ok(trace_id), Ok(parent_span_id), Ok(flags)) = (
+        TraceId::from_hex(trace_id),
+        SpanId::from_hex(parent_span_id),
+        u8::from_str_radix(flags, 16),
+      ) {
+        let parent = SpanContext::new(
+          trace_id,
+          parent_span_id,
+          TraceFlags::new(flags),
+          true,
+          TraceState::default(),
+        );
+        span.set_parent(opentelemetry::Context::new().with_remote_span_context(parent));
+      }
+    }
+  }
+
+  span
+}
+
+/// Parses a `traceparent` header value into its trace ID, parent span ID and trace flags.
+#[cfg(feature = "tracing")]
+fn parse_traceparent(value: &str) -> Option<(&str, &str, &str)> {
+  let mut fields = value.trim().split('-');
+  let version = fields.next()?;
+  let trace_id = fields.next()?;
+  let parent_span_id = fields.next()?;
+  let flags = fields.next()?;
+
+  let is_id = |field: &str, len: usize| {
+    field.len() == len
+      && field
+        .bytes()
+        .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+  };
+  let is_zero = |field: &str| field.bytes().all(|b| b == b'0');
+
+  if !is_id(version, 2) || version == "ff" || !is_id(flags, 2) {
+    return None;
+  }
+  // future versions may append fields, but version 00 has exactly four
+  if version == "00" && fields.next().is_some() {
+    return None;
+  }
+  if !is_id(trace_id, 32)
+    || is_zero(trace_id)
+    || !is_id(parent_span_id, 16)
+    || is_zero(parent_span_id)
+  {
+    return None;
+  }
+
+  Some((trace_id, parent_span_id, flags))
+}
+
 fn parse_invoke_request<R: Runtime>(
   #[allow(unused_variables)] manager: &AppManager<R>,
   request: http::Request<Vec<u8>>,
@@ -758,4 +860,31 @@ mod tests {
     assert_eq!(invoke_request.headers, headers);
     assert_eq!(invoke_request.body, InvokeBody::Json(body_json));
   }
+
+  #[test]
+  #[cfg(feature = "tracing")]
+  fn parse_traceparent() {
+    assert_eq!(
+      super::parse_traceparent("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"),
+      Some(("4bf92f3577b34da6a3ce929d0e0e4736", "00f067aa0ba902b7", "01"))
+    );
+    // unknown versions may carry more fields
+    assert!(super::parse_traceparent(
+      "cc-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra"
+    )
+    .is_some());
+
+    for invalid in [
+      "",
+      "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+      "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+      "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+      "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+      "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+      "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+      "00-4bf92f3577b34da6-00f067aa0ba902b7-01",
+    ] {
+      assert_eq!(super::parse_traceparent(invalid), None, "{invalid}");
+    }
+  }
 }
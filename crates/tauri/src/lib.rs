@@ -16,6 +16,7 @@
 //! - **common-controls-v6** *(enabled by default)*: Enables [Common Controls v6](https://learn.microsoft.com/en-us/windows/win32/controls/common-control-versions) support on Windows, mainly for the predefined `about` menu item.
 //! - **unstable**: Enables unstable features. Be careful, it might introduce breaking changes in future minor releases.
 //! - **tracing**: Enables [`tracing`](https://docs.rs/tracing/latest/tracing) for window startup, plugins, `Window::eval`, events, IPC, updater and custom protocol request handlers.
+//! - **tracing-opentelemetry**: Sets the span of a frontend [`traceparent`](https://www.w3.org/TR/trace-context/#traceparent-header) header as the remote OpenTelemetry parent of the IPC request span, see [`tracing-opentelemetry`](https://docs.rs/tracing-opentelemetry).
 //! - **test**: Enables the [`mod@test`] module exposing unit test helpers.
 //! - **objc-exception**: Wrap each msg_send! in a @try/@catch and panics if an exception is caught, preventing Objective-C from unwinding into Rust.
 //! - **linux-libxdo**: Enables linking to libxdo which enables Cut, Copy, Paste and SelectAll menu items to work on Linux.
@@ -35,8 +36,13 @@
 //! - **config-toml**: Adds support to TOML format for the configuration `Tauri.toml`.
 //! - **image-ico**: Adds support to parse `.ico` image, see [`Image`].
 //! - **image-png**: Adds support to parse `.png` image, see [`Image`].
+//! - **image-jpeg**: Adds support to parse `.jpg` and `.jpeg` image, see [`Image`].
+//! - **image-webp**: Adds support to parse `.webp` image, see [`Image`].
+//! - **image-bmp**: Adds support to parse `.bmp` image, see [`Image`].
+//! - **image-svg**: Adds support to render `.svg` image, see [`Image`].
 //! - **macos-proxy**: Adds support for [`WebviewBuilder::proxy_url`] on macOS. Requires macOS 14+.
 //! - **specta**: Add support for [`specta::specta`](https://docs.rs/specta/%5E2.0.0-rc.9/specta/attr.specta.html) with Tauri arguments such as [`State`](crate::State), [`Window`](crate::Window) and [`AppHandle`](crate::AppHandle)
+//! - **fluent**: Adds support to parse [Fluent](https://projectfluent.org) message catalogs, see [`locale::Catalog::from_fluent`].
 //!
 //! ## Cargo allowlist features
 //!
@@ -94,8 +100,12 @@ pub mod webview;
 pub mod window;
 use tauri_runtime as runtime;
 pub mod image;
+#[cfg(desktop)]
+#[cfg_attr(docsrs, doc(cfg(desktop)))]
+pub mod instance;
 #[cfg(target_os = "ios")]
 mod ios;
+pub mod locale;
 #[cfg(desktop)]
 #[cfg_attr(docsrs, doc(cfg(desktop)))]
 pub mod menu;
@@ -385,6 +395,8 @@ pub struct Context<R: Runtime> {
   pub(crate) app_icon: Option<Vec<u8>>,
   #[cfg(all(desktop, feature = "tray-icon"))]
   pub(crate) tray_icon: Option<image::Image<'static>>,
+  #[cfg(desktop)]
+  pub(crate) menu_icons: HashMap<std::path::PathBuf, image::Image<'static>>,
   pub(crate) package_info: PackageInfo,
   pub(crate) pattern: Pattern,
   pub(crate) runtime_authority: RuntimeAuthority,
@@ -461,6 +473,24 @@ impl<R: Runtime> Context<R> {
     self.tray_icon = icon;
   }
 
+  /// The icon of the menu items defined in the configuration with the given icon path.
+  #[cfg(desktop)]
+  #[inline(always)]
+  pub fn menu_icon<P: AsRef<std::path::Path>>(&self, path: P) -> Option<&image::Image<'_>> {
+    self.menu_icons.get(path.as_ref())
+  }
+
+  /// Set the icon of the menu items defined in the configuration with the given icon path.
+  #[cfg(desktop)]
+  #[inline(always)]
+  pub fn set_menu_icon<P: Into<std::path::PathBuf>>(
+    &mut self,
+    path: P,
+    icon: image::Image<'static>,
+  ) {
+    self.menu_icons.insert(path.into(), icon);
+  }
+
   /// Package information.
   #[inline(always)]
   pub fn package_info(&self) -> &PackageInfo {
@@ -512,6 +542,8 @@ impl<R: Runtime> Context<R> {
       app_icon,
       #[cfg(all(desktop, feature = "tray-icon"))]
       tray_icon: None,
+      #[cfg(desktop)]
+      menu_icons: Default::default(),
       package_info,
       pattern,
       runtime_authority,
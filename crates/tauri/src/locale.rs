@@ -0,0 +1,207 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Localization of the native user interface, such as the menu items and the tray icon tooltips.
+//!
+//! Message catalogs are registered per locale with [`AppHandle::register_catalog`](crate::AppHandle::register_catalog)
+//! and the active locale is switched with [`AppHandle::set_locale`](crate::AppHandle::set_locale),
+//! which relabels every live item bound to a message key and emits the `tauri://locale-changed` event to the frontend.
+
+use std::collections::HashMap;
+
+#[cfg(feature = "fluent")]
+use fluent_bundle::{FluentBundle, FluentResource};
+#[cfg(feature = "fluent")]
+use fluent_syntax::ast;
+
+/// Event emitted to the frontend when the application locale changes, with the new locale as payload.
+pub(crate) const LOCALE_CHANGED_EVENT: &str = "tauri://locale-changed";
+
+/// A message catalog, mapping message keys to their translation in a given locale.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Catalog {
+  messages: HashMap<String, String>,
+}
+
+impl Catalog {
+  /// Creates an empty catalog.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Parses a JSON catalog.
+  ///
+  /// The catalog is an object mapping message keys to strings.
+  /// Nested objects are flattened, joining the keys with a `.`:
+  ///
+  /// ```json
+  /// { "file": { "open": "Open", "quit": "Quit" } }
+  /// ```
+  ///
+  /// defines the `file.open` and `file.quit` messages.
+  pub fn from_json(json: &str) -> crate::Result<Self> {
+    let value: serde_json::Value = serde_json::from_str(json)?;
+    let mut catalog = Self::new();
+    catalog.insert_json(None, value);
+    Ok(catalog)
+  }
+
+  /// Parses a [Fluent](https://projectfluent.org) resource.
+  ///
+  /// Each message is resolved once, with its term and message references expanded,
+  /// and its attributes are flattened to `message.attribute` keys.
+  /// Messages are static text, so placeables referencing variables or functions are rejected.
+  #[cfg(feature = "fluent")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "fluent")))]
+  pub fn from_fluent(source: &str) -> crate::Result<Self> {
+    let resource = FluentResource::try_new(source.to_string())
+      .map_err(|(_, errors)| invalid_catalog(&errors))?;
+
+    let mut bundle = FluentBundle::new(Vec::new());
+    // the translations are plain labels, do not wrap the placeables in Unicode isolation marks
+    bundle.set_use_isolating(false);
+    bundle
+      .add_resource(&resource)
+      .map_err(|errors| invalid_catalog(&errors))?;
+
+    let mut catalog = Self::new();
+    let mut errors = Vec::new();
+    for entry in resource.entries() {
+      let ast::Entry::Message(message) = entry else {
+        continue;
+      };
+      let id = message.id.name;
+      if let Some(value) = &message.value {
+        let text = bundle.format_pattern(value, None, &mut errors);
+        catalog.insert(id, text);
+      }
+      for attribute in &message.attributes {
+        let text = bundle.format_pattern(&attribute.value, None, &mut errors);
+        catalog.insert(format!("{id}.{}", attribute.id.name), text);
+      }
+    }
+
+    if errors.is_empty() {
+      Ok(catalog)
+    } else {
+      Err(invalid_catalog(&errors))
+    }
+  }
+
+  /// Adds a message to the catalog, replacing the previous translation of the key.
+  pub fn insert<K: Into<String>, V: Into<String>>(&mut self, key: K, message: V) -> &mut Self {
+    self.messages.insert(key.into(), message.into());
+    self
+  }
+
+  /// Returns the translation of the given message key.
+  pub fn get(&self, key: &str) -> Option<&str> {
+    self.messages.get(key).map(|m| m.as_str())
+  }
+
+  /// Whether the catalog has no messages.
+  pub fn is_empty(&self) -> bool {
+    self.messages.is_empty()
+  }
+
+  fn insert_json(&mut self, prefix: Option<&str>, value: serde_json::Value) {
+    match value {
+      serde_json::Value::Object(map) => {
+        for (key, value) in map {
+          let key = match prefix {
+            Some(prefix) => format!("{prefix}.{key}"),
+            None => key,
+          };
+          self.insert_json(Some(&key), value);
+        }
+      }
+      serde_json::Value::String(message) => {
+        if let Some(key) = prefix {
+          self.insert(key, message);
+        }
+      }
+      _ => {}
+    }
+  }
+}
+
+#[cfg(feature = "fluent")]
+fn invalid_catalog<E: std::fmt::Display>(errors: &[E]) -> crate::Error {
+  crate::Error::InvalidCatalog(
+    errors
+      .iter()
+      .map(ToString::to_string)
+      .collect::<Vec<_>>()
+      .join(", "),
+  )
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for Catalog {
+  fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+    Self {
+      messages: iter
+        .into_iter()
+        .map(|(k, v)| (k.into(), v.into()))
+        .collect(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::Catalog;
+
+  #[test]
+  fn json_catalog() {
+    let catalog = Catalog::from_json(
+      r#"{ "quit": "Quitter", "file": { "open": "Ouvrir", "recent": { "clear": "Effacer" } }, "count": 1 }"#,
+    )
+    .unwrap();
+    assert_eq!(catalog.get("quit"), Some("Quitter"));
+    assert_eq!(catalog.get("file.open"), Some("Ouvrir"));
+    assert_eq!(catalog.get("file.recent.clear"), Some("Effacer"));
+    assert_eq!(catalog.get("count"), None);
+    assert!(Catalog::from_json("[]").unwrap().is_empty());
+    assert!(Catalog::from_json("{").is_err());
+  }
+
+  #[test]
+  #[cfg(feature = "fluent")]
+  fn fluent_catalog() {
+    let catalog = Catalog::from_fluent(
+      r#"
+# File menu
+-brand-name = Exemple
+file-open = Ouvrir
+file-quit = Quitter { -brand-name }
+tray =
+    .tooltip = { -brand-name }
+    .title = Titre
+about = Première ligne
+    seconde ligne
+about-title = À propos de { file-open }
+"#,
+    )
+    .unwrap();
+    assert_eq!(catalog.get("file-open"), Some("Ouvrir"));
+    assert_eq!(catalog.get("file-quit"), Some("Quitter Exemple"));
+    assert_eq!(catalog.get("tray"), None);
+    assert_eq!(catalog.get("tray.tooltip"), Some("Exemple"));
+    assert_eq!(catalog.get("tray.title"), Some("Titre"));
+    assert_eq!(catalog.get("about"), Some("Première ligne\nseconde ligne"));
+    assert_eq!(catalog.get("about-title"), Some("À propos de Ouvrir"));
+    assert_eq!(catalog.get("-brand-name"), None);
+  }
+
+  #[test]
+  #[cfg(feature = "fluent")]
+  fn fluent_catalog_rejects_unsupported_syntax() {
+    // variables cannot be resolved in static labels
+    assert!(Catalog::from_fluent("greeting = Bonjour { $name }").is_err());
+    // unknown references
+    assert!(Catalog::from_fluent("file-quit = Quitter { -missing }").is_err());
+    // syntax errors
+    assert!(Catalog::from_fluent("file-quit = Quitter {").is_err());
+  }
+}
@@ -0,0 +1,68 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{
+  collections::HashMap,
+  sync::{Mutex, MutexGuard},
+};
+
+use crate::locale::Catalog;
+
+#[derive(Debug, Default)]
+pub struct LocaleManager {
+  /// The active locale.
+  pub locale: Mutex<Option<String>>,
+  /// Message catalogs, keyed by their locale.
+  pub catalogs: Mutex<HashMap<String, Catalog>>,
+}
+
+impl LocaleManager {
+  pub fn locale_lock(&self) -> MutexGuard<'_, Option<String>> {
+    self.locale.lock().expect("poisoned locale mutex")
+  }
+
+  pub fn catalogs_lock(&self) -> MutexGuard<'_, HashMap<String, Catalog>> {
+    self.catalogs.lock().expect("poisoned catalogs mutex")
+  }
+
+  /// Translates the given message key in the active locale.
+  ///
+  /// Falls back to the catalog of the language when the locale has a region (`fr` for `fr-CA`).
+  pub fn translate(&self, key: &str) -> Option<String> {
+    let locale = self.locale_lock().clone()?;
+    let catalogs = self.catalogs_lock();
+    let language = locale.split(['-', '_']).next().unwrap_or(&locale);
+    for locale in [locale.as_str(), language] {
+      if let Some(text) = catalogs.get(locale).and_then(|catalog| catalog.get(key)) {
+        return Some(text.to_string());
+      }
+    }
+    None
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::LocaleManager;
+  use crate::locale::Catalog;
+
+  #[test]
+  fn translate_falls_back_to_language() {
+    let manager = LocaleManager::default();
+    manager
+      .catalogs_lock()
+      .insert("fr".into(), Catalog::from_iter([("quit", "Quitter")]));
+    manager.catalogs_lock().insert(
+      "fr-CA".into(),
+      Catalog::from_iter([("open", "Ouvrir (CA)")]),
+    );
+
+    assert_eq!(manager.translate("quit"), None);
+
+    manager.locale_lock().replace("fr-CA".into());
+    assert_eq!(manager.translate("open").as_deref(), Some("Ouvrir (CA)"));
+    assert_eq!(manager.translate("quit").as_deref(), Some("Quitter"));
+    assert_eq!(manager.translate("missing"), None);
+  }
+}
@@ -4,11 +4,15 @@
 
 use std::{
   collections::HashMap,
+  path::PathBuf,
   sync::{Arc, Mutex, MutexGuard},
 };
 
+use muda::accelerator::Accelerator;
+
 use crate::{
-  menu::{Menu, MenuEvent, MenuId},
+  image::Image,
+  menu::{accelerators::WindowAccelerators, Menu, MenuEvent, MenuId, MenuItemKind, WeakMenuItem},
   AppHandle, Runtime, Window,
 };
 
@@ -25,6 +29,14 @@ pub struct MenuManager<R: Runtime> {
   pub global_event_listeners: Mutex<Vec<crate::app::GlobalMenuEventListener<AppHandle<R>>>>,
   /// Menu event listeners to specific windows.
   pub event_listeners: Mutex<HashMap<String, crate::app::GlobalMenuEventListener<Window<R>>>>,
+  /// Icons of the menu items defined in the configuration, keyed by their configured path.
+  pub config_icons: HashMap<PathBuf, Image<'static>>,
+  /// Menu items whose text is bound to a message key, with the key and the text the item had before.
+  pub(crate) localized_items: Mutex<Vec<(WeakMenuItem<R>, String, String)>>,
+  /// Accelerators registered on specific windows, keyed by the window label.
+  pub(crate) accelerators: Mutex<HashMap<String, WindowAccelerators<R>>>,
+  /// Accelerators of the menu items, which muda does not expose.
+  pub(crate) item_accelerators: Mutex<Vec<(WeakMenuItem<R>, Accelerator)>>,
 }
 
 impl<R: Runtime> MenuManager<R> {
@@ -51,6 +63,60 @@ impl<R: Runtime> MenuManager<R> {
       .insert(menu.id().clone(), menu.clone());
   }
 
+  pub(crate) fn localized_items_lock(
+    &self,
+  ) -> MutexGuard<'_, Vec<(WeakMenuItem<R>, String, String)>> {
+    self
+      .localized_items
+      .lock()
+      .expect("poisoned localized items mutex")
+  }
+
+  pub(crate) fn accelerators_lock(&self) -> MutexGuard<'_, HashMap<String, WindowAccelerators<R>>> {
+    self
+      .accelerators
+      .lock()
+      .expect("poisoned accelerators mutex")
+  }
+
+  pub(crate) fn item_accelerators_lock(
+    &self,
+  ) -> MutexGuard<'_, Vec<(WeakMenuItem<R>, Accelerator)>> {
+    self
+      .item_accelerators
+      .lock()
+      .expect("poisoned item accelerators mutex")
+  }
+
+  /// Binds the text of the item to the message key, replacing its previous key.
+  ///
+  /// `text` is the current text of the item, kept to restore it when the key has no translation.
+  /// An item bound again keeps the text it had before its first binding, which is returned.
+  pub fn insert_localized_item(&self, item: &MenuItemKind<R>, key: String, text: String) -> String {
+    let item = WeakMenuItem::new(item);
+    let mut items = self.localized_items_lock();
+    let text = items
+      .iter()
+      .find(|(i, _, _)| i.ptr_eq(&item))
+      .map(|(_, _, text)| text.clone())
+      .unwrap_or(text);
+    items.retain(|(i, _, _)| i.is_alive() && !i.ptr_eq(&item));
+    items.push((item, key, text.clone()));
+    text
+  }
+
+  /// The live items bound to a message key, with the key and their original text.
+  ///
+  /// The items are cloned so their text can be set without holding the lock.
+  pub fn localized_items(&self) -> Vec<(MenuItemKind<R>, String, String)> {
+    let mut items = self.localized_items_lock();
+    items.retain(|(i, _, _)| i.is_alive());
+    items
+      .iter()
+      .filter_map(|(i, key, text)| i.upgrade().map(|i| (i, key.clone(), text.clone())))
+      .collect()
+  }
+
   pub(crate) fn prepare_window_menu_creation_handler(
     &self,
     window_menu: Option<&crate::window::WindowMenu<R>>,
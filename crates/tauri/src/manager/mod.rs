@@ -32,6 +32,7 @@ use crate::{
 };
 use crate::{event::EmitArgs, resources::ResourceTable, Webview};
 
+mod locale;
 #[cfg(desktop)]
 mod menu;
 #[cfg(all(desktop, feature = "tray-icon"))]
@@ -195,6 +196,13 @@ pub struct AppManager<R: Runtime> {
   pub tray: tray::TrayManager<R>,
   #[cfg(desktop)]
   pub menu: menu::MenuManager<R>,
+  pub locale: locale::LocaleManager,
+  /// The socket or lock file held while this is the running instance of the app.
+  #[cfg(desktop)]
+  pub(crate) instance: Mutex<Option<crate::instance::InstanceGuard>>,
+  /// The messages received from the other instances, delivered before the main events are cleared.
+  #[cfg(desktop)]
+  pub(crate) instance_messages: Arc<Mutex<Vec<crate::instance::InstanceMessage>>>,
 
   pub(crate) plugins: Mutex<PluginStore<R>>,
   pub listeners: Listeners,
@@ -285,6 +293,7 @@ impl<R: Runtime> AppManager<R> {
         event_listeners: Arc::new(webiew_event_listeners),
         invoke_initialization_script,
         invoke_key: invoke_key.clone(),
+        new_window_handlers: Mutex::default(),
       },
       #[cfg(all(desktop, feature = "tray-icon"))]
       tray: tray::TrayManager {
@@ -292,6 +301,7 @@ impl<R: Runtime> AppManager<R> {
         icons: Default::default(),
         global_event_listeners: Default::default(),
         event_listeners: Default::default(),
+        tooltip_keys: Default::default(),
       },
       #[cfg(desktop)]
       menu: menu::MenuManager {
@@ -299,7 +309,16 @@ impl<R: Runtime> AppManager<R> {
         menu: Default::default(),
         global_event_listeners: Mutex::new(menu_event_listener),
         event_listeners: Mutex::new(window_menu_event_listeners),
+        config_icons: context.menu_icons,
+        localized_items: Default::default(),
+        accelerators: Default::default(),
+        item_accelerators: Default::default(),
       },
+      locale: Default::default(),
+      #[cfg(desktop)]
+      instance: Default::default(),
+      #[cfg(desktop)]
+      instance_messages: Default::default(),
       plugins: Mutex::new(plugins),
       listeners: Listeners::default(),
       state: Arc::new(state),
@@ -624,13 +643,39 @@ impl<R: Runtime> AppManager<R> {
     if let Some(window) = window {
       for webview in window.webviews() {
         self.webview.webviews_lock().remove(webview.label());
+        self.close_message_ports(webview.label());
+        self
+          .webview
+          .new_window_handlers
+          .lock()
+          .unwrap()
+          .remove(webview.label());
       }
     }
+    #[cfg(desktop)]
+    self.menu.accelerators_lock().remove(label);
   }
 
   #[cfg(desktop)]
   pub(crate) fn on_webview_close(&self, label: &str) {
     self.webview.webviews_lock().remove(label);
+    self.close_message_ports(label);
+    self
+      .webview
+      .new_window_handlers
+      .lock()
+      .unwrap()
+      .remove(label);
+  }
+
+  /// Closes the message channels with an end owned by the given webview.
+  fn close_message_ports(&self, label: &str) {
+    if let Some(ports) = self
+      .state
+      .try_get::<crate::ipc::message_port::MessagePorts>()
+    {
+      ports.close_owned_by(label);
+    }
   }
 
   pub fn windows(&self) -> HashMap<String, Window<R>> {
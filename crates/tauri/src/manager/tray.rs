@@ -19,6 +19,8 @@ pub struct TrayManager<R: Runtime> {
   pub(crate) global_event_listeners: Mutex<Vec<GlobalTrayIconEventListener<AppHandle<R>>>>,
   /// Tray icon event listeners.
   pub(crate) event_listeners: Mutex<HashMap<TrayIconId, GlobalTrayIconEventListener<TrayIcon<R>>>>,
+  /// Message keys of the localized tray icon tooltips.
+  pub(crate) tooltip_keys: Mutex<HashMap<TrayIconId, String>>,
 }
 
 impl<R: Runtime> fmt::Debug for TrayManager<R> {
@@ -63,8 +65,24 @@ impl<R: Runtime> TrayManager<R> {
     let mut icons = self.icons.lock().unwrap();
     let idx = icons.iter().position(|t| t.id() == &id);
     if let Some(idx) = idx {
-      return Some(icons.swap_remove(idx));
+      let icon = icons.swap_remove(idx);
+      drop(icons);
+      self.tooltip_keys.lock().unwrap().remove(icon.id());
+      return Some(icon);
     }
     None
   }
+
+  /// The tray icons whose tooltip is bound to a message key.
+  pub fn localized_tooltips(&self) -> Vec<(TrayIcon<R>, String)> {
+    let icons = self.icons.lock().unwrap().clone();
+    let tooltip_keys = self.tooltip_keys.lock().unwrap();
+    icons
+      .into_iter()
+      .filter_map(|icon| {
+        let key = tooltip_keys.get(icon.id())?.clone();
+        Some((icon, key))
+      })
+      .collect()
+  }
 }
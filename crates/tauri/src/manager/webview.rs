@@ -24,7 +24,7 @@ use crate::{
   ipc::InvokeHandler,
   pattern::PatternJavascript,
   sealed::ManagerBase,
-  webview::PageLoadPayload,
+  webview::{new_window::OnNewWindow, PageLoadPayload},
   Emitter, EventLoopMessage, EventTarget, Manager, Runtime, Scopes, UriSchemeContext, Webview,
   Window,
 };
@@ -40,6 +40,8 @@ use super::{
 // must also keep in sync with the `let mut response` assignment in prepare_uri_scheme_protocol
 pub(crate) const PROXY_DEV_SERVER: bool = cfg!(all(dev, mobile));
 
+const NAVIGATION_STATE_CHANGED_EVENT: &str = "tauri://navigation-state-changed";
+
 pub(crate) const PROCESS_IPC_MESSAGE_FN: &str =
   include_str!("../../scripts/process-ipc-message-fn.js");
 
@@ -81,6 +83,9 @@ pub struct WebviewManager<R: Runtime> {
 
   /// A runtime generated invoke key.
   pub(crate) invoke_key: String,
+
+  /// The new window handlers of each webview.
+  pub(crate) new_window_handlers: Mutex<HashMap<String, Arc<OnNewWindow<R>>>>,
 }
 
 impl<R: Runtime> fmt::Debug for WebviewManager<R> {
@@ -213,9 +218,20 @@ impl<R: Runtime> WebviewManager<R> {
       let protocol = protocol.clone();
       let app_handle = manager.app_handle().clone();
 
+      #[cfg(feature = "tracing")]
+      let uri_scheme_ = uri_scheme.clone();
       pending.register_uri_scheme_protocol(
         uri_scheme.clone(),
         move |webview_id, request, responder| {
+          #[cfg(feature = "tracing")]
+          let _span = tracing::trace_span!(
+            "protocol::request",
+            scheme = uri_scheme_,
+            webview = webview_id,
+            method = request.method().as_str(),
+            uri = request.uri().to_string()
+          )
+          .entered();
           let context = UriSchemeContext {
             app_handle: &app_handle,
             webview_label: webview_id,
@@ -286,6 +302,11 @@ impl<R: Runtime> WebviewManager<R> {
             .lock()
             .unwrap()
             .on_page_load(&w, &payload);
+
+          #[cfg(all(desktop, feature = "unstable"))]
+          if event == crate::webview::PageLoadEvent::Finished {
+            crate::window::layout::on_page_load(&w);
+          }
         }
 
         if let Some(handler) = &on_page_load_handler {
@@ -500,10 +521,7 @@ impl<R: Runtime> WebviewManager<R> {
     // but we do respect user-specification
     #[cfg(any(target_os = "linux", target_os = "windows"))]
     if pending.webview_attributes.data_directory.is_none() {
-      let local_app_data = manager.path().resolve(
-        &app_manager.config.identifier,
-        crate::path::BaseDirectory::LocalData,
-      );
+      let local_app_data = manager.path().app_local_data_dir();
       if let Ok(user_data_dir) = local_app_data {
         pending.webview_attributes.data_directory = Some(user_data_dir);
       }
@@ -516,6 +534,23 @@ impl<R: Runtime> WebviewManager<R> {
       }
     }
 
+    #[cfg(desktop)]
+    {
+      #[derive(Template)]
+      #[default_template("../window/scripts/accelerators.js")]
+      struct Accelerators {
+        accelerators: Vec<crate::menu::accelerators::AcceleratorKeys>,
+      }
+
+      pending.webview_attributes.initialization_scripts.push(
+        Accelerators {
+          accelerators: crate::menu::accelerators::window_keys(manager.manager(), window_label),
+        }
+        .render_default(&Default::default())?
+        .into_string(),
+      );
+    }
+
     #[cfg(all(desktop, not(target_os = "windows")))]
     if pending.webview_attributes.zoom_hotkeys_enabled {
       #[derive(Template)]
@@ -652,6 +687,14 @@ impl<R: Runtime> Webview<R> {
   }
 }
 
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct NavigationStatePayload<'a> {
+  url: &'a Url,
+  can_go_back: bool,
+  can_go_forward: bool,
+}
+
 fn on_webview_event<R: Runtime>(webview: &Webview<R>, event: &WebviewEvent) -> crate::Result<()> {
   match event {
     WebviewEvent::DragDrop(event) => match event {
@@ -687,6 +730,18 @@ fn on_webview_event<R: Runtime>(webview: &Webview<R>, event: &WebviewEvent) -> c
       DragDropEvent::Leave => webview.emit_to_webview(DRAG_LEAVE_EVENT, ())?,
       _ => unimplemented!(),
     },
+    WebviewEvent::NavigationStateChanged {
+      url,
+      can_go_back,
+      can_go_forward,
+    } => webview.emit_to_webview(
+      NAVIGATION_STATE_CHANGED_EVENT,
+      NavigationStatePayload {
+        url,
+        can_go_back: *can_go_back,
+        can_go_forward: *can_go_forward,
+      },
+    )?,
   }
 
   Ok(())
@@ -151,6 +151,9 @@ pub(crate) struct DragDropPayload<'a> {
 }
 
 fn on_window_event<R: Runtime>(window: &Window<R>, event: &WindowEvent) -> crate::Result<()> {
+  #[cfg(all(desktop, feature = "unstable"))]
+  crate::window::layout::on_window_event(window, event);
+
   match event {
     WindowEvent::Resized(size) => window.emit_to_window(WINDOW_RESIZED_EVENT, size)?,
     WindowEvent::Moved(position) => window.emit_to_window(WINDOW_MOVED_EVENT, position)?,
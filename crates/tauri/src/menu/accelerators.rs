@@ -0,0 +1,427 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::sync::Arc;
+
+use muda::accelerator::{Accelerator, Code, Modifiers};
+use serde::Serialize;
+use tauri_runtime::{EventLoopProxy, RuntimeHandle};
+
+use super::{Menu, MenuEvent, MenuId, MenuItemKind, WeakMenuItem};
+use crate::{AppHandle, EventLoopMessage, Runtime, Window};
+
+type AcceleratorHandler<R> = Arc<dyn Fn(&Window<R>) + Send + Sync>;
+
+/// What happens when a window accelerator is pressed.
+pub(crate) enum AcceleratorAction<R: Runtime> {
+  /// Emits a [`MenuEvent`] for the menu item.
+  MenuItem(MenuId),
+  /// Calls a handler on the main thread.
+  Handler(AcceleratorHandler<R>),
+}
+
+impl<R: Runtime> Clone for AcceleratorAction<R> {
+  fn clone(&self) -> Self {
+    match self {
+      Self::MenuItem(id) => Self::MenuItem(id.clone()),
+      Self::Handler(handler) => Self::Handler(handler.clone()),
+    }
+  }
+}
+
+/// The accelerators registered on a window.
+pub(crate) struct WindowAccelerators<R: Runtime> {
+  entries: Vec<(Accelerator, AcceleratorAction<R>)>,
+}
+
+impl<R: Runtime> Default for WindowAccelerators<R> {
+  fn default() -> Self {
+    Self {
+      entries: Vec::new(),
+    }
+  }
+}
+
+pub(crate) fn parse(accelerator: &str) -> crate::Result<Accelerator> {
+  accelerator
+    .parse()
+    .map_err(|e| crate::Error::InvalidAccelerator(accelerator.into(), format!("{e}")))
+}
+
+/// The keys of an accelerator, matched against the key presses by the webviews of the window.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub(crate) struct AcceleratorKeys {
+  code: String,
+  shift: bool,
+  control: bool,
+  alt: bool,
+  meta: bool,
+}
+
+impl AcceleratorKeys {
+  fn new(accelerator: &Accelerator) -> Self {
+    // muda does not expose the keys of an accelerator, only its serialized form
+    let code = serde_json::to_value(accelerator)
+      .ok()
+      .and_then(|value| value.get("key")?.as_str().map(ToString::to_string))
+      .unwrap_or_default();
+    let modifiers = code.parse::<Code>().ok().and_then(|code| {
+      let mut modifiers = Modifiers::empty();
+      for bits in 0..16 {
+        modifiers.set(Modifiers::SHIFT, bits & 1 != 0);
+        modifiers.set(Modifiers::CONTROL, bits & 2 != 0);
+        modifiers.set(Modifiers::ALT, bits & 4 != 0);
+        modifiers.set(Modifiers::SUPER, bits & 8 != 0);
+        if accelerator.matches(modifiers, code) {
+          return Some(modifiers);
+        }
+      }
+      None
+    });
+    let modifiers = modifiers.unwrap_or_else(Modifiers::empty);
+    Self {
+      code,
+      shift: modifiers.contains(Modifiers::SHIFT),
+      control: modifiers.contains(Modifiers::CONTROL),
+      alt: modifiers.contains(Modifiers::ALT),
+      meta: modifiers.contains(Modifiers::SUPER),
+    }
+  }
+}
+
+/// Records the accelerator of a menu item, to detect the window accelerators conflicting with it.
+///
+/// The entries of dropped items are removed along the way.
+pub(crate) fn set_item_accelerator<R: Runtime>(
+  app_handle: &AppHandle<R>,
+  item: &MenuItemKind<R>,
+  accelerator: Option<Accelerator>,
+) {
+  let item = WeakMenuItem::new(item);
+  let mut item_accelerators = app_handle.manager.menu.item_accelerators_lock();
+  item_accelerators.retain(|(i, _)| i.is_alive() && !i.ptr_eq(&item));
+  if let Some(accelerator) = accelerator {
+    item_accelerators.push((item, accelerator));
+  }
+}
+
+/// Finds the item of the window menu or the app menu using the accelerator.
+pub(crate) fn menu_item_using<R: Runtime>(
+  window: &Window<R>,
+  accelerator: &Accelerator,
+) -> crate::Result<Option<MenuId>> {
+  let candidates: Vec<MenuId> = window
+    .manager
+    .menu
+    .item_accelerators_lock()
+    .iter()
+    .filter(|(_, a)| a == accelerator)
+    .filter_map(|(i, _)| i.upgrade())
+    .map(|i| i.id().clone())
+    .collect();
+  if candidates.is_empty() {
+    return Ok(None);
+  }
+
+  for menu in window.menu().into_iter().chain(window.app_handle.menu()) {
+    if let Some(id) = find_item(&menu, &candidates)? {
+      return Ok(Some(id));
+    }
+  }
+  Ok(None)
+}
+
+fn find_item<R: Runtime>(menu: &Menu<R>, ids: &[MenuId]) -> crate::Result<Option<MenuId>> {
+  let mut items = menu.items()?;
+  while let Some(item) = items.pop() {
+    if ids.contains(item.id()) {
+      return Ok(Some(item.id().clone()));
+    }
+    if let MenuItemKind::Submenu(submenu) = item {
+      items.extend(submenu.items()?);
+    }
+  }
+  Ok(None)
+}
+
+/// Sends the accelerators registered on the window to its webviews,
+/// so they only forward the key presses matching one of them.
+pub(crate) fn sync<R: Runtime>(window: &Window<R>) -> crate::Result<()> {
+  let keys = window_keys(window.manager.as_ref(), window.label());
+  let script = format!(
+    "window.__TAURI_INTERNALS__.setAccelerators && window.__TAURI_INTERNALS__.setAccelerators({})",
+    serde_json::to_string(&keys)?
+  );
+  for webview in window.webviews() {
+    webview.eval(&script)?;
+  }
+  Ok(())
+}
+
+/// The keys of the accelerators registered on the window.
+pub(crate) fn window_keys<R: Runtime>(
+  manager: &crate::manager::AppManager<R>,
+  window_label: &str,
+) -> Vec<AcceleratorKeys> {
+  manager
+    .menu
+    .accelerators_lock()
+    .get(window_label)
+    .map(|accelerators| {
+      accelerators
+        .entries
+        .iter()
+        .map(|(accelerator, _)| AcceleratorKeys::new(accelerator))
+        .collect()
+    })
+    .unwrap_or_default()
+}
+
+impl<R: Runtime> WindowAccelerators<R> {
+  /// Registers the accelerator.
+  ///
+  /// Registering it again for the same menu item is a no-op,
+  /// any other registration of an accelerator already in use is a conflict.
+  pub(crate) fn register(
+    &mut self,
+    accelerator: &str,
+    action: AcceleratorAction<R>,
+  ) -> crate::Result<()> {
+    let parsed = parse(accelerator)?;
+    let Some((_, existing)) = self.entries.iter().find(|(a, _)| *a == parsed) else {
+      self.entries.push((parsed, action));
+      return Ok(());
+    };
+    match (existing, &action) {
+      (AcceleratorAction::MenuItem(existing), AcceleratorAction::MenuItem(id))
+        if existing == id =>
+      {
+        Ok(())
+      }
+      _ => Err(crate::Error::AcceleratorConflict(accelerator.into())),
+    }
+  }
+
+  /// Unregisters the accelerator, returning whether it was registered.
+  pub(crate) fn unregister(&mut self, accelerator: &str) -> crate::Result<bool> {
+    let parsed = parse(accelerator)?;
+    let len = self.entries.len();
+    self.entries.retain(|(a, _)| *a != parsed);
+    Ok(self.entries.len() != len)
+  }
+
+  pub(crate) fn is_registered(&self, accelerator: &str) -> crate::Result<bool> {
+    let parsed = parse(accelerator)?;
+    Ok(self.entries.iter().any(|(a, _)| *a == parsed))
+  }
+
+  /// Finds the action of the accelerator matching the pressed keys.
+  pub(crate) fn find(&self, modifiers: Modifiers, code: Code) -> Option<AcceleratorAction<R>> {
+    self
+      .entries
+      .iter()
+      .find(|(a, _)| a.matches(modifiers, code))
+      .map(|(_, action)| action.clone())
+  }
+}
+
+/// Runs the action of the window accelerator matching the pressed keys, if the window is focused.
+///
+/// Returns whether an accelerator matched.
+pub(crate) fn dispatch<R: Runtime>(
+  window: &Window<R>,
+  modifiers: Modifiers,
+  code: Code,
+) -> crate::Result<bool> {
+  if !window.is_focused()? {
+    return Ok(false);
+  }
+
+  let action = window
+    .manager
+    .menu
+    .accelerators_lock()
+    .get(window.label())
+    .and_then(|accelerators| accelerators.find(modifiers, code));
+
+  match action {
+    Some(AcceleratorAction::MenuItem(id)) => {
+      // go through the event loop so the event reaches the same listeners as native menu events
+      window
+        .app_handle
+        .runtime_handle
+        .create_proxy()
+        .send_event(EventLoopMessage::MenuEvent(MenuEvent { id }))?;
+      Ok(true)
+    }
+    Some(AcceleratorAction::Handler(handler)) => {
+      let window_ = window.clone();
+      window.run_on_main_thread(move || handler(&window_))?;
+      Ok(true)
+    }
+    None => Ok(false),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+
+  use muda::accelerator::{Code, Modifiers};
+
+  use super::{AcceleratorAction, AcceleratorKeys, WindowAccelerators};
+  use crate::{menu::MenuId, test::MockRuntime};
+
+  #[test]
+  fn registry_detects_conflicts() {
+    let mut accelerators = WindowAccelerators::<MockRuntime>::default();
+    accelerators
+      .register("Ctrl+S", AcceleratorAction::MenuItem(MenuId::new("save")))
+      .unwrap();
+    // same item, different spelling
+    accelerators
+      .register(
+        "Control+s",
+        AcceleratorAction::MenuItem(MenuId::new("save")),
+      )
+      .unwrap();
+
+    assert!(matches!(
+      accelerators.register("Ctrl+S", AcceleratorAction::MenuItem(MenuId::new("share"))),
+      Err(crate::Error::AcceleratorConflict(_))
+    ));
+    assert!(matches!(
+      accelerators.register("Ctrl+S", AcceleratorAction::Handler(Arc::new(|_| {}))),
+      Err(crate::Error::AcceleratorConflict(_))
+    ));
+    assert!(matches!(
+      accelerators.register("Ctrl+Nope", AcceleratorAction::Handler(Arc::new(|_| {}))),
+      Err(crate::Error::InvalidAccelerator(..))
+    ));
+
+    accelerators
+      .register("Ctrl+Shift+S", AcceleratorAction::Handler(Arc::new(|_| {})))
+      .unwrap();
+
+    assert!(matches!(
+      accelerators.find(Modifiers::CONTROL, Code::KeyS),
+      Some(AcceleratorAction::MenuItem(id)) if id == MenuId::new("save")
+    ));
+    assert!(matches!(
+      accelerators.find(Modifiers::CONTROL | Modifiers::SHIFT, Code::KeyS),
+      Some(AcceleratorAction::Handler(_))
+    ));
+    assert!(accelerators.find(Modifiers::ALT, Code::KeyS).is_none());
+
+    assert!(accelerators.unregister("Ctrl+S").unwrap());
+    assert!(!accelerators.unregister("Ctrl+S").unwrap());
+    assert!(!accelerators.is_registered("Ctrl+S").unwrap());
+    assert!(accelerators.is_registered("Shift+Ctrl+S").unwrap());
+  }
+
+  #[test]
+  fn exposes_accelerator_keys() {
+    let keys = AcceleratorKeys::new(&"Ctrl+Shift+F5".parse().unwrap());
+    assert_eq!(
+      keys,
+      AcceleratorKeys {
+        code: "F5".into(),
+        shift: true,
+        control: true,
+        alt: false,
+        meta: false,
+      }
+    );
+    assert_eq!(
+      serde_json::to_value(AcceleratorKeys::new(&"Alt+Super+KeyK".parse().unwrap())).unwrap(),
+      serde_json::json!({
+        "code": "KeyK",
+        "shift": false,
+        "control": false,
+        "alt": true,
+        "meta": true
+      })
+    );
+  }
+
+  #[test]
+  fn conflicts_with_menu_accelerators() {
+    use crate::menu::{MenuBuilder, MenuItemBuilder, SubmenuBuilder};
+
+    let app = crate::test::mock_app();
+    let save = MenuItemBuilder::with_id("save", "Save")
+      .accelerator("CmdOrCtrl+S")
+      .build(&app)
+      .unwrap();
+    let file = SubmenuBuilder::new(&app, "File")
+      .item(&save)
+      .build()
+      .unwrap();
+    let menu = MenuBuilder::new(&app).item(&file).build().unwrap();
+    app.set_menu(menu).unwrap();
+    let window = crate::WebviewWindowBuilder::new(&app, "main", Default::default())
+      .build()
+      .unwrap()
+      .as_ref()
+      .window();
+
+    assert!(matches!(
+      window.register_accelerator_handler("CmdOrCtrl+S", |_| {}),
+      Err(crate::Error::AcceleratorConflict(_))
+    ));
+    window.register_accelerator("CmdOrCtrl+S", "save").unwrap();
+
+    // the accelerator is released with the item
+    save.set_accelerator(None::<&str>).unwrap();
+    window.unregister_all_accelerators().unwrap();
+    window
+      .register_accelerator_handler("CmdOrCtrl+S", |_| {})
+      .unwrap();
+  }
+
+  #[test]
+  fn conflicts_with_predefined_accelerators() {
+    use crate::menu::{MenuBuilder, PredefinedMenuItem, SubmenuBuilder};
+
+    let app = crate::test::mock_app();
+    let edit = SubmenuBuilder::new(&app, "Edit")
+      .item(&PredefinedMenuItem::copy(&app, None).unwrap())
+      .build()
+      .unwrap();
+    let menu = MenuBuilder::new(&app).item(&edit).build().unwrap();
+    app.set_menu(menu).unwrap();
+    let window = crate::WebviewWindowBuilder::new(&app, "main", Default::default())
+      .build()
+      .unwrap()
+      .as_ref()
+      .window();
+
+    assert!(matches!(
+      window.register_accelerator_handler("CmdOrCtrl+C", |_| {}),
+      Err(crate::Error::AcceleratorConflict(_))
+    ));
+  }
+
+  #[test]
+  fn forgets_dropped_items() {
+    use crate::menu::MenuItemBuilder;
+
+    let app = crate::test::mock_app();
+    let build = |id: &str| {
+      MenuItemBuilder::with_id(id, id)
+        .accelerator("CmdOrCtrl+S")
+        .build(&app)
+        .unwrap()
+    };
+
+    drop(build("first"));
+    let second = build("second");
+    let item_accelerators = app.handle().manager.menu.item_accelerators_lock();
+    assert_eq!(item_accelerators.len(), 1);
+    assert!(matches!(
+      item_accelerators[0].0.upgrade(),
+      Some(item) if item.id() == second.id()
+    ));
+  }
+}
@@ -11,6 +11,7 @@ pub struct CheckMenuItemBuilder {
   enabled: bool,
   checked: bool,
   accelerator: Option<String>,
+  text_key: Option<String>,
 }
 
 impl CheckMenuItemBuilder {
@@ -25,6 +26,7 @@ impl CheckMenuItemBuilder {
       enabled: true,
       checked: true,
       accelerator: None,
+      text_key: None,
     }
   }
 
@@ -39,6 +41,7 @@ impl CheckMenuItemBuilder {
       enabled: true,
       checked: true,
       accelerator: None,
+      text_key: None,
     }
   }
 
@@ -66,9 +69,18 @@ impl CheckMenuItemBuilder {
     self
   }
 
+  /// Set the message key used to localize the text of this menu item.
+  ///
+  /// See [`CheckMenuItem::set_text_key`].
+  pub fn text_key<S: Into<String>>(mut self, key: S) -> Self {
+    self.text_key.replace(key.into());
+    self
+  }
+
   /// Build the menu item
   pub fn build<R: Runtime, M: Manager<R>>(self, manager: &M) -> crate::Result<CheckMenuItem<R>> {
-    if let Some(id) = self.id {
+    let text_key = self.text_key;
+    let item = if let Some(id) = self.id {
       CheckMenuItem::with_id(
         manager,
         id,
@@ -85,6 +97,10 @@ impl CheckMenuItemBuilder {
         self.checked,
         self.accelerator,
       )
+    }?;
+    if let Some(key) = text_key {
+      item.set_text_key(key)?;
     }
+    Ok(item)
   }
 }
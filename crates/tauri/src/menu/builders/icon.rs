@@ -16,6 +16,7 @@ pub struct IconMenuItemBuilder<'a> {
   icon: Option<Image<'a>>,
   native_icon: Option<NativeIcon>,
   accelerator: Option<String>,
+  text_key: Option<String>,
 }
 
 impl<'a> IconMenuItemBuilder<'a> {
@@ -31,6 +32,7 @@ impl<'a> IconMenuItemBuilder<'a> {
       icon: None,
       native_icon: None,
       accelerator: None,
+      text_key: None,
     }
   }
 
@@ -46,6 +48,7 @@ impl<'a> IconMenuItemBuilder<'a> {
       icon: None,
       native_icon: None,
       accelerator: None,
+      text_key: None,
     }
   }
 
@@ -87,9 +90,18 @@ impl<'a> IconMenuItemBuilder<'a> {
     self
   }
 
+  /// Set the message key used to localize the text of this menu item.
+  ///
+  /// See [`IconMenuItem::set_text_key`].
+  pub fn text_key<S: Into<String>>(mut self, key: S) -> Self {
+    self.text_key.replace(key.into());
+    self
+  }
+
   /// Build the menu item
   pub fn build<R: Runtime, M: Manager<R>>(self, manager: &M) -> crate::Result<IconMenuItem<R>> {
-    if self.icon.is_some() {
+    let text_key = self.text_key;
+    let item = if self.icon.is_some() {
       if let Some(id) = self.id {
         IconMenuItem::with_id(
           manager,
@@ -125,6 +137,10 @@ impl<'a> IconMenuItemBuilder<'a> {
         self.native_icon,
         self.accelerator,
       )
+    }?;
+    if let Some(key) = text_key {
+      item.set_text_key(key)?;
     }
+    Ok(item)
   }
 }
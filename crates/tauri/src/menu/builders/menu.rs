@@ -116,6 +116,7 @@ pub struct SubmenuBuilder<'m, R: Runtime, M: Manager<R>> {
   pub(crate) manager: &'m M,
   pub(crate) text: String,
   pub(crate) enabled: bool,
+  pub(crate) text_key: Option<String>,
   pub(crate) items: Vec<crate::Result<MenuItemKind<R>>>,
 }
 
@@ -130,6 +131,7 @@ impl<'m, R: Runtime, M: Manager<R>> SubmenuBuilder<'m, R, M> {
       items: Vec::new(),
       text: text.as_ref().to_string(),
       enabled: true,
+      text_key: None,
       manager,
     }
   }
@@ -143,6 +145,7 @@ impl<'m, R: Runtime, M: Manager<R>> SubmenuBuilder<'m, R, M> {
       id: Some(id.into()),
       text: text.as_ref().to_string(),
       enabled: true,
+      text_key: None,
       items: Vec::new(),
       manager,
     }
@@ -154,6 +157,14 @@ impl<'m, R: Runtime, M: Manager<R>> SubmenuBuilder<'m, R, M> {
     self
   }
 
+  /// Set the message key used to localize the text of the submenu.
+  ///
+  /// See [`Submenu::set_text_key`].
+  pub fn text_key<S: Into<String>>(mut self, key: S) -> Self {
+    self.text_key.replace(key.into());
+    self
+  }
+
   /// Builds this submenu
   pub fn build(self) -> crate::Result<Submenu<R>> {
     let submenu = if let Some(id) = self.id {
@@ -162,6 +173,10 @@ impl<'m, R: Runtime, M: Manager<R>> SubmenuBuilder<'m, R, M> {
       Submenu::new(self.manager, self.text, self.enabled)?
     };
 
+    if let Some(key) = self.text_key {
+      submenu.set_text_key(key)?;
+    }
+
     for item in self.items {
       let item = item?;
       submenu.append(&item)?;
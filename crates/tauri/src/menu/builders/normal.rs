@@ -10,6 +10,7 @@ pub struct MenuItemBuilder {
   text: String,
   enabled: bool,
   accelerator: Option<String>,
+  text_key: Option<String>,
 }
 
 impl MenuItemBuilder {
@@ -23,6 +24,7 @@ impl MenuItemBuilder {
       text: text.as_ref().to_string(),
       enabled: true,
       accelerator: None,
+      text_key: None,
     }
   }
 
@@ -36,6 +38,7 @@ impl MenuItemBuilder {
       text: text.as_ref().to_string(),
       enabled: true,
       accelerator: None,
+      text_key: None,
     }
   }
 
@@ -57,12 +60,25 @@ impl MenuItemBuilder {
     self
   }
 
+  /// Set the message key used to localize the text of this menu item.
+  ///
+  /// See [`MenuItem::set_text_key`].
+  pub fn text_key<S: Into<String>>(mut self, key: S) -> Self {
+    self.text_key.replace(key.into());
+    self
+  }
+
   /// Build the menu item
   pub fn build<R: Runtime, M: Manager<R>>(self, manager: &M) -> crate::Result<MenuItem<R>> {
-    if let Some(id) = self.id {
+    let text_key = self.text_key;
+    let item = if let Some(id) = self.id {
       MenuItem::with_id(manager, id, self.text, self.enabled, self.accelerator)
     } else {
       MenuItem::new(manager, self.text, self.enabled, self.accelerator)
+    }?;
+    if let Some(key) = text_key {
+      item.set_text_key(key)?;
     }
+    Ok(item)
   }
 }
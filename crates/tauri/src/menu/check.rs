@@ -9,7 +9,7 @@ use crate::menu::CheckMenuItemInner;
 use crate::run_main_thread;
 use crate::{menu::MenuId, AppHandle, Manager, Runtime};
 
-use super::CheckMenuItem;
+use super::{CheckMenuItem, IsMenuItem};
 
 impl<R: Runtime> CheckMenuItem<R> {
   /// Create a new menu item.
@@ -42,8 +42,10 @@ impl<R: Runtime> CheckMenuItem<R> {
         app_handle,
       }
     })?;
+    let item = Self(Arc::new(item));
+    super::accelerators::set_item_accelerator(handle, &item.kind(), accelerator);
 
-    Ok(Self(Arc::new(item)))
+    Ok(item)
   }
 
   /// Create a new menu item with the specified id.
@@ -79,8 +81,10 @@ impl<R: Runtime> CheckMenuItem<R> {
         app_handle,
       }
     })?;
+    let item = Self(Arc::new(item));
+    super::accelerators::set_item_accelerator(handle, &item.kind(), accelerator);
 
-    Ok(Self(Arc::new(item)))
+    Ok(item)
   }
 
   /// The application handle associated with this type.
@@ -106,6 +110,20 @@ impl<R: Runtime> CheckMenuItem<R> {
     run_item_main_thread!(self, |self_: Self| (*self_.0).as_ref().set_text(text))
   }
 
+  /// Binds the text of this menu item to a message key of the catalogs registered with
+  /// [`AppHandle::register_catalog`](crate::AppHandle::register_catalog),
+  /// relabeling it on every [`AppHandle::set_locale`](crate::AppHandle::set_locale) call.
+  ///
+  /// The text is set right away if the active locale has a translation for the key,
+  /// otherwise the item keeps the text it had before being bound to a message key.
+  pub fn set_text_key<S: Into<String>>(&self, key: S) -> crate::Result<()> {
+    super::localize(
+      self.app_handle(),
+      super::MenuItemKind::Check(self.clone()),
+      key.into(),
+    )
+  }
+
   /// Get whether this menu item is enabled or not.
   pub fn is_enabled(&self) -> crate::Result<bool> {
     run_item_main_thread!(self, |self_: Self| (*self_.0).as_ref().is_enabled())
@@ -119,6 +137,7 @@ impl<R: Runtime> CheckMenuItem<R> {
   /// Set this menu item accelerator.
   pub fn set_accelerator<S: AsRef<str>>(&self, accelerator: Option<S>) -> crate::Result<()> {
     let accel = accelerator.and_then(|s| s.as_ref().parse().ok());
+    super::accelerators::set_item_accelerator(self.app_handle(), &self.kind(), accel);
     run_item_main_thread!(self, |self_: Self| (*self_.0)
       .as_ref()
       .set_accelerator(accel))?
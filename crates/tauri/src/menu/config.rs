@@ -0,0 +1,166 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use tauri_utils::{
+  config::{MenuItemConfig, PredefinedMenuItemConfig, PredefinedMenuItemKind},
+  platform::Target,
+};
+
+use super::{
+  menu::default_about_metadata, CheckMenuItemBuilder, IconMenuItemBuilder, IsMenuItem, Menu,
+  MenuItemBuilder, MenuItemKind, PredefinedMenuItem, SubmenuBuilder,
+};
+use crate::{Manager, Runtime};
+
+impl<R: Runtime> Menu<R> {
+  /// Creates a menu with the items defined in the configuration, skipping the items not added on the current platform.
+  pub(crate) fn from_config<M: Manager<R>>(
+    manager: &M,
+    items: &[MenuItemConfig],
+  ) -> crate::Result<Self> {
+    let items = create_items(manager, items)?;
+    Menu::with_items(manager, &as_items(&items))
+  }
+}
+
+fn as_items<R: Runtime>(items: &[MenuItemKind<R>]) -> Vec<&dyn IsMenuItem<R>> {
+  items.iter().map(|i| i as &dyn IsMenuItem<R>).collect()
+}
+
+fn create_items<R: Runtime, M: Manager<R>>(
+  manager: &M,
+  items: &[MenuItemConfig],
+) -> crate::Result<Vec<MenuItemKind<R>>> {
+  let target = Target::current();
+  items
+    .iter()
+    .filter(|item| item.is_supported(target))
+    .map(|item| create_item(manager, item))
+    .collect()
+}
+
+fn create_item<R: Runtime, M: Manager<R>>(
+  manager: &M,
+  item: &MenuItemConfig,
+) -> crate::Result<MenuItemKind<R>> {
+  match item {
+    MenuItemConfig::Normal(item) => {
+      let mut builder = MenuItemBuilder::with_id(&item.id, &item.text).enabled(item.enabled);
+      if let Some(accelerator) = &item.accelerator {
+        builder = builder.accelerator(accelerator);
+      }
+      builder.build(manager).map(MenuItemKind::MenuItem)
+    }
+    MenuItemConfig::Check(item) => {
+      let mut builder = CheckMenuItemBuilder::with_id(&item.id, &item.text)
+        .checked(item.checked)
+        .enabled(item.enabled);
+      if let Some(accelerator) = &item.accelerator {
+        builder = builder.accelerator(accelerator);
+      }
+      builder.build(manager).map(MenuItemKind::Check)
+    }
+    MenuItemConfig::Icon(item) => {
+      let mut builder = IconMenuItemBuilder::with_id(&item.id, &item.text).enabled(item.enabled);
+      if let Some(accelerator) = &item.accelerator {
+        builder = builder.accelerator(accelerator);
+      }
+      // the icons are embedded by the context codegen
+      if let Some(icon) = manager.manager().menu.config_icons.get(&item.icon) {
+        builder = builder.icon(icon.clone());
+      }
+      builder.build(manager).map(MenuItemKind::Icon)
+    }
+    MenuItemConfig::Submenu(submenu) => {
+      let items = create_items(manager, &submenu.items)?;
+      let builder = match &submenu.id {
+        Some(id) => SubmenuBuilder::with_id(manager, id, &submenu.text),
+        None => SubmenuBuilder::new(manager, &submenu.text),
+      };
+      builder
+        .enabled(submenu.enabled)
+        .items(&as_items(&items))
+        .build()
+        .map(MenuItemKind::Submenu)
+    }
+    MenuItemConfig::Predefined(item) => create_predefined_item(manager, item),
+  }
+}
+
+fn create_predefined_item<R: Runtime, M: Manager<R>>(
+  manager: &M,
+  item: &PredefinedMenuItemConfig,
+) -> crate::Result<MenuItemKind<R>> {
+  let text = item.text.as_deref();
+  match item.item {
+    PredefinedMenuItemKind::Separator => PredefinedMenuItem::separator(manager),
+    PredefinedMenuItemKind::Copy => PredefinedMenuItem::copy(manager, text),
+    PredefinedMenuItemKind::Cut => PredefinedMenuItem::cut(manager, text),
+    PredefinedMenuItemKind::Paste => PredefinedMenuItem::paste(manager, text),
+    PredefinedMenuItemKind::SelectAll => PredefinedMenuItem::select_all(manager, text),
+    PredefinedMenuItemKind::Undo => PredefinedMenuItem::undo(manager, text),
+    PredefinedMenuItemKind::Redo => PredefinedMenuItem::redo(manager, text),
+    PredefinedMenuItemKind::Minimize => PredefinedMenuItem::minimize(manager, text),
+    PredefinedMenuItemKind::Maximize => PredefinedMenuItem::maximize(manager, text),
+    PredefinedMenuItemKind::Fullscreen => PredefinedMenuItem::fullscreen(manager, text),
+    PredefinedMenuItemKind::Hide => PredefinedMenuItem::hide(manager, text),
+    PredefinedMenuItemKind::HideOthers => PredefinedMenuItem::hide_others(manager, text),
+    PredefinedMenuItemKind::ShowAll => PredefinedMenuItem::show_all(manager, text),
+    PredefinedMenuItemKind::CloseWindow => PredefinedMenuItem::close_window(manager, text),
+    PredefinedMenuItemKind::Quit => PredefinedMenuItem::quit(manager, text),
+    PredefinedMenuItemKind::About => PredefinedMenuItem::about(
+      manager,
+      text,
+      Some(default_about_metadata(manager.app_handle())),
+    ),
+    PredefinedMenuItemKind::Services => PredefinedMenuItem::services(manager, text),
+  }
+  .map(MenuItemKind::Predefined)
+}
+
+#[cfg(test)]
+mod tests {
+  use tauri_utils::{config::MenuItemConfig, platform::Target};
+
+  use crate::menu::{Menu, MenuItemKind};
+
+  #[test]
+  fn menu_from_config() {
+    let app = crate::test::mock_app();
+    let other_target = if Target::current() == Target::Linux {
+      "windows"
+    } else {
+      "linux"
+    };
+    let items: Vec<MenuItemConfig> = serde_json::from_value(serde_json::json!([
+      {
+        "id": "file",
+        "text": "File",
+        "items": [
+          { "id": "open", "text": "Open", "accelerator": "CmdOrCtrl+O" },
+          { "id": "autosave", "text": "Auto Save", "checked": true },
+          { "item": "separator" },
+          { "id": "other", "text": "Other", "platforms": [other_target] }
+        ]
+      }
+    ]))
+    .unwrap();
+
+    let menu = Menu::from_config(&app, &items).unwrap();
+    let items = menu.items().unwrap();
+    let [MenuItemKind::Submenu(file)] = items.as_slice() else {
+      panic!("expected a submenu");
+    };
+    assert_eq!(file.id().as_ref(), "file");
+
+    let items = file.items().unwrap();
+    assert_eq!(items.len(), 3);
+    assert_eq!(items[0].id().as_ref(), "open");
+    let MenuItemKind::Check(autosave) = &items[1] else {
+      panic!("expected a check menu item");
+    };
+    assert!(autosave.is_checked().unwrap());
+    assert!(matches!(items[2], MenuItemKind::Predefined(_)));
+  }
+}
@@ -5,7 +5,7 @@
 use std::sync::Arc;
 
 use super::run_item_main_thread;
-use super::{IconMenuItem, NativeIcon};
+use super::{IconMenuItem, IsMenuItem, NativeIcon};
 use crate::menu::IconMenuItemInner;
 use crate::run_main_thread;
 use crate::{image::Image, menu::MenuId, AppHandle, Manager, Runtime};
@@ -45,8 +45,10 @@ impl<R: Runtime> IconMenuItem<R> {
         app_handle,
       }
     })?;
+    let item = Self(Arc::new(item));
+    super::accelerators::set_item_accelerator(handle, &item.kind(), accelerator);
 
-    Ok(Self(Arc::new(item)))
+    Ok(item)
   }
 
   /// Create a new menu item with the specified id.
@@ -86,8 +88,10 @@ impl<R: Runtime> IconMenuItem<R> {
         app_handle,
       }
     })?;
+    let item = Self(Arc::new(item));
+    super::accelerators::set_item_accelerator(handle, &item.kind(), accelerator);
 
-    Ok(Self(Arc::new(item)))
+    Ok(item)
   }
 
   /// Create a new icon menu item but with a native icon.
@@ -124,8 +128,10 @@ impl<R: Runtime> IconMenuItem<R> {
         app_handle,
       }
     })?;
+    let item = Self(Arc::new(item));
+    super::accelerators::set_item_accelerator(handle, &item.kind(), accelerator);
 
-    Ok(Self(Arc::new(item)))
+    Ok(item)
   }
 
   /// Create a new icon menu item with the specified id but with a native icon.
@@ -166,8 +172,10 @@ impl<R: Runtime> IconMenuItem<R> {
         app_handle,
       }
     })?;
+    let item = Self(Arc::new(item));
+    super::accelerators::set_item_accelerator(handle, &item.kind(), accelerator);
 
-    Ok(Self(Arc::new(item)))
+    Ok(item)
   }
 
   /// The application handle associated with this type.
@@ -193,6 +201,20 @@ impl<R: Runtime> IconMenuItem<R> {
     run_item_main_thread!(self, |self_: Self| (*self_.0).as_ref().set_text(text))
   }
 
+  /// Binds the text of this menu item to a message key of the catalogs registered with
+  /// [`AppHandle::register_catalog`](crate::AppHandle::register_catalog),
+  /// relabeling it on every [`AppHandle::set_locale`](crate::AppHandle::set_locale) call.
+  ///
+  /// The text is set right away if the active locale has a translation for the key,
+  /// otherwise the item keeps the text it had before being bound to a message key.
+  pub fn set_text_key<S: Into<String>>(&self, key: S) -> crate::Result<()> {
+    super::localize(
+      self.app_handle(),
+      super::MenuItemKind::Icon(self.clone()),
+      key.into(),
+    )
+  }
+
   /// Get whether this menu item is enabled or not.
   pub fn is_enabled(&self) -> crate::Result<bool> {
     run_item_main_thread!(self, |self_: Self| (*self_.0).as_ref().is_enabled())
@@ -206,6 +228,7 @@ impl<R: Runtime> IconMenuItem<R> {
   /// Set this menu item accelerator.
   pub fn set_accelerator<S: AsRef<str>>(&self, accelerator: Option<S>) -> crate::Result<()> {
     let accel = accelerator.and_then(|s| s.as_ref().parse().ok());
+    super::accelerators::set_item_accelerator(self.app_handle(), &self.kind(), accel);
     run_item_main_thread!(self, |self_: Self| (*self_.0)
       .as_ref()
       .set_accelerator(accel))?
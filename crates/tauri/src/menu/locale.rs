@@ -0,0 +1,145 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use super::MenuItemKind;
+use crate::{AppHandle, Runtime};
+
+fn text<R: Runtime>(item: &MenuItemKind<R>) -> crate::Result<String> {
+  match item {
+    MenuItemKind::MenuItem(i) => i.text(),
+    MenuItemKind::Submenu(i) => i.text(),
+    MenuItemKind::Predefined(i) => i.text(),
+    MenuItemKind::Check(i) => i.text(),
+    MenuItemKind::Icon(i) => i.text(),
+  }
+}
+
+fn set_text<R: Runtime>(item: &MenuItemKind<R>, text: &str) -> crate::Result<()> {
+  match item {
+    MenuItemKind::MenuItem(i) => i.set_text(text),
+    MenuItemKind::Submenu(i) => i.set_text(text),
+    MenuItemKind::Predefined(i) => i.set_text(text),
+    MenuItemKind::Check(i) => i.set_text(text),
+    MenuItemKind::Icon(i) => i.set_text(text),
+  }
+}
+
+/// Binds the text of the item to the message key and sets its translation in the active locale.
+///
+/// Without a translation, the item keeps the text it had before being bound to a message key.
+pub(crate) fn localize<R: Runtime>(
+  app_handle: &AppHandle<R>,
+  item: MenuItemKind<R>,
+  key: String,
+) -> crate::Result<()> {
+  let translation = app_handle.manager.locale.translate(&key);
+  let original = app_handle
+    .manager
+    .menu
+    .insert_localized_item(&item, key, text(&item)?);
+  set_text(&item, translation.as_deref().unwrap_or(&original))
+}
+
+/// Sets the translation in the active locale of all the live items bound to a message key,
+/// restoring the original text of the items whose key has no translation.
+pub(crate) fn relabel<R: Runtime>(app_handle: &AppHandle<R>) -> crate::Result<()> {
+  for (item, key, original) in app_handle.manager.menu.localized_items() {
+    let translation = app_handle.manager.locale.translate(&key);
+    set_text(&item, translation.as_deref().unwrap_or(&original))?;
+  }
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    locale::Catalog,
+    menu::{AboutMetadataBuilder, MenuItemBuilder, SubmenuBuilder},
+  };
+
+  #[test]
+  fn relabel_on_locale_change() {
+    let app = crate::test::mock_app();
+    let handle = app.handle();
+    handle.register_catalog(
+      "fr",
+      Catalog::from_iter([("file", "Fichier"), ("file.open", "Ouvrir")]),
+    );
+
+    let open = MenuItemBuilder::new("Open")
+      .text_key("file.open")
+      .build(&app)
+      .unwrap();
+    let file = SubmenuBuilder::new(&app, "File")
+      .text_key("file")
+      .item(&open)
+      .build()
+      .unwrap();
+    let dropped = MenuItemBuilder::new("Dropped")
+      .text_key("file.open")
+      .build(&app)
+      .unwrap();
+    drop(dropped);
+
+    assert_eq!(open.text().unwrap(), "Open");
+
+    handle.set_locale("fr-FR").unwrap();
+    assert_eq!(handle.locale().as_deref(), Some("fr-FR"));
+    assert_eq!(open.text().unwrap(), "Ouvrir");
+    assert_eq!(file.text().unwrap(), "Fichier");
+    assert_eq!(handle.manager.menu.localized_items().len(), 2);
+
+    // items created after the locale change are translated right away
+    let late = MenuItemBuilder::new("Open")
+      .text_key("file.open")
+      .build(&app)
+      .unwrap();
+    assert_eq!(late.text().unwrap(), "Ouvrir");
+
+    // the original text is restored when the locale has no translation
+    handle.set_locale("de").unwrap();
+    assert_eq!(open.text().unwrap(), "Open");
+    assert_eq!(file.text().unwrap(), "File");
+    assert_eq!(late.text().unwrap(), "Open");
+
+    // binding an item again keeps its original text
+    handle.set_locale("fr").unwrap();
+    open.set_text_key("file.missing").unwrap();
+    assert_eq!(open.text().unwrap(), "Open");
+    open.set_text_key("file.open").unwrap();
+    assert_eq!(open.text().unwrap(), "Ouvrir");
+    handle.set_locale("de").unwrap();
+    assert_eq!(open.text().unwrap(), "Open");
+  }
+
+  #[test]
+  fn localize_about_metadata() {
+    let app = crate::test::mock_app();
+    let handle = app.handle();
+    handle.register_catalog(
+      "fr",
+      Catalog::from_iter([
+        ("about.name", "Exemple"),
+        ("about.comments", "Une application"),
+      ]),
+    );
+
+    let metadata = AboutMetadataBuilder::new()
+      .name(Some("Example"))
+      .copyright(Some("(c) Example"))
+      .localize(&app, "about")
+      .build();
+    assert_eq!(metadata.name.as_deref(), Some("Example"));
+
+    handle.set_locale("fr").unwrap();
+    let metadata = AboutMetadataBuilder::new()
+      .name(Some("Example"))
+      .copyright(Some("(c) Example"))
+      .localize(&app, "about")
+      .build();
+    assert_eq!(metadata.name.as_deref(), Some("Exemple"));
+    assert_eq!(metadata.comments.as_deref(), Some("Une application"));
+    assert_eq!(metadata.copyright.as_deref(), Some("(c) Example"));
+  }
+}
@@ -20,6 +20,23 @@ pub const WINDOW_SUBMENU_ID: &str = "__tauri_window_menu__";
 /// Expected submenu id of the Help menu for macOS.
 pub const HELP_SUBMENU_ID: &str = "__tauri_help_menu__";
 
+/// The about metadata built from the package information and the bundle configuration,
+/// translated with the `about.*` messages of the active locale.
+pub(crate) fn default_about_metadata<R: Runtime>(
+  app_handle: &AppHandle<R>,
+) -> AboutMetadata<'static> {
+  let pkg_info = app_handle.package_info();
+  let config = app_handle.config();
+  AboutMetadata {
+    name: Some(pkg_info.name.clone()),
+    version: Some(pkg_info.version.to_string()),
+    copyright: config.bundle.copyright.clone(),
+    authors: config.bundle.publisher.clone().map(|p| vec![p]),
+    ..Default::default()
+  }
+  .localize(app_handle, "about")
+}
+
 impl<R: Runtime> super::ContextMenu for Menu<R> {
   #[cfg(target_os = "windows")]
   fn hpopupmenu(&self) -> crate::Result<isize> {
@@ -148,15 +165,9 @@ impl<R: Runtime> Menu<R> {
 
   /// Creates a menu filled with default menu items and submenus.
   pub fn default(app_handle: &AppHandle<R>) -> crate::Result<Self> {
+    #[cfg(target_os = "macos")]
     let pkg_info = app_handle.package_info();
-    let config = app_handle.config();
-    let about_metadata = AboutMetadata {
-      name: Some(pkg_info.name.clone()),
-      version: Some(pkg_info.version.to_string()),
-      copyright: config.bundle.copyright.clone(),
-      authors: config.bundle.publisher.clone().map(|p| vec![p]),
-      ..Default::default()
-    };
+    let about_metadata = default_about_metadata(app_handle);
 
     let window_menu = Submenu::with_id_and_items(
       app_handle,
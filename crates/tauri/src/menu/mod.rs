@@ -4,18 +4,22 @@
 
 //! Menu types and utilities.
 
+pub(crate) mod accelerators;
 mod builders;
 mod check;
+mod config;
 mod icon;
+mod locale;
 #[allow(clippy::module_inception)]
 mod menu;
 mod normal;
 pub(crate) mod plugin;
 mod predefined;
 mod submenu;
-use std::sync::Arc;
+use std::sync::{Arc, Weak};
 
 pub use builders::*;
+pub(crate) use locale::{localize, relabel};
 pub use menu::{HELP_SUBMENU_ID, WINDOW_SUBMENU_ID};
 use serde::{Deserialize, Serialize};
 
@@ -226,6 +230,32 @@ pub struct AboutMetadata<'a> {
   pub icon: Option<Image<'a>>,
 }
 
+impl AboutMetadata<'_> {
+  /// Translates the text fields in the active locale, see [`AppHandle::set_locale`].
+  ///
+  /// The `name`, `comments`, `copyright`, `license`, `website_label` and `credits` fields
+  /// are replaced with the `{prefix}.name`, `{prefix}.comments`... messages when the active locale defines them.
+  ///
+  /// The about dialog cannot be relabeled once its menu item is created,
+  /// so the item must be recreated with new metadata to follow a locale change.
+  pub fn localize<R: Runtime, M: crate::Manager<R>>(mut self, manager: &M, prefix: &str) -> Self {
+    let locale = &manager.app_handle().manager.locale;
+    for (field, value) in [
+      ("name", &mut self.name),
+      ("comments", &mut self.comments),
+      ("copyright", &mut self.copyright),
+      ("license", &mut self.license),
+      ("website_label", &mut self.website_label),
+      ("credits", &mut self.credits),
+    ] {
+      if let Some(text) = locale.translate(&format!("{prefix}.{field}")) {
+        value.replace(text);
+      }
+    }
+    self
+  }
+}
+
 /// A builder type for [`AboutMetadata`].
 #[derive(Clone, Debug, Default)]
 pub struct AboutMetadataBuilder<'a>(AboutMetadata<'a>);
@@ -324,6 +354,12 @@ impl<'a> AboutMetadataBuilder<'a> {
     self
   }
 
+  /// Translates the text fields in the active locale, see [`AboutMetadata::localize`].
+  pub fn localize<R: Runtime, M: crate::Manager<R>>(mut self, manager: &M, prefix: &str) -> Self {
+    self.0 = self.0.localize(manager, prefix);
+    self
+  }
+
   /// Construct the final [`AboutMetadata`]
   pub fn build(self) -> AboutMetadata<'a> {
     self.0
@@ -701,6 +737,62 @@ impl<R: Runtime> Clone for MenuItemKind<R> {
   }
 }
 
+/// A weak reference to a menu item, so the registries keyed by menu items do not keep dropped items alive.
+pub(crate) enum WeakMenuItem<R: Runtime> {
+  MenuItem(Weak<MenuItemInner<R>>),
+  Submenu(Weak<SubmenuInner<R>>),
+  Predefined(Weak<PredefinedMenuItemInner<R>>),
+  Check(Weak<CheckMenuItemInner<R>>),
+  Icon(Weak<IconMenuItemInner<R>>),
+}
+
+impl<R: Runtime> WeakMenuItem<R> {
+  pub(crate) fn new(item: &MenuItemKind<R>) -> Self {
+    match item {
+      MenuItemKind::MenuItem(i) => Self::MenuItem(Arc::downgrade(&i.0)),
+      MenuItemKind::Submenu(i) => Self::Submenu(Arc::downgrade(&i.0)),
+      MenuItemKind::Predefined(i) => Self::Predefined(Arc::downgrade(&i.0)),
+      MenuItemKind::Check(i) => Self::Check(Arc::downgrade(&i.0)),
+      MenuItemKind::Icon(i) => Self::Icon(Arc::downgrade(&i.0)),
+    }
+  }
+
+  /// Returns the item if it is still alive.
+  pub(crate) fn upgrade(&self) -> Option<MenuItemKind<R>> {
+    match self {
+      Self::MenuItem(i) => i.upgrade().map(|i| MenuItemKind::MenuItem(MenuItem(i))),
+      Self::Submenu(i) => i.upgrade().map(|i| MenuItemKind::Submenu(Submenu(i))),
+      Self::Predefined(i) => i
+        .upgrade()
+        .map(|i| MenuItemKind::Predefined(PredefinedMenuItem(i))),
+      Self::Check(i) => i.upgrade().map(|i| MenuItemKind::Check(CheckMenuItem(i))),
+      Self::Icon(i) => i.upgrade().map(|i| MenuItemKind::Icon(IconMenuItem(i))),
+    }
+  }
+
+  pub(crate) fn is_alive(&self) -> bool {
+    match self {
+      Self::MenuItem(i) => i.strong_count() > 0,
+      Self::Submenu(i) => i.strong_count() > 0,
+      Self::Predefined(i) => i.strong_count() > 0,
+      Self::Check(i) => i.strong_count() > 0,
+      Self::Icon(i) => i.strong_count() > 0,
+    }
+  }
+
+  /// Whether this references the same item as `other`.
+  pub(crate) fn ptr_eq(&self, other: &Self) -> bool {
+    match (self, other) {
+      (Self::MenuItem(a), Self::MenuItem(b)) => a.ptr_eq(b),
+      (Self::Submenu(a), Self::Submenu(b)) => a.ptr_eq(b),
+      (Self::Predefined(a), Self::Predefined(b)) => a.ptr_eq(b),
+      (Self::Check(a), Self::Check(b)) => a.ptr_eq(b),
+      (Self::Icon(a), Self::Icon(b)) => a.ptr_eq(b),
+      _ => false,
+    }
+  }
+}
+
 impl<R: Runtime> sealed::IsMenuItemBase for MenuItemKind<R> {
   fn inner_muda(&self) -> &dyn muda::IsMenuItem {
     self.inner().inner_muda()
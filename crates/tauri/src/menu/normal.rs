@@ -9,7 +9,7 @@ use crate::menu::MenuItemInner;
 use crate::run_main_thread;
 use crate::{menu::MenuId, AppHandle, Manager, Runtime};
 
-use super::MenuItem;
+use super::{IsMenuItem, MenuItem};
 
 impl<R: Runtime> MenuItem<R> {
   /// Create a new menu item.
@@ -41,8 +41,10 @@ impl<R: Runtime> MenuItem<R> {
         app_handle,
       }
     })?;
+    let item = Self(Arc::new(item));
+    super::accelerators::set_item_accelerator(handle, &item.kind(), accelerator);
 
-    Ok(Self(Arc::new(item)))
+    Ok(item)
   }
 
   /// Create a new menu item with the specified id.
@@ -77,8 +79,10 @@ impl<R: Runtime> MenuItem<R> {
         app_handle,
       }
     })?;
+    let item = Self(Arc::new(item));
+    super::accelerators::set_item_accelerator(handle, &item.kind(), accelerator);
 
-    Ok(Self(Arc::new(item)))
+    Ok(item)
   }
 
   /// The application handle associated with this type.
@@ -104,6 +108,20 @@ impl<R: Runtime> MenuItem<R> {
     run_item_main_thread!(self, |self_: Self| (*self_.0).as_ref().set_text(text))
   }
 
+  /// Binds the text of this menu item to a message key of the catalogs registered with
+  /// [`AppHandle::register_catalog`](crate::AppHandle::register_catalog),
+  /// relabeling it on every [`AppHandle::set_locale`](crate::AppHandle::set_locale) call.
+  ///
+  /// The text is set right away if the active locale has a translation for the key,
+  /// otherwise the item keeps the text it had before being bound to a message key.
+  pub fn set_text_key<S: Into<String>>(&self, key: S) -> crate::Result<()> {
+    super::localize(
+      self.app_handle(),
+      super::MenuItemKind::MenuItem(self.clone()),
+      key.into(),
+    )
+  }
+
   /// Get whether this menu item is enabled or not.
   pub fn is_enabled(&self) -> crate::Result<bool> {
     run_item_main_thread!(self, |self_: Self| (*self_.0).as_ref().is_enabled())
@@ -117,6 +135,7 @@ impl<R: Runtime> MenuItem<R> {
   /// Set this menu item accelerator.
   pub fn set_accelerator<S: AsRef<str>>(&self, accelerator: Option<S>) -> crate::Result<()> {
     let accel = accelerator.and_then(|s| s.as_ref().parse().ok());
+    super::accelerators::set_item_accelerator(self.app_handle(), &self.kind(), accel);
     run_item_main_thread!(self, |self_: Self| (*self_.0)
       .as_ref()
       .set_accelerator(accel))?
@@ -867,6 +867,28 @@ fn set_icon<R: Runtime>(
   }
 }
 
+#[command(root = "crate")]
+fn dispatch_accelerator<R: Runtime>(
+  window: Window<R>,
+  code: String,
+  shift: bool,
+  control: bool,
+  alt: bool,
+  meta: bool,
+) -> crate::Result<bool> {
+  use muda::accelerator::{Code, Modifiers};
+
+  let Ok(code) = code.parse::<Code>() else {
+    return Ok(false);
+  };
+  let mut modifiers = Modifiers::empty();
+  modifiers.set(Modifiers::SHIFT, shift);
+  modifiers.set(Modifiers::CONTROL, control);
+  modifiers.set(Modifiers::ALT, alt);
+  modifiers.set(Modifiers::SUPER, meta);
+  accelerators::dispatch(&window, modifiers, code)
+}
+
 struct MenuChannels(Mutex<HashMap<MenuId, Channel<MenuId>>>);
 
 pub(crate) fn init<R: Runtime>() -> TauriPlugin<R> {
@@ -905,6 +927,7 @@ pub(crate) fn init<R: Runtime>() -> TauriPlugin<R> {
       is_checked,
       set_checked,
       set_icon,
+      dispatch_accelerator,
     ])
     .build()
 }
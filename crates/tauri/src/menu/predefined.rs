@@ -5,7 +5,7 @@
 use std::sync::Arc;
 
 use super::run_item_main_thread;
-use super::{AboutMetadata, PredefinedMenuItem};
+use super::{AboutMetadata, IsMenuItem, PredefinedMenuItem};
 use crate::menu::PredefinedMenuItemInner;
 use crate::run_main_thread;
 use crate::{menu::MenuId, AppHandle, Manager, Runtime};
@@ -44,7 +44,7 @@ impl<R: Runtime> PredefinedMenuItem<R> {
       }
     })?;
 
-    Ok(Self(Arc::new(item)))
+    Ok(Self(Arc::new(item)).with_accelerator(Some("CmdOrCtrl+C")))
   }
 
   /// Cut menu item
@@ -63,7 +63,7 @@ impl<R: Runtime> PredefinedMenuItem<R> {
       }
     })?;
 
-    Ok(Self(Arc::new(item)))
+    Ok(Self(Arc::new(item)).with_accelerator(Some("CmdOrCtrl+X")))
   }
 
   /// Paste menu item
@@ -82,7 +82,7 @@ impl<R: Runtime> PredefinedMenuItem<R> {
       }
     })?;
 
-    Ok(Self(Arc::new(item)))
+    Ok(Self(Arc::new(item)).with_accelerator(Some("CmdOrCtrl+V")))
   }
 
   /// SelectAll menu item
@@ -101,7 +101,7 @@ impl<R: Runtime> PredefinedMenuItem<R> {
       }
     })?;
 
-    Ok(Self(Arc::new(item)))
+    Ok(Self(Arc::new(item)).with_accelerator(Some("CmdOrCtrl+A")))
   }
 
   /// Undo menu item
@@ -124,7 +124,7 @@ impl<R: Runtime> PredefinedMenuItem<R> {
       }
     })?;
 
-    Ok(Self(Arc::new(item)))
+    Ok(Self(Arc::new(item)).with_accelerator(Some("CmdOrCtrl+Z")))
   }
   /// Redo menu item
   ///
@@ -146,7 +146,12 @@ impl<R: Runtime> PredefinedMenuItem<R> {
       }
     })?;
 
-    Ok(Self(Arc::new(item)))
+    let accelerator = if cfg!(target_os = "macos") {
+      "CmdOrCtrl+Shift+Z"
+    } else {
+      "CmdOrCtrl+Y"
+    };
+    Ok(Self(Arc::new(item)).with_accelerator(Some(accelerator)))
   }
 
   /// Minimize window menu item
@@ -169,7 +174,7 @@ impl<R: Runtime> PredefinedMenuItem<R> {
       }
     })?;
 
-    Ok(Self(Arc::new(item)))
+    Ok(Self(Arc::new(item)).with_accelerator(Some("CmdOrCtrl+M")))
   }
 
   /// Maximize window menu item
@@ -215,7 +220,8 @@ impl<R: Runtime> PredefinedMenuItem<R> {
       }
     })?;
 
-    Ok(Self(Arc::new(item)))
+    let accelerator = cfg!(target_os = "macos").then_some("Super+Control+F");
+    Ok(Self(Arc::new(item)).with_accelerator(accelerator))
   }
 
   /// Hide window menu item
@@ -238,7 +244,7 @@ impl<R: Runtime> PredefinedMenuItem<R> {
       }
     })?;
 
-    Ok(Self(Arc::new(item)))
+    Ok(Self(Arc::new(item)).with_accelerator(Some("CmdOrCtrl+H")))
   }
 
   /// Hide other windows menu item
@@ -261,7 +267,7 @@ impl<R: Runtime> PredefinedMenuItem<R> {
       }
     })?;
 
-    Ok(Self(Arc::new(item)))
+    Ok(Self(Arc::new(item)).with_accelerator(Some("CmdOrCtrl+Alt+H")))
   }
 
   /// Show all app windows menu item
@@ -307,7 +313,12 @@ impl<R: Runtime> PredefinedMenuItem<R> {
       }
     })?;
 
-    Ok(Self(Arc::new(item)))
+    let accelerator = if cfg!(target_os = "macos") {
+      "CmdOrCtrl+W"
+    } else {
+      "Alt+F4"
+    };
+    Ok(Self(Arc::new(item)).with_accelerator(Some(accelerator)))
   }
 
   /// Quit app menu item
@@ -330,7 +341,8 @@ impl<R: Runtime> PredefinedMenuItem<R> {
       }
     })?;
 
-    Ok(Self(Arc::new(item)))
+    let accelerator = cfg!(target_os = "macos").then_some("CmdOrCtrl+Q");
+    Ok(Self(Arc::new(item)).with_accelerator(accelerator))
   }
 
   /// About app menu item
@@ -384,6 +396,13 @@ impl<R: Runtime> PredefinedMenuItem<R> {
     Ok(Self(Arc::new(item)))
   }
 
+  /// Records the accelerator muda assigns to the item, which it does not expose.
+  fn with_accelerator(self, accelerator: Option<&str>) -> Self {
+    let accelerator = accelerator.and_then(|a| a.parse().ok());
+    super::accelerators::set_item_accelerator(self.app_handle(), &self.kind(), accelerator);
+    self
+  }
+
   /// Returns a unique identifier associated with this menu item.
   pub fn id(&self) -> &MenuId {
     &self.0.id
@@ -402,6 +421,20 @@ impl<R: Runtime> PredefinedMenuItem<R> {
     run_item_main_thread!(self, |self_: Self| (*self_.0).as_ref().set_text(text))
   }
 
+  /// Binds the text of this menu item to a message key of the catalogs registered with
+  /// [`AppHandle::register_catalog`](crate::AppHandle::register_catalog),
+  /// relabeling it on every [`AppHandle::set_locale`](crate::AppHandle::set_locale) call.
+  ///
+  /// The text is set right away if the active locale has a translation for the key,
+  /// otherwise the item keeps the text it had before being bound to a message key.
+  pub fn set_text_key<S: Into<String>>(&self, key: S) -> crate::Result<()> {
+    super::localize(
+      self.app_handle(),
+      super::MenuItemKind::Predefined(self.clone()),
+      key.into(),
+    )
+  }
+
   /// The application handle associated with this type.
   pub fn app_handle(&self) -> &AppHandle<R> {
     &self.0.app_handle
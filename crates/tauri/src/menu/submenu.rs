@@ -277,6 +277,20 @@ impl<R: Runtime> Submenu<R> {
     run_item_main_thread!(self, |self_: Self| (*self_.0).as_ref().set_text(text))
   }
 
+  /// Binds the text of this submenu to a message key of the catalogs registered with
+  /// [`AppHandle::register_catalog`](crate::AppHandle::register_catalog),
+  /// relabeling it on every [`AppHandle::set_locale`](crate::AppHandle::set_locale) call.
+  ///
+  /// The text is set right away if the active locale has a translation for the key,
+  /// otherwise the item keeps the text it had before being bound to a message key.
+  pub fn set_text_key<S: Into<String>>(&self, key: S) -> crate::Result<()> {
+    super::localize(
+      self.app_handle(),
+      super::MenuItemKind::Submenu(self.clone()),
+      key.into(),
+    )
+  }
+
   /// Get whether this submenu is enabled or not.
   pub fn is_enabled(&self) -> crate::Result<bool> {
     run_item_main_thread!(self, |self_: Self| (*self_.0).as_ref().is_enabled())
@@ -3,8 +3,94 @@
 // SPDX-License-Identifier: MIT
 
 use super::{Error, Result};
-use crate::{AppHandle, Manager, Runtime};
-use std::path::PathBuf;
+use crate::{utils::config::AppDirectoriesConfig, AppHandle, Manager, Runtime};
+use std::{
+  ffi::OsString,
+  path::{Path, PathBuf},
+};
+
+/// The file next to the executable that enables portable mode.
+const PORTABLE_MARKER: &str = "portable";
+
+/// Returns the directory the app directory overrides are resolved from.
+///
+/// This is the directory of the executable, or the directory containing the app bundle
+/// when the executable is in `Foo.app/Contents/MacOS`, so portable apps can be distributed
+/// as a folder holding the `.app` bundle and its data.
+#[cfg(any(test, target_os = "macos"))]
+fn app_bundle_parent(exe_dir: &Path) -> &Path {
+  if exe_dir.ends_with("Contents/MacOS") {
+    if let Some(bundle) = exe_dir.parent().and_then(Path::parent) {
+      if bundle.extension().is_some_and(|ext| ext == "app") {
+        return bundle.parent().unwrap_or(exe_dir);
+      }
+    }
+  }
+  exe_dir
+}
+
+/// An app directory that can be overridden.
+#[derive(Debug, Clone, Copy)]
+enum AppDirectory {
+  Config,
+  Data,
+  LocalData,
+  Cache,
+  Log,
+}
+
+impl AppDirectory {
+  fn env_var(self) -> &'static str {
+    match self {
+      Self::Config => "TAURI_APP_CONFIG_DIR",
+      Self::Data => "TAURI_APP_DATA_DIR",
+      Self::LocalData => "TAURI_APP_LOCAL_DATA_DIR",
+      Self::Cache => "TAURI_APP_CACHE_DIR",
+      Self::Log => "TAURI_APP_LOG_DIR",
+    }
+  }
+
+  fn configured(self, config: &AppDirectoriesConfig) -> Option<&PathBuf> {
+    match self {
+      Self::Config => config.config.as_ref(),
+      Self::Data => config.data.as_ref(),
+      Self::LocalData => config.local_data.as_ref(),
+      Self::Cache => config.cache.as_ref(),
+      Self::Log => config.log.as_ref(),
+    }
+  }
+
+  /// The folder next to the executable used in portable mode.
+  fn portable_folder(self) -> &'static str {
+    match self {
+      Self::Config => "config",
+      Self::Data => "data",
+      Self::LocalData => "local-data",
+      Self::Cache => "cache",
+      Self::Log => "logs",
+    }
+  }
+
+  /// Resolves the override of this directory, in order of precedence:
+  /// the environment variable, the configuration and the portable mode.
+  fn resolve_override(
+    self,
+    config: &AppDirectoriesConfig,
+    env_var: impl Fn(&str) -> Option<OsString>,
+    exe_dir: &Path,
+  ) -> Option<PathBuf> {
+    if let Some(path) = env_var(self.env_var()).filter(|path| !path.is_empty()) {
+      return Some(exe_dir.join(path));
+    }
+    if let Some(path) = self.configured(config) {
+      return Some(exe_dir.join(path));
+    }
+    if config.portable || exe_dir.join(PORTABLE_MARKER).is_file() {
+      return Some(exe_dir.join(self.portable_folder()));
+    }
+    None
+  }
+}
 
 /// The path resolver is a helper class for general and application-specific path APIs.
 pub struct PathResolver<R: Runtime>(pub(crate) AppHandle<R>);
@@ -192,10 +278,28 @@ impl<R: Runtime> PathResolver<R> {
       .map_err(|_| Error::UnknownPath)
   }
 
+  /// Returns the override of the app directory, see [`AppDirectoriesConfig`].
+  fn app_dir_override(&self, dir: AppDirectory) -> Result<Option<PathBuf>> {
+    let exe = tauri_utils::platform::current_exe().map_err(|_| Error::UnknownPath)?;
+    let exe_dir = exe.parent().ok_or(Error::UnknownPath)?;
+    #[cfg(target_os = "macos")]
+    let exe_dir = app_bundle_parent(exe_dir);
+    Ok(dir.resolve_override(
+      &self.0.config().app.directories,
+      |var| std::env::var_os(var),
+      exe_dir,
+    ))
+  }
+
   /// Returns the path to the suggested directory for your app's config files.
   ///
-  /// Resolves to [`config_dir`](Self::config_dir)`/${bundle_identifier}`.
+  /// Resolves to [`config_dir`](Self::config_dir)`/${bundle_identifier}`
+  /// unless overridden by the `TAURI_APP_CONFIG_DIR` environment variable,
+  /// the `app > directories` configuration or portable mode, see [`AppDirectoriesConfig`].
   pub fn app_config_dir(&self) -> Result<PathBuf> {
+    if let Some(dir) = self.app_dir_override(AppDirectory::Config)? {
+      return Ok(dir);
+    }
     dirs::config_dir()
       .ok_or(Error::UnknownPath)
       .map(|dir| dir.join(&self.0.config().identifier))
@@ -203,8 +307,13 @@ impl<R: Runtime> PathResolver<R> {
 
   /// Returns the path to the suggested directory for your app's data files.
   ///
-  /// Resolves to [`data_dir`](Self::data_dir)`/${bundle_identifier}`.
+  /// Resolves to [`data_dir`](Self::data_dir)`/${bundle_identifier}`
+  /// unless overridden by the `TAURI_APP_DATA_DIR` environment variable,
+  /// the `app > directories` configuration or portable mode, see [`AppDirectoriesConfig`].
   pub fn app_data_dir(&self) -> Result<PathBuf> {
+    if let Some(dir) = self.app_dir_override(AppDirectory::Data)? {
+      return Ok(dir);
+    }
     dirs::data_dir()
       .ok_or(Error::UnknownPath)
       .map(|dir| dir.join(&self.0.config().identifier))
@@ -212,8 +321,13 @@ impl<R: Runtime> PathResolver<R> {
 
   /// Returns the path to the suggested directory for your app's local data files.
   ///
-  /// Resolves to [`local_data_dir`](Self::local_data_dir)`/${bundle_identifier}`.
+  /// Resolves to [`local_data_dir`](Self::local_data_dir)`/${bundle_identifier}`
+  /// unless overridden by the `TAURI_APP_LOCAL_DATA_DIR` environment variable,
+  /// the `app > directories` configuration or portable mode, see [`AppDirectoriesConfig`].
   pub fn app_local_data_dir(&self) -> Result<PathBuf> {
+    if let Some(dir) = self.app_dir_override(AppDirectory::LocalData)? {
+      return Ok(dir);
+    }
     dirs::data_local_dir()
       .ok_or(Error::UnknownPath)
       .map(|dir| dir.join(&self.0.config().identifier))
@@ -221,8 +335,13 @@ impl<R: Runtime> PathResolver<R> {
 
   /// Returns the path to the suggested directory for your app's cache files.
   ///
-  /// Resolves to [`cache_dir`](Self::cache_dir)`/${bundle_identifier}`.
+  /// Resolves to [`cache_dir`](Self::cache_dir)`/${bundle_identifier}`
+  /// unless overridden by the `TAURI_APP_CACHE_DIR` environment variable,
+  /// the `app > directories` configuration or portable mode, see [`AppDirectoriesConfig`].
   pub fn app_cache_dir(&self) -> Result<PathBuf> {
+    if let Some(dir) = self.app_dir_override(AppDirectory::Cache)? {
+      return Ok(dir);
+    }
     dirs::cache_dir()
       .ok_or(Error::UnknownPath)
       .map(|dir| dir.join(&self.0.config().identifier))
@@ -235,7 +354,14 @@ impl<R: Runtime> PathResolver<R> {
   /// - **Linux:** Resolves to [`local_data_dir`](Self::local_data_dir)`/${bundle_identifier}/logs`.
   /// - **macOS:** Resolves to [`home_dir`](Self::home_dir)`/Library/Logs/${bundle_identifier}`
   /// - **Windows:** Resolves to [`local_data_dir`](Self::local_data_dir)`/${bundle_identifier}/logs`.
+  ///
+  /// Can be overridden by the `TAURI_APP_LOG_DIR` environment variable,
+  /// the `app > directories` configuration or portable mode, see [`AppDirectoriesConfig`].
   pub fn app_log_dir(&self) -> Result<PathBuf> {
+    if let Some(dir) = self.app_dir_override(AppDirectory::Log)? {
+      return Ok(dir);
+    }
+
     #[cfg(target_os = "macos")]
     let path = dirs::home_dir()
       .ok_or(Error::UnknownPath)
@@ -254,3 +380,80 @@ impl<R: Runtime> PathResolver<R> {
     Ok(std::env::temp_dir())
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use std::{ffi::OsString, path::Path};
+
+  use super::{app_bundle_parent, AppDirectory, PORTABLE_MARKER};
+  use crate::utils::config::AppDirectoriesConfig;
+
+  #[test]
+  fn app_dir_overrides() {
+    let exe_dir = std::env::temp_dir().join(format!("tauri-path-test-{}", std::process::id()));
+    std::fs::create_dir_all(&exe_dir).unwrap();
+    let env_data_dir = std::env::temp_dir().join("env-data");
+    let no_env = |_: &str| None;
+    let env = |var: &str| (var == "TAURI_APP_DATA_DIR").then(|| OsString::from(&env_data_dir));
+
+    let mut config = AppDirectoriesConfig::default();
+    assert_eq!(
+      AppDirectory::Data.resolve_override(&config, no_env, &exe_dir),
+      None
+    );
+
+    config.data = Some("relative/data".into());
+    config.log = Some(std::env::temp_dir().join("app-logs"));
+    assert_eq!(
+      AppDirectory::Data.resolve_override(&config, no_env, &exe_dir),
+      Some(exe_dir.join("relative/data"))
+    );
+    assert_eq!(
+      AppDirectory::Log.resolve_override(&config, no_env, &exe_dir),
+      Some(std::env::temp_dir().join("app-logs"))
+    );
+    // the environment variable takes precedence
+    assert_eq!(
+      AppDirectory::Data.resolve_override(&config, env, &exe_dir),
+      Some(env_data_dir.clone())
+    );
+    assert_eq!(
+      AppDirectory::Cache.resolve_override(&config, env, &exe_dir),
+      None
+    );
+
+    // portable mode enabled by the marker file
+    std::fs::write(exe_dir.join(PORTABLE_MARKER), "").unwrap();
+    assert_eq!(
+      AppDirectory::Cache.resolve_override(&config, env, &exe_dir),
+      Some(exe_dir.join("cache"))
+    );
+    assert_eq!(
+      AppDirectory::LocalData.resolve_override(&config, env, &exe_dir),
+      Some(exe_dir.join("local-data"))
+    );
+    assert_eq!(
+      AppDirectory::Data.resolve_override(&config, no_env, &exe_dir),
+      Some(exe_dir.join("relative/data"))
+    );
+
+    std::fs::remove_dir_all(&exe_dir).unwrap();
+  }
+
+  #[test]
+  fn resolves_from_the_app_bundle_parent() {
+    assert_eq!(
+      app_bundle_parent(Path::new("/Apps/Portable/Foo.app/Contents/MacOS")),
+      Path::new("/Apps/Portable")
+    );
+    assert_eq!(
+      app_bundle_parent(Path::new("/opt/foo/bin")),
+      Path::new("/opt/foo/bin")
+    );
+    // not an app bundle
+    assert_eq!(
+      app_bundle_parent(Path::new("/opt/foo/Contents/MacOS")),
+      Path::new("/opt/foo/Contents/MacOS")
+    );
+  }
+}
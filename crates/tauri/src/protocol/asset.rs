@@ -11,8 +11,18 @@ use tokio::fs::File;
 use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
 
 pub fn get(scope: scope::fs::Scope, window_origin: String) -> UriSchemeProtocolHandler {
-  Box::new(
-    move |_, request, responder| match get_response(request, &scope, &window_origin) {
+  Box::new(move |_label, request, responder| {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::trace_span!(
+      "protocol::request",
+      scheme = "asset",
+      webview = _label,
+      method = request.method().as_str(),
+      uri = request.uri().to_string()
+    )
+    .entered();
+
+    match get_response(request, &scope, &window_origin) {
       Ok(response) => responder.respond(response),
       Err(e) => responder.respond(
         http::Response::builder()
@@ -22,8 +32,8 @@ pub fn get(scope: scope::fs::Scope, window_origin: String) -> UriSchemeProtocolH
           .body(e.to_string().as_bytes().to_vec())
           .unwrap(),
       ),
-    },
-  )
+    }
+  })
 }
 
 fn get_response(
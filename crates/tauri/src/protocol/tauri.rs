@@ -42,7 +42,17 @@ pub fn get<R: Runtime>(
   #[cfg(all(dev, mobile))]
   let response_cache = Arc::new(Mutex::new(HashMap::new()));
 
-  Box::new(move |_, request, responder| {
+  Box::new(move |_label, request, responder| {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::trace_span!(
+      "protocol::request",
+      scheme = "tauri",
+      webview = _label,
+      method = request.method().as_str(),
+      uri = request.uri().to_string()
+    )
+    .entered();
+
     match get_response(
       request,
       &manager,
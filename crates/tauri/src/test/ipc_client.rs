@@ -0,0 +1,379 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{
+  collections::{HashMap, HashSet},
+  sync::{
+    atomic::{AtomicU32, Ordering},
+    Arc, Mutex,
+  },
+};
+
+use http::HeaderMap;
+use serde::{de::DeserializeOwned, Serialize, Serializer};
+use serde_json::Value as JsonValue;
+use url::Url;
+
+use super::{ipc_response, MockRuntime, INVOKE_KEY};
+use crate::{
+  event::{EmitArgs, EventId, EventTarget},
+  ipc::{channel::IPC_PAYLOAD_PREFIX, CallbackFn, InvokeBody, InvokeResponseBody},
+  webview::InvokeRequest,
+  Manager, Webview,
+};
+
+static CALLBACK_COUNTER: AtomicU32 = AtomicU32::new(0);
+
+fn next_callback() -> CallbackFn {
+  CallbackFn(CALLBACK_COUNTER.fetch_add(1, Ordering::Relaxed))
+}
+
+/// An event delivered to the frontend of a mock webview, see [`IpcClient::listen`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmittedEvent {
+  /// The event name.
+  pub event: String,
+  /// The event payload.
+  pub payload: JsonValue,
+}
+
+/// The messages sent to the frontend of a webview.
+#[derive(Default)]
+struct WebviewMessages {
+  /// The emitted events and the frontend listeners they were delivered to.
+  events: Vec<(EmittedEvent, Vec<EventId>)>,
+  channels: HashMap<u32, Vec<InvokeResponseBody>>,
+}
+
+/// Records the events and [`crate::ipc::Channel`] messages sent to the webviews by the emit and channel APIs.
+///
+/// Managed by the app when the first [`IpcClient`] is created.
+#[derive(Default)]
+pub(crate) struct IpcRecorder(Mutex<HashMap<String, WebviewMessages>>);
+
+impl IpcRecorder {
+  pub(crate) fn record_event(&self, webview: &str, emit_args: &EmitArgs, ids: &[EventId]) {
+    let event = EmittedEvent {
+      event: emit_args.event_name.clone(),
+      payload: serde_json::from_str(&emit_args.payload).unwrap_or_default(),
+    };
+    self
+      .0
+      .lock()
+      .unwrap()
+      .entry(webview.into())
+      .or_default()
+      .events
+      .push((event, ids.to_vec()));
+  }
+
+  pub(crate) fn record_channel_message(
+    &self,
+    webview: &str,
+    channel: u32,
+    message: &InvokeResponseBody,
+  ) {
+    self
+      .0
+      .lock()
+      .unwrap()
+      .entry(webview.into())
+      .or_default()
+      .channels
+      .entry(channel)
+      .or_default()
+      .push(message.clone());
+  }
+}
+
+/// Plays the role of the frontend of a [`MockRuntime`] webview.
+///
+/// The client sends IPC requests through the same path as the webview IPC handler, including the capability checks,
+/// and records everything the backend sends to the webview: emitted events, [`crate::ipc::Channel`] messages and evaluated scripts.
+///
+/// # Examples
+///
+/// ```rust
+/// use tauri::{ipc::Channel, test::{mock_builder, mock_context, noop_assets, IpcClient}, Emitter};
+///
+/// #[tauri::command]
+/// fn download(on_progress: Channel<u32>) {
+///   for progress in [50, 100] {
+///     on_progress.send(progress).unwrap();
+///   }
+/// }
+///
+/// let app = mock_builder()
+///   .invoke_handler(tauri::generate_handler![download])
+///   .build(mock_context(noop_assets()))
+///   .unwrap();
+/// let webview = tauri::WebviewWindowBuilder::new(&app, "main", Default::default()).build().unwrap();
+///
+/// let client = IpcClient::new(&webview);
+///
+/// let channel = client.channel();
+/// client
+///   .invoke("download", serde_json::json!({ "onProgress": channel }))
+///   .unwrap();
+/// assert_eq!(channel.messages::<u32>(), vec![50, 100]);
+///
+/// client.listen("finished").unwrap();
+/// app.emit("finished", "done").unwrap();
+/// assert_eq!(client.emitted_events()[0].payload, "done");
+/// ```
+#[derive(Clone)]
+pub struct IpcClient {
+  webview: Webview<MockRuntime>,
+  url: Url,
+  /// The frontend listeners registered by this client.
+  listeners: Arc<Mutex<HashSet<EventId>>>,
+}
+
+impl IpcClient {
+  /// Creates a client for the given webview.
+  ///
+  /// The requests are sent from the current webview URL by default, see [`Self::with_url`].
+  pub fn new<W: AsRef<Webview<MockRuntime>>>(webview: &W) -> Self {
+    let webview = webview.as_ref().clone();
+    let url = webview.url().expect("failed to get webview URL");
+    // no-op if another client already started the recording
+    webview.manage(IpcRecorder::default());
+    Self {
+      webview,
+      url,
+      listeners: Default::default(),
+    }
+  }
+
+  /// Sends the requests from the given URL instead of the webview URL.
+  ///
+  /// Useful to test how the capabilities are resolved for remote origins.
+  pub fn with_url(mut self, url: Url) -> Self {
+    self.url = url;
+    self
+  }
+
+  /// The URL the requests are sent from.
+  pub fn url(&self) -> &Url {
+    &self.url
+  }
+
+  /// Runs the given command and returns its response.
+  ///
+  /// Commands rejected by the capabilities return the access error.
+  pub fn invoke<B: Into<InvokeBody>>(
+    &self,
+    cmd: &str,
+    body: B,
+  ) -> Result<InvokeResponseBody, JsonValue> {
+    self.invoke_with_headers(cmd, body, HeaderMap::new())
+  }
+
+  /// Runs the given command with the given request headers and returns its response.
+  pub fn invoke_with_headers<B: Into<InvokeBody>>(
+    &self,
+    cmd: &str,
+    body: B,
+    headers: HeaderMap,
+  ) -> Result<InvokeResponseBody, JsonValue> {
+    ipc_response(
+      &self.webview,
+      InvokeRequest {
+        cmd: cmd.into(),
+        callback: next_callback(),
+        error: next_callback(),
+        url: self.url.clone(),
+        body: body.into(),
+        headers,
+        invoke_key: INVOKE_KEY.to_string(),
+      },
+    )
+  }
+
+  /// Registers a frontend listener for the given event, so its emits are recorded in [`Self::emitted_events`].
+  pub fn listen(&self, event: &str) -> crate::Result<EventId> {
+    let id = self
+      .webview
+      .listen_js(event, EventTarget::Any, next_callback())?;
+    self.listeners.lock().unwrap().insert(id);
+    Ok(id)
+  }
+
+  /// Creates a frontend channel to be passed as a [`crate::ipc::Channel`] command argument.
+  pub fn channel(&self) -> TestChannel {
+    TestChannel {
+      id: next_callback().0,
+      client: self.clone(),
+    }
+  }
+
+  /// The events delivered to the listeners registered with [`Self::listen`], in order.
+  pub fn emitted_events(&self) -> Vec<EmittedEvent> {
+    let listeners = self.listeners.lock().unwrap();
+    self.with_messages(|messages| {
+      messages
+        .events
+        .iter()
+        .filter(|(_, ids)| ids.iter().any(|id| listeners.contains(id)))
+        .map(|(event, _)| event.clone())
+        .collect()
+    })
+  }
+
+  /// All scripts evaluated on the webview, in order.
+  pub fn evaluated_scripts(&self) -> Vec<String> {
+    self.webview.webview.dispatcher.evaluated_scripts()
+  }
+
+  fn with_messages<T: Default>(&self, f: impl FnOnce(&WebviewMessages) -> T) -> T {
+    self
+      .webview
+      .state::<IpcRecorder>()
+      .0
+      .lock()
+      .unwrap()
+      .get(self.webview.label())
+      .map(f)
+      .unwrap_or_default()
+  }
+}
+
+/// A frontend channel created with [`IpcClient::channel`].
+///
+/// Serializes to the channel identifier expected by a [`crate::ipc::Channel`] command argument.
+#[derive(Clone)]
+pub struct TestChannel {
+  id: u32,
+  client: IpcClient,
+}
+
+impl TestChannel {
+  /// The channel identifier.
+  pub fn id(&self) -> u32 {
+    self.id
+  }
+
+  /// The raw messages received by this channel, in order.
+  pub fn raw_messages(&self) -> Vec<InvokeResponseBody> {
+    self
+      .client
+      .with_messages(|messages| messages.channels.get(&self.id).cloned())
+      .unwrap_or_default()
+  }
+
+  /// The messages received by this channel, in order.
+  ///
+  /// # Panics
+  ///
+  /// Panics if a message cannot be deserialized to `T`.
+  pub fn messages<T: DeserializeOwned>(&self) -> Vec<T> {
+    self
+      .raw_messages()
+      .into_iter()
+      .map(|message| {
+        message
+          .deserialize()
+          .expect("failed to deserialize channel message")
+      })
+      .collect()
+  }
+}
+
+impl Serialize for TestChannel {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    serializer.serialize_str(&format!("{IPC_PAYLOAD_PREFIX}{}", self.id))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use serde_json::json;
+  use tauri_utils::acl::{
+    resolved::{Resolved, ResolvedCommand},
+    ExecutionContext,
+  };
+
+  use super::IpcClient;
+  use crate::{
+    ipc::{Channel, RuntimeAuthority},
+    test::{mock_builder, mock_context, noop_assets},
+    Emitter, WebviewWindowBuilder,
+  };
+
+  #[crate::command(root = "crate")]
+  fn stream(on_event: Channel<u32>) {
+    for i in 0..3 {
+      on_event.send(i).unwrap();
+    }
+  }
+
+  #[test]
+  fn records_events_and_channels() {
+    let app = mock_builder()
+      .invoke_handler(crate::generate_handler![stream])
+      .build(mock_context(noop_assets()))
+      .unwrap();
+    let webview = WebviewWindowBuilder::new(&app, "main", Default::default())
+      .build()
+      .unwrap();
+    let client = IpcClient::new(&webview);
+
+    let channel = client.channel();
+    client
+      .invoke("stream", json!({ "onEvent": channel }))
+      .unwrap();
+    assert_eq!(channel.messages::<u32>(), vec![0, 1, 2]);
+
+    client.listen("greet").unwrap();
+    app.emit("ignored", ()).unwrap();
+    app.emit("greet", "hello").unwrap();
+    let events = client.emitted_events();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].event, "greet");
+    assert_eq!(events[0].payload, json!("hello"));
+
+    assert!(!client.evaluated_scripts().is_empty());
+  }
+
+  #[test]
+  fn resolves_capabilities_from_url() {
+    let mut context = mock_context(noop_assets());
+    context.runtime_authority = RuntimeAuthority::new(
+      Default::default(),
+      Resolved {
+        allowed_commands: [(
+          "plugin:event|emit".to_string(),
+          vec![ResolvedCommand {
+            context: ExecutionContext::Remote {
+              url: "https://tauri.app/*".parse().unwrap(),
+            },
+            windows: vec!["main".parse().unwrap()],
+            ..Default::default()
+          }],
+        )]
+        .into_iter()
+        .collect(),
+        ..Default::default()
+      },
+    );
+    let app = mock_builder().build(context).unwrap();
+    let webview = WebviewWindowBuilder::new(&app, "main", Default::default())
+      .build()
+      .unwrap();
+
+    let body = json!({ "event": "ping", "payload": null });
+
+    let local = IpcClient::new(&webview);
+    assert!(local.invoke("plugin:event|emit", body.clone()).is_err());
+
+    let allowed = IpcClient::new(&webview).with_url("https://tauri.app/page".parse().unwrap());
+    assert!(allowed.invoke("plugin:event|emit", body.clone()).is_ok());
+
+    let denied = IpcClient::new(&webview).with_url("https://example.com".parse().unwrap());
+    assert!(denied.invoke("plugin:event|emit", body).is_err());
+  }
+}
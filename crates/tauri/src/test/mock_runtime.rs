@@ -9,11 +9,13 @@ use tauri_runtime::{
   dpi::{PhysicalPosition, PhysicalSize, Position, Size},
   monitor::Monitor,
   webview::{DetachedWebview, PendingWebview},
-  window::{CursorIcon, DetachedWindow, PendingWindow, RawWindow, WindowEvent, WindowId},
+  window::{
+    CursorIcon, DetachedWindow, PendingWindow, RawWindow, WebviewEvent, WindowEvent, WindowId,
+  },
   window::{WindowBuilder, WindowBuilderBase},
   DeviceEventFilter, Error, EventLoopProxy, ExitRequestedEventAction, Icon, ProgressBarState,
   Result, RunEvent, Runtime, RuntimeHandle, RuntimeInitArgs, UserAttentionType, UserEvent,
-  WebviewDispatch, WindowDispatch, WindowEventId,
+  WebviewDispatch, WebviewEventId, WindowDispatch, WindowEventId,
 };
 
 #[cfg(target_os = "macos")]
@@ -30,24 +32,186 @@ use std::{
   fmt,
   sync::{
     atomic::{AtomicBool, AtomicU32, Ordering},
-    mpsc::{channel, sync_channel, Receiver, SyncSender},
+    mpsc::{channel, Receiver, Sender},
     Arc, Mutex,
   },
+  time::{Duration, Instant},
 };
 
 type ShortcutMap = HashMap<String, Box<dyn Fn() + Send + 'static>>;
+type WindowEventHandler = Box<dyn Fn(&WindowEvent) + Send>;
+type WebviewEventHandler = Box<dyn Fn(&WebviewEvent) + Send>;
+type TimerTask = Box<dyn FnOnce() + Send>;
 
 enum Message {
   Task(Box<dyn FnOnce() + Send>),
   CloseWindow(WindowId),
   DestroyWindow(WindowId),
+  WindowEvent(WindowId, WindowEvent),
+  WebviewEvent {
+    label: String,
+    listeners: WebviewEventListeners,
+    event: WebviewEvent,
+  },
+}
+
+#[derive(Clone, Default)]
+struct WindowEventListeners(Arc<Mutex<HashMap<WindowEventId, WindowEventHandler>>>);
+
+impl WindowEventListeners {
+  fn dispatch(&self, event: &WindowEvent) {
+    for handler in self.0.lock().unwrap().values() {
+      handler(event);
+    }
+  }
+}
+
+impl fmt::Debug for WindowEventListeners {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("WindowEventListeners").finish()
+  }
+}
+
+#[derive(Clone, Default)]
+struct WebviewEventListeners(Arc<Mutex<HashMap<WebviewEventId, WebviewEventHandler>>>);
+
+impl WebviewEventListeners {
+  fn dispatch(&self, event: &WebviewEvent) {
+    for handler in self.0.lock().unwrap().values() {
+      handler(event);
+    }
+  }
+}
+
+impl fmt::Debug for WebviewEventListeners {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("WebviewEventListeners").finish()
+  }
 }
 
-struct Webview;
+/// The state of a mock window, updated by the dispatcher setters and the window events.
+#[derive(Debug, Clone)]
+struct WindowState {
+  title: String,
+  position: PhysicalPosition<i32>,
+  size: PhysicalSize<u32>,
+  min_size: Option<PhysicalSize<u32>>,
+  max_size: Option<PhysicalSize<u32>>,
+  scale_factor: f64,
+  visible: bool,
+  focused: bool,
+  maximized: bool,
+  minimized: bool,
+  fullscreen: bool,
+  resizable: bool,
+  maximizable: bool,
+  minimizable: bool,
+  closable: bool,
+  decorated: bool,
+  enabled: bool,
+  theme: Option<Theme>,
+}
+
+impl Default for WindowState {
+  fn default() -> Self {
+    Self {
+      title: "Tauri App".into(),
+      position: PhysicalPosition::new(0, 0),
+      size: PhysicalSize::new(800, 600),
+      min_size: None,
+      max_size: None,
+      scale_factor: 1.0,
+      visible: true,
+      focused: true,
+      maximized: false,
+      minimized: false,
+      fullscreen: false,
+      resizable: true,
+      maximizable: true,
+      minimizable: true,
+      closable: true,
+      decorated: true,
+      enabled: true,
+      theme: None,
+    }
+  }
+}
+
+impl WindowState {
+  fn apply(&mut self, event: &WindowEvent) {
+    match event {
+      WindowEvent::Resized(size) => self.size = *size,
+      WindowEvent::Moved(position) => self.position = *position,
+      WindowEvent::Focused(focused) => self.focused = *focused,
+      WindowEvent::ScaleFactorChanged {
+        scale_factor,
+        new_inner_size,
+      } => {
+        self.scale_factor = *scale_factor;
+        self.size = *new_inner_size;
+      }
+      WindowEvent::ThemeChanged(theme) => self.theme = Some(*theme),
+      _ => (),
+    }
+  }
+
+  fn clamp_size(&self, size: PhysicalSize<u32>) -> PhysicalSize<u32> {
+    let mut size = size;
+    if let Some(min) = self.min_size {
+      size.width = size.width.max(min.width);
+      size.height = size.height.max(min.height);
+    }
+    if let Some(max) = self.max_size {
+      size.width = size.width.min(max.width);
+      size.height = size.height.min(max.height);
+    }
+    size
+  }
+}
+
+/// The state of a mock webview, updated by the dispatcher setters.
+#[derive(Debug)]
+struct WebviewState {
+  history: Vec<String>,
+  history_index: usize,
+  position: PhysicalPosition<i32>,
+  size: PhysicalSize<u32>,
+}
+
+impl WebviewState {
+  fn new(url: String, position: PhysicalPosition<i32>, size: PhysicalSize<u32>) -> Self {
+    Self {
+      history: vec![url],
+      history_index: 0,
+      position,
+      size,
+    }
+  }
+
+  fn url(&self) -> &str {
+    &self.history[self.history_index]
+  }
+
+  fn navigation_state_changed(&self) -> Option<WebviewEvent> {
+    Url::parse(self.url())
+      .ok()
+      .map(|url| WebviewEvent::NavigationStateChanged {
+        url,
+        can_go_back: self.history_index > 0,
+        can_go_forward: self.history_index + 1 < self.history.len(),
+      })
+  }
+}
+
+struct Webview {
+  id: u32,
+}
 
 struct Window {
   label: String,
   webviews: Vec<Webview>,
+  state: Arc<Mutex<WindowState>>,
+  listeners: WindowEventListeners,
 }
 
 #[derive(Clone)]
@@ -55,11 +219,12 @@ pub struct RuntimeContext {
   is_running: Arc<AtomicBool>,
   windows: Arc<RefCell<HashMap<WindowId, Window>>>,
   shortcuts: Arc<Mutex<ShortcutMap>>,
-  run_tx: SyncSender<Message>,
+  run_tx: Sender<Message>,
   next_window_id: Arc<AtomicU32>,
   next_webview_id: Arc<AtomicU32>,
   next_window_event_id: Arc<AtomicU32>,
   next_webview_event_id: Arc<AtomicU32>,
+  timers: Arc<Mutex<Vec<(Instant, TimerTask)>>>,
 }
 
 // SAFETY: we ensure this type is only used on the main thread.
@@ -81,8 +246,25 @@ impl RuntimeContext {
       match message {
         Message::Task(task) => task(),
         Message::CloseWindow(id) | Message::DestroyWindow(id) => {
-          self.windows.borrow_mut().remove(&id);
+          let window = self.windows.borrow_mut().remove(&id);
+          if let Some(window) = window {
+            window.listeners.dispatch(&WindowEvent::Destroyed);
+          }
         }
+        Message::WindowEvent(id, event) => {
+          let window = self
+            .windows
+            .borrow()
+            .get(&id)
+            .map(|w| (w.state.clone(), w.listeners.clone()));
+          if let Some((state, listeners)) = window {
+            state.lock().unwrap().apply(&event);
+            listeners.dispatch(&event);
+          }
+        }
+        Message::WebviewEvent {
+          listeners, event, ..
+        } => listeners.dispatch(&event),
       }
       Ok(())
     }
@@ -100,9 +282,81 @@ impl RuntimeContext {
     self.next_window_event_id.fetch_add(1, Ordering::Relaxed)
   }
 
-  fn next_webview_event_id(&self) -> WindowEventId {
+  fn next_webview_event_id(&self) -> WebviewEventId {
     self.next_webview_event_id.fetch_add(1, Ordering::Relaxed)
   }
+
+  fn create_window<T: UserEvent>(
+    &self,
+    pending: PendingWindow<T, MockRuntime>,
+  ) -> Result<DetachedWindow<T, MockRuntime>> {
+    let id = self.next_window_id();
+    let state = Arc::new(Mutex::new(pending.window_builder.state));
+    let listeners = WindowEventListeners::default();
+
+    self.windows.borrow_mut().insert(
+      id,
+      Window {
+        label: pending.label.clone(),
+        webviews: Vec::new(),
+        state: state.clone(),
+        listeners: listeners.clone(),
+      },
+    );
+
+    let webview = pending
+      .webview
+      .map(|webview| self.create_webview(id, webview))
+      .transpose()?;
+
+    Ok(DetachedWindow {
+      id,
+      label: pending.label,
+      dispatcher: MockWindowDispatcher {
+        id,
+        context: self.clone(),
+        state,
+        listeners,
+      },
+      webview,
+    })
+  }
+
+  fn create_webview<T: UserEvent>(
+    &self,
+    window_id: WindowId,
+    pending: PendingWebview<T, MockRuntime>,
+  ) -> Result<DetachedWebview<T, MockRuntime>> {
+    let id = self.next_webview_id();
+
+    let mut windows = self.windows.borrow_mut();
+    let window = windows.get_mut(&window_id);
+
+    let (position, size) = match (pending.webview_attributes.bounds, &window) {
+      (Some(bounds), _) => (bounds.position.to_physical(1.), bounds.size.to_physical(1.)),
+      (None, Some(window)) => (
+        PhysicalPosition::new(0, 0),
+        window.state.lock().unwrap().size,
+      ),
+      (None, None) => Default::default(),
+    };
+
+    if let Some(window) = window {
+      window.webviews.push(Webview { id });
+    }
+
+    Ok(DetachedWebview {
+      label: pending.label.clone(),
+      dispatcher: MockWebviewDispatcher {
+        id,
+        label: pending.label,
+        context: self.clone(),
+        state: Arc::new(Mutex::new(WebviewState::new(pending.url, position, size))),
+        listeners: Default::default(),
+        evaluated_scripts: Default::default(),
+      },
+    })
+  }
 }
 
 impl fmt::Debug for RuntimeContext {
@@ -142,41 +396,7 @@ impl<T: UserEvent> RuntimeHandle<T> for MockRuntimeHandle {
     pending: PendingWindow<T, Self::Runtime>,
     _after_window_creation: Option<F>,
   ) -> Result<DetachedWindow<T, Self::Runtime>> {
-    let id = self.context.next_window_id();
-
-    let (webview_id, webviews) = if let Some(w) = &pending.webview {
-      (Some(self.context.next_webview_id()), vec![Webview])
-    } else {
-      (None, Vec::new())
-    };
-
-    self.context.windows.borrow_mut().insert(
-      id,
-      Window {
-        label: pending.label.clone(),
-        webviews,
-      },
-    );
-
-    let webview = webview_id.map(|id| DetachedWebview {
-      label: pending.label.clone(),
-      dispatcher: MockWebviewDispatcher {
-        id,
-        context: self.context.clone(),
-        url: Arc::new(Mutex::new(pending.webview.unwrap().url)),
-        last_evaluated_script: Default::default(),
-      },
-    });
-
-    Ok(DetachedWindow {
-      id,
-      label: pending.label,
-      dispatcher: MockWindowDispatcher {
-        id,
-        context: self.context.clone(),
-      },
-      webview,
-    })
+    self.context.create_window(pending)
   }
 
   fn create_webview(
@@ -184,21 +404,7 @@ impl<T: UserEvent> RuntimeHandle<T> for MockRuntimeHandle {
     window_id: WindowId,
     pending: PendingWebview<T, Self::Runtime>,
   ) -> Result<DetachedWebview<T, Self::Runtime>> {
-    let id = self.context.next_webview_id();
-    let webview = Webview;
-    if let Some(w) = self.context.windows.borrow_mut().get_mut(&window_id) {
-      w.webviews.push(webview);
-    }
-
-    Ok(DetachedWebview {
-      label: pending.label,
-      dispatcher: MockWebviewDispatcher {
-        id,
-        context: self.context.clone(),
-        last_evaluated_script: Default::default(),
-        url: Arc::new(Mutex::new(pending.url)),
-      },
-    })
+    self.context.create_webview(window_id, pending)
   }
 
   /// Run a task on the main thread.
@@ -206,6 +412,20 @@ impl<T: UserEvent> RuntimeHandle<T> for MockRuntimeHandle {
     self.context.send_message(Message::Task(Box::new(f)))
   }
 
+  fn run_on_main_thread_after<F: FnOnce() + Send + 'static>(
+    &self,
+    delay: Duration,
+    f: F,
+  ) -> Result<()> {
+    self
+      .context
+      .timers
+      .lock()
+      .unwrap()
+      .push((Instant::now() + delay, Box::new(f)));
+    Ok(())
+  }
+
   fn display_handle(
     &self,
   ) -> std::result::Result<raw_window_handle::DisplayHandle<'_>, raw_window_handle::HandleError> {
@@ -285,14 +505,48 @@ impl<T: UserEvent> RuntimeHandle<T> for MockRuntimeHandle {
 #[derive(Debug, Clone)]
 pub struct MockWebviewDispatcher {
   id: u32,
+  label: String,
   context: RuntimeContext,
-  url: Arc<Mutex<String>>,
-  last_evaluated_script: Arc<Mutex<Option<String>>>,
+  state: Arc<Mutex<WebviewState>>,
+  listeners: WebviewEventListeners,
+  evaluated_scripts: Arc<Mutex<Vec<String>>>,
 }
 
 impl MockWebviewDispatcher {
+  /// The last script evaluated on this webview.
   pub fn last_evaluated_script(&self) -> Option<String> {
-    self.last_evaluated_script.lock().unwrap().clone()
+    self.evaluated_scripts.lock().unwrap().last().cloned()
+  }
+
+  /// All scripts evaluated on this webview, in order.
+  pub fn evaluated_scripts(&self) -> Vec<String> {
+    self.evaluated_scripts.lock().unwrap().clone()
+  }
+
+  /// Sends a webview event as if it was emitted by the webview.
+  ///
+  /// The event is delivered on the next iteration of the event loop.
+  pub fn send_event(&self, event: WebviewEvent) -> Result<()> {
+    self.context.send_message(Message::WebviewEvent {
+      label: self.label.clone(),
+      listeners: self.listeners.clone(),
+      event,
+    })
+  }
+
+  fn navigate_history(&self, f: impl FnOnce(&mut WebviewState) -> bool) -> Result<()> {
+    let event = {
+      let mut state = self.state.lock().unwrap();
+      if f(&mut state) {
+        state.navigation_state_changed()
+      } else {
+        None
+      }
+    };
+    if let Some(event) = event {
+      self.send_event(event)?;
+    }
+    Ok(())
   }
 }
 
@@ -300,39 +554,91 @@ impl MockWebviewDispatcher {
 pub struct MockWindowDispatcher {
   id: WindowId,
   context: RuntimeContext,
+  state: Arc<Mutex<WindowState>>,
+  listeners: WindowEventListeners,
 }
 
-#[derive(Debug, Clone)]
-pub struct MockWindowBuilder {}
+impl MockWindowDispatcher {
+  /// Sends a window event as if it was emitted by the windowing system.
+  ///
+  /// The event is delivered on the next iteration of the event loop.
+  pub fn send_event(&self, event: WindowEvent) -> Result<()> {
+    self
+      .context
+      .send_message(Message::WindowEvent(self.id, event))
+  }
+
+  fn update(&self, f: impl FnOnce(&mut WindowState)) -> Result<()> {
+    f(&mut self.state.lock().unwrap());
+    Ok(())
+  }
+
+  fn state<V>(&self, f: impl FnOnce(&WindowState) -> V) -> Result<V> {
+    Ok(f(&self.state.lock().unwrap()))
+  }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MockWindowBuilder {
+  state: WindowState,
+}
 
 impl WindowBuilderBase for MockWindowBuilder {}
 
 impl WindowBuilder for MockWindowBuilder {
   fn new() -> Self {
-    Self {}
+    Self::default()
   }
 
   fn with_config(config: &WindowConfig) -> Self {
-    Self {}
+    let mut builder = Self::new()
+      .title(config.title.clone())
+      .inner_size(config.width, config.height)
+      .resizable(config.resizable)
+      .maximizable(config.maximizable)
+      .minimizable(config.minimizable)
+      .closable(config.closable)
+      .fullscreen(config.fullscreen)
+      .focused(config.focus)
+      .maximized(config.maximized)
+      .visible(config.visible)
+      .decorations(config.decorations)
+      .theme(config.theme);
+
+    if let (Some(x), Some(y)) = (config.x, config.y) {
+      builder = builder.position(x, y);
+    }
+    if let (Some(min_width), Some(min_height)) = (config.min_width, config.min_height) {
+      builder = builder.min_inner_size(min_width, min_height);
+    }
+    if let (Some(max_width), Some(max_height)) = (config.max_width, config.max_height) {
+      builder = builder.max_inner_size(max_width, max_height);
+    }
+
+    builder
   }
 
   fn center(self) -> Self {
     self
   }
 
-  fn position(self, x: f64, y: f64) -> Self {
+  fn position(mut self, x: f64, y: f64) -> Self {
+    self.state.position = PhysicalPosition::new(x as i32, y as i32);
     self
   }
 
-  fn inner_size(self, min_width: f64, min_height: f64) -> Self {
+  fn inner_size(mut self, width: f64, height: f64) -> Self {
+    self.state.size = PhysicalSize::new(width as u32, height as u32);
     self
   }
 
-  fn min_inner_size(self, min_width: f64, min_height: f64) -> Self {
+  fn min_inner_size(mut self, min_width: f64, min_height: f64) -> Self {
+    self.state.min_size = Some(PhysicalSize::new(min_width as u32, min_height as u32));
     self
   }
 
-  fn max_inner_size(self, max_width: f64, max_height: f64) -> Self {
+  fn max_inner_size(mut self, max_width: f64, max_height: f64) -> Self {
+    self.state.max_size = Some(PhysicalSize::new(max_width as u32, max_height as u32));
     self
   }
 
@@ -343,39 +649,48 @@ impl WindowBuilder for MockWindowBuilder {
     self
   }
 
-  fn resizable(self, resizable: bool) -> Self {
+  fn resizable(mut self, resizable: bool) -> Self {
+    self.state.resizable = resizable;
     self
   }
 
-  fn maximizable(self, resizable: bool) -> Self {
+  fn maximizable(mut self, maximizable: bool) -> Self {
+    self.state.maximizable = maximizable;
     self
   }
 
-  fn minimizable(self, resizable: bool) -> Self {
+  fn minimizable(mut self, minimizable: bool) -> Self {
+    self.state.minimizable = minimizable;
     self
   }
 
-  fn closable(self, resizable: bool) -> Self {
+  fn closable(mut self, closable: bool) -> Self {
+    self.state.closable = closable;
     self
   }
 
-  fn title<S: Into<String>>(self, title: S) -> Self {
+  fn title<S: Into<String>>(mut self, title: S) -> Self {
+    self.state.title = title.into();
     self
   }
 
-  fn fullscreen(self, fullscreen: bool) -> Self {
+  fn fullscreen(mut self, fullscreen: bool) -> Self {
+    self.state.fullscreen = fullscreen;
     self
   }
 
-  fn focused(self, focused: bool) -> Self {
+  fn focused(mut self, focused: bool) -> Self {
+    self.state.focused = focused;
     self
   }
 
-  fn maximized(self, maximized: bool) -> Self {
+  fn maximized(mut self, maximized: bool) -> Self {
+    self.state.maximized = maximized;
     self
   }
 
-  fn visible(self, visible: bool) -> Self {
+  fn visible(mut self, visible: bool) -> Self {
+    self.state.visible = visible;
     self
   }
 
@@ -388,7 +703,8 @@ impl WindowBuilder for MockWindowBuilder {
     self
   }
 
-  fn decorations(self, decorations: bool) -> Self {
+  fn decorations(mut self, decorations: bool) -> Self {
+    self.state.decorated = decorations;
     self
   }
 
@@ -466,7 +782,8 @@ impl WindowBuilder for MockWindowBuilder {
     self
   }
 
-  fn theme(self, theme: Option<Theme>) -> Self {
+  fn theme(mut self, theme: Option<Theme>) -> Self {
+    self.state.theme = theme;
     self
   }
 
@@ -475,7 +792,7 @@ impl WindowBuilder for MockWindowBuilder {
   }
 
   fn get_theme(&self) -> Option<Theme> {
-    None
+    self.state.theme
   }
 }
 
@@ -490,7 +807,9 @@ impl<T: UserEvent> WebviewDispatch<T> for MockWebviewDispatcher {
     &self,
     f: F,
   ) -> tauri_runtime::WebviewEventId {
-    self.context.next_window_event_id()
+    let id = self.context.next_webview_event_id();
+    self.listeners.0.lock().unwrap().insert(id, Box::new(f));
+    id
   }
 
   fn with_webview<F: FnOnce(Box<dyn std::any::Any>) + Send + 'static>(&self, f: F) -> Result<()> {
@@ -513,35 +832,69 @@ impl<T: UserEvent> WebviewDispatch<T> for MockWebviewDispatcher {
   }
 
   fn eval_script<S: Into<String>>(&self, script: S) -> Result<()> {
-    self
-      .last_evaluated_script
-      .lock()
-      .unwrap()
-      .replace(script.into());
+    self.evaluated_scripts.lock().unwrap().push(script.into());
     Ok(())
   }
 
   fn url(&self) -> Result<String> {
-    Ok(self.url.lock().unwrap().clone())
+    Ok(self.state.lock().unwrap().url().to_string())
   }
 
   fn bounds(&self) -> Result<tauri_runtime::Rect> {
-    Ok(tauri_runtime::Rect::default())
+    let state = self.state.lock().unwrap();
+    Ok(tauri_runtime::Rect {
+      position: state.position.into(),
+      size: state.size.into(),
+    })
   }
 
   fn position(&self) -> Result<PhysicalPosition<i32>> {
-    Ok(PhysicalPosition { x: 0, y: 0 })
+    Ok(self.state.lock().unwrap().position)
   }
 
   fn size(&self) -> Result<PhysicalSize<u32>> {
-    Ok(PhysicalSize {
-      width: 0,
-      height: 0,
-    })
+    Ok(self.state.lock().unwrap().size)
+  }
+
+  fn can_go_back(&self) -> Result<bool> {
+    Ok(self.state.lock().unwrap().history_index > 0)
+  }
+
+  fn can_go_forward(&self) -> Result<bool> {
+    let state = self.state.lock().unwrap();
+    Ok(state.history_index + 1 < state.history.len())
   }
 
   fn navigate(&self, url: Url) -> Result<()> {
-    *self.url.lock().unwrap() = url.to_string();
+    self.navigate_history(|state| {
+      state.history.truncate(state.history_index + 1);
+      state.history.push(url.to_string());
+      state.history_index += 1;
+      true
+    })
+  }
+
+  fn go_back(&self) -> Result<()> {
+    self.navigate_history(|state| {
+      let can_go_back = state.history_index > 0;
+      if can_go_back {
+        state.history_index -= 1;
+      }
+      can_go_back
+    })
+  }
+
+  fn go_forward(&self) -> Result<()> {
+    self.navigate_history(|state| {
+      let can_go_forward = state.history_index + 1 < state.history.len();
+      if can_go_forward {
+        state.history_index += 1;
+      }
+      can_go_forward
+    })
+  }
+
+  fn reload(&self, bypass_cache: bool) -> Result<()> {
     Ok(())
   }
 
@@ -550,18 +903,26 @@ impl<T: UserEvent> WebviewDispatch<T> for MockWebviewDispatcher {
   }
 
   fn close(&self) -> Result<()> {
+    for window in self.context.windows.borrow_mut().values_mut() {
+      window.webviews.retain(|w| w.id != self.id);
+    }
     Ok(())
   }
 
   fn set_bounds(&self, bounds: tauri_runtime::Rect) -> Result<()> {
+    let mut state = self.state.lock().unwrap();
+    state.position = bounds.position.to_physical(1.);
+    state.size = bounds.size.to_physical(1.);
     Ok(())
   }
 
-  fn set_size(&self, _size: Size) -> Result<()> {
+  fn set_size(&self, size: Size) -> Result<()> {
+    self.state.lock().unwrap().size = size.to_physical(1.);
     Ok(())
   }
 
-  fn set_position(&self, _position: Position) -> Result<()> {
+  fn set_position(&self, position: Position) -> Result<()> {
+    self.state.lock().unwrap().position = position.to_physical(1.);
     Ok(())
   }
 
@@ -570,6 +931,13 @@ impl<T: UserEvent> WebviewDispatch<T> for MockWebviewDispatcher {
   }
 
   fn reparent(&self, window_id: WindowId) -> Result<()> {
+    let mut windows = self.context.windows.borrow_mut();
+    for window in windows.values_mut() {
+      window.webviews.retain(|w| w.id != self.id);
+    }
+    if let Some(window) = windows.get_mut(&window_id) {
+      window.webviews.push(Webview { id: self.id });
+    }
     Ok(())
   }
 
@@ -600,77 +968,73 @@ impl<T: UserEvent> WindowDispatch<T> for MockWindowDispatcher {
   }
 
   fn on_window_event<F: Fn(&WindowEvent) + Send + 'static>(&self, f: F) -> WindowEventId {
-    self.context.next_window_event_id()
+    let id = self.context.next_window_event_id();
+    self.listeners.0.lock().unwrap().insert(id, Box::new(f));
+    id
   }
 
   fn scale_factor(&self) -> Result<f64> {
-    Ok(1.0)
+    self.state(|s| s.scale_factor)
   }
 
   fn inner_position(&self) -> Result<PhysicalPosition<i32>> {
-    Ok(PhysicalPosition { x: 0, y: 0 })
+    self.state(|s| s.position)
   }
 
   fn outer_position(&self) -> Result<PhysicalPosition<i32>> {
-    Ok(PhysicalPosition { x: 0, y: 0 })
+    self.state(|s| s.position)
   }
 
   fn inner_size(&self) -> Result<PhysicalSize<u32>> {
-    Ok(PhysicalSize {
-      width: 0,
-      height: 0,
-    })
+    self.state(|s| s.size)
   }
 
   fn outer_size(&self) -> Result<PhysicalSize<u32>> {
-    Ok(PhysicalSize {
-      width: 0,
-      height: 0,
-    })
+    self.state(|s| s.size)
   }
 
   fn is_fullscreen(&self) -> Result<bool> {
-    Ok(false)
+    self.state(|s| s.fullscreen)
   }
 
   fn is_minimized(&self) -> Result<bool> {
-    Ok(false)
+    self.state(|s| s.minimized)
   }
 
   fn is_maximized(&self) -> Result<bool> {
-    Ok(false)
+    self.state(|s| s.maximized)
   }
 
   fn is_focused(&self) -> Result<bool> {
-    Ok(false)
+    self.state(|s| s.focused)
   }
 
   fn is_decorated(&self) -> Result<bool> {
-    Ok(false)
+    self.state(|s| s.decorated)
   }
 
   fn is_resizable(&self) -> Result<bool> {
-    Ok(false)
+    self.state(|s| s.resizable)
   }
 
   fn is_maximizable(&self) -> Result<bool> {
-    Ok(true)
+    self.state(|s| s.maximizable)
   }
 
   fn is_minimizable(&self) -> Result<bool> {
-    Ok(true)
+    self.state(|s| s.minimizable)
   }
 
   fn is_closable(&self) -> Result<bool> {
-    Ok(true)
+    self.state(|s| s.closable)
   }
 
   fn is_visible(&self) -> Result<bool> {
-    Ok(true)
+    self.state(|s| s.visible)
   }
 
   fn title(&self) -> Result<String> {
-    Ok(String::new())
+    self.state(|s| s.title.clone())
   }
 
   fn current_monitor(&self) -> Result<Option<Monitor>> {
@@ -690,7 +1054,7 @@ impl<T: UserEvent> WindowDispatch<T> for MockWindowDispatcher {
   }
 
   fn theme(&self) -> Result<Theme> {
-    Ok(Theme::Light)
+    self.state(|s| s.theme.unwrap_or(Theme::Light))
   }
 
   #[cfg(any(
@@ -701,7 +1065,8 @@ impl<T: UserEvent> WindowDispatch<T> for MockWindowDispatcher {
     target_os = "openbsd"
   ))]
   fn gtk_window(&self) -> Result<gtk::ApplicationWindow> {
-    unimplemented!()
+    // there is no native window to attach menus to, their state is tracked by the `Window`
+    Err(Error::WindowNotFound)
   }
 
   #[cfg(any(
@@ -712,7 +1077,7 @@ impl<T: UserEvent> WindowDispatch<T> for MockWindowDispatcher {
     target_os = "openbsd"
   ))]
   fn default_vbox(&self) -> Result<gtk::Box> {
-    unimplemented!()
+    Err(Error::WindowNotFound)
   }
 
   fn window_handle(
@@ -757,106 +1122,59 @@ impl<T: UserEvent> WindowDispatch<T> for MockWindowDispatcher {
     pending: PendingWindow<T, Self::Runtime>,
     _after_window_creation: Option<F>,
   ) -> Result<DetachedWindow<T, Self::Runtime>> {
-    let id = self.context.next_window_id();
-
-    let (webview_id, webviews) = if let Some(w) = &pending.webview {
-      (Some(self.context.next_webview_id()), vec![Webview])
-    } else {
-      (None, Vec::new())
-    };
-
-    self.context.windows.borrow_mut().insert(
-      id,
-      Window {
-        label: pending.label.clone(),
-        webviews,
-      },
-    );
-
-    let webview = webview_id.map(|id| DetachedWebview {
-      label: pending.label.clone(),
-      dispatcher: MockWebviewDispatcher {
-        id,
-        context: self.context.clone(),
-        url: Arc::new(Mutex::new(pending.webview.unwrap().url)),
-        last_evaluated_script: Default::default(),
-      },
-    });
-
-    Ok(DetachedWindow {
-      id,
-      label: pending.label,
-      dispatcher: MockWindowDispatcher {
-        id,
-        context: self.context.clone(),
-      },
-      webview,
-    })
+    self.context.create_window(pending)
   }
 
   fn create_webview(
     &mut self,
     pending: PendingWebview<T, Self::Runtime>,
   ) -> Result<DetachedWebview<T, Self::Runtime>> {
-    let id = self.context.next_webview_id();
-    let webview = Webview;
-    if let Some(w) = self.context.windows.borrow_mut().get_mut(&self.id) {
-      w.webviews.push(webview);
-    }
-
-    Ok(DetachedWebview {
-      label: pending.label,
-      dispatcher: MockWebviewDispatcher {
-        id,
-        context: self.context.clone(),
-        last_evaluated_script: Default::default(),
-        url: Arc::new(Mutex::new(pending.url)),
-      },
-    })
+    self.context.create_webview(self.id, pending)
   }
 
   fn set_resizable(&self, resizable: bool) -> Result<()> {
-    Ok(())
+    self.update(|s| s.resizable = resizable)
   }
 
   fn set_maximizable(&self, maximizable: bool) -> Result<()> {
-    Ok(())
+    self.update(|s| s.maximizable = maximizable)
   }
 
   fn set_minimizable(&self, minimizable: bool) -> Result<()> {
-    Ok(())
+    self.update(|s| s.minimizable = minimizable)
   }
 
   fn set_closable(&self, closable: bool) -> Result<()> {
-    Ok(())
+    self.update(|s| s.closable = closable)
   }
 
   fn set_title<S: Into<String>>(&self, title: S) -> Result<()> {
-    Ok(())
+    let title = title.into();
+    self.update(|s| s.title = title)
   }
 
   fn maximize(&self) -> Result<()> {
-    Ok(())
+    self.update(|s| s.maximized = true)
   }
 
   fn unmaximize(&self) -> Result<()> {
-    Ok(())
+    self.update(|s| s.maximized = false)
   }
 
   fn minimize(&self) -> Result<()> {
-    Ok(())
+    self.update(|s| s.minimized = true)
   }
 
   fn unminimize(&self) -> Result<()> {
-    Ok(())
+    self.update(|s| s.minimized = false)
   }
 
   fn show(&self) -> Result<()> {
-    Ok(())
+    self.update(|s| s.visible = true)
   }
 
   fn hide(&self) -> Result<()> {
-    Ok(())
+    self.update(|s| s.visible = false)
   }
 
   fn close(&self) -> Result<()> {
@@ -870,7 +1188,7 @@ impl<T: UserEvent> WindowDispatch<T> for MockWindowDispatcher {
   }
 
   fn set_decorations(&self, decorations: bool) -> Result<()> {
-    Ok(())
+    self.update(|s| s.decorated = decorations)
   }
 
   fn set_shadow(&self, shadow: bool) -> Result<()> {
@@ -894,26 +1212,53 @@ impl<T: UserEvent> WindowDispatch<T> for MockWindowDispatcher {
   }
 
   fn set_size(&self, size: Size) -> Result<()> {
-    Ok(())
+    let size = self.state(|s| s.clamp_size(size.to_physical(s.scale_factor)))?;
+    self.update(|s| s.size = size)?;
+    self.send_event(WindowEvent::Resized(size))
   }
 
   fn set_min_size(&self, size: Option<Size>) -> Result<()> {
-    Ok(())
+    self.update(|s| s.min_size = size.map(|size| size.to_physical(s.scale_factor)))
   }
 
   fn set_max_size(&self, size: Option<Size>) -> Result<()> {
-    Ok(())
+    self.update(|s| s.max_size = size.map(|size| size.to_physical(s.scale_factor)))
   }
 
   fn set_position(&self, position: Position) -> Result<()> {
-    Ok(())
+    let position = self.state(|s| position.to_physical(s.scale_factor))?;
+    self.update(|s| s.position = position)?;
+    self.send_event(WindowEvent::Moved(position))
   }
 
   fn set_fullscreen(&self, fullscreen: bool) -> Result<()> {
-    Ok(())
+    self.update(|s| s.fullscreen = fullscreen)
   }
 
   fn set_focus(&self) -> Result<()> {
+    // only one window can be focused at a time
+    let blurred_windows = self
+      .context
+      .windows
+      .borrow()
+      .iter()
+      .filter(|(id, w)| **id != self.id && std::mem::take(&mut w.state.lock().unwrap().focused))
+      .map(|(id, _)| *id)
+      .collect::<Vec<_>>();
+    for id in blurred_windows {
+      self
+        .context
+        .send_message(Message::WindowEvent(id, WindowEvent::Focused(false)))?;
+    }
+
+    let was_focused = self.state(|s| s.focused)?;
+    self.update(|s| {
+      s.focused = true;
+      s.minimized = false;
+    })?;
+    if !was_focused {
+      self.send_event(WindowEvent::Focused(true))?;
+    }
     Ok(())
   }
 
@@ -969,15 +1314,15 @@ impl<T: UserEvent> WindowDispatch<T> for MockWindowDispatcher {
   }
 
   fn set_theme(&self, theme: Option<Theme>) -> Result<()> {
-    Ok(())
+    self.update(|s| s.theme = theme)
   }
 
   fn set_enabled(&self, enabled: bool) -> Result<()> {
-    Ok(())
+    self.update(|s| s.enabled = enabled)
   }
 
   fn is_enabled(&self) -> Result<bool> {
-    Ok(true)
+    self.state(|s| s.enabled)
   }
 }
 
@@ -1000,7 +1345,7 @@ pub struct MockRuntime {
 impl MockRuntime {
   fn init() -> Self {
     let is_running = Arc::new(AtomicBool::new(false));
-    let (tx, rx) = sync_channel(256);
+    let (tx, rx) = channel();
     let context = RuntimeContext {
       is_running: is_running.clone(),
       windows: Default::default(),
@@ -1010,6 +1355,7 @@ impl MockRuntime {
       next_webview_id: Default::default(),
       next_window_event_id: Default::default(),
       next_webview_event_id: Default::default(),
+      timers: Default::default(),
     };
     Self {
       is_running,
@@ -1017,6 +1363,119 @@ impl MockRuntime {
       run_rx: rx,
     }
   }
+
+  /// Handles a message sent to the event loop, returning `true` if the event loop must exit.
+  fn handle_message<T: UserEvent>(
+    &self,
+    message: Message,
+    callback: &mut dyn FnMut(RunEvent<T>),
+  ) -> bool {
+    match message {
+      Message::Task(task) => task(),
+      Message::CloseWindow(id) => {
+        let window = self
+          .context
+          .windows
+          .borrow()
+          .get(&id)
+          .map(|w| (w.label.clone(), w.listeners.clone()));
+        if let Some((label, listeners)) = window {
+          let (tx, rx) = channel();
+          listeners.dispatch(&WindowEvent::CloseRequested {
+            signal_tx: tx.clone(),
+          });
+          callback(RunEvent::WindowEvent {
+            label,
+            event: WindowEvent::CloseRequested { signal_tx: tx },
+          });
+
+          let should_prevent = matches!(rx.try_recv(), Ok(true));
+          if !should_prevent {
+            return self.destroy_window(id, callback);
+          }
+        }
+      }
+      Message::DestroyWindow(id) => return self.destroy_window(id, callback),
+      Message::WindowEvent(id, event) => {
+        let window = self
+          .context
+          .windows
+          .borrow()
+          .get(&id)
+          .map(|w| (w.label.clone(), w.state.clone(), w.listeners.clone()));
+        if let Some((label, state, listeners)) = window {
+          state.lock().unwrap().apply(&event);
+          listeners.dispatch(&event);
+          callback(RunEvent::WindowEvent { label, event });
+        }
+      }
+      Message::WebviewEvent {
+        label,
+        listeners,
+        event,
+      } => {
+        listeners.dispatch(&event);
+        callback(RunEvent::WebviewEvent { label, event });
+      }
+    }
+
+    false
+  }
+
+  fn destroy_window<T: UserEvent>(
+    &self,
+    id: WindowId,
+    callback: &mut dyn FnMut(RunEvent<T>),
+  ) -> bool {
+    let window = self.context.windows.borrow_mut().remove(&id);
+    if let Some(window) = window {
+      window.listeners.dispatch(&WindowEvent::Destroyed);
+      callback(RunEvent::WindowEvent {
+        label: window.label,
+        event: WindowEvent::Destroyed,
+      });
+
+      let is_empty = self.context.windows.borrow().is_empty();
+      if is_empty {
+        let (tx, rx) = channel();
+        callback(RunEvent::ExitRequested { code: None, tx });
+
+        let recv = rx.try_recv();
+        return !matches!(recv, Ok(ExitRequestedEventAction::Prevent));
+      }
+    }
+
+    false
+  }
+
+  /// Handles the messages sent to the event loop before this call,
+  /// returning `true` if the event loop must exit.
+  ///
+  /// Messages sent while handling them are delayed to the next iteration so stepping is deterministic.
+  /// Timers whose deadline is reached run first.
+  fn step<T: UserEvent>(&self, callback: &mut dyn FnMut(RunEvent<T>)) -> bool {
+    let now = Instant::now();
+    let due = {
+      let mut timers = self.context.timers.lock().unwrap();
+      let (due, pending) = timers
+        .drain(..)
+        .partition::<Vec<_>, _>(|(deadline, _)| *deadline <= now);
+      *timers = pending;
+      due
+    };
+    for (_, task) in due {
+      task();
+    }
+
+    let messages = self.run_rx.try_iter().collect::<Vec<_>>();
+    for message in messages {
+      if self.handle_message(message, callback) {
+        return true;
+      }
+    }
+    callback(RunEvent::MainEventsCleared);
+    false
+  }
 }
 
 impl<T: UserEvent> Runtime<T> for MockRuntime {
@@ -1049,41 +1508,7 @@ impl<T: UserEvent> Runtime<T> for MockRuntime {
     pending: PendingWindow<T, Self>,
     _after_window_creation: Option<F>,
   ) -> Result<DetachedWindow<T, Self>> {
-    let id = self.context.next_window_id();
-
-    let (webview_id, webviews) = if let Some(w) = &pending.webview {
-      (Some(self.context.next_webview_id()), vec![Webview])
-    } else {
-      (None, Vec::new())
-    };
-
-    self.context.windows.borrow_mut().insert(
-      id,
-      Window {
-        label: pending.label.clone(),
-        webviews,
-      },
-    );
-
-    let webview = webview_id.map(|id| DetachedWebview {
-      label: pending.label.clone(),
-      dispatcher: MockWebviewDispatcher {
-        id,
-        context: self.context.clone(),
-        url: Arc::new(Mutex::new(pending.webview.unwrap().url)),
-        last_evaluated_script: Default::default(),
-      },
-    });
-
-    Ok(DetachedWindow {
-      id,
-      label: pending.label,
-      dispatcher: MockWindowDispatcher {
-        id,
-        context: self.context.clone(),
-      },
-      webview,
-    })
+    self.context.create_window(pending)
   }
 
   fn create_webview(
@@ -1091,21 +1516,7 @@ impl<T: UserEvent> Runtime<T> for MockRuntime {
     window_id: WindowId,
     pending: PendingWebview<T, Self>,
   ) -> Result<DetachedWebview<T, Self>> {
-    let id = self.context.next_webview_id();
-    let webview = Webview;
-    if let Some(w) = self.context.windows.borrow_mut().get_mut(&window_id) {
-      w.webviews.push(webview);
-    }
-
-    Ok(DetachedWebview {
-      label: pending.label,
-      dispatcher: MockWebviewDispatcher {
-        id,
-        context: self.context.clone(),
-        last_evaluated_script: Default::default(),
-        url: Arc::new(Mutex::new(pending.url)),
-      },
-    })
+    self.context.create_webview(window_id, pending)
   }
 
   fn primary_monitor(&self) -> Option<Monitor> {
@@ -1147,72 +1558,20 @@ impl<T: UserEvent> Runtime<T> for MockRuntime {
     target_os = "netbsd",
     target_os = "openbsd"
   ))]
-  fn run_iteration<F: FnMut(RunEvent<T>)>(&mut self, callback: F) {}
+  fn run_iteration<F: FnMut(RunEvent<T>)>(&mut self, mut callback: F) {
+    // from now on messages are queued until the next iteration
+    self.is_running.store(true, Ordering::Relaxed);
+    if self.step(&mut callback) {
+      callback(RunEvent::Exit);
+    }
+  }
 
   fn run<F: FnMut(RunEvent<T>) + 'static>(self, mut callback: F) {
     self.is_running.store(true, Ordering::Relaxed);
     callback(RunEvent::Ready);
 
-    loop {
-      if let Ok(m) = self.run_rx.try_recv() {
-        match m {
-          Message::Task(p) => p(),
-          Message::CloseWindow(id) => {
-            let label = self
-              .context
-              .windows
-              .borrow()
-              .get(&id)
-              .map(|w| w.label.clone());
-            if let Some(label) = label {
-              let (tx, rx) = channel();
-              callback(RunEvent::WindowEvent {
-                label,
-                event: WindowEvent::CloseRequested { signal_tx: tx },
-              });
-
-              let should_prevent = matches!(rx.try_recv(), Ok(true));
-              if !should_prevent {
-                self.context.windows.borrow_mut().remove(&id);
-
-                let is_empty = self.context.windows.borrow().is_empty();
-                if is_empty {
-                  let (tx, rx) = channel();
-                  callback(RunEvent::ExitRequested { code: None, tx });
-
-                  let recv = rx.try_recv();
-                  let should_prevent = matches!(recv, Ok(ExitRequestedEventAction::Prevent));
-
-                  if !should_prevent {
-                    break;
-                  }
-                }
-              }
-            }
-          }
-          Message::DestroyWindow(id) => {
-            let removed = self.context.windows.borrow_mut().remove(&id).is_some();
-            if removed {
-              let is_empty = self.context.windows.borrow().is_empty();
-              if is_empty {
-                let (tx, rx) = channel();
-                callback(RunEvent::ExitRequested { code: None, tx });
-
-                let recv = rx.try_recv();
-                let should_prevent = matches!(recv, Ok(ExitRequestedEventAction::Prevent));
-
-                if !should_prevent {
-                  break;
-                }
-              }
-            }
-          }
-        }
-      }
-
-      callback(RunEvent::MainEventsCleared);
-
-      std::thread::sleep(std::time::Duration::from_secs(1));
+    while !self.step(&mut callback) {
+      std::thread::sleep(std::time::Duration::from_millis(100));
     }
 
     callback(RunEvent::Exit);
@@ -50,7 +50,9 @@
 
 #![allow(unused_variables)]
 
+mod ipc_client;
 mod mock_runtime;
+pub use ipc_client::*;
 pub use mock_runtime::*;
 use serde::Serialize;
 use serialize_to_javascript::DefaultTemplate;
@@ -60,7 +62,7 @@ use std::{borrow::Cow, collections::HashMap, fmt::Debug};
 use crate::{
   ipc::{InvokeError, InvokeResponse, InvokeResponseBody, RuntimeAuthority},
   webview::InvokeRequest,
-  App, Assets, Builder, Context, Pattern, Runtime, Webview,
+  App, Assets, Builder, Context, Pattern, Runtime, Webview, WebviewEvent, Window, WindowEvent,
 };
 use tauri_utils::{
   acl::resolved::Resolved,
@@ -118,8 +120,10 @@ pub fn mock_context<R: Runtime, A: Assets<R>>(assets: A) -> crate::Context<R> {
         windows: Vec::new(),
         security: Default::default(),
         tray_icon: None,
+        menu: None,
         macos_private_api: false,
         enable_gtk_app_id: false,
+        directories: Default::default(),
       },
       bundle: Default::default(),
       build: Default::default(),
@@ -130,6 +134,8 @@ pub fn mock_context<R: Runtime, A: Assets<R>>(assets: A) -> crate::Context<R> {
     app_icon: None,
     #[cfg(all(desktop, feature = "tray-icon"))]
     tray_icon: None,
+    #[cfg(desktop)]
+    menu_icons: Default::default(),
     package_info: crate::PackageInfo {
       name: "test".into(),
       version: "0.1.0".parse().unwrap(),
@@ -286,9 +292,16 @@ pub fn assert_ipc_response<
 pub fn get_ipc_response<W: AsRef<Webview<MockRuntime>>>(
   webview: &W,
   request: InvokeRequest,
+) -> Result<InvokeResponseBody, serde_json::Value> {
+  ipc_response(webview.as_ref(), request)
+}
+
+fn ipc_response(
+  webview: &Webview<MockRuntime>,
+  request: InvokeRequest,
 ) -> Result<InvokeResponseBody, serde_json::Value> {
   let (tx, rx) = std::sync::mpsc::sync_channel(1);
-  webview.as_ref().clone().on_message(
+  webview.clone().on_message(
     request,
     Box::new(move |_window, _cmd, response, _callback, _error| {
       tx.send(response).unwrap();
@@ -302,11 +315,92 @@ pub fn get_ipc_response<W: AsRef<Webview<MockRuntime>>>(
   }
 }
 
+/// Injects a window event as if it was emitted by the windowing system.
+///
+/// Before the app starts running, the event is delivered immediately.
+/// Afterwards, it is delivered on the next [`App::run_iteration`] so tests can step the event loop deterministically.
+///
+/// The window state tracked by the [`MockRuntime`] is updated accordingly,
+/// e.g. [`WindowEvent::Resized`] changes the value returned by [`Window::inner_size`].
+/// [`WindowEvent::CloseRequested`] and [`WindowEvent::Destroyed`] are emitted by closing and destroying the window respectively.
+///
+/// # Examples
+///
+/// ```rust
+/// use tauri::{test::{mock_app, send_window_event}, Manager, PhysicalSize, WindowEvent};
+///
+/// let app = mock_app();
+/// let webview = tauri::WebviewWindowBuilder::new(&app, "main", Default::default()).build().unwrap();
+/// let window = webview.as_ref().window();
+///
+/// send_window_event(&window, WindowEvent::Resized(PhysicalSize::new(400, 300))).unwrap();
+/// assert_eq!(window.inner_size().unwrap(), PhysicalSize::new(400, 300));
+/// ```
+pub fn send_window_event(window: &Window<MockRuntime>, event: WindowEvent) -> crate::Result<()> {
+  use tauri_runtime::window::WindowEvent as RuntimeWindowEvent;
+
+  let event = match event {
+    WindowEvent::Resized(size) => RuntimeWindowEvent::Resized(size),
+    WindowEvent::Moved(position) => RuntimeWindowEvent::Moved(position),
+    WindowEvent::CloseRequested { .. } => return window.close(),
+    WindowEvent::Destroyed => return window.destroy(),
+    WindowEvent::Focused(focused) => RuntimeWindowEvent::Focused(focused),
+    WindowEvent::ScaleFactorChanged {
+      scale_factor,
+      new_inner_size,
+    } => RuntimeWindowEvent::ScaleFactorChanged {
+      scale_factor,
+      new_inner_size,
+    },
+    WindowEvent::DragDrop(event) => RuntimeWindowEvent::DragDrop(event),
+    WindowEvent::ThemeChanged(theme) => RuntimeWindowEvent::ThemeChanged(theme),
+  };
+  window
+    .window
+    .dispatcher
+    .send_event(event)
+    .map_err(Into::into)
+}
+
+/// Injects a webview event as if it was emitted by the webview.
+///
+/// Before the app starts running, the event is delivered immediately.
+/// Afterwards, it is delivered on the next [`App::run_iteration`].
+pub fn send_webview_event<W: AsRef<Webview<MockRuntime>>>(
+  webview: &W,
+  event: WebviewEvent,
+) -> crate::Result<()> {
+  use tauri_runtime::window::WebviewEvent as RuntimeWebviewEvent;
+
+  let event = match event {
+    WebviewEvent::DragDrop(event) => RuntimeWebviewEvent::DragDrop(event),
+    WebviewEvent::NavigationStateChanged {
+      url,
+      can_go_back,
+      can_go_forward,
+    } => RuntimeWebviewEvent::NavigationStateChanged {
+      url,
+      can_go_back,
+      can_go_forward,
+    },
+  };
+  webview
+    .as_ref()
+    .webview
+    .dispatcher
+    .send_event(event)
+    .map_err(Into::into)
+}
+
 #[cfg(test)]
 mod tests {
-  use std::time::Duration;
+  use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+  };
 
-  use super::mock_app;
+  use super::{mock_app, send_webview_event, send_window_event};
+  use crate::{PhysicalPosition, PhysicalSize, WindowEvent};
 
   #[test]
   fn run_app() {
@@ -325,4 +419,197 @@ mod tests {
       println!("{event:?}");
     });
   }
+
+  #[test]
+  fn window_state() {
+    let app = mock_app();
+
+    let main = crate::WebviewWindowBuilder::new(&app, "main", Default::default())
+      .title("Main")
+      .inner_size(400., 300.)
+      .position(10., 20.)
+      .resizable(false)
+      .build()
+      .unwrap();
+    assert_eq!(main.title().unwrap(), "Main");
+    assert_eq!(main.inner_size().unwrap(), PhysicalSize::new(400, 300));
+    assert_eq!(
+      main.outer_position().unwrap(),
+      PhysicalPosition::new(10, 20)
+    );
+    assert!(!main.is_resizable().unwrap());
+    assert!(main.is_visible().unwrap());
+
+    main.set_title("Renamed").unwrap();
+    main.set_size(PhysicalSize::new(500, 200)).unwrap();
+    main.maximize().unwrap();
+    main.hide().unwrap();
+    assert_eq!(main.title().unwrap(), "Renamed");
+    assert_eq!(main.inner_size().unwrap(), PhysicalSize::new(500, 200));
+    assert!(main.is_maximized().unwrap());
+    assert!(!main.is_visible().unwrap());
+
+    let other = crate::WebviewWindowBuilder::new(&app, "other", Default::default())
+      .focused(false)
+      .build()
+      .unwrap();
+    main.set_focus().unwrap();
+    assert!(!other.is_focused().unwrap());
+    other.set_focus().unwrap();
+    assert!(other.is_focused().unwrap());
+    assert!(!main.is_focused().unwrap());
+  }
+
+  #[cfg(not(target_os = "macos"))]
+  #[test]
+  fn window_menu() {
+    let app = mock_app();
+
+    let webview = crate::WebviewWindowBuilder::new(&app, "main", Default::default())
+      .build()
+      .unwrap();
+    let window = webview.as_ref().window();
+    assert!(window.menu().is_none());
+    assert!(!window.is_menu_visible().unwrap());
+
+    let menu = crate::menu::MenuBuilder::new(&app)
+      .text("quit", "Quit")
+      .build()
+      .unwrap();
+    assert!(window.set_menu(menu.clone()).unwrap().is_none());
+    assert_eq!(window.menu().unwrap().id(), menu.id());
+    assert!(window.is_menu_visible().unwrap());
+
+    window.hide_menu().unwrap();
+    assert!(!window.is_menu_visible().unwrap());
+    window.show_menu().unwrap();
+    assert!(window.is_menu_visible().unwrap());
+
+    let removed = window.remove_menu().unwrap();
+    assert_eq!(removed.as_ref().map(|m| m.id()), Some(menu.id()));
+    assert!(window.menu().is_none());
+    assert!(!window.is_menu_visible().unwrap());
+  }
+
+  #[test]
+  fn inject_window_events() {
+    let mut app = mock_app();
+
+    let webview = crate::WebviewWindowBuilder::new(&app, "main", Default::default())
+      .build()
+      .unwrap();
+    let window = webview.as_ref().window();
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let events_ = events.clone();
+    window.on_window_event(move |event| {
+      if let WindowEvent::Resized(size) = event {
+        events_.lock().unwrap().push(*size);
+      }
+    });
+
+    // the first iteration starts the event loop, events are queued afterwards
+    app.run_iteration(|_, _| {});
+
+    send_window_event(&window, WindowEvent::Resized(PhysicalSize::new(640, 480))).unwrap();
+    assert!(events.lock().unwrap().is_empty());
+
+    app.run_iteration(|_, _| {});
+    assert_eq!(*events.lock().unwrap(), vec![PhysicalSize::new(640, 480)]);
+    assert_eq!(window.inner_size().unwrap(), PhysicalSize::new(640, 480));
+
+    send_webview_event(
+      &webview,
+      crate::WebviewEvent::NavigationStateChanged {
+        url: "http://tauri.localhost/page".parse().unwrap(),
+        can_go_back: true,
+        can_go_forward: false,
+      },
+    )
+    .unwrap();
+    app.run_iteration(|_, _| {});
+  }
+
+  #[test]
+  fn webview_history() {
+    let app = mock_app();
+
+    let mut webview = crate::WebviewWindowBuilder::new(
+      &app,
+      "main",
+      crate::WebviewUrl::External("http://localhost/first".parse().unwrap()),
+    )
+    .build()
+    .unwrap();
+    assert!(!webview.can_go_back().unwrap());
+
+    webview
+      .navigate("http://localhost/second".parse().unwrap())
+      .unwrap();
+    assert_eq!(webview.url().unwrap().path(), "/second");
+    assert!(webview.can_go_back().unwrap());
+
+    webview.go_back().unwrap();
+    assert_eq!(webview.url().unwrap().path(), "/first");
+    assert!(webview.can_go_forward().unwrap());
+  }
+
+  #[test]
+  fn navigation_state_changed() {
+    let mut app = mock_app();
+
+    let mut webview = crate::WebviewWindowBuilder::new(
+      &app,
+      "main",
+      crate::WebviewUrl::External("http://localhost/first".parse().unwrap()),
+    )
+    .build()
+    .unwrap();
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let events_ = events.clone();
+    webview.as_ref().on_webview_event(move |event| {
+      if let crate::WebviewEvent::NavigationStateChanged {
+        url,
+        can_go_back,
+        can_go_forward,
+      } = event
+      {
+        events_
+          .lock()
+          .unwrap()
+          .push((url.path().to_string(), *can_go_back, *can_go_forward));
+      }
+    });
+
+    app.run_iteration(|_, _| {});
+
+    webview
+      .navigate("http://localhost/second".parse().unwrap())
+      .unwrap();
+    webview.go_back().unwrap();
+
+    let run_events = Arc::new(Mutex::new(0));
+    let run_events_ = run_events.clone();
+    app.run_iteration(move |_, event| {
+      if let crate::RunEvent::WebviewEvent {
+        label,
+        event: crate::WebviewEvent::NavigationStateChanged { .. },
+        ..
+      } = event
+      {
+        assert_eq!(label, "main");
+        *run_events_.lock().unwrap() += 1;
+      }
+    });
+
+    assert_eq!(
+      *events.lock().unwrap(),
+      vec![
+        ("/second".to_string(), true, false),
+        ("/first".to_string(), false, true),
+      ]
+    );
+    assert_eq!(*run_events.lock().unwrap(), 2);
+  }
 }
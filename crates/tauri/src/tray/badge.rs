@@ -0,0 +1,184 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use serde::Deserialize;
+
+use crate::image::Image;
+
+/// A badge drawn on the top-right corner of a tray icon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TrayIconBadge {
+  /// A dot, usually to signal new activity.
+  Dot,
+  /// A count, such as the number of unread messages. Counts above 99 are shown as `99+`.
+  Count(u32),
+}
+
+const BADGE_COLOR: [u8; 3] = [0xe5, 0x39, 0x35];
+const TEXT_COLOR: [u8; 3] = [0xff, 0xff, 0xff];
+
+const GLYPH_WIDTH: u32 = 3;
+const GLYPH_HEIGHT: u32 = 5;
+
+/// 3x5 bitmap glyphs, one row per byte with the 3 lowest bits as pixels.
+fn glyph(c: char) -> [u8; GLYPH_HEIGHT as usize] {
+  match c {
+    '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+    '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+    '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+    '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+    '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+    '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+    '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+    '7' => [0b111, 0b001, 0b010, 0b010, 0b010],
+    '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+    '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+    '+' => [0b000, 0b010, 0b111, 0b010, 0b000],
+    _ => [0; GLYPH_HEIGHT as usize],
+  }
+}
+
+impl TrayIconBadge {
+  fn text(&self) -> Option<String> {
+    match self {
+      Self::Dot => None,
+      Self::Count(count) if *count > 99 => Some("99+".into()),
+      Self::Count(count) => Some(count.to_string()),
+    }
+  }
+
+  /// Composites the badge onto a copy of the icon.
+  pub(crate) fn composite(&self, icon: &Image<'_>) -> Image<'static> {
+    let (width, height) = (icon.width(), icon.height());
+    let mut rgba = icon.rgba().to_vec();
+    let size = width.min(height) as f32;
+    if size == 0.0 {
+      return Image::new_owned(rgba, width, height);
+    }
+
+    let text = self.text();
+    let badge_height = match text {
+      Some(_) => (size * 0.6).round(),
+      None => (size * 0.4).round(),
+    }
+    .max(1.0);
+    let radius = badge_height / 2.0;
+
+    // integer glyph scale so the digits stay crisp
+    let scale = ((badge_height * 0.7) / GLYPH_HEIGHT as f32)
+      .floor()
+      .max(1.0) as u32;
+    let text_width = text
+      .as_ref()
+      .map(|t| (t.len() as u32 * (GLYPH_WIDTH + 1) - 1) * scale)
+      .unwrap_or(0);
+
+    // a pill wide enough for the text, anchored to the top-right corner
+    let badge_width = (text_width as f32 + radius)
+      .max(badge_height)
+      .min(width as f32);
+    let right = width as f32;
+    let center_y = radius;
+    let (start_x, end_x) = (right - badge_width + radius, right - radius);
+
+    for y in 0..(badge_height.ceil() as u32).min(height) {
+      for x in ((right - badge_width).floor().max(0.0) as u32)..width {
+        let (px, py) = (x as f32 + 0.5, y as f32 + 0.5);
+        let dx = px - px.clamp(start_x, end_x);
+        let dy = py - center_y;
+        let coverage = (radius - (dx * dx + dy * dy).sqrt() + 0.5).clamp(0.0, 1.0);
+        blend(&mut rgba, width, x, y, BADGE_COLOR, coverage);
+      }
+    }
+
+    if let Some(text) = text {
+      let origin_x = (right - badge_width / 2.0 - text_width as f32 / 2.0).round() as i64;
+      let origin_y = (center_y - (GLYPH_HEIGHT * scale) as f32 / 2.0).round() as i64;
+      for (i, c) in text.chars().enumerate() {
+        let glyph_x = origin_x + (i as u32 * (GLYPH_WIDTH + 1) * scale) as i64;
+        for (row, bits) in glyph(c).into_iter().enumerate() {
+          for col in 0..GLYPH_WIDTH {
+            if bits & (1 << (GLYPH_WIDTH - 1 - col)) == 0 {
+              continue;
+            }
+            for sy in 0..scale {
+              for sx in 0..scale {
+                let x = glyph_x + (col * scale + sx) as i64;
+                let y = origin_y + (row as u32 * scale + sy) as i64;
+                if (0..width as i64).contains(&x) && (0..height as i64).contains(&y) {
+                  blend(&mut rgba, width, x as u32, y as u32, TEXT_COLOR, 1.0);
+                }
+              }
+            }
+          }
+        }
+      }
+    }
+
+    Image::new_owned(rgba, width, height)
+  }
+}
+
+/// Blends an opaque color with the given coverage over a pixel with straight alpha.
+fn blend(rgba: &mut [u8], width: u32, x: u32, y: u32, color: [u8; 3], coverage: f32) {
+  if coverage <= 0.0 {
+    return;
+  }
+  let i = ((y * width + x) * 4) as usize;
+  let Some(pixel) = rgba.get_mut(i..i + 4) else {
+    return;
+  };
+  let dst_alpha = pixel[3] as f32 / 255.0;
+  let alpha = coverage + dst_alpha * (1.0 - coverage);
+  for (channel, src) in pixel.iter_mut().zip(color) {
+    let value = (src as f32 * coverage + *channel as f32 * dst_alpha * (1.0 - coverage)) / alpha;
+    *channel = value.round() as u8;
+  }
+  pixel[3] = (alpha * 255.0).round() as u8;
+}
+
+#[cfg(test)]
+mod tests {
+  use super::TrayIconBadge;
+  use crate::image::Image;
+
+  fn pixel(image: &Image<'_>, x: u32, y: u32) -> [u8; 4] {
+    let i = ((y * image.width() + x) * 4) as usize;
+    image.rgba()[i..i + 4].try_into().unwrap()
+  }
+
+  #[test]
+  fn badge_is_drawn_on_top_right_corner() {
+    let icon = Image::new_owned(vec![0; 32 * 32 * 4], 32, 32);
+
+    let dot = TrayIconBadge::Dot.composite(&icon);
+    assert_eq!((dot.width(), dot.height()), (32, 32));
+    // center of the dot
+    assert_eq!(pixel(&dot, 25, 6), [0xe5, 0x39, 0x35, 0xff]);
+    // the rest of the icon is untouched
+    assert_eq!(pixel(&dot, 4, 28), [0, 0, 0, 0]);
+    assert_eq!(pixel(&dot, 10, 4), [0, 0, 0, 0]);
+
+    let count = TrayIconBadge::Count(7).composite(&icon);
+    let white = count
+      .rgba()
+      .chunks(4)
+      .filter(|p| p == &[0xff, 0xff, 0xff, 0xff])
+      .count();
+    assert!(white > 0, "the count must be drawn");
+    assert_eq!(pixel(&count, 4, 28), [0, 0, 0, 0]);
+
+    let overflow = TrayIconBadge::Count(1000).composite(&icon);
+    assert_ne!(overflow.rgba(), count.rgba());
+  }
+
+  #[test]
+  fn badge_keeps_opaque_icon_opaque() {
+    let icon = Image::new_owned([0x10, 0x20, 0x30, 0xff].repeat(16 * 16), 16, 16);
+    let badged = TrayIconBadge::Count(3).composite(&icon);
+    assert!(badged.rgba().chunks(4).all(|p| p[3] == 0xff));
+    assert_eq!(pixel(&badged, 0, 15), [0x10, 0x20, 0x30, 0xff]);
+  }
+}
@@ -4,6 +4,7 @@
 
 //! Tray icon types and utilities.
 
+mod badge;
 pub(crate) mod plugin;
 
 use crate::app::{GlobalMenuEventListener, GlobalTrayIconEventListener};
@@ -11,12 +12,25 @@ use crate::menu::ContextMenu;
 use crate::menu::MenuEvent;
 use crate::resources::Resource;
 use crate::{
-  image::Image, menu::run_item_main_thread, AppHandle, Manager, PhysicalPosition, Rect, Runtime,
+  image::Image, menu::run_item_main_thread, runtime::RuntimeHandle as _, AppHandle, Manager,
+  PhysicalPosition, Rect, Runtime,
 };
 use serde::Serialize;
-use std::path::Path;
+use std::{
+  path::Path,
+  sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+  },
+  time::Duration,
+};
 pub use tray_icon::TrayIconId;
 
+pub use badge::TrayIconBadge;
+
+/// The shortest time a frame of a tray icon animation is shown, about one frame at 60Hz.
+const MIN_ANIMATION_INTERVAL: Duration = Duration::from_millis(16);
+
 /// Describes the mouse button state.
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
 pub enum MouseButtonState {
@@ -212,6 +226,8 @@ impl From<tray_icon::TrayIconEvent> for TrayIconEvent {
 pub struct TrayIconBuilder<R: Runtime> {
   on_menu_event: Option<GlobalMenuEventListener<AppHandle<R>>>,
   on_tray_icon_event: Option<GlobalTrayIconEventListener<TrayIcon<R>>>,
+  tooltip_key: Option<String>,
+  icon: Option<Image<'static>>,
   inner: tray_icon::TrayIconBuilder,
 }
 
@@ -227,6 +243,8 @@ impl<R: Runtime> TrayIconBuilder<R> {
       inner: tray_icon::TrayIconBuilder::new(),
       on_menu_event: None,
       on_tray_icon_event: None,
+      tooltip_key: None,
+      icon: None,
     }
   }
 
@@ -259,9 +277,10 @@ impl<R: Runtime> TrayIconBuilder<R> {
   /// - **Linux:** Sometimes the icon won't be visible unless a menu is set.
   ///   Setting an empty [`Menu`](crate::menu::Menu) is enough.
   pub fn icon(mut self, icon: Image<'_>) -> Self {
-    let icon = icon.try_into().ok();
-    if let Some(icon) = icon {
-      self.inner = self.inner.with_icon(icon);
+    let icon = icon.to_owned();
+    if let Ok(tray_icon) = icon.clone().try_into() {
+      self.inner = self.inner.with_icon(tray_icon);
+      self.icon.replace(icon);
     }
     self
   }
@@ -276,6 +295,18 @@ impl<R: Runtime> TrayIconBuilder<R> {
     self
   }
 
+  /// Set the message key used to localize the tooltip of this tray icon.
+  ///
+  /// See [`TrayIcon::set_tooltip_key`].
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Linux:** Unsupported.
+  pub fn tooltip_key<S: Into<String>>(mut self, key: S) -> Self {
+    self.tooltip_key.replace(key.into());
+    self
+  }
+
   /// Set the tray icon title.
   ///
   /// ## Platform-specific
@@ -347,6 +378,11 @@ impl<R: Runtime> TrayIconBuilder<R> {
       id,
       inner,
       app_handle: manager.app_handle().clone(),
+      state: Arc::new(TrayIconState {
+        icon: Mutex::new(self.icon),
+        badge: Default::default(),
+        animation: Default::default(),
+      }),
     };
 
     icon.register(
@@ -355,6 +391,10 @@ impl<R: Runtime> TrayIconBuilder<R> {
       self.on_tray_icon_event,
     );
 
+    if let Some(key) = self.tooltip_key {
+      icon.set_tooltip_key(key)?;
+    }
+
     Ok(icon)
   }
 }
@@ -369,6 +409,7 @@ pub struct TrayIcon<R: Runtime> {
   id: TrayIconId,
   inner: tray_icon::TrayIcon,
   app_handle: AppHandle<R>,
+  state: Arc<TrayIconState>,
 }
 
 impl<R: Runtime> Clone for TrayIcon<R> {
@@ -377,10 +418,43 @@ impl<R: Runtime> Clone for TrayIcon<R> {
       id: self.id.clone(),
       inner: self.inner.clone(),
       app_handle: self.app_handle.clone(),
+      state: self.state.clone(),
     }
   }
 }
 
+/// State shared by the clones of a [`TrayIcon`].
+struct TrayIconState {
+  /// The icon without its badge.
+  icon: Mutex<Option<Image<'static>>>,
+  badge: Mutex<Option<TrayIconBadge>>,
+  /// Whether the running animation should keep going.
+  animation: Mutex<Option<Arc<AtomicBool>>>,
+}
+
+impl TrayIconState {
+  fn stop_animation(&self) {
+    if let Some(running) = self.animation.lock().unwrap().take() {
+      running.store(false, Ordering::Relaxed);
+    }
+  }
+
+  fn is_animating(&self) -> bool {
+    self
+      .animation
+      .lock()
+      .unwrap()
+      .as_ref()
+      .is_some_and(|running| running.load(Ordering::Relaxed))
+  }
+}
+
+impl Drop for TrayIconState {
+  fn drop(&mut self) {
+    self.stop_animation();
+  }
+}
+
 /// # Safety
 ///
 /// We make sure it always runs on the main thread.
@@ -464,12 +538,112 @@ impl<R: Runtime> TrayIcon<R> {
   }
 
   /// Sets a new tray icon. If `None` is provided, it will remove the icon.
+  ///
+  /// Stops the running animation and keeps the badge set with [`Self::set_badge`].
   pub fn set_icon(&self, icon: Option<Image<'_>>) -> crate::Result<()> {
-    let icon = match icon {
-      Some(i) => Some(i.try_into()?),
-      None => None,
+    let icon = icon.map(|i| i.to_owned());
+    *self.state.icon.lock().unwrap() = icon.clone();
+    self.state.stop_animation();
+    self.render(icon.as_ref(), None)
+  }
+
+  /// Sets a badge drawn on the top-right corner of the tray icon. If `None` is provided, it will remove the badge.
+  ///
+  /// The badge is composited onto the icon set with [`Self::set_icon`] or [`TrayIconBuilder::icon`],
+  /// and onto every frame of a running animation.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **macOS:** The badge is lost when the icon is used as a template.
+  pub fn set_badge(&self, badge: Option<TrayIconBadge>) -> crate::Result<()> {
+    *self.state.badge.lock().unwrap() = badge;
+    // the next frame of the animation picks up the badge
+    if self.state.is_animating() {
+      return Ok(());
+    }
+    let icon = self.state.icon.lock().unwrap().clone();
+    self.render(icon.as_ref(), None)
+  }
+
+  /// Returns the badge drawn on the tray icon.
+  pub fn badge(&self) -> Option<TrayIconBadge> {
+    *self.state.badge.lock().unwrap()
+  }
+
+  /// Cycles the tray icon through the given frames, showing each one for `interval`.
+  ///
+  /// The frames are set on the main thread by a timer of the event loop. The animation replaces the running one
+  /// and stops when [`Self::stop_animation`] or [`Self::set_icon`] is called, or when the tray icon is removed.
+  ///
+  /// Intervals shorter than 16 milliseconds are raised to 16 milliseconds.
+  pub fn start_animation(&self, frames: Vec<Image<'_>>, interval: Duration) -> crate::Result<()> {
+    let interval = interval.max(MIN_ANIMATION_INTERVAL);
+    let frames = frames
+      .into_iter()
+      .map(|frame| frame.to_owned())
+      .collect::<Vec<_>>();
+    let Some(first_frame) = frames.first() else {
+      return self.stop_animation();
     };
-    run_item_main_thread!(self, |self_: Self| self_.inner.set_icon(icon))?.map_err(Into::into)
+
+    let running = Arc::new(AtomicBool::new(true));
+    if let Some(previous) = self
+      .state
+      .animation
+      .lock()
+      .unwrap()
+      .replace(running.clone())
+    {
+      previous.store(false, Ordering::Relaxed);
+    }
+
+    self.render(Some(first_frame), Some(running.clone()))?;
+
+    let next = 1 % frames.len();
+    schedule_animation_frame(
+      self.app_handle.clone(),
+      self.id.clone(),
+      Arc::new(frames),
+      next,
+      interval,
+      running,
+    )
+  }
+
+  /// Stops the running animation and restores the icon set with [`Self::set_icon`] or [`TrayIconBuilder::icon`].
+  pub fn stop_animation(&self) -> crate::Result<()> {
+    if !self.state.is_animating() {
+      return Ok(());
+    }
+    self.state.stop_animation();
+    let icon = self.state.icon.lock().unwrap().clone();
+    self.render(icon.as_ref(), None)
+  }
+
+  /// Sets the icon with the badge composited onto it.
+  ///
+  /// When given the running flag of an animation, the icon is only set if the animation
+  /// was not stopped by the time the main thread handles it.
+  fn render(
+    &self,
+    icon: Option<&Image<'_>>,
+    animation: Option<Arc<AtomicBool>>,
+  ) -> crate::Result<()> {
+    let badge = *self.state.badge.lock().unwrap();
+    let icon: Option<tray_icon::Icon> = match (icon, badge) {
+      (Some(icon), Some(badge)) => Some(badge.composite(icon).try_into()?),
+      (Some(icon), None) => Some(icon.clone().try_into()?),
+      (None, _) => None,
+    };
+    run_item_main_thread!(self, |self_: Self| {
+      let stopped = animation.is_some_and(|running| !running.load(Ordering::Relaxed));
+      if stopped {
+        Ok(())
+      } else {
+        self_.inner.set_icon(icon)
+      }
+    })?
+    .map_err(Into::into)
   }
 
   /// Sets a new tray menu.
@@ -493,6 +667,31 @@ impl<R: Runtime> TrayIcon<R> {
     run_item_main_thread!(self, |self_: Self| self_.inner.set_tooltip(s))?.map_err(Into::into)
   }
 
+  /// Binds the tooltip of this tray icon to a message key of the catalogs registered with
+  /// [`AppHandle::register_catalog`], relabeling it on every [`AppHandle::set_locale`] call.
+  ///
+  /// The tooltip is set right away if the active locale has a translation for the key.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Linux:** Unsupported
+  pub fn set_tooltip_key<S: Into<String>>(&self, key: S) -> crate::Result<()> {
+    let key = key.into();
+    let tooltip = self.app_handle.manager.locale.translate(&key);
+    self
+      .app_handle
+      .manager
+      .tray
+      .tooltip_keys
+      .lock()
+      .unwrap()
+      .insert(self.id.clone(), key);
+    match tooltip {
+      Some(tooltip) => self.set_tooltip(Some(tooltip)),
+      None => Ok(()),
+    }
+  }
+
   /// Sets the title for this tray icon.
   ///
   /// ## Platform-specific:
@@ -558,6 +757,38 @@ impl<R: Runtime> TrayIcon<R> {
   }
 }
 
+/// Shows the frame `index` of an animation once `interval` has elapsed and schedules the next one,
+/// until the animation is stopped or the tray icon is removed.
+fn schedule_animation_frame<R: Runtime>(
+  app_handle: AppHandle<R>,
+  id: TrayIconId,
+  frames: Arc<Vec<Image<'static>>>,
+  index: usize,
+  interval: Duration,
+  running: Arc<AtomicBool>,
+) -> crate::Result<()> {
+  let runtime_handle = app_handle.runtime_handle.clone();
+  runtime_handle
+    .run_on_main_thread_after(interval, move || {
+      if !running.load(Ordering::Relaxed) {
+        return;
+      }
+      let Some(tray) = app_handle.tray_by_id(&id) else {
+        return;
+      };
+      if tray
+        .render(Some(&frames[index]), Some(running.clone()))
+        .is_ok()
+      {
+        let next = (index + 1) % frames.len();
+        if let Err(e) = schedule_animation_frame(app_handle, id, frames, next, interval, running) {
+          log::error!("failed to schedule the next tray icon animation frame: {e}");
+        }
+      }
+    })
+    .map_err(Into::into)
+}
+
 impl<R: Runtime> Resource for TrayIcon<R> {
   fn close(self: std::sync::Arc<Self>) {
     self.app_handle.remove_tray_by_id(&self.id);
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use std::path::PathBuf;
+use std::{path::PathBuf, time::Duration};
 
 use serde::Deserialize;
 
@@ -17,7 +17,7 @@ use crate::{
   AppHandle, Manager, Runtime, Webview,
 };
 
-use super::{TrayIcon, TrayIconEvent};
+use super::{TrayIcon, TrayIconBadge, TrayIconEvent};
 
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -127,6 +127,40 @@ fn set_icon<R: Runtime>(
   tray.set_icon(icon)
 }
 
+#[command(root = "crate")]
+fn set_badge<R: Runtime>(
+  webview: Webview<R>,
+  rid: ResourceId,
+  badge: Option<TrayIconBadge>,
+) -> crate::Result<()> {
+  let resources_table = webview.resources_table();
+  let tray = resources_table.get::<TrayIcon<R>>(rid)?;
+  tray.set_badge(badge)
+}
+
+#[command(root = "crate")]
+fn start_animation<R: Runtime>(
+  webview: Webview<R>,
+  rid: ResourceId,
+  frames: Vec<JsImage>,
+  interval: u64,
+) -> crate::Result<()> {
+  let resources_table = webview.resources_table();
+  let tray = resources_table.get::<TrayIcon<R>>(rid)?;
+  let frames = frames
+    .into_iter()
+    .map(|frame| Ok(frame.into_img(&resources_table)?.as_ref().clone()))
+    .collect::<crate::Result<Vec<_>>>()?;
+  tray.start_animation(frames, Duration::from_millis(interval))
+}
+
+#[command(root = "crate")]
+fn stop_animation<R: Runtime>(webview: Webview<R>, rid: ResourceId) -> crate::Result<()> {
+  let resources_table = webview.resources_table();
+  let tray = resources_table.get::<TrayIcon<R>>(rid)?;
+  tray.stop_animation()
+}
+
 #[command(root = "crate")]
 fn set_menu<R: Runtime>(
   webview: Webview<R>,
@@ -226,6 +260,9 @@ pub(crate) fn init<R: Runtime>() -> TauriPlugin<R> {
       get_by_id,
       remove_by_id,
       set_icon,
+      set_badge,
+      start_animation,
+      stop_animation,
       set_menu,
       set_tooltip,
       set_title,
@@ -4,15 +4,18 @@
 
 //! The Tauri webview types and functions.
 
+pub(crate) mod new_window;
 pub(crate) mod plugin;
 mod webview_window;
 
+pub use new_window::{NewWindowFeatures, NewWindowResponse};
 pub use webview_window::{WebviewWindow, WebviewWindowBuilder};
 
 use http::HeaderMap;
 use serde::Serialize;
+use serialize_to_javascript::DefaultTemplate;
 use tauri_macros::default_runtime;
-pub use tauri_runtime::webview::PageLoadEvent;
+pub use tauri_runtime::webview::{PageLoadEvent, PermissionResponse};
 #[cfg(desktop)]
 use tauri_runtime::{
   dpi::{PhysicalPosition, PhysicalSize, Position, Size},
@@ -22,7 +25,8 @@ use tauri_runtime::{
   webview::{DetachedWebview, PendingWebview, WebviewAttributes},
   WebviewDispatch,
 };
-use tauri_utils::config::{WebviewUrl, WindowConfig};
+pub use tauri_utils::config::WebPermissionKind;
+use tauri_utils::config::{WebPermissionDecision, WebviewUrl, WindowConfig};
 pub use url::Url;
 
 use crate::{
@@ -52,6 +56,9 @@ pub(crate) type UriSchemeProtocolHandler =
   Box<dyn Fn(&str, http::Request<Vec<u8>>, UriSchemeResponder) + Send + Sync>;
 pub(crate) type OnPageLoad<R> = dyn Fn(Webview<R>, PageLoadPayload<'_>) + Send + Sync + 'static;
 
+pub(crate) type PermissionRequestHandler<R> =
+  dyn Fn(Webview<R>, &Url, WebPermissionKind) -> PermissionResponse + Send + Sync;
+
 pub(crate) type DownloadHandler<R> = dyn Fn(Webview<R>, DownloadEvent<'_>) -> bool + Send + Sync;
 
 #[derive(Clone, Serialize)]
@@ -236,6 +243,9 @@ unstable_struct!(
     pub(crate) navigation_handler: Option<Box<NavigationHandler>>,
     pub(crate) on_page_load_handler: Option<Box<OnPageLoad<R>>>,
     pub(crate) download_handler: Option<Arc<DownloadHandler<R>>>,
+    pub(crate) new_window_handler: Option<Arc<new_window::OnNewWindow<R>>>,
+    pub(crate) opener_port: Option<u32>,
+    pub(crate) permission_request_handler: Option<Box<PermissionRequestHandler<R>>>,
   }
 );
 
@@ -312,6 +322,9 @@ async fn create_window(app: tauri::AppHandle) {
       navigation_handler: None,
       on_page_load_handler: None,
       download_handler: None,
+      new_window_handler: None,
+      opener_port: None,
+      permission_request_handler: None,
     }
   }
 
@@ -352,6 +365,9 @@ async fn reopen_window(app: tauri::AppHandle) {
       navigation_handler: None,
       on_page_load_handler: None,
       download_handler: None,
+      new_window_handler: None,
+      opener_port: None,
+      permission_request_handler: None,
     }
   }
 
@@ -448,6 +464,81 @@ tauri::Builder::default()
     self
   }
 
+  /// Defines a closure to be executed when the webview requests a new window,
+  /// either with `window.open()` or with a link targeting a new browsing context.
+  ///
+  /// The closure receives the requested URL and [`NewWindowFeatures`] and decides how the request is handled.
+  /// To link the new window with the webview that requested it, so `window.opener` is available,
+  /// build it with [`WebviewWindowBuilder::window_features`].
+  /// Windows requested by links, or by `window.open()` with `noopener`, are never linked to their opener.
+  ///
+  /// `window.open()` and `window.opener` do not return a real `WindowProxy` but an object backed by a message channel:
+  /// it only supports `postMessage`, `close()` and `closed`, which is only set once the page closed the channel
+  /// or a message could not be delivered. `location` is not available and `focus()` does nothing.
+  ///
+  /// The closure runs on the async runtime, so it is safe to create windows from it.
+  /// Handling `window.open()` requires the `core:webview:allow-internal-on-new-window` permission.
+  /// The message channel goes through the IPC, so both pages need the `core:message-port:default` permission:
+  /// a window loading a remote URL with [`WebviewUrl::External`] can only use `window.opener`
+  /// if a capability grants it to that URL with [`remote` URLs](https://v2.tauri.app/security/capabilities/#remote-api-access).
+  ///
+  /// # Examples
+  ///
+  #[cfg_attr(
+    feature = "unstable",
+    doc = r####"
+```rust,no_run
+use tauri::{
+  webview::{NewWindowResponse, WebviewBuilder, WebviewWindowBuilder},
+  Manager, WebviewUrl,
+};
+
+tauri::Builder::default()
+  .setup(|app| {
+    let window = tauri::window::WindowBuilder::new(app, "label").build()?;
+    let handle = app.handle().clone();
+
+    let webview_builder = WebviewBuilder::new("core", WebviewUrl::App("index.html".into()))
+      .on_new_window(move |url, features| {
+        // remote pages cannot use the IPC to reach `window.opener`, open them in the browser
+        let is_app_url = handle
+          .get_webview("core")
+          .and_then(|webview| webview.url().ok())
+          .is_some_and(|app_url| app_url.origin() == url.origin());
+        if !is_app_url {
+          return NewWindowResponse::OpenExternally;
+        }
+        let label = format!("popup-{}", handle.webview_windows().len());
+        let path = url.path().trim_start_matches('/').into();
+        match WebviewWindowBuilder::new(&handle, label, WebviewUrl::App(path))
+          .window_features(features)
+          .build()
+        {
+          Ok(window) => NewWindowResponse::Create { window },
+          Err(_) => NewWindowResponse::Deny,
+        }
+      });
+
+    let webview = window.add_child(webview_builder, tauri::LogicalPosition::new(0, 0), window.inner_size().unwrap())?;
+    Ok(())
+  });
+```
+  "####
+  )]
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Android / iOS:** Unsupported.
+  pub fn on_new_window<
+    F: Fn(Url, NewWindowFeatures) -> NewWindowResponse<R> + Send + Sync + 'static,
+  >(
+    mut self,
+    f: F,
+  ) -> Self {
+    self.new_window_handler.replace(Arc::new(f));
+    self
+  }
+
   /// Set a download event handler to be notified when a download is requested or finished.
   ///
   /// Returning `false` prevents the download from happening on a [`DownloadEvent::Requested`] event.
@@ -542,6 +633,66 @@ tauri::Builder::default()
     self
   }
 
+  /// Defines a closure to be executed when the page requests a web API permission,
+  /// such as camera, microphone, geolocation or notifications access.
+  ///
+  /// The closure receives the URL of the top-level page, whose origin can be checked with [`Url::origin`],
+  /// and the requested [`WebPermissionKind`]. A request for both camera and microphone calls the closure for each permission.
+  ///
+  /// Requests matching one of the rules defined in the [`webPermissions`](https://v2.tauri.app/reference/config/#webpermissions)
+  /// security configuration are resolved without calling the closure.
+  ///
+  /// # Examples
+  ///
+  #[cfg_attr(
+    feature = "unstable",
+    doc = r####"
+```rust,no_run
+use tauri::{
+  utils::config::WebviewUrl,
+  webview::{PermissionResponse, WebPermissionKind, WebviewBuilder},
+};
+
+tauri::Builder::default()
+  .setup(|app| {
+    let window = tauri::window::WindowBuilder::new(app, "label").build()?;
+    let webview_builder = WebviewBuilder::new("core", WebviewUrl::App("index.html".into()))
+      .on_permission_request(|_webview, url, permission| {
+        match permission {
+          WebPermissionKind::Camera | WebPermissionKind::Microphone
+            if url.host_str() == Some("meet.example.com") =>
+          {
+            PermissionResponse::Allow
+          }
+          WebPermissionKind::Geolocation => PermissionResponse::Deny,
+          _ => PermissionResponse::Default,
+        }
+      });
+    let webview = window.add_child(webview_builder, tauri::LogicalPosition::new(0, 0), window.inner_size().unwrap())?;
+    Ok(())
+  });
+```
+  "####
+  )]
+  ///
+  /// The closure is called synchronously on the main thread while the page waits for the decision,
+  /// so it must not block: return [`PermissionResponse::Default`] and prompt the user separately if needed.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux**: The URL is the one of the top-level page, as WebKitGTK does not tell which frame made the request,
+  ///   so the requests of cross-origin iframes are denied without calling the closure.
+  /// - **Windows / macOS / Android / iOS**: Unsupported, the closure is never called.
+  pub fn on_permission_request<
+    F: Fn(Webview<R>, &Url, WebPermissionKind) -> PermissionResponse + Send + Sync + 'static,
+  >(
+    mut self,
+    f: F,
+  ) -> Self {
+    self.permission_request_handler.replace(Box::new(f));
+    self
+  }
+
   pub(crate) fn into_pending_webview<M: Manager<R>>(
     mut self,
     manager: &M,
@@ -585,10 +736,76 @@ tauri::Builder::default()
         }
       }));
 
-    manager
+    let rules = manager.config().app.security.web_permissions.clone();
+    if !rules.is_empty() || self.permission_request_handler.is_some() {
+      let handler = self.permission_request_handler.take();
+      let label = pending.label.clone();
+      let manager = manager.manager_owned();
+      pending
+        .permission_request_handler
+        .replace(Box::new(move |url, permission| {
+          if let Some(rule) = rules.iter().find(|rule| rule.matches(url, permission)) {
+            return match rule.decision {
+              WebPermissionDecision::Allow => PermissionResponse::Allow,
+              WebPermissionDecision::Deny => PermissionResponse::Deny,
+            };
+          }
+          match (&handler, manager.get_webview(&label)) {
+            (Some(handler), Some(webview)) => handler(webview, url, permission),
+            _ => PermissionResponse::Default,
+          }
+        }));
+    }
+
+    if self.new_window_handler.is_some() {
+      let label = pending.label.clone();
+      let manager = manager.manager_owned();
+      pending.new_window_handler.replace(Box::new(move |url| {
+        if let Some(w) = manager.get_webview(&label) {
+          crate::async_runtime::spawn(async move {
+            if let Err(e) = w.handle_new_window(url, NewWindowFeatures::link()) {
+              log::error!("failed to handle new window request: {e}");
+            }
+          });
+        }
+        false
+      }));
+    }
+
+    let mut pending = manager
       .manager()
       .webview
-      .prepare_webview(manager, pending, window_label)
+      .prepare_webview(manager, pending, window_label)?;
+
+    if let Some(port) = self.opener_port {
+      manager
+        .state::<crate::ipc::message_port::MessagePorts>()
+        .set_owner(port, &pending.label);
+    }
+
+    let handle_window_open = self.new_window_handler.is_some();
+    if handle_window_open || self.opener_port.is_some() {
+      if let Some(new_window_handler) = self.new_window_handler {
+        manager
+          .manager()
+          .webview
+          .new_window_handlers
+          .lock()
+          .unwrap()
+          .insert(pending.label.clone(), new_window_handler);
+      }
+
+      pending.webview_attributes.initialization_scripts.push(
+        new_window::NewWindowJavascript {
+          handle_window_open,
+          opener_port: self.opener_port,
+        }
+        .render_default(&Default::default())?
+        .into_string(),
+      );
+    }
+
+    Ok(pending)
   }
 
   /// Creates a new webview on the given window.
@@ -875,10 +1092,11 @@ impl<R: Runtime> Webview<R> {
 
   /// Registers a window event listener.
   pub fn on_webview_event<F: Fn(&WebviewEvent) + Send + 'static>(&self, f: F) {
-    self
-      .webview
-      .dispatcher
-      .on_webview_event(move |event| f(&event.clone().into()));
+    self.webview.dispatcher.on_webview_event(move |event| {
+      if let Ok(event) = event.clone().try_into() {
+        f(&event);
+      }
+    });
   }
 
   /// Resolves the given command scope for this webview on the currently loaded URL.
@@ -1172,6 +1390,53 @@ fn main() {
     self.webview.dispatcher.navigate(url).map_err(Into::into)
   }
 
+  /// Navigates back to the previous page in the webview's history.
+  pub fn go_back(&self) -> crate::Result<()> {
+    self.webview.dispatcher.go_back().map_err(Into::into)
+  }
+
+  /// Navigates forward to the next page in the webview's history.
+  pub fn go_forward(&self) -> crate::Result<()> {
+    self.webview.dispatcher.go_forward().map_err(Into::into)
+  }
+
+  /// Reloads the current page.
+  ///
+  /// When `bypass_cache` is `true`, cached resources are ignored and fetched again.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Android**: `bypass_cache` is ignored.
+  pub fn reload(&self, bypass_cache: bool) -> crate::Result<()> {
+    self
+      .webview
+      .dispatcher
+      .reload(bypass_cache)
+      .map_err(Into::into)
+  }
+
+  /// Whether the webview can navigate back in its history.
+  ///
+  /// Listen to [`WebviewEvent::NavigationStateChanged`] to be notified when this value changes.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Android**: Unsupported, always returns `false`.
+  pub fn can_go_back(&self) -> crate::Result<bool> {
+    self.webview.dispatcher.can_go_back().map_err(Into::into)
+  }
+
+  /// Whether the webview can navigate forward in its history.
+  ///
+  /// Listen to [`WebviewEvent::NavigationStateChanged`] to be notified when this value changes.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Android**: Unsupported, always returns `false`.
+  pub fn can_go_forward(&self) -> crate::Result<bool> {
+    self.webview.dispatcher.can_go_forward().map_err(Into::into)
+  }
+
   fn is_local_url(&self, current_url: &Url) -> bool {
     // if from `tauri://` custom protocol
     ({
@@ -1268,6 +1533,13 @@ fn main() {
       }
     };
     let (resolved_acl, has_app_acl_manifest) = {
+      #[cfg(feature = "tracing")]
+      let _span = tracing::trace_span!(
+        "ipc::request::acl",
+        cmd = request.cmd,
+        allowed = tracing::field::Empty
+      )
+      .entered();
       let runtime_authority = manager.runtime_authority.lock().unwrap();
       let acl = runtime_authority.resolve_access(
         &request.cmd,
@@ -1275,6 +1547,8 @@ fn main() {
         message.webview.label(),
         &acl_origin,
       );
+      #[cfg(feature = "tracing")]
+      _span.record("allowed", acl.is_some());
       (acl, runtime_authority.has_app_manifest())
     };
 
@@ -1430,6 +1704,11 @@ fn main() {
   }
 
   pub(crate) fn emit_js(&self, emit_args: &EmitArgs, ids: &[u32]) -> crate::Result<()> {
+    #[cfg(any(test, feature = "test"))]
+    if let Some(recorder) = self.try_state::<crate::test::IpcRecorder>() {
+      recorder.record_event(self.label(), emit_args, ids);
+    }
+
     self.eval(&crate::event::emit_js_script(
       self.manager().listeners().function_name(),
       emit_args,
@@ -1799,9 +2078,128 @@ impl<T: ScopeObject> ResolvedScope<T> {
 
 #[cfg(test)]
 mod tests {
+  use std::sync::{Arc, Mutex};
+
+  use tauri_utils::config::{WebPermissionDecision, WebPermissionKind, WebPermissionRule};
+
+  use super::{PermissionResponse, WebviewBuilder};
+  use crate::test::{mock_builder, mock_context, noop_assets, MockRuntime};
+
   #[test]
   fn webview_is_send_sync() {
     crate::test_utils::assert_send::<super::Webview>();
     crate::test_utils::assert_sync::<super::Webview>();
   }
+
+  #[test]
+  fn permission_rules_take_precedence_over_the_handler() {
+    let mut context = mock_context(noop_assets());
+    context.config_mut().app.security.web_permissions = vec![
+      WebPermissionRule {
+        origin: "https://meet.example.com/".into(),
+        permissions: vec![WebPermissionKind::Camera, WebPermissionKind::Microphone],
+        decision: WebPermissionDecision::Allow,
+      },
+      WebPermissionRule {
+        origin: "*".into(),
+        permissions: vec![WebPermissionKind::Geolocation],
+        decision: WebPermissionDecision::Deny,
+      },
+    ];
+    let app = mock_builder().build(context).unwrap();
+    crate::WebviewWindowBuilder::new(&app, "main", Default::default())
+      .build()
+      .unwrap();
+
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let requests_ = requests.clone();
+    let pending = WebviewBuilder::<MockRuntime>::new("main", Default::default())
+      .on_permission_request(move |webview, url, permission| {
+        requests_
+          .lock()
+          .unwrap()
+          .push((webview.label().to_string(), url.to_string(), permission));
+        PermissionResponse::Default
+      })
+      .into_pending_webview(&app, "main")
+      .unwrap();
+    let handler = pending.permission_request_handler.unwrap();
+
+    let meet = "https://meet.example.com/room".parse().unwrap();
+    let other = "https://other.example.com/".parse().unwrap();
+    assert_eq!(
+      handler(&meet, WebPermissionKind::Camera),
+      PermissionResponse::Allow
+    );
+    assert_eq!(
+      handler(&meet, WebPermissionKind::Geolocation),
+      PermissionResponse::Deny
+    );
+    assert_eq!(
+      handler(&other, WebPermissionKind::Geolocation),
+      PermissionResponse::Deny
+    );
+    assert!(requests.lock().unwrap().is_empty());
+
+    // requests without a matching rule reach the handler
+    assert_eq!(
+      handler(&other, WebPermissionKind::Camera),
+      PermissionResponse::Default
+    );
+    assert_eq!(
+      handler(&meet, WebPermissionKind::Notifications),
+      PermissionResponse::Default
+    );
+    assert_eq!(
+      *requests.lock().unwrap(),
+      [
+        (
+          "main".to_string(),
+          "https://other.example.com/".to_string(),
+          WebPermissionKind::Camera
+        ),
+        (
+          "main".to_string(),
+          "https://meet.example.com/room".to_string(),
+          WebPermissionKind::Notifications
+        ),
+      ]
+    );
+  }
+
+  #[test]
+  fn permission_rules_apply_without_a_handler() {
+    let mut context = mock_context(noop_assets());
+    context.config_mut().app.security.web_permissions = vec![WebPermissionRule {
+      origin: "*".into(),
+      permissions: vec![WebPermissionKind::Notifications],
+      decision: WebPermissionDecision::Allow,
+    }];
+    let app = mock_builder().build(context).unwrap();
+
+    let handler = WebviewBuilder::<MockRuntime>::new("main", Default::default())
+      .into_pending_webview(&app, "main")
+      .unwrap()
+      .permission_request_handler
+      .unwrap();
+    let url = "https://example.com".parse().unwrap();
+    assert_eq!(
+      handler(&url, WebPermissionKind::Notifications),
+      PermissionResponse::Allow
+    );
+    assert_eq!(
+      handler(&url, WebPermissionKind::Camera),
+      PermissionResponse::Default
+    );
+
+    // without rules nor handler the webview default behavior applies
+    let app = crate::test::mock_app();
+    assert!(
+      WebviewBuilder::<MockRuntime>::new("main", Default::default())
+        .into_pending_webview(&app, "main")
+        .unwrap()
+        .permission_request_handler
+        .is_none()
+    );
+  }
 }
@@ -0,0 +1,215 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use serialize_to_javascript::{default_template, Template};
+use tauri_runtime::dpi::{LogicalPosition, LogicalSize};
+use url::Url;
+
+use crate::{
+  ipc::{message_port::MessagePorts, MessagePort},
+  Manager, Runtime, Webview, WebviewWindow,
+};
+
+pub(crate) type OnNewWindow<R> =
+  dyn Fn(Url, NewWindowFeatures) -> NewWindowResponse<R> + Send + Sync + 'static;
+
+#[derive(Template)]
+#[default_template("./scripts/new-window.js")]
+pub(crate) struct NewWindowJavascript {
+  /// Whether `window.open` must be routed to the new window handler.
+  pub(crate) handle_window_open: bool,
+  /// The message port linking this webview to its opener.
+  pub(crate) opener_port: Option<u32>,
+}
+
+/// The features of a window requested by a webview, e.g. with the third argument of `window.open()`.
+///
+/// Pass it to [`crate::WebviewWindowBuilder::window_features`] to apply the requested size and position
+/// and link the new window to its opener.
+#[derive(Debug, Clone, Default)]
+pub struct NewWindowFeatures {
+  pub(crate) size: Option<LogicalSize<f64>>,
+  pub(crate) position: Option<LogicalPosition<f64>>,
+  /// The end of the message channel the new window uses to talk to its opener.
+  pub(crate) opener_port: Option<u32>,
+  /// Whether the new window must not be linked to its opener.
+  pub(crate) noopener: bool,
+}
+
+impl NewWindowFeatures {
+  /// Parses a `window.open()` features string such as `width=400,height=300,left=10,top=10`.
+  pub(crate) fn parse(features: &str) -> Self {
+    let mut width = None;
+    let mut height = None;
+    let mut left = None;
+    let mut top = None;
+    let mut noopener = false;
+
+    for feature in features.split([',', ' ']) {
+      let Some((key, value)) = feature.split_once('=') else {
+        if matches!(
+          feature.trim().to_lowercase().as_str(),
+          "noopener" | "noreferrer"
+        ) {
+          noopener = true;
+        }
+        continue;
+      };
+      let Ok(value) = value.trim().parse::<f64>() else {
+        continue;
+      };
+      match key.trim().to_lowercase().as_str() {
+        "width" | "innerwidth" => width = Some(value),
+        "height" | "innerheight" => height = Some(value),
+        "left" | "screenx" => left = Some(value),
+        "top" | "screeny" => top = Some(value),
+        _ => (),
+      }
+    }
+
+    Self {
+      size: width.zip(height).map(|(w, h)| LogicalSize::new(w, h)),
+      position: left
+        .or(top.map(|_| 0.))
+        .zip(top.or(left.map(|_| 0.)))
+        .map(|(x, y)| LogicalPosition::new(x, y)),
+      opener_port: None,
+      noopener,
+    }
+  }
+
+  /// The features of a request made by a link targeting a new browsing context, which never exposes its opener.
+  pub(crate) fn link() -> Self {
+    Self {
+      noopener: true,
+      ..Default::default()
+    }
+  }
+
+  /// The requested window size.
+  pub fn size(&self) -> Option<LogicalSize<f64>> {
+    self.size
+  }
+
+  /// The requested window position.
+  pub fn position(&self) -> Option<LogicalPosition<f64>> {
+    self.position
+  }
+}
+
+/// How a request to open a new window is handled, see [`crate::webview::WebviewBuilder::on_new_window`].
+pub enum NewWindowResponse<R: Runtime> {
+  /// Ignore the request.
+  Deny,
+  /// Open the URL with the default application of the system, usually the web browser.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Android / iOS:** Unsupported.
+  OpenExternally,
+  /// Use the given window for the request.
+  ///
+  /// The window is linked to the webview that made the request only if it was built with
+  /// [`crate::WebviewWindowBuilder::window_features`]. In that case `window.opener` is available in the new window
+  /// and `window.open()` returns an object that can post messages to it and close it.
+  Create {
+    /// The created window.
+    window: WebviewWindow<R>,
+  },
+}
+
+impl<R: Runtime> Webview<R> {
+  /// Runs the new window handler of this webview.
+  ///
+  /// Returns the end of the channel linking this webview to the new window, if any.
+  pub(crate) fn handle_new_window(
+    &self,
+    url: Url,
+    mut features: NewWindowFeatures,
+  ) -> crate::Result<Option<MessagePort>> {
+    let handler = self
+      .manager
+      .webview
+      .new_window_handlers
+      .lock()
+      .unwrap()
+      .get(self.label())
+      .cloned();
+    let Some(handler) = handler else {
+      return Ok(None);
+    };
+
+    if features.noopener {
+      if let NewWindowResponse::OpenExternally = handler(url.clone(), features) {
+        open_externally(&url)?;
+      }
+      return Ok(None);
+    }
+
+    // the other end is transferred to the new window when it is built with these features
+    let ports = self.state::<MessagePorts>();
+    let (port, opener_port) = ports.link(self.label(), self.label());
+    features.opener_port.replace(opener_port.id());
+
+    match handler(url.clone(), features) {
+      NewWindowResponse::Deny => {
+        port.close(self);
+        Ok(None)
+      }
+      NewWindowResponse::OpenExternally => {
+        port.close(self);
+        open_externally(&url)?;
+        Ok(None)
+      }
+      NewWindowResponse::Create { window } => {
+        if ports.owner(opener_port.id()).as_deref() == Some(window.label()) {
+          Ok(Some(port))
+        } else {
+          port.close(self);
+          Ok(None)
+        }
+      }
+    }
+  }
+}
+
+#[cfg(desktop)]
+fn open_externally(url: &Url) -> crate::Result<()> {
+  open::that_detached(url.as_str())?;
+  Ok(())
+}
+
+#[cfg(mobile)]
+fn open_externally(url: &Url) -> crate::Result<()> {
+  log::warn!("opening {url} externally is not supported on mobile");
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::NewWindowFeatures;
+
+  #[test]
+  fn parse_features() {
+    let features = NewWindowFeatures::parse("width=400,height=300,left=10,top=20");
+    assert_eq!(
+      features.size().map(|s| (s.width, s.height)),
+      Some((400., 300.))
+    );
+    assert_eq!(features.position().map(|p| (p.x, p.y)), Some((10., 20.)));
+
+    let features = NewWindowFeatures::parse("popup, innerWidth=200 innerHeight=100 screenY=5");
+    assert_eq!(
+      features.size().map(|s| (s.width, s.height)),
+      Some((200., 100.))
+    );
+    assert_eq!(features.position().map(|p| (p.x, p.y)), Some((0., 5.)));
+
+    let features = NewWindowFeatures::parse("noopener,width=200");
+    assert!(features.size().is_none());
+    assert!(features.position().is_none());
+    assert!(features.noopener);
+    assert!(!NewWindowFeatures::parse("width=200").noopener);
+  }
+}
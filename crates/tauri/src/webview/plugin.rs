@@ -169,6 +169,8 @@ mod desktop_commands {
     tauri_runtime::dpi::PhysicalPosition<i32>
   );
   getter!(webview_size, size, tauri_runtime::dpi::PhysicalSize<u32>);
+  getter!(webview_can_go_back, can_go_back, bool);
+  getter!(webview_can_go_forward, can_go_forward, bool);
   //getter!(is_focused, bool);
 
   setter!(print);
@@ -180,6 +182,9 @@ mod desktop_commands {
   setter!(webview_show, show);
   setter!(set_webview_zoom, set_zoom, f64);
   setter!(clear_all_browsing_data, clear_all_browsing_data);
+  setter!(webview_go_back, go_back);
+  setter!(webview_go_forward, go_forward);
+  setter!(webview_reload, reload, bool);
 
   #[command(root = "crate")]
   pub async fn reparent<R: Runtime>(
@@ -194,6 +199,17 @@ mod desktop_commands {
     Ok(())
   }
 
+  #[command(root = "crate")]
+  pub async fn internal_on_new_window<R: Runtime>(
+    webview: crate::Webview<R>,
+    url: url::Url,
+    features: String,
+  ) -> crate::Result<Option<u32>> {
+    let port =
+      webview.handle_new_window(url, crate::webview::NewWindowFeatures::parse(&features))?;
+    Ok(port.map(|port| port.id()))
+  }
+
   #[cfg(any(debug_assertions, feature = "devtools"))]
   #[command(root = "crate")]
   pub async fn internal_toggle_devtools<R: Runtime>(
@@ -257,6 +273,8 @@ pub fn init<R: Runtime>() -> TauriPlugin<R> {
             desktop_commands::get_all_webviews,
             desktop_commands::webview_position,
             desktop_commands::webview_size,
+            desktop_commands::webview_can_go_back,
+            desktop_commands::webview_can_go_forward,
             // setters
             desktop_commands::webview_close,
             desktop_commands::set_webview_size,
@@ -268,6 +286,10 @@ pub fn init<R: Runtime>() -> TauriPlugin<R> {
             desktop_commands::print,
             desktop_commands::reparent,
             desktop_commands::clear_all_browsing_data,
+            desktop_commands::webview_go_back,
+            desktop_commands::webview_go_forward,
+            desktop_commands::webview_reload,
+            desktop_commands::internal_on_new_window,
             #[cfg(any(debug_assertions, feature = "devtools"))]
             desktop_commands::internal_toggle_devtools,
           ]);
@@ -0,0 +1,125 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+;(function () {
+  const HANDLE_WINDOW_OPEN = __TEMPLATE_handle_window_open__
+  const OPENER_PORT = __TEMPLATE_opener_port__
+
+  // stands in for the `WindowProxy` of the other window, backed by a message port:
+  // only `postMessage`, `close` and `closed` are supported, there is no `location`, `focus()` is a no-op
+  // and `closed` is only set once this side closed the channel or a message could not be delivered
+  function createWindowProxy(portPromise) {
+    const pendingMessages = []
+    let port = null
+    let sequence = 0
+    let closed = false
+
+    function send(message) {
+      const payload =
+        message instanceof ArrayBuffer || message instanceof Uint8Array
+          ? message
+          : { message }
+      window.__TAURI_INTERNALS__
+        .invoke('plugin:message-port|post', payload, {
+          headers: {
+            'Tauri-Message-Port': port.toString(),
+            'Tauri-Message-Port-Sequence': (sequence++).toString()
+          }
+        })
+        .catch(() => {
+          closed = true
+        })
+    }
+
+    const proxy = {
+      get closed() {
+        return closed
+      },
+      postMessage(message) {
+        if (closed) {
+          return
+        }
+        if (port === null) {
+          pendingMessages.push(message)
+        } else {
+          send(message)
+        }
+      },
+      close() {
+        if (port !== null && !closed) {
+          window.__TAURI_INTERNALS__
+            .invoke('plugin:message-port|close', { port })
+            .catch(console.error)
+        }
+        closed = true
+      },
+      focus() {
+        // no-op
+      }
+    }
+
+    portPromise.then((p) => {
+      if (p === null) {
+        closed = true
+        return
+      }
+      port = p
+
+      // the message id is used to preserve the message order
+      let nextMessageId = 0
+      const outOfOrder = {}
+      const onMessage = window.__TAURI_INTERNALS__.transformCallback(
+        ({ message, id }) => {
+          outOfOrder[id] = message
+          while (nextMessageId in outOfOrder) {
+            const data = outOfOrder[nextMessageId]
+            delete outOfOrder[nextMessageId]
+            nextMessageId += 1
+            window.dispatchEvent(new MessageEvent('message', { data }))
+          }
+        }
+      )
+
+      window.__TAURI_INTERNALS__
+        .invoke('plugin:message-port|connect', {
+          port,
+          onMessage: `__CHANNEL__:${onMessage}`
+        })
+        .then(() => {
+          for (const message of pendingMessages.splice(0)) {
+            send(message)
+          }
+        })
+        .catch(() => {
+          closed = true
+        })
+    })
+
+    return proxy
+  }
+
+  if (HANDLE_WINDOW_OPEN) {
+    window.open = function (url, _target, features) {
+      const href = new URL(url ?? '', window.location.href).toString()
+      return createWindowProxy(
+        window.__TAURI_INTERNALS__
+          .invoke('plugin:webview|internal_on_new_window', {
+            url: href,
+            features: features ?? ''
+          })
+          .catch((e) => {
+            console.error(e)
+            return null
+          })
+      )
+    }
+  }
+
+  if (OPENER_PORT !== null) {
+    Object.defineProperty(window, 'opener', {
+      value: createWindowProxy(Promise.resolve(OPENER_PORT)),
+      configurable: true
+    })
+  }
+})()
@@ -39,7 +39,9 @@ use crate::{
   manager::AppManager,
   sealed::{ManagerBase, RuntimeOrDispatch},
   webview::PageLoadPayload,
-  webview::WebviewBuilder,
+  webview::{
+    NewWindowFeatures, NewWindowResponse, PermissionResponse, WebPermissionKind, WebviewBuilder,
+  },
   window::WindowBuilder,
   AppHandle, Event, EventId, Manager, Runtime, Webview, WindowEvent,
 };
@@ -351,6 +353,105 @@ tauri::Builder::default()
     self
   }
 
+  /// Defines a closure to be executed when the webview requests a new window,
+  /// either with `window.open()` or with a link targeting a new browsing context.
+  ///
+  /// See [`WebviewBuilder::on_new_window`] for more information.
+  ///
+  /// # Examples
+  /// ```rust,no_run
+  /// use tauri::{
+  ///   webview::{NewWindowResponse, WebviewWindowBuilder},
+  ///   WebviewUrl,
+  /// };
+  /// tauri::Builder::default()
+  ///   .setup(|app| {
+  ///     let handle = app.handle().clone();
+  ///     let webview_window = WebviewWindowBuilder::new(app, "core", WebviewUrl::App("index.html".into()))
+  ///       .on_new_window(move |url, features| {
+  ///         match WebviewWindowBuilder::new(&handle, "popup", WebviewUrl::External(url))
+  ///           .window_features(features)
+  ///           .build()
+  ///         {
+  ///           Ok(window) => NewWindowResponse::Create { window },
+  ///           Err(_) => NewWindowResponse::Deny,
+  ///         }
+  ///       })
+  ///       .build()?;
+  ///     Ok(())
+  ///   });
+  /// ```
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Android / iOS:** Unsupported.
+  pub fn on_new_window<
+    F: Fn(Url, NewWindowFeatures) -> NewWindowResponse<R> + Send + Sync + 'static,
+  >(
+    mut self,
+    f: F,
+  ) -> Self {
+    self.webview_builder = self.webview_builder.on_new_window(f);
+    self
+  }
+
+  /// Defines a closure to be executed when the page requests a web API permission,
+  /// such as camera, microphone, geolocation or notifications access.
+  ///
+  /// See [`WebviewBuilder::on_permission_request`] for more information.
+  ///
+  /// # Examples
+  /// ```rust,no_run
+  /// use tauri::{
+  ///   webview::{PermissionResponse, WebPermissionKind, WebviewWindowBuilder},
+  ///   WebviewUrl,
+  /// };
+  /// tauri::Builder::default()
+  ///   .setup(|app| {
+  ///     let webview_window = WebviewWindowBuilder::new(app, "core", WebviewUrl::App("index.html".into()))
+  ///       .on_permission_request(|_webview, _url, permission| match permission {
+  ///         WebPermissionKind::Notifications => PermissionResponse::Allow,
+  ///         _ => PermissionResponse::Deny,
+  ///       })
+  ///       .build()?;
+  ///     Ok(())
+  ///   });
+  /// ```
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux**: Requests of cross-origin iframes are denied without calling the closure,
+  ///   and clipboard access is not requested through it.
+  /// - **Windows / macOS / Android / iOS**: Unsupported, the closure is never called.
+  pub fn on_permission_request<
+    F: Fn(Webview<R>, &Url, WebPermissionKind) -> PermissionResponse + Send + Sync + 'static,
+  >(
+    mut self,
+    f: F,
+  ) -> Self {
+    self.webview_builder = self.webview_builder.on_permission_request(f);
+    self
+  }
+
+  /// Applies the [`NewWindowFeatures`] received by a new window handler,
+  /// setting the requested size and position and linking the window to the webview that requested it.
+  ///
+  /// See [`Self::on_new_window`].
+  #[must_use]
+  pub fn window_features(mut self, features: NewWindowFeatures) -> Self {
+    #[cfg(desktop)]
+    {
+      if let Some(size) = features.size {
+        self.window_builder = self.window_builder.inner_size(size.width, size.height);
+      }
+      if let Some(position) = features.position {
+        self.window_builder = self.window_builder.position(position.x, position.y);
+      }
+    }
+    self.webview_builder.opener_port = features.opener_port;
+    self
+  }
+
   /// Creates a new window.
   pub fn build(self) -> crate::Result<WebviewWindow<R>> {
     let (window, webview) = self.window_builder.with_webview(self.webview_builder)?;
@@ -1743,6 +1844,45 @@ impl<R: Runtime> WebviewWindow<R> {
     self.webview.navigate(url)
   }
 
+  /// Navigates back to the previous page in the webview's history.
+  pub fn go_back(&self) -> crate::Result<()> {
+    self.webview.go_back()
+  }
+
+  /// Navigates forward to the next page in the webview's history.
+  pub fn go_forward(&self) -> crate::Result<()> {
+    self.webview.go_forward()
+  }
+
+  /// Reloads the current page.
+  ///
+  /// When `bypass_cache` is `true`, cached resources are ignored and fetched again.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Android**: `bypass_cache` is ignored.
+  pub fn reload(&self, bypass_cache: bool) -> crate::Result<()> {
+    self.webview.reload(bypass_cache)
+  }
+
+  /// Whether the webview can navigate back in its history.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Android**: Unsupported, always returns `false`.
+  pub fn can_go_back(&self) -> crate::Result<bool> {
+    self.webview.can_go_back()
+  }
+
+  /// Whether the webview can navigate forward in its history.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Android**: Unsupported, always returns `false`.
+  pub fn can_go_forward(&self) -> crate::Result<bool> {
+    self.webview.can_go_forward()
+  }
+
   /// Handles this window receiving an [`crate::webview::InvokeRequest`].
   pub fn on_message(
     self,
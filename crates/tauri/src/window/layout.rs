@@ -0,0 +1,1026 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Declarative layouts of child webviews, see [`crate::utils::config::WindowConfig::layout`].
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+use serialize_to_javascript::{default_template, DefaultTemplate, Template};
+use tauri_runtime::{
+  dpi::{LogicalPosition, LogicalSize},
+  Rect,
+};
+
+use crate::{
+  utils::config::{LayoutConfig, LayoutDirection, LayoutKind, WindowConfig},
+  webview::WebviewBuilder,
+  Manager, Runtime, Webview, Window, WindowEvent,
+};
+
+use super::WindowBuilder;
+
+/// A logical rectangle computed by the layout engine.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct LayoutRect {
+  pub x: f64,
+  pub y: f64,
+  pub width: f64,
+  pub height: f64,
+}
+
+impl LayoutRect {
+  fn along(&self, direction: LayoutDirection) -> f64 {
+    match direction {
+      LayoutDirection::Horizontal => self.width,
+      LayoutDirection::Vertical => self.height,
+    }
+  }
+
+  /// Splits this rectangle along `direction` into consecutive slices of the given sizes,
+  /// separated by `gap`.
+  fn slice(&self, direction: LayoutDirection, sizes: &[f64], gap: f64) -> Vec<LayoutRect> {
+    let mut offset = 0.;
+    sizes
+      .iter()
+      .map(|size| {
+        let rect = match direction {
+          LayoutDirection::Horizontal => LayoutRect {
+            x: self.x + offset,
+            y: self.y,
+            width: *size,
+            height: self.height,
+          },
+          LayoutDirection::Vertical => LayoutRect {
+            x: self.x,
+            y: self.y + offset,
+            width: self.width,
+            height: *size,
+          },
+        };
+        offset += size + gap;
+        rect
+      })
+      .collect()
+  }
+}
+
+/// A splitter between two children of a split node that has an id.
+#[derive(Debug, Clone)]
+struct Splitter {
+  id: String,
+  index: usize,
+  direction: LayoutDirection,
+  rect: LayoutRect,
+}
+
+/// The edge of a webview that borders a splitter.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum SplitterEdge {
+  Left,
+  Right,
+  Top,
+  Bottom,
+}
+
+/// A splitter the user can drag from the edge of a webview.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct SplitterHandle {
+  pub id: String,
+  pub index: usize,
+  pub edge: SplitterEdge,
+}
+
+/// What must be done to a webview to apply a layout pass.
+struct WebviewUpdate {
+  label: String,
+  bounds: Option<LayoutRect>,
+  show: bool,
+  hide: bool,
+  handles: Option<Vec<SplitterHandle>>,
+}
+
+#[derive(Template)]
+#[default_template("./scripts/layout.js")]
+struct LayoutScript<'a> {
+  splitters: &'a [SplitterHandle],
+}
+
+/// The state of a layout attached to a window.
+#[derive(Debug)]
+pub(crate) struct WindowLayout {
+  root: LayoutConfig,
+  /// The last computed bounds of every node with an id.
+  node_bounds: HashMap<String, LayoutRect>,
+  /// The splitters of the last layout pass.
+  splitters: Vec<Splitter>,
+  /// The labels of the webviews hidden by the last layout pass.
+  hidden: Vec<String>,
+  /// The splitter handles last sent to each webview.
+  handles: HashMap<String, Vec<SplitterHandle>>,
+}
+
+impl WindowLayout {
+  pub fn new(root: LayoutConfig) -> Self {
+    Self {
+      root,
+      node_bounds: Default::default(),
+      splitters: Default::default(),
+      hidden: Default::default(),
+      handles: Default::default(),
+    }
+  }
+
+  /// Labels of every webview of the layout, including the ones in inactive tabs.
+  pub fn webview_labels(&self) -> Vec<&str> {
+    let mut labels = Vec::new();
+    collect_webviews(&self.root, &mut |label, _| labels.push(label));
+    labels
+  }
+
+  /// Lays out the webviews in a window of the given logical size.
+  ///
+  /// Returns the bounds of each webview, or `None` if it must be hidden.
+  pub fn compute(&mut self, width: f64, height: f64) -> Vec<(String, Option<LayoutRect>)> {
+    let mut webviews = Vec::new();
+    self.node_bounds.clear();
+    self.splitters.clear();
+    layout_node(
+      &self.root,
+      Some(LayoutRect {
+        x: 0.,
+        y: 0.,
+        width,
+        height,
+      }),
+      &mut webviews,
+      &mut self.node_bounds,
+      &mut self.splitters,
+    );
+    webviews
+  }
+
+  /// The splitters of the last layout pass bordering a webview with the given bounds.
+  pub fn splitter_handles(&self, bounds: &LayoutRect) -> Vec<SplitterHandle> {
+    // tolerate rounding errors in the computed bounds
+    let touches = |a: f64, b: f64| (a - b).abs() < 0.5;
+    let overlaps = |start: f64, size: f64, other_start: f64, other_size: f64| {
+      start < other_start + other_size && other_start < start + size
+    };
+
+    self
+      .splitters
+      .iter()
+      .filter_map(|splitter| {
+        let s = &splitter.rect;
+        let edge = match splitter.direction {
+          LayoutDirection::Horizontal if overlaps(bounds.y, bounds.height, s.y, s.height) => {
+            if touches(bounds.x + bounds.width, s.x) {
+              Some(SplitterEdge::Right)
+            } else if touches(bounds.x, s.x + s.width) {
+              Some(SplitterEdge::Left)
+            } else {
+              None
+            }
+          }
+          LayoutDirection::Vertical if overlaps(bounds.x, bounds.width, s.x, s.width) => {
+            if touches(bounds.y + bounds.height, s.y) {
+              Some(SplitterEdge::Bottom)
+            } else if touches(bounds.y, s.y + s.height) {
+              Some(SplitterEdge::Top)
+            } else {
+              None
+            }
+          }
+          _ => None,
+        };
+        edge.map(|edge| SplitterHandle {
+          id: splitter.id.clone(),
+          index: splitter.index,
+          edge,
+        })
+      })
+      .collect()
+  }
+
+  /// Computes the layout and the changes to apply to each webview since the last pass.
+  fn update(&mut self, width: f64, height: f64) -> Vec<WebviewUpdate> {
+    let mut hidden = Vec::new();
+    let mut updates = Vec::new();
+    for (label, bounds) in self.compute(width, height) {
+      let handles = bounds
+        .map(|bounds| self.splitter_handles(&bounds))
+        .unwrap_or_default();
+      let handles_changed = self.handles.get(&label) != Some(&handles);
+      if handles_changed {
+        self.handles.insert(label.clone(), handles.clone());
+      }
+
+      let was_hidden = self.hidden.contains(&label);
+      if bounds.is_none() {
+        hidden.push(label.clone());
+      }
+
+      updates.push(WebviewUpdate {
+        show: bounds.is_some() && was_hidden,
+        hide: bounds.is_none() && !was_hidden,
+        handles: handles_changed.then_some(handles),
+        bounds,
+        label,
+      });
+    }
+    self.hidden = hidden;
+    updates
+  }
+
+  /// Moves the splitter between the child `index` and `index + 1` of the split node `id` by `delta` logical pixels.
+  ///
+  /// The resulting sizes are clamped so both children keep their minimum size.
+  pub fn move_splitter(&mut self, id: &str, index: usize, delta: f64) -> crate::Result<()> {
+    let bounds = self.node_bounds.get(id).copied();
+    let node = find_node_mut(&mut self.root, id)
+      .ok_or_else(|| crate::Error::LayoutNodeNotFound(id.into()))?;
+
+    let LayoutKind::Split {
+      direction,
+      splitter_size,
+      children,
+    } = &mut node.kind
+    else {
+      return Err(crate::Error::LayoutNodeNotFound(id.into()));
+    };
+
+    if index + 1 >= children.len() {
+      return Err(crate::Error::LayoutChildOutOfBounds(id.into(), index + 1));
+    }
+
+    // the node is hidden in an inactive tab, nothing to move
+    let Some(bounds) = bounds else {
+      return Ok(());
+    };
+
+    let mins = children
+      .iter()
+      .map(|c| min_size(c).along(*direction))
+      .collect::<Vec<_>>();
+    let available = (bounds.along(*direction) - gaps(*splitter_size, children.len())).max(0.);
+    let mut sizes = distribute(available, &split_weights(children), &mins);
+
+    let total = sizes[index] + sizes[index + 1];
+    let first = (sizes[index] + delta)
+      .min(total - mins[index + 1])
+      .max(mins[index]);
+    sizes[index] = first;
+    sizes[index + 1] = total - first;
+
+    // the pixel sizes become the new weights so the proportions are kept when the window is resized
+    for (child, size) in children.iter_mut().zip(sizes) {
+      child.weight.replace(size);
+    }
+
+    Ok(())
+  }
+
+  /// Shows the child `index` of the tabs node `id`.
+  pub fn set_active_tab(&mut self, id: &str, index: usize) -> crate::Result<()> {
+    let node = find_node_mut(&mut self.root, id)
+      .ok_or_else(|| crate::Error::LayoutNodeNotFound(id.into()))?;
+
+    let LayoutKind::Tabs { active, children } = &mut node.kind else {
+      return Err(crate::Error::LayoutNodeNotFound(id.into()));
+    };
+
+    if index >= children.len() {
+      return Err(crate::Error::LayoutChildOutOfBounds(id.into(), index));
+    }
+
+    *active = index;
+    Ok(())
+  }
+}
+
+impl<R: Runtime> Window<R> {
+  /// Attaches a declarative layout to this window.
+  ///
+  /// Every `webview` node must reference an existing child webview, which is moved to this window
+  /// with [`Webview::reparent`] if it belongs to another one.
+  /// The webview bounds are managed by the layout from now on and updated whenever the window is resized.
+  ///
+  /// The splitters of split nodes with an id can be dragged from the edges of the webviews next to them
+  /// if their capabilities grant the `core:window:allow-move-layout-splitter` permission.
+  pub fn set_layout(&self, layout: LayoutConfig) -> crate::Result<()> {
+    let layout = WindowLayout::new(layout);
+
+    let webviews = layout
+      .webview_labels()
+      .into_iter()
+      .map(|label| {
+        self
+          .manager
+          .get_webview(label)
+          .ok_or(crate::Error::WebviewNotFound)
+      })
+      .collect::<crate::Result<Vec<_>>>()?;
+    for webview in webviews {
+      if webview.window_label() != self.label() {
+        webview.reparent(self)?;
+      }
+      webview.set_auto_resize(false)?;
+    }
+
+    self.layout.lock().unwrap().replace(layout);
+
+    let size = self.inner_size()?.to_logical::<f64>(self.scale_factor()?);
+    self.apply_layout(size.width, size.height)
+  }
+
+  /// Moves the splitter between the child `index` and `index + 1` of the split layout node `id`
+  /// by `delta` logical pixels.
+  pub fn move_layout_splitter(&self, id: &str, index: usize, delta: f64) -> crate::Result<()> {
+    self
+      .layout
+      .lock()
+      .unwrap()
+      .as_mut()
+      .ok_or_else(|| crate::Error::LayoutNodeNotFound(id.into()))?
+      .move_splitter(id, index, delta)?;
+    let size = self.inner_size()?.to_logical::<f64>(self.scale_factor()?);
+    self.apply_layout(size.width, size.height)
+  }
+
+  /// Shows the child `index` of the tabs layout node `id`, hiding the other tabs.
+  pub fn set_layout_active_tab(&self, id: &str, index: usize) -> crate::Result<()> {
+    self
+      .layout
+      .lock()
+      .unwrap()
+      .as_mut()
+      .ok_or_else(|| crate::Error::LayoutNodeNotFound(id.into()))?
+      .set_active_tab(id, index)?;
+    let size = self.inner_size()?.to_logical::<f64>(self.scale_factor()?);
+    self.apply_layout(size.width, size.height)
+  }
+
+  fn apply_layout(&self, width: f64, height: f64) -> crate::Result<()> {
+    // the lock must not be held while calling into the webviews, their events might need it
+    let updates = match self.layout.lock().unwrap().as_mut() {
+      Some(layout) => layout.update(width, height),
+      None => return Ok(()),
+    };
+
+    for update in updates {
+      // the webview might have been closed since the layout was set
+      let Some(webview) = self.manager.get_webview(&update.label) else {
+        continue;
+      };
+
+      if let Some(bounds) = update.bounds {
+        webview.set_bounds(Rect {
+          position: LogicalPosition::new(bounds.x, bounds.y).into(),
+          size: LogicalSize::new(bounds.width, bounds.height).into(),
+        })?;
+      }
+      if update.show {
+        webview.show()?;
+      }
+      if update.hide {
+        webview.hide()?;
+      }
+      if let Some(handles) = update.handles {
+        eval_splitters(&webview, &handles)?;
+      }
+    }
+
+    Ok(())
+  }
+}
+
+/// Lays out the webviews again when the window is resized.
+///
+/// Called by the window event handler the manager registers on every window.
+pub(crate) fn on_window_event<R: Runtime>(window: &Window<R>, event: &WindowEvent) {
+  let size = match event {
+    WindowEvent::Resized(size) => match window.scale_factor() {
+      Ok(scale_factor) => size.to_logical::<f64>(scale_factor),
+      Err(_) => return,
+    },
+    WindowEvent::ScaleFactorChanged {
+      scale_factor,
+      new_inner_size,
+    } => new_inner_size.to_logical::<f64>(*scale_factor),
+    _ => return,
+  };
+  if let Err(e) = window.apply_layout(size.width, size.height) {
+    log::error!("failed to apply window layout: {e}");
+  }
+}
+
+/// Sends the splitters bordering the webview after its page has been loaded.
+pub(crate) fn on_page_load<R: Runtime>(webview: &Webview<R>) {
+  let handles = webview
+    .window()
+    .layout
+    .lock()
+    .unwrap()
+    .as_ref()
+    .and_then(|layout| layout.handles.get(webview.label()).cloned());
+  if let Some(handles) = handles {
+    if let Err(e) = eval_splitters(webview, &handles) {
+      log::error!("failed to update the layout splitters of the webview: {e}");
+    }
+  }
+}
+
+fn eval_splitters<R: Runtime>(
+  webview: &Webview<R>,
+  handles: &[SplitterHandle],
+) -> crate::Result<()> {
+  let script = LayoutScript { splitters: handles }
+    .render_default(&Default::default())?
+    .into_string();
+  webview.eval(&script)
+}
+
+/// Creates a window and the child webviews of its configured layout.
+pub(crate) fn build_window<R: Runtime, M: Manager<R>>(
+  manager: &M,
+  config: &WindowConfig,
+) -> crate::Result<Window<R>> {
+  let window = WindowBuilder::from_config(manager, config)?.build()?;
+
+  if let Some(layout) = &config.layout {
+    let mut builders = Vec::new();
+    collect_webviews(layout, &mut |label, kind| {
+      if let LayoutKind::Webview {
+        url,
+        user_agent,
+        drag_drop_enabled,
+        transparent,
+        incognito,
+        ..
+      } = kind
+      {
+        let mut builder = WebviewBuilder::new(label, url.clone()).incognito(*incognito);
+        if let Some(user_agent) = user_agent {
+          builder = builder.user_agent(user_agent);
+        }
+        if !drag_drop_enabled {
+          builder = builder.disable_drag_drop_handler();
+        }
+        #[cfg(any(not(target_os = "macos"), feature = "macos-private-api"))]
+        {
+          builder = builder.transparent(*transparent);
+        }
+        #[cfg(all(target_os = "macos", not(feature = "macos-private-api")))]
+        let _ = transparent;
+        builders.push(builder);
+      }
+    });
+
+    for builder in builders {
+      window.add_child(
+        builder,
+        LogicalPosition::new(0., 0.),
+        LogicalSize::new(config.width, config.height),
+      )?;
+    }
+
+    window.set_layout(layout.clone())?;
+  }
+
+  Ok(window)
+}
+
+fn collect_webviews<'a>(node: &'a LayoutConfig, f: &mut impl FnMut(&'a str, &'a LayoutKind)) {
+  match &node.kind {
+    LayoutKind::Split { children, .. }
+    | LayoutKind::Stack { children, .. }
+    | LayoutKind::Tabs { children, .. } => {
+      for child in children {
+        collect_webviews(child, f);
+      }
+    }
+    LayoutKind::Webview { label, .. } => f(label, &node.kind),
+  }
+}
+
+fn find_node_mut<'a>(node: &'a mut LayoutConfig, id: &str) -> Option<&'a mut LayoutConfig> {
+  if node.id.as_deref() == Some(id) {
+    return Some(node);
+  }
+  match &mut node.kind {
+    LayoutKind::Split { children, .. }
+    | LayoutKind::Stack { children, .. }
+    | LayoutKind::Tabs { children, .. } => children.iter_mut().find_map(|c| find_node_mut(c, id)),
+    LayoutKind::Webview { .. } => None,
+  }
+}
+
+fn gaps(splitter_size: f64, count: usize) -> f64 {
+  splitter_size * count.saturating_sub(1) as f64
+}
+
+fn split_weights(children: &[LayoutConfig]) -> Vec<f64> {
+  children
+    .iter()
+    .map(|c| c.weight.unwrap_or(1.).max(0.))
+    .collect()
+}
+
+/// The minimum size of a node, taking its children into account.
+fn min_size(node: &LayoutConfig) -> LayoutRect {
+  let (width, height) = match &node.kind {
+    LayoutKind::Split {
+      direction,
+      splitter_size,
+      children,
+    } => {
+      let gaps = gaps(*splitter_size, children.len());
+      stacked_min_size(*direction, children.iter().map(min_size), gaps)
+    }
+    LayoutKind::Stack {
+      direction,
+      children,
+    } => {
+      let sizes = children.iter().map(|c| {
+        let mut min = min_size(c);
+        if let Some(size) = c.size {
+          match direction {
+            LayoutDirection::Horizontal => min.width = min.width.max(size),
+            LayoutDirection::Vertical => min.height = min.height.max(size),
+          }
+        }
+        min
+      });
+      stacked_min_size(*direction, sizes, 0.)
+    }
+    LayoutKind::Tabs { children, .. } => children
+      .iter()
+      .map(min_size)
+      .fold((0f64, 0f64), |(w, h), min| {
+        (w.max(min.width), h.max(min.height))
+      }),
+    LayoutKind::Webview { .. } => (0., 0.),
+  };
+
+  LayoutRect {
+    x: 0.,
+    y: 0.,
+    width: width.max(node.min_width),
+    height: height.max(node.min_height),
+  }
+}
+
+fn stacked_min_size(
+  direction: LayoutDirection,
+  sizes: impl Iterator<Item = LayoutRect>,
+  gaps: f64,
+) -> (f64, f64) {
+  let (along, across) = sizes.fold((gaps, 0f64), |(along, across), min| match direction {
+    LayoutDirection::Horizontal => (along + min.width, across.max(min.height)),
+    LayoutDirection::Vertical => (along + min.height, across.max(min.width)),
+  });
+  match direction {
+    LayoutDirection::Horizontal => (along, across),
+    LayoutDirection::Vertical => (across, along),
+  }
+}
+
+/// Distributes `available` between items proportionally to their weights,
+/// making sure no item gets less than its minimum.
+///
+/// Items that would be smaller than their minimum are fixed to it and the rest of the space
+/// is distributed again between the remaining items.
+fn distribute(available: f64, weights: &[f64], mins: &[f64]) -> Vec<f64> {
+  let mut sizes: Vec<Option<f64>> = vec![None; weights.len()];
+
+  loop {
+    let fixed = sizes.iter().flatten().sum::<f64>();
+    let remaining = (available - fixed).max(0.);
+    let total_weight = weights
+      .iter()
+      .zip(&sizes)
+      .filter(|(_, s)| s.is_none())
+      .map(|(w, _)| w)
+      .sum::<f64>();
+
+    let mut changed = false;
+    for i in 0..weights.len() {
+      if sizes[i].is_some() {
+        continue;
+      }
+      let share = if total_weight > 0. {
+        remaining * weights[i] / total_weight
+      } else {
+        0.
+      };
+      if share < mins[i] {
+        sizes[i].replace(mins[i]);
+        changed = true;
+      }
+    }
+
+    if !changed {
+      return sizes
+        .iter()
+        .enumerate()
+        .map(|(i, size)| {
+          size.unwrap_or_else(|| {
+            if total_weight > 0. {
+              remaining * weights[i] / total_weight
+            } else {
+              0.
+            }
+          })
+        })
+        .collect();
+    }
+  }
+}
+
+fn layout_node(
+  node: &LayoutConfig,
+  rect: Option<LayoutRect>,
+  webviews: &mut Vec<(String, Option<LayoutRect>)>,
+  node_bounds: &mut HashMap<String, LayoutRect>,
+  splitters: &mut Vec<Splitter>,
+) {
+  if let (Some(id), Some(rect)) = (&node.id, rect) {
+    node_bounds.insert(id.clone(), rect);
+  }
+
+  match &node.kind {
+    LayoutKind::Split {
+      direction,
+      splitter_size,
+      children,
+    } => {
+      let rects = rect.map(|rect| {
+        let mins = children
+          .iter()
+          .map(|c| min_size(c).along(*direction))
+          .collect::<Vec<_>>();
+        let available = (rect.along(*direction) - gaps(*splitter_size, children.len())).max(0.);
+        let sizes = distribute(available, &split_weights(children), &mins);
+        rect.slice(*direction, &sizes, *splitter_size)
+      });
+      if let (Some(id), Some(rects)) = (&node.id, &rects) {
+        for (index, pair) in rects.windows(2).enumerate() {
+          let rect = match direction {
+            LayoutDirection::Horizontal => LayoutRect {
+              x: pair[0].x + pair[0].width,
+              y: pair[0].y,
+              width: pair[1].x - pair[0].x - pair[0].width,
+              height: pair[0].height,
+            },
+            LayoutDirection::Vertical => LayoutRect {
+              x: pair[0].x,
+              y: pair[0].y + pair[0].height,
+              width: pair[0].width,
+              height: pair[1].y - pair[0].y - pair[0].height,
+            },
+          };
+          splitters.push(Splitter {
+            id: id.clone(),
+            index,
+            direction: *direction,
+            rect,
+          });
+        }
+      }
+      for (i, child) in children.iter().enumerate() {
+        layout_node(
+          child,
+          rects.as_ref().map(|r| r[i]),
+          webviews,
+          node_bounds,
+          splitters,
+        );
+      }
+    }
+    LayoutKind::Stack {
+      direction,
+      children,
+    } => {
+      let rects = rect.map(|rect| {
+        let mins = children
+          .iter()
+          .map(|c| min_size(c).along(*direction))
+          .collect::<Vec<_>>();
+        let fixed = children
+          .iter()
+          .zip(&mins)
+          .filter_map(|(c, min)| c.size.map(|s| s.max(*min)))
+          .sum::<f64>();
+        let flexible = children
+          .iter()
+          .zip(&mins)
+          .filter(|(c, _)| c.size.is_none())
+          .map(|(_, min)| *min)
+          .collect::<Vec<_>>();
+        let mut flexible_sizes = distribute(
+          (rect.along(*direction) - fixed).max(0.),
+          &vec![1.; flexible.len()],
+          &flexible,
+        )
+        .into_iter();
+        let sizes = children
+          .iter()
+          .zip(&mins)
+          .map(|(c, min)| match c.size {
+            Some(size) => size.max(*min),
+            None => flexible_sizes.next().unwrap_or_default(),
+          })
+          .collect::<Vec<_>>();
+        rect.slice(*direction, &sizes, 0.)
+      });
+      for (i, child) in children.iter().enumerate() {
+        layout_node(
+          child,
+          rects.as_ref().map(|r| r[i]),
+          webviews,
+          node_bounds,
+          splitters,
+        );
+      }
+    }
+    LayoutKind::Tabs { active, children } => {
+      for (i, child) in children.iter().enumerate() {
+        layout_node(
+          child,
+          rect.filter(|_| i == *active),
+          webviews,
+          node_bounds,
+          splitters,
+        );
+      }
+    }
+    LayoutKind::Webview { label, .. } => webviews.push((label.clone(), rect)),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn webview(label: &str) -> LayoutConfig {
+    LayoutConfig {
+      id: None,
+      weight: None,
+      size: None,
+      min_width: 0.,
+      min_height: 0.,
+      kind: LayoutKind::Webview {
+        label: label.into(),
+        url: Default::default(),
+        user_agent: None,
+        drag_drop_enabled: true,
+        transparent: false,
+        incognito: false,
+      },
+    }
+  }
+
+  fn rect(x: f64, y: f64, width: f64, height: f64) -> Option<LayoutRect> {
+    Some(LayoutRect {
+      x,
+      y,
+      width,
+      height,
+    })
+  }
+
+  #[test]
+  fn distribute_respects_minimums() {
+    assert_eq!(distribute(300., &[1., 2.], &[0., 0.]), vec![100., 200.]);
+    assert_eq!(distribute(300., &[1., 2.], &[150., 0.]), vec![150., 150.]);
+    assert_eq!(distribute(100., &[1., 1.], &[80., 80.]), vec![80., 80.]);
+  }
+
+  #[test]
+  fn split_layout() {
+    let mut sidebar = webview("sidebar");
+    sidebar.min_width = 200.;
+    let mut layout = WindowLayout::new(LayoutConfig {
+      id: Some("main".into()),
+      weight: None,
+      size: None,
+      min_width: 0.,
+      min_height: 0.,
+      kind: LayoutKind::Split {
+        direction: LayoutDirection::Horizontal,
+        splitter_size: 4.,
+        children: vec![sidebar, webview("content")],
+      },
+    });
+
+    assert_eq!(
+      layout.compute(604., 400.),
+      vec![
+        ("sidebar".into(), rect(0., 0., 300., 400.)),
+        ("content".into(), rect(304., 0., 300., 400.)),
+      ]
+    );
+    assert_eq!(
+      layout.compute(304., 400.),
+      vec![
+        ("sidebar".into(), rect(0., 0., 200., 400.)),
+        ("content".into(), rect(204., 0., 100., 400.)),
+      ]
+    );
+
+    layout.compute(604., 400.);
+    layout.move_splitter("main", 0, -150.).unwrap();
+    assert_eq!(
+      layout.compute(604., 400.),
+      vec![
+        ("sidebar".into(), rect(0., 0., 200., 400.)),
+        ("content".into(), rect(204., 0., 400., 400.)),
+      ]
+    );
+
+    assert!(matches!(
+      layout.move_splitter("main", 1, 10.),
+      Err(crate::Error::LayoutChildOutOfBounds(_, 2))
+    ));
+    assert!(matches!(
+      layout.move_splitter("other", 0, 10.),
+      Err(crate::Error::LayoutNodeNotFound(_))
+    ));
+  }
+
+  #[test]
+  fn splitter_handles() {
+    let mut layout = WindowLayout::new(LayoutConfig {
+      id: Some("main".into()),
+      weight: None,
+      size: None,
+      min_width: 0.,
+      min_height: 0.,
+      kind: LayoutKind::Split {
+        direction: LayoutDirection::Horizontal,
+        splitter_size: 4.,
+        children: vec![
+          webview("sidebar"),
+          LayoutConfig {
+            id: Some("right".into()),
+            weight: None,
+            size: None,
+            min_width: 0.,
+            min_height: 0.,
+            kind: LayoutKind::Split {
+              direction: LayoutDirection::Vertical,
+              splitter_size: 4.,
+              children: vec![webview("content"), webview("console")],
+            },
+          },
+        ],
+      },
+    });
+
+    let handle = |id: &str, index, edge| SplitterHandle {
+      id: id.into(),
+      index,
+      edge,
+    };
+    fn handles(updates: &[WebviewUpdate]) -> Vec<(&str, Option<Vec<SplitterHandle>>)> {
+      updates
+        .iter()
+        .map(|u| (u.label.as_str(), u.handles.clone()))
+        .collect()
+    }
+
+    let updates = layout.update(604., 404.);
+    assert_eq!(
+      handles(&updates),
+      vec![
+        (
+          "sidebar",
+          Some(vec![handle("main", 0, SplitterEdge::Right)])
+        ),
+        (
+          "content",
+          Some(vec![
+            handle("main", 0, SplitterEdge::Left),
+            handle("right", 0, SplitterEdge::Bottom)
+          ])
+        ),
+        (
+          "console",
+          Some(vec![
+            handle("main", 0, SplitterEdge::Left),
+            handle("right", 0, SplitterEdge::Top)
+          ])
+        ),
+      ]
+    );
+
+    // unchanged handles are not sent again
+    let updates = layout.update(800., 600.);
+    assert!(updates.iter().all(|u| u.handles.is_none()));
+  }
+
+  #[test]
+  fn stack_and_tabs_layout() {
+    let mut toolbar = webview("toolbar");
+    toolbar.size.replace(40.);
+    let mut layout = WindowLayout::new(LayoutConfig {
+      id: None,
+      weight: None,
+      size: None,
+      min_width: 0.,
+      min_height: 0.,
+      kind: LayoutKind::Stack {
+        direction: LayoutDirection::Vertical,
+        children: vec![
+          toolbar,
+          LayoutConfig {
+            id: Some("tabs".into()),
+            weight: None,
+            size: None,
+            min_width: 0.,
+            min_height: 0.,
+            kind: LayoutKind::Tabs {
+              active: 0,
+              children: vec![webview("first"), webview("second")],
+            },
+          },
+        ],
+      },
+    });
+
+    assert_eq!(
+      layout.compute(800., 600.),
+      vec![
+        ("toolbar".into(), rect(0., 0., 800., 40.)),
+        ("first".into(), rect(0., 40., 800., 560.)),
+        ("second".into(), None),
+      ]
+    );
+
+    layout.set_active_tab("tabs", 1).unwrap();
+    assert_eq!(
+      layout.compute(800., 600.),
+      vec![
+        ("toolbar".into(), rect(0., 0., 800., 40.)),
+        ("first".into(), None),
+        ("second".into(), rect(0., 40., 800., 560.)),
+      ]
+    );
+
+    assert!(layout.set_active_tab("tabs", 2).is_err());
+  }
+
+  #[test]
+  fn follows_window_resizes() {
+    use crate::{test::send_window_event, window::WindowBuilder, PhysicalSize};
+
+    let app = crate::test::mock_app();
+    let window = WindowBuilder::new(&app, "main")
+      .inner_size(604., 400.)
+      .build()
+      .unwrap();
+    let other = WindowBuilder::new(&app, "other").build().unwrap();
+    let add_child = |window: &Window<_>, label: &str| {
+      window
+        .add_child(
+          WebviewBuilder::new(label, Default::default()),
+          LogicalPosition::new(0., 0.),
+          LogicalSize::new(1., 1.),
+        )
+        .unwrap()
+    };
+    let sidebar = add_child(&window, "sidebar");
+    let content = add_child(&other, "content");
+
+    window
+      .set_layout(LayoutConfig {
+        id: None,
+        weight: None,
+        size: None,
+        min_width: 0.,
+        min_height: 0.,
+        kind: LayoutKind::Split {
+          direction: LayoutDirection::Horizontal,
+          splitter_size: 4.,
+          children: vec![webview("sidebar"), webview("content")],
+        },
+      })
+      .unwrap();
+    // webviews of other windows are moved to the window of the layout
+    assert_eq!(content.window().label(), "main");
+    assert_eq!(sidebar.size().unwrap(), PhysicalSize::new(300, 400));
+    assert_eq!(content.size().unwrap(), PhysicalSize::new(300, 400));
+
+    send_window_event(
+      &window,
+      crate::WindowEvent::Resized(PhysicalSize::new(804, 600)),
+    )
+    .unwrap();
+    assert_eq!(sidebar.size().unwrap(), PhysicalSize::new(400, 600));
+    assert_eq!(content.size().unwrap(), PhysicalSize::new(400, 600));
+    assert_eq!(content.position().unwrap().x, 404);
+  }
+}
@@ -4,6 +4,8 @@
 
 //! The Tauri window types and functions.
 
+#[cfg(all(desktop, feature = "unstable"))]
+pub(crate) mod layout;
 pub(crate) mod plugin;
 
 use tauri_runtime::{
@@ -356,7 +358,12 @@ tauri::Builder::default()
       self
         .menu
         .or_else(|| self.manager.app_handle().menu())
-        .map(|menu| WindowMenu { is_app_wide, menu })
+        .map(|menu| WindowMenu {
+          is_app_wide,
+          menu,
+          #[cfg(feature = "test")]
+          visible: true,
+        })
     };
 
     #[cfg(desktop)]
@@ -841,6 +848,11 @@ impl<'a, R: Runtime, M: Manager<R>> WindowBuilder<'a, R, M> {
 pub(crate) struct WindowMenu<R: Runtime> {
   pub(crate) is_app_wide: bool,
   pub(crate) menu: Menu<R>,
+  /// Whether the menu is shown on the window, updated by [`Window::hide_menu`] and [`Window::show_menu`].
+  ///
+  /// Only read for the windows of the [`MockRuntime`](crate::test::MockRuntime), which have no native menu to query.
+  #[cfg(feature = "test")]
+  pub(crate) visible: bool,
 }
 
 // TODO: expand these docs since this is a pretty important type
@@ -859,6 +871,9 @@ pub struct Window<R: Runtime> {
   #[cfg(desktop)]
   pub(crate) menu: Arc<Mutex<Option<WindowMenu<R>>>>,
   pub(crate) resources_table: Arc<Mutex<ResourceTable>>,
+  // The declarative layout of the child webviews
+  #[cfg(all(desktop, feature = "unstable"))]
+  pub(crate) layout: Arc<Mutex<Option<layout::WindowLayout>>>,
 }
 
 impl<R: Runtime> std::fmt::Debug for Window<R> {
@@ -896,6 +911,8 @@ impl<R: Runtime> Clone for Window<R> {
       #[cfg(desktop)]
       menu: self.menu.clone(),
       resources_table: self.resources_table.clone(),
+      #[cfg(all(desktop, feature = "unstable"))]
+      layout: self.layout.clone(),
     }
   }
 }
@@ -965,6 +982,8 @@ impl<R: Runtime> Window<R> {
       #[cfg(desktop)]
       menu: Arc::new(std::sync::Mutex::new(menu)),
       resources_table: Default::default(),
+      #[cfg(all(desktop, feature = "unstable"))]
+      layout: Default::default(),
     }
   }
 
@@ -1161,6 +1180,8 @@ tauri::Builder::default()
     self.menu_lock().replace(WindowMenu {
       is_app_wide: false,
       menu,
+      #[cfg(feature = "test")]
+      visible: true,
     });
 
     Ok(prev_menu)
@@ -1209,7 +1230,11 @@ tauri::Builder::default()
   pub fn hide_menu(&self) -> crate::Result<()> {
     // remove from the window
     #[cfg_attr(target_os = "macos", allow(unused_variables))]
-    if let Some(window_menu) = &*self.menu_lock() {
+    if let Some(window_menu) = &mut *self.menu_lock() {
+      #[cfg(feature = "test")]
+      {
+        window_menu.visible = false;
+      }
       let window = self.clone();
       let menu_ = window_menu.menu.clone();
       self.run_on_main_thread(move || {
@@ -1237,7 +1262,11 @@ tauri::Builder::default()
   pub fn show_menu(&self) -> crate::Result<()> {
     // remove from the window
     #[cfg_attr(target_os = "macos", allow(unused_variables))]
-    if let Some(window_menu) = &*self.menu_lock() {
+    if let Some(window_menu) = &mut *self.menu_lock() {
+      #[cfg(feature = "test")]
+      {
+        window_menu.visible = true;
+      }
       let window = self.clone();
       let menu_ = window_menu.menu.clone();
       self.run_on_main_thread(move || {
@@ -1261,9 +1290,12 @@ tauri::Builder::default()
     Ok(())
   }
 
-  /// Shows the window menu.
+  /// Returns whether the window menu is visible.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **macOS:** Unsupported, always returns `false`.
   pub fn is_menu_visible(&self) -> crate::Result<bool> {
-    // remove from the window
     #[cfg_attr(target_os = "macos", allow(unused_variables))]
     if let Some(window_menu) = &*self.menu_lock() {
       let (tx, rx) = std::sync::mpsc::channel();
@@ -1286,7 +1318,14 @@ tauri::Builder::default()
         }
       })?;
 
-      return Ok(rx.recv().unwrap_or(false));
+      return match rx.recv() {
+        Ok(visible) => Ok(visible),
+        // the mock runtime has no native window to query
+        #[cfg(all(feature = "test", not(target_os = "macos")))]
+        Err(_) => Ok(window_menu.visible),
+        #[cfg(not(all(feature = "test", not(target_os = "macos"))))]
+        Err(_) => Ok(false),
+      };
     }
 
     Ok(false)
@@ -1307,6 +1346,93 @@ tauri::Builder::default()
   ) -> crate::Result<()> {
     menu.popup_at(self.clone(), position)
   }
+
+  /// Registers an accelerator on this window that emits a [`MenuEvent`](crate::menu::MenuEvent)
+  /// for the menu item with the given id when pressed while the window is focused.
+  ///
+  /// This makes the accelerators of menu items that are not attached to a menu bar,
+  /// such as the items of a context menu, work while their menu is closed.
+  /// Registering an accelerator that is already used on this window for another action fails,
+  /// including the accelerators of the items of the window menu and the app menu.
+  ///
+  /// The key presses are captured by the webviews of the window, which stop the propagation of the
+  /// matching ones and forward them to the app. The capability of the webviews must grant the
+  /// `core:menu:allow-dispatch-accelerator` permission, which is not part of the default permissions.
+  pub fn register_accelerator<A: AsRef<str>, I: Into<MenuId>>(
+    &self,
+    accelerator: A,
+    id: I,
+  ) -> crate::Result<()> {
+    self.insert_accelerator(
+      accelerator.as_ref(),
+      crate::menu::accelerators::AcceleratorAction::MenuItem(id.into()),
+    )
+  }
+
+  /// Registers an accelerator on this window that calls the handler on the main thread
+  /// when pressed while the window is focused.
+  ///
+  /// See [`Self::register_accelerator`] for the limitations.
+  pub fn register_accelerator_handler<A: AsRef<str>, F: Fn(&Window<R>) + Send + Sync + 'static>(
+    &self,
+    accelerator: A,
+    handler: F,
+  ) -> crate::Result<()> {
+    self.insert_accelerator(
+      accelerator.as_ref(),
+      crate::menu::accelerators::AcceleratorAction::Handler(Arc::new(handler)),
+    )
+  }
+
+  fn insert_accelerator(
+    &self,
+    accelerator: &str,
+    action: crate::menu::accelerators::AcceleratorAction<R>,
+  ) -> crate::Result<()> {
+    use crate::menu::accelerators::{self, AcceleratorAction};
+
+    let parsed = accelerators::parse(accelerator)?;
+    if let Some(item) = accelerators::menu_item_using(self, &parsed)? {
+      if !matches!(&action, AcceleratorAction::MenuItem(id) if *id == item) {
+        return Err(crate::Error::AcceleratorConflict(accelerator.into()));
+      }
+    }
+
+    self
+      .manager
+      .menu
+      .accelerators_lock()
+      .entry(self.label().to_string())
+      .or_default()
+      .register(accelerator, action)?;
+    accelerators::sync(self)
+  }
+
+  /// Unregisters an accelerator from this window, returning whether it was registered.
+  pub fn unregister_accelerator<A: AsRef<str>>(&self, accelerator: A) -> crate::Result<bool> {
+    let unregistered = match self.manager.menu.accelerators_lock().get_mut(self.label()) {
+      Some(accelerators) => accelerators.unregister(accelerator.as_ref())?,
+      None => crate::menu::accelerators::parse(accelerator.as_ref()).map(|_| false)?,
+    };
+    if unregistered {
+      crate::menu::accelerators::sync(self)?;
+    }
+    Ok(unregistered)
+  }
+
+  /// Unregisters all the accelerators of this window.
+  pub fn unregister_all_accelerators(&self) -> crate::Result<()> {
+    self.manager.menu.accelerators_lock().remove(self.label());
+    crate::menu::accelerators::sync(self)
+  }
+
+  /// Whether the accelerator is registered on this window.
+  pub fn is_accelerator_registered<A: AsRef<str>>(&self, accelerator: A) -> crate::Result<bool> {
+    match self.manager.menu.accelerators_lock().get(self.label()) {
+      Some(accelerators) => accelerators.is_registered(accelerator.as_ref()),
+      None => crate::menu::accelerators::parse(accelerator.as_ref()).map(|_| false),
+    }
+  }
 }
 
 /// Window getters.
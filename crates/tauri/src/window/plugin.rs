@@ -194,6 +194,43 @@ mod desktop_commands {
     let window = get_window(window, label)?;
     window.monitor_from_point(x, y)
   }
+
+  #[cfg(not(feature = "unstable"))]
+  #[command(root = "crate")]
+  pub async fn move_layout_splitter() -> crate::Result<()> {
+    Err(crate::Error::UnstableFeatureNotSupported)
+  }
+
+  #[cfg(feature = "unstable")]
+  #[command(root = "crate")]
+  pub async fn move_layout_splitter<R: Runtime>(
+    window: Window<R>,
+    label: Option<String>,
+    id: String,
+    index: usize,
+    delta: f64,
+  ) -> crate::Result<()> {
+    let window = get_window(window, label)?;
+    window.move_layout_splitter(&id, index, delta)
+  }
+
+  #[cfg(not(feature = "unstable"))]
+  #[command(root = "crate")]
+  pub async fn set_layout_active_tab() -> crate::Result<()> {
+    Err(crate::Error::UnstableFeatureNotSupported)
+  }
+
+  #[cfg(feature = "unstable")]
+  #[command(root = "crate")]
+  pub async fn set_layout_active_tab<R: Runtime>(
+    window: Window<R>,
+    label: Option<String>,
+    id: String,
+    index: usize,
+  ) -> crate::Result<()> {
+    let window = get_window(window, label)?;
+    window.set_layout_active_tab(&id, index)
+  }
 }
 
 /// Initializes the plugin.
@@ -295,6 +332,8 @@ pub fn init<R: Runtime>() -> TauriPlugin<R> {
             desktop_commands::set_theme,
             desktop_commands::toggle_maximize,
             desktop_commands::internal_toggle_maximize,
+            desktop_commands::move_layout_splitter,
+            desktop_commands::set_layout_active_tab,
           ]);
         handler(invoke)
       }
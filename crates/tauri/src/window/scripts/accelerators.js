@@ -0,0 +1,46 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+;(function () {
+  // the accelerators registered on the window, updated by the app
+  let accelerators = __TEMPLATE_accelerators__
+
+  Object.defineProperty(window.__TAURI_INTERNALS__, 'setAccelerators', {
+    value: (value) => {
+      accelerators = value
+    }
+  })
+
+  window.addEventListener(
+    'keydown',
+    (event) => {
+      if (event.repeat || event.isComposing) {
+        return
+      }
+      const accelerator = accelerators.find(
+        (a) =>
+          a.code === event.code &&
+          a.shift === event.shiftKey &&
+          a.control === event.ctrlKey &&
+          a.alt === event.altKey &&
+          a.meta === event.metaKey
+      )
+      if (!accelerator) {
+        return
+      }
+      event.preventDefault()
+      event.stopPropagation()
+      window.__TAURI_INTERNALS__
+        .invoke('plugin:menu|dispatch_accelerator', {
+          code: event.code,
+          shift: event.shiftKey,
+          control: event.ctrlKey,
+          alt: event.altKey,
+          meta: event.metaKey
+        })
+        .catch(() => {})
+    },
+    true
+  )
+})()
@@ -0,0 +1,116 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+;(function () {
+  // the layout splitters bordering this webview, updated by the window layout
+  const splitters = __TEMPLATE_splitters__
+
+  if (window.__TAURI_INTERNALS__.setLayoutSplitters) {
+    window.__TAURI_INTERNALS__.setLayoutSplitters(splitters)
+    return
+  }
+
+  // the distance from the webview edge where a splitter can be grabbed, in CSS pixels
+  const HANDLE_SIZE = 6
+
+  let handles = splitters
+  let drag = null
+  let cursorSet = false
+
+  Object.defineProperty(window.__TAURI_INTERNALS__, 'setLayoutSplitters', {
+    value: (value) => {
+      handles = value
+    }
+  })
+
+  function isHorizontal(handle) {
+    return handle.edge === 'left' || handle.edge === 'right'
+  }
+
+  function handleAt(event) {
+    return handles.find((h) => {
+      switch (h.edge) {
+        case 'left':
+          return event.clientX <= HANDLE_SIZE
+        case 'right':
+          return event.clientX >= window.innerWidth - HANDLE_SIZE
+        case 'top':
+          return event.clientY <= HANDLE_SIZE
+        case 'bottom':
+          return event.clientY >= window.innerHeight - HANDLE_SIZE
+      }
+      return false
+    })
+  }
+
+  function setCursor(handle) {
+    if (handle) {
+      document.documentElement.style.cursor = isHorizontal(handle)
+        ? 'col-resize'
+        : 'row-resize'
+      cursorSet = true
+    } else if (cursorSet) {
+      document.documentElement.style.cursor = ''
+      cursorSet = false
+    }
+  }
+
+  // screen coordinates do not change when the webview itself is moved by the layout
+  function position(handle, event) {
+    return isHorizontal(handle) ? event.screenX : event.screenY
+  }
+
+  window.addEventListener(
+    'pointerdown',
+    (event) => {
+      const handle = event.button === 0 && handleAt(event)
+      if (!handle) {
+        return
+      }
+      event.preventDefault()
+      event.stopPropagation()
+      drag = { handle, position: position(handle, event) }
+      document.documentElement.setPointerCapture(event.pointerId)
+    },
+    true
+  )
+
+  window.addEventListener(
+    'pointermove',
+    (event) => {
+      if (!drag) {
+        setCursor(handleAt(event))
+        return
+      }
+      event.preventDefault()
+      event.stopPropagation()
+      const current = position(drag.handle, event)
+      const delta = current - drag.position
+      if (delta === 0) {
+        return
+      }
+      drag.position = current
+      window.__TAURI_INTERNALS__
+        .invoke('plugin:window|move_layout_splitter', {
+          id: drag.handle.id,
+          index: drag.handle.index,
+          delta
+        })
+        .catch(() => {})
+    },
+    true
+  )
+
+  function endDrag(event) {
+    if (!drag) {
+      return
+    }
+    drag = null
+    document.documentElement.releasePointerCapture(event.pointerId)
+    setCursor(handleAt(event))
+  }
+
+  window.addEventListener('pointerup', endDrag, true)
+  window.addEventListener('pointercancel', endDrag, true)
+})()
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: MIT
 
 import { invoke } from './core'
+import { listen, TauriEvent, type UnlistenFn } from './event'
 import { Image } from './image'
 import { Theme } from './window'
 
@@ -121,6 +122,50 @@ async function setTheme(theme?: Theme | null): Promise<void> {
   return invoke('plugin:app|set_app_theme', { theme })
 }
 
+/**
+ * Gets the application locale, set on the Rust side with `AppHandle::set_locale`.
+ *
+ * @example
+ * ```typescript
+ * import { getLocale } from '@tauri-apps/api/app';
+ * const locale = await getLocale();
+ * ```
+ *
+ * @returns The locale, or `null` if it was never set.
+ *
+ * @since 2.1.0
+ */
+async function getLocale(): Promise<string | null> {
+  return invoke('plugin:app|locale')
+}
+
+/**
+ * Listen to application locale changes.
+ *
+ * @example
+ * ```typescript
+ * import { onLocaleChanged } from '@tauri-apps/api/app';
+ * const unlisten = await onLocaleChanged((locale) => {
+ *   console.log(`locale changed to ${locale}`);
+ * });
+ *
+ * // you need to call unlisten if your handler goes out of scope e.g. the component is unmounted
+ * unlisten();
+ * ```
+ *
+ * @returns A promise resolving to a function to unlisten to the event.
+ * Note that removing the listener is required if your listener goes out of scope e.g. the component is unmounted.
+ *
+ * @since 2.1.0
+ */
+async function onLocaleChanged(
+  handler: (locale: string) => void
+): Promise<UnlistenFn> {
+  return listen<string>(TauriEvent.LOCALE_CHANGED, (event) =>
+    handler(event.payload)
+  )
+}
+
 export {
   getName,
   getVersion,
@@ -128,5 +173,7 @@ export {
   show,
   hide,
   defaultWindowIcon,
-  setTheme
+  setTheme,
+  getLocale,
+  onLocaleChanged
 }
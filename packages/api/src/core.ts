@@ -154,6 +154,14 @@ type InvokeArgs = Record<string, unknown> | number[] | ArrayBuffer | Uint8Array
  * @since 2.0.0
  */
 interface InvokeOptions {
+  /**
+   * The request headers, available to the command through its `Request`.
+   *
+   * When the `tracing` Cargo feature is enabled, the trace ID and parent span ID of a
+   * [`traceparent`](https://www.w3.org/TR/trace-context/#traceparent-header) header
+   * are recorded as the `trace_id` and `parent_span_id` fields of the request span,
+   * and the `tracing-opentelemetry` feature makes the frontend span its OpenTelemetry parent.
+   */
   headers: Headers | Record<string, string>
 }
 
@@ -60,7 +60,10 @@ enum TauriEvent {
   DRAG_ENTER = 'tauri://drag-enter',
   DRAG_OVER = 'tauri://drag-over',
   DRAG_DROP = 'tauri://drag-drop',
-  DRAG_LEAVE = 'tauri://drag-leave'
+  DRAG_LEAVE = 'tauri://drag-leave',
+  NAVIGATION_STATE_CHANGED = 'tauri://navigation-state-changed',
+  MESSAGE_PORT = 'tauri://message-port',
+  LOCALE_CHANGED = 'tauri://locale-changed'
 }
 
 /**
@@ -12,6 +12,18 @@ export interface ImageSize {
   height: number
 }
 
+/**
+ * The filter used to sample pixels when resizing an image.
+ *
+ * @since 2.1.0
+ */
+export type ResizeFilter =
+  | 'nearest'
+  | 'triangle'
+  | 'catmullRom'
+  | 'gaussian'
+  | 'lanczos3'
+
 /** An RGBA Image in row-major order from top to bottom. */
 export class Image extends Resource {
   /**
@@ -23,7 +35,11 @@ export class Image extends Resource {
     super(rid)
   }
 
-  /** Creates a new Image using RGBA data, in row-major order from top to bottom, and with specified width and height. */
+  /**
+   * Creates a new Image using RGBA data, in row-major order from top to bottom, and with specified width and height.
+   *
+   * Rejects if the data is not `width * height * 4` bytes long.
+   */
   static async new(
     rgba: number[] | Uint8Array | ArrayBuffer,
     width: number,
@@ -40,9 +56,9 @@ export class Image extends Resource {
    * Creates a new image using the provided bytes by inferring the file format.
    * If the format is known, prefer [@link Image.fromPngBytes] or [@link Image.fromIcoBytes].
    *
-   * Only `ico` and `png` are supported (based on activated feature flag).
+   * Only `ico`, `png`, `jpeg`, `webp`, `bmp` and `svg` are supported (based on activated feature flag).
    *
-   * Note that you need the `image-ico` or `image-png` Cargo features to use this API.
+   * Note that you need one of the `image-ico`, `image-png`, `image-jpeg`, `image-webp`, `image-bmp` or `image-svg` Cargo features to use this API.
    * To enable it, change your Cargo.toml file:
    * ```toml
    * [dependencies]
@@ -60,9 +76,9 @@ export class Image extends Resource {
   /**
    * Creates a new image using the provided path.
    *
-   * Only `ico` and `png` are supported (based on activated feature flag).
+   * Only `ico`, `png`, `jpeg`, `webp`, `bmp` and `svg` are supported (based on activated feature flag).
    *
-   * Note that you need the `image-ico` or `image-png` Cargo features to use this API.
+   * Note that you need one of the `image-ico`, `image-png`, `image-jpeg`, `image-webp`, `image-bmp` or `image-svg` Cargo features to use this API.
    * To enable it, change your Cargo.toml file:
    * ```toml
    * [dependencies]
@@ -75,6 +91,28 @@ export class Image extends Resource {
     )
   }
 
+  /**
+   * Creates a new image from the entry of a multi-resolution `ico` file that best fits
+   * a logical size at the given scale factor.
+   *
+   * Picks the smallest entry at least `size * scaleFactor` pixels wide, or the largest one if none is.
+   *
+   * Note that you need the `image-ico` Cargo feature to use this API.
+   *
+   * @since 2.1.0
+   */
+  static async fromIcoForScaleFactor(
+    bytes: number[] | Uint8Array | ArrayBuffer,
+    size: number,
+    scaleFactor: number
+  ): Promise<Image> {
+    return invoke<number>('plugin:image|from_ico_for_scale_factor', {
+      bytes: transformImage(bytes),
+      size,
+      scaleFactor
+    }).then((rid) => new Image(rid))
+  }
+
   /** Returns the RGBA data for this image, in row-major order from top to bottom.  */
   async rgba(): Promise<Uint8Array> {
     return invoke<number[]>('plugin:image|rgba', {
@@ -86,6 +124,51 @@ export class Image extends Resource {
   async size(): Promise<ImageSize> {
     return invoke<ImageSize>('plugin:image|size', { rid: this.rid })
   }
+
+  /**
+   * Returns a copy of this image resized to the exact given size, ignoring the aspect ratio.
+   * Fails if the size is empty or has more pixels than 8192x8192.
+   *
+   * Note that you need one of the `image-*` Cargo features to use this API.
+   *
+   * @param filter The filter used to sample pixels, defaults to `lanczos3`.
+   *
+   * @since 2.1.0
+   */
+  async resize(
+    width: number,
+    height: number,
+    filter?: ResizeFilter
+  ): Promise<Image> {
+    return invoke<number>('plugin:image|resize', {
+      rid: this.rid,
+      width,
+      height,
+      filter
+    }).then((rid) => new Image(rid))
+  }
+
+  /**
+   * Returns a copy of the given region of this image.
+   *
+   * Note that you need one of the `image-*` Cargo features to use this API.
+   *
+   * @since 2.1.0
+   */
+  async crop(
+    x: number,
+    y: number,
+    width: number,
+    height: number
+  ): Promise<Image> {
+    return invoke<number>('plugin:image|crop', {
+      rid: this.rid,
+      x,
+      y,
+      width,
+      height
+    }).then((rid) => new Image(rid))
+  }
 }
 
 /**
@@ -70,6 +70,11 @@ type RustTrayIconEvent = Omit<TrayIconEvent, 'rect'> & {
  * @module
  */
 
+/**
+ * A badge drawn on the top-right corner of a tray icon, either a dot or a count.
+ */
+export type TrayIconBadge = 'dot' | { count: number }
+
 /** {@link TrayIcon.new|`TrayIcon`} creation options */
 export interface TrayIconOptions {
   /** The tray icon id. If undefined, a random one will be assigned */
@@ -213,6 +218,52 @@ export class TrayIcon extends Resource {
     return invoke('plugin:tray|set_icon', { rid: this.rid, icon: trayIcon })
   }
 
+  /**
+   * Sets a badge drawn on the top-right corner of the tray icon. If `null` is provided, it will remove the badge.
+   *
+   * The badge is composited onto the tray icon and kept when the icon changes.
+   * Counts above 99 are shown as `99+`.
+   *
+   * @example
+   * ```typescript
+   * await tray.setBadge({ count: 3 })
+   * await tray.setBadge('dot')
+   * ```
+   */
+  async setBadge(badge: TrayIconBadge | null): Promise<void> {
+    return invoke('plugin:tray|set_badge', { rid: this.rid, badge })
+  }
+
+  /**
+   * Cycles the tray icon through the given frames, showing each one for `interval` milliseconds.
+   * Intervals shorter than 16 milliseconds are raised to 16 milliseconds.
+   *
+   * The animation replaces the running one and stops when {@linkcode TrayIcon.stopAnimation}
+   * or {@linkcode TrayIcon.setIcon} is called, or when the tray icon is removed.
+   *
+   * @example
+   * ```typescript
+   * await tray.startAnimation(['icons/busy-1.png', 'icons/busy-2.png'], 200)
+   * ```
+   */
+  async startAnimation(
+    frames: Array<string | Image | Uint8Array | ArrayBuffer | number[]>,
+    interval: number
+  ): Promise<void> {
+    return invoke('plugin:tray|start_animation', {
+      rid: this.rid,
+      frames: frames.map((frame) => transformImage(frame)),
+      interval
+    })
+  }
+
+  /**
+   * Stops the running animation and restores the tray icon.
+   */
+  async stopAnimation(): Promise<void> {
+    return invoke('plugin:tray|stop_animation', { rid: this.rid })
+  }
+
   /**
    * Sets a new tray menu.
    *
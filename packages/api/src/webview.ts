@@ -28,7 +28,7 @@ import {
   listen,
   once
 } from './event'
-import { invoke } from './core'
+import { Channel, invoke } from './core'
 import { Window, getCurrentWindow } from './window'
 import { WebviewWindow } from './webviewWindow'
 
@@ -39,6 +39,96 @@ type DragDropEvent =
   | { type: 'drop'; paths: string[]; position: PhysicalPosition }
   | { type: 'leave' }
 
+/** The navigation history state of a webview. */
+interface NavigationState {
+  /** The current URL of the webview. */
+  url: string
+  /** Whether the webview can navigate back in its history. */
+  canGoBack: boolean
+  /** Whether the webview can navigate forward in its history. */
+  canGoForward: boolean
+}
+
+/**
+ * One end of a message channel between two webviews.
+ *
+ * Messages posted on a port are delivered in order to the other end of the channel,
+ * without going through the event system. `ArrayBuffer` and `Uint8Array` messages are sent as binary payloads.
+ *
+ * @example
+ * ```typescript
+ * import { getCurrentWebview } from '@tauri-apps/api/webview';
+ * const port = await getCurrentWebview().createMessageChannel('sidebar');
+ * port.onmessage = (message) => console.log(message);
+ * await port.start();
+ * await port.postMessage({ hello: 'world' });
+ * ```
+ */
+class WebviewMessagePort<T = unknown> {
+  /** The port identifier. */
+  id: number
+  /** The label of the webview that owns the other end of the channel. */
+  peer: string
+  #onmessage: (message: T) => void = () => {
+    // no-op
+  }
+  #nextSequence = 0
+
+  constructor(id: number, peer: string) {
+    this.id = id
+    this.peer = peer
+  }
+
+  set onmessage(handler: (message: T) => void) {
+    this.#onmessage = handler
+  }
+
+  get onmessage(): (message: T) => void {
+    return this.#onmessage
+  }
+
+  /**
+   * Starts receiving messages. Messages posted by the other end before the port is started are queued.
+   *
+   * @returns A promise indicating the success or failure of the operation.
+   */
+  async start(): Promise<void> {
+    const onMessage = new Channel<T>()
+    onMessage.onmessage = (message) => this.#onmessage(message)
+    return invoke('plugin:message-port|connect', {
+      port: this.id,
+      onMessage
+    })
+  }
+
+  /**
+   * Posts a message to the other end of the channel.
+   *
+   * @param message A JSON serializable value, or an `ArrayBuffer` or `Uint8Array` for binary payloads.
+   * @returns A promise indicating the success or failure of the operation.
+   */
+  async postMessage(message: T | ArrayBuffer | Uint8Array): Promise<void> {
+    const headers = {
+      'Tauri-Message-Port': this.id.toString(),
+      'Tauri-Message-Port-Sequence': (this.#nextSequence++).toString()
+    }
+    const payload =
+      message instanceof ArrayBuffer || message instanceof Uint8Array
+        ? message
+        : { message }
+    return invoke('plugin:message-port|post', payload, { headers })
+  }
+
+  /**
+   * Closes both ends of the channel.
+   *
+   * @returns A promise indicating the success or failure of the operation.
+   */
+  async close(): Promise<void> {
+    return invoke('plugin:message-port|close', { port: this.id })
+  }
+}
+
 /**
  * Get an instance of `Webview` for the current webview.
  *
@@ -384,8 +474,98 @@ class Webview {
     ).then(({ width, height }) => new PhysicalSize(width, height))
   }
 
+  /**
+   * Whether the webview can navigate back in its history.
+   * @example
+   * ```typescript
+   * import { getCurrentWebview } from '@tauri-apps/api/webview';
+   * const canGoBack = await getCurrentWebview().canGoBack();
+   * ```
+   *
+   * #### Platform-specific
+   *
+   * - **Android**: Unsupported, always returns `false`.
+   *
+   * @returns Whether there is a previous history entry.
+   */
+  async canGoBack(): Promise<boolean> {
+    return invoke('plugin:webview|webview_can_go_back', {
+      label: this.label
+    })
+  }
+
+  /**
+   * Whether the webview can navigate forward in its history.
+   * @example
+   * ```typescript
+   * import { getCurrentWebview } from '@tauri-apps/api/webview';
+   * const canGoForward = await getCurrentWebview().canGoForward();
+   * ```
+   *
+   * #### Platform-specific
+   *
+   * - **Android**: Unsupported, always returns `false`.
+   *
+   * @returns Whether there is a next history entry.
+   */
+  async canGoForward(): Promise<boolean> {
+    return invoke('plugin:webview|webview_can_go_forward', {
+      label: this.label
+    })
+  }
+
   // Setters
 
+  /**
+   * Navigates back to the previous page in the webview's history.
+   * @example
+   * ```typescript
+   * import { getCurrentWebview } from '@tauri-apps/api/webview';
+   * await getCurrentWebview().goBack();
+   * ```
+   *
+   * @returns A promise indicating the success or failure of the operation.
+   */
+  async goBack(): Promise<void> {
+    return invoke('plugin:webview|webview_go_back', {
+      label: this.label
+    })
+  }
+
+  /**
+   * Navigates forward to the next page in the webview's history.
+   * @example
+   * ```typescript
+   * import { getCurrentWebview } from '@tauri-apps/api/webview';
+   * await getCurrentWebview().goForward();
+   * ```
+   *
+   * @returns A promise indicating the success or failure of the operation.
+   */
+  async goForward(): Promise<void> {
+    return invoke('plugin:webview|webview_go_forward', {
+      label: this.label
+    })
+  }
+
+  /**
+   * Reloads the current page.
+   * @example
+   * ```typescript
+   * import { getCurrentWebview } from '@tauri-apps/api/webview';
+   * await getCurrentWebview().reload({ bypassCache: true });
+   * ```
+   *
+   * @param options.bypassCache Whether cached resources should be ignored and fetched again.
+   * @returns A promise indicating the success or failure of the operation.
+   */
+  async reload(options?: { bypassCache?: boolean }): Promise<void> {
+    return invoke('plugin:webview|webview_reload', {
+      label: this.label,
+      value: options?.bypassCache ?? false
+    })
+  }
+
   /**
    * Closes the webview.
    * @example
@@ -650,6 +830,92 @@ class Webview {
       unlistenDragLeave()
     }
   }
+
+  /**
+   * Listen to changes of the webview navigation history,
+   * triggered when a page finishes loading and on same-document navigations
+   * such as `history.pushState`, `history.replaceState` and fragment changes.
+   *
+   * @example
+   * ```typescript
+   * import { getCurrentWebview } from "@tauri-apps/api/webview";
+   * const unlisten = await getCurrentWebview().onNavigationStateChanged(({ payload }) => {
+   *   backButton.disabled = !payload.canGoBack;
+   *   forwardButton.disabled = !payload.canGoForward;
+   * });
+   *
+   * // you need to call unlisten if your handler goes out of scope e.g. the component is unmounted
+   * unlisten();
+   * ```
+   *
+   * @returns A promise resolving to a function to unlisten to the event.
+   * Note that removing the listener is required if your listener goes out of scope e.g. the component is unmounted.
+   */
+  async onNavigationStateChanged(
+    handler: EventCallback<NavigationState>
+  ): Promise<UnlistenFn> {
+    return this.listen<NavigationState>(
+      TauriEvent.NAVIGATION_STATE_CHANGED,
+      handler
+    )
+  }
+
+  // Message ports
+
+  /**
+   * Creates a message channel between this webview and the `target` webview.
+   * The target webview receives the other end of the channel through {@link Webview.onMessagePort}.
+   *
+   * Requires the `core:message-port:allow-create` permission.
+   *
+   * @example
+   * ```typescript
+   * import { getCurrentWebview } from '@tauri-apps/api/webview';
+   * const port = await getCurrentWebview().createMessageChannel('sidebar');
+   * await port.postMessage(new Uint8Array([1, 2, 3]));
+   * ```
+   *
+   * @param target The label of the webview to connect to.
+   * @returns The end of the channel owned by this webview.
+   */
+  async createMessageChannel<T = unknown>(
+    target: string
+  ): Promise<WebviewMessagePort<T>> {
+    return invoke<{ port: number; peer: string }>(
+      'plugin:message-port|create',
+      { target }
+    ).then(({ port, peer }) => new WebviewMessagePort<T>(port, peer))
+  }
+
+  /**
+   * Listen to message ports transferred to this webview,
+   * either by {@link Webview.createMessageChannel} or `Webview::create_message_channel` on the Rust side.
+   *
+   * @example
+   * ```typescript
+   * import { getCurrentWebview } from '@tauri-apps/api/webview';
+   * const unlisten = await getCurrentWebview().onMessagePort((port) => {
+   *   port.onmessage = (message) => console.log(`message from ${port.peer}`, message);
+   *   port.start();
+   * });
+   *
+   * // you need to call unlisten if your handler goes out of scope e.g. the component is unmounted
+   * unlisten();
+   * ```
+   *
+   * @returns A promise resolving to a function to unlisten to the event.
+   * Note that removing the listener is required if your listener goes out of scope e.g. the component is unmounted.
+   */
+  async onMessagePort<T = unknown>(
+    handler: (port: WebviewMessagePort<T>) => void
+  ): Promise<UnlistenFn> {
+    return this.listen<{ port: number; peer: string }>(
+      TauriEvent.MESSAGE_PORT,
+      ({ payload }) => {
+        handler(new WebviewMessagePort<T>(payload.port, payload.peer))
+      }
+    )
+  }
 }
 
 function mapPhysicalPosition(m: PhysicalPosition): PhysicalPosition {
@@ -730,6 +996,6 @@ interface WebviewOptions {
   zoomHotkeysEnabled?: boolean
 }
 
-export { Webview, getCurrentWebview, getAllWebviews }
+export { Webview, WebviewMessagePort, getCurrentWebview, getAllWebviews }
 
-export type { DragDropEvent, WebviewOptions }
+export type { DragDropEvent, NavigationState, WebviewOptions }
@@ -1730,6 +1730,54 @@ class Window {
     })
   }
 
+  /**
+   * Moves the splitter between the children `index` and `index + 1` of the `split` layout node `id`.
+   * Requires the window to have a declarative layout and the `unstable` Cargo feature.
+   * @example
+   * ```typescript
+   * import { getCurrentWindow } from '@tauri-apps/api/window';
+   * await getCurrentWindow().moveLayoutSplitter('main', 0, 50);
+   * ```
+   *
+   * @param id The layout node id.
+   * @param index The index of the child before the splitter.
+   * @param delta The logical distance to move the splitter by.
+   * @returns A promise indicating the success or failure of the operation.
+   */
+  async moveLayoutSplitter(
+    id: string,
+    index: number,
+    delta: number
+  ): Promise<void> {
+    return invoke('plugin:window|move_layout_splitter', {
+      label: this.label,
+      id,
+      index,
+      delta
+    })
+  }
+
+  /**
+   * Shows the child `index` of the `tabs` layout node `id`, hiding the other tabs.
+   * Requires the window to have a declarative layout and the `unstable` Cargo feature.
+   * @example
+   * ```typescript
+   * import { getCurrentWindow } from '@tauri-apps/api/window';
+   * await getCurrentWindow().setLayoutActiveTab('editors', 1);
+   * ```
+   *
+   * @param id The layout node id.
+   * @param index The index of the tab to show.
+   * @returns A promise indicating the success or failure of the operation.
+   */
+  async setLayoutActiveTab(id: string, index: number): Promise<void> {
+    return invoke('plugin:window|set_layout_active_tab', {
+      label: this.label,
+      id,
+      index
+    })
+  }
+
   // Listeners
 
   /**